@@ -0,0 +1,98 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use logproof::{
+    linear_algebra::PolynomialMatrix,
+    rings::{ZqSeal128_1024, ZqSeal128_2048, ZqSeal128_4096, ZqSeal128_8192},
+};
+use rand::{distributions::Uniform, prelude::Distribution, thread_rng};
+use sunscreen_math::{poly::Polynomial, ring::Ring};
+
+fn random_poly<R: Ring + From<u64>>(degree: usize) -> Polynomial<R> {
+    let dist = Uniform::from(0..u64::MAX);
+
+    Polynomial {
+        coeffs: (0..degree)
+            .map(|_| R::from(dist.sample(&mut thread_rng())))
+            .collect(),
+    }
+}
+
+fn bench_degree<R: Ring + From<u64>>(c: &mut Criterion, degree: usize) {
+    let mut group = c.benchmark_group(format!("poly_mul_{degree}"));
+    group.sample_size(10);
+
+    let a = random_poly::<R>(degree);
+    let b = random_poly::<R>(degree);
+
+    group.bench_function(BenchmarkId::new("schoolbook", degree), |bench| {
+        bench.iter(|| a.mul_schoolbook(&b))
+    });
+
+    // Uses an NTT at these degrees.
+    group.bench_function(BenchmarkId::new("ntt", degree), |bench| {
+        bench.iter(|| &a * &b)
+    });
+
+    group.bench_function(BenchmarkId::new("negacyclic_ntt", degree), |bench| {
+        bench.iter(|| a.mul_negacyclic(&b, degree))
+    });
+
+    group.finish();
+}
+
+/// Computes `A * S mod (x^n + 1)` for a public key encryption statement's `A` and `S`, both by
+/// reducing the full product and with a negacyclic NTT.
+fn bench_a_times_s<R: Ring + From<u64>>(c: &mut Criterion, degree: usize) {
+    let mut group = c.benchmark_group(format!("a_times_s_{degree}"));
+    group.sample_size(10);
+
+    let a = PolynomialMatrix::from(vec![
+        vec![
+            random_poly::<R>(1),
+            random_poly(degree),
+            random_poly(1),
+            random_poly(1),
+        ],
+        vec![
+            random_poly(1),
+            random_poly(degree),
+            random_poly(1),
+            random_poly(1),
+        ],
+    ]);
+    let s = PolynomialMatrix::from(vec![
+        vec![random_poly::<R>(degree)],
+        vec![random_poly(degree)],
+        vec![random_poly(degree)],
+        vec![random_poly(degree)],
+    ]);
+    let mut f = Polynomial {
+        coeffs: vec![R::zero(); degree + 1],
+    };
+    f.coeffs[0] = R::one();
+    f.coeffs[degree] = R::one();
+
+    group.bench_function(BenchmarkId::new("div_rem", degree), |bench| {
+        bench.iter(|| (&a * &s).map(|x| x.vartime_div_rem_restricted_rhs(&f).1))
+    });
+
+    group.bench_function(BenchmarkId::new("negacyclic_ntt", degree), |bench| {
+        bench.iter(|| a.mul_negacyclic(&s, degree))
+    });
+
+    group.finish();
+}
+
+fn poly_mul(c: &mut Criterion) {
+    bench_degree::<ZqSeal128_1024>(c, 1024);
+    bench_degree::<ZqSeal128_2048>(c, 2048);
+    bench_degree::<ZqSeal128_4096>(c, 4096);
+    bench_degree::<ZqSeal128_8192>(c, 8192);
+
+    bench_a_times_s::<ZqSeal128_1024>(c, 1024);
+    bench_a_times_s::<ZqSeal128_2048>(c, 2048);
+    bench_a_times_s::<ZqSeal128_4096>(c, 4096);
+    bench_a_times_s::<ZqSeal128_8192>(c, 8192);
+}
+
+criterion_group!(benches, poly_mul);
+criterion_main!(benches);
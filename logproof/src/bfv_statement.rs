@@ -14,14 +14,14 @@ use seal_fhe::{
 };
 use sunscreen_math::{
     poly::Polynomial,
-    ring::{BarrettBackend, BarrettConfig, Ring, Zq},
+    ring::{extend_bigint, BarrettBackend, BarrettConfig, Ring, Zq},
     One, Zero,
 };
 
 use crate::{
     linear_algebra::{Matrix, PolynomialMatrix},
     math::Log2,
-    Bounds, LogProofProverKnowledge, LogProofVerifierKnowledge,
+    Bounds, LogProofProverKnowledge, LogProofVerifierKnowledge, ProofError,
 };
 
 /// In SEAL, `u` is sampled from a ternary distribution. The number of bits is 1.
@@ -31,11 +31,23 @@ const U_COEFFICIENT_BOUND: u32 = 1;
 const E_COEFFICIENT_BOUND: u32 = 5;
 /// In SEAL, secret keys are sampled from a ternary distribution. The number of bits is 1.
 const S_COEFFICIENT_BOUND: u32 = 1;
-
-/// A proof statement verifying that a ciphertext is an encryption of a known plaintext message.
-/// Note that these statements are per SEAL plain/ciphertexts, where Sunscreen encodings are at a
-/// higher level. A single Sunscreen plaintext may actually encode multiple SEAL plaintexts, and
-/// hence multiple proof statements.
+/// In SEAL's `multiply_plain`, each plaintext coefficient is lifted either to itself or to itself
+/// minus the plain modulus. The indicator of the latter is binary, so the number of bits is 1.
+const LIFT_COEFFICIENT_BOUND: u32 = 1;
+
+/// A proof statement verifying that a ciphertext is an encryption of a known plaintext message,
+/// that a ciphertext is the result of a linear homomorphic operation, or that a set of keys was
+/// generated from a single secret key. Note that these
+/// statements are per SEAL plain/ciphertexts, where Sunscreen encodings are at a higher level. A
+/// single Sunscreen plaintext may actually encode multiple SEAL plaintexts, and hence multiple
+/// proof statements.
+///
+/// # Remarks
+/// Ciphertext multiplication rescales its tensor product, which isn't linear over `Z_q[X]/f`, so
+/// [`BfvProofStatement::RelinearizedProduct`] can't prove anything about secret operands.
+/// Multiplying and relinearizing ciphertexts the verifier already knows is deterministic, however,
+/// so each party computes the product when building the statement, and the statement binds it to
+/// the rest of the proof.
 #[derive(Debug)]
 pub enum BfvProofStatement<'p> {
     /// A statement that the ciphertext symmetrically encrypts the identified message.
@@ -67,24 +79,156 @@ pub enum BfvProofStatement<'p> {
         /// The ciphertext of the encryption statement.
         ciphertext: Ciphertext,
     },
+    /// A statement that `result` is the sum of the ciphertexts `lhs` and `rhs`, as computed by
+    /// SEAL's `add`. This statement has no secrets, but including it binds the sum to the other
+    /// statements in the proof.
+    Addition {
+        /// The left operand.
+        lhs: Ciphertext,
+        /// The right operand.
+        rhs: Ciphertext,
+        /// The sum of `lhs` and `rhs`.
+        result: Ciphertext,
+    },
+    /// A statement that `result` is the product of the ciphertexts `lhs` and `rhs`, as computed
+    /// by SEAL's `multiply` followed by `relinearize`. Like [`BfvProofStatement::Addition`], this
+    /// statement has no secrets; whoever creates the statement multiplies and relinearizes the
+    /// operands themselves, so generating the knowledge can't fail on malformed ciphertexts.
+    RelinearizedProduct {
+        /// The left operand.
+        lhs: Ciphertext,
+        /// The right operand.
+        rhs: Ciphertext,
+        /// The relinearized product of `lhs` and `rhs`, as computed by the creator of the
+        /// statement.
+        product: Ciphertext,
+        /// The claimed product, which must equal `product`.
+        result: Ciphertext,
+    },
+    /// A statement that `result` is `ciphertext` plus the identified message, as computed by
+    /// SEAL's `add_plain`.
+    PlaintextAddition {
+        /// Column index in the A matrix, or equivalently the index of the message slice provided
+        /// when generating the prover knowledge.
+        message_id: usize,
+        /// The ciphertext operand.
+        ciphertext: Ciphertext,
+        /// The sum of `ciphertext` and the message.
+        result: Ciphertext,
+    },
+    /// A statement that `result` is `ciphertext` times the identified message, as computed by
+    /// SEAL's `multiply_plain`.
+    PlaintextMultiplication {
+        /// Column index in the A matrix, or equivalently the index of the message slice provided
+        /// when generating the prover knowledge.
+        message_id: usize,
+        /// The ciphertext operand.
+        ciphertext: Ciphertext,
+        /// The product of `ciphertext` and the message.
+        result: Ciphertext,
+    },
+    /// A statement that the public key and every key switching key are RLWE samples under the
+    /// same short secret key, as generated by SEAL's `KeyGenerator`.
+    ///
+    /// This authenticates the public key only. The relation is linear over the ring, and neither
+    /// `s^2` nor `s(x^g)` is a ring-linear function of `s`, so the function each key switching key
+    /// encrypts is an unconstrained witness column, bounded like the honest one. The key
+    /// switching keys are therefore not authenticated: a prover can substitute keys that encrypt
+    /// any short polynomial.
+    KeyGeneration {
+        /// The public key.
+        public_key: Cow<'p, PublicKey>,
+        /// The relinearization and Galois keys.
+        switching_keys: Vec<KeySwitchingKey>,
+    },
+    /// A statement that the public key is an RLWE sample under a short secret key, and that every
+    /// ciphertext decrypts to zero under that same secret.
+    ///
+    /// Subtracting a known plaintext from a ciphertext first proves that the ciphertext decrypts
+    /// to that plaintext under the secret key of `public_key`. Unlike
+    /// [`BfvProofStatement::Decryption`], the plaintext isn't part of the witness.
+    ZeroDecryption {
+        /// The public key.
+        public_key: Cow<'p, PublicKey>,
+        /// The ciphertexts decrypting to zero, each with two polynomials.
+        ciphertexts: Vec<Ciphertext>,
+    },
+}
+
+/// The key switching keys SEAL generates to switch ciphertexts from a function of the secret key
+/// `s` back to `s`.
+#[derive(Debug, Clone)]
+pub struct KeySwitchingKey {
+    /// The function of `s` these keys switch from.
+    pub target: KeySwitchingTarget,
+    /// The keys, one per modulus in the ciphertext modulus excluding the special modulus.
+    pub keys: Vec<PublicKey>,
+}
+
+/// The function of the secret key `s` a [`KeySwitchingKey`] encrypts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySwitchingTarget {
+    /// `s^2`, for relinearization keys.
+    Relinearization,
+    /// `s(x^g)` for the contained Galois element `g`, for Galois keys.
+    Galois(u64),
 }
 
 impl BfvProofStatement<'_> {
     /// Get the message index of this statement.
+    ///
+    /// # Panics
+    /// Panics if the statement doesn't involve a message. See [`Self::try_message_id`].
     pub fn message_id(&self) -> usize {
+        self.try_message_id()
+            .expect("statement doesn't involve a message")
+    }
+
+    /// Get the message index of this statement, or `None` for an [`Addition`],
+    /// [`RelinearizedProduct`], [`KeyGeneration`] or [`ZeroDecryption`] statement, which don't
+    /// involve a message.
+    ///
+    /// [`Addition`]: BfvProofStatement::Addition
+    /// [`RelinearizedProduct`]: BfvProofStatement::RelinearizedProduct
+    /// [`KeyGeneration`]: BfvProofStatement::KeyGeneration
+    /// [`ZeroDecryption`]: BfvProofStatement::ZeroDecryption
+    pub fn try_message_id(&self) -> Option<usize> {
         match self {
-            BfvProofStatement::PrivateKeyEncryption { message_id, .. } => *message_id,
-            BfvProofStatement::PublicKeyEncryption { message_id, .. } => *message_id,
-            BfvProofStatement::Decryption { message_id, .. } => *message_id,
+            BfvProofStatement::PrivateKeyEncryption { message_id, .. } => Some(*message_id),
+            BfvProofStatement::PublicKeyEncryption { message_id, .. } => Some(*message_id),
+            BfvProofStatement::Decryption { message_id, .. } => Some(*message_id),
+            BfvProofStatement::Addition { .. } => None,
+            BfvProofStatement::RelinearizedProduct { .. } => None,
+            BfvProofStatement::PlaintextAddition { message_id, .. } => Some(*message_id),
+            BfvProofStatement::PlaintextMultiplication { message_id, .. } => Some(*message_id),
+            BfvProofStatement::KeyGeneration { .. } => None,
+            BfvProofStatement::ZeroDecryption { .. } => None,
         }
     }
 
-    /// Get the ciphertext of this statement.
+    /// Get the ciphertext of this statement. For homomorphic operations, this is the result.
+    ///
+    /// # Panics
+    /// Panics for key generation and zero decryption statements, which have no single
+    /// ciphertext. See [`Self::try_ciphertext`].
     pub fn ciphertext(&self) -> &Ciphertext {
+        self.try_ciphertext()
+            .expect("statement has no single ciphertext")
+    }
+
+    /// Get the ciphertext of this statement, or `None` for key generation and zero decryption
+    /// statements. For homomorphic operations, this is the result.
+    pub fn try_ciphertext(&self) -> Option<&Ciphertext> {
         match self {
-            BfvProofStatement::PrivateKeyEncryption { ciphertext, .. } => ciphertext,
-            BfvProofStatement::PublicKeyEncryption { ciphertext, .. } => ciphertext,
-            BfvProofStatement::Decryption { ciphertext, .. } => ciphertext,
+            BfvProofStatement::PrivateKeyEncryption { ciphertext, .. } => Some(ciphertext),
+            BfvProofStatement::PublicKeyEncryption { ciphertext, .. } => Some(ciphertext),
+            BfvProofStatement::Decryption { ciphertext, .. } => Some(ciphertext),
+            BfvProofStatement::Addition { result, .. } => Some(result),
+            BfvProofStatement::RelinearizedProduct { result, .. } => Some(result),
+            BfvProofStatement::PlaintextAddition { result, .. } => Some(result),
+            BfvProofStatement::PlaintextMultiplication { result, .. } => Some(result),
+            BfvProofStatement::KeyGeneration { .. } => None,
+            BfvProofStatement::ZeroDecryption { .. } => None,
         }
     }
 
@@ -96,7 +240,81 @@ impl BfvProofStatement<'_> {
     /// Return whether or not this is a private encryption statement. Note that decryption
     /// statements are technically private encryption statements.
     pub fn is_private(&self) -> bool {
-        !self.is_public()
+        matches!(
+            self,
+            BfvProofStatement::PrivateKeyEncryption { .. } | BfvProofStatement::Decryption { .. }
+        )
+    }
+
+    /// Return whether or not this is a statement about a homomorphic operation.
+    pub fn is_evaluation(&self) -> bool {
+        matches!(
+            self,
+            BfvProofStatement::Addition { .. }
+                | BfvProofStatement::RelinearizedProduct { .. }
+                | BfvProofStatement::PlaintextAddition { .. }
+                | BfvProofStatement::PlaintextMultiplication { .. }
+        )
+    }
+
+    /// Whether this statement has a column in the remainder block. Every statement except
+    /// [`BfvProofStatement::Addition`], [`BfvProofStatement::RelinearizedProduct`],
+    /// [`BfvProofStatement::KeyGeneration`] and [`BfvProofStatement::ZeroDecryption`] does.
+    fn has_remainder(&self) -> bool {
+        !matches!(
+            self,
+            BfvProofStatement::Addition { .. }
+                | BfvProofStatement::RelinearizedProduct { .. }
+                | BfvProofStatement::KeyGeneration { .. }
+                | BfvProofStatement::ZeroDecryption { .. }
+        )
+    }
+
+    /// The number of rows this statement takes up in `A`.
+    fn num_rows(&self) -> usize {
+        match self {
+            BfvProofStatement::PublicKeyEncryption { .. } => 2,
+            BfvProofStatement::PrivateKeyEncryption { .. }
+            | BfvProofStatement::Decryption { .. } => 1,
+            // One row per ciphertext polynomial, which is 3 for an unrelinearized product.
+            BfvProofStatement::Addition { lhs, rhs, result } => [lhs, rhs, result]
+                .iter()
+                .map(|c| c.num_polynomials() as usize)
+                .max()
+                .unwrap(),
+            // One row per polynomial of the relinearized product.
+            BfvProofStatement::RelinearizedProduct { result, .. } => {
+                usize::max(result.num_polynomials() as usize, 2)
+            }
+            BfvProofStatement::PlaintextAddition {
+                ciphertext, result, ..
+            }
+            | BfvProofStatement::PlaintextMultiplication {
+                ciphertext, result, ..
+            } => usize::max(
+                ciphertext.num_polynomials() as usize,
+                result.num_polynomials() as usize,
+            ),
+            // One row for the public key and one per key switching key.
+            BfvProofStatement::KeyGeneration { switching_keys, .. } => {
+                1 + switching_keys.iter().map(|k| k.keys.len()).sum::<usize>()
+            }
+            // One row for the public key and one per ciphertext.
+            BfvProofStatement::ZeroDecryption { ciphertexts, .. } => 1 + ciphertexts.len(),
+        }
+    }
+
+    /// The number of columns this statement takes up in the key generation block of `A`.
+    fn num_key_generation_cols(&self) -> usize {
+        match self {
+            // s, one target per key switching key, and one error per row.
+            BfvProofStatement::KeyGeneration { switching_keys, .. } => {
+                1 + switching_keys.len() + self.num_rows()
+            }
+            // s and one error per row.
+            BfvProofStatement::ZeroDecryption { .. } => 1 + self.num_rows(),
+            _ => 0,
+        }
     }
 }
 
@@ -120,6 +338,21 @@ pub enum BfvWitness<'s> {
         /// The private key used for the decryption.
         private_key: Cow<'s, SecretKey>,
     },
+    /// A witness for the [`BfvProofStatement::Addition`],
+    /// [`BfvProofStatement::RelinearizedProduct`], [`BfvProofStatement::PlaintextAddition`], and
+    /// [`BfvProofStatement::PlaintextMultiplication`] variants. The only secret in these is the
+    /// message, and the remaining witness is derived from it and the statement.
+    Evaluation,
+    /// A witness for the [`BfvProofStatement::KeyGeneration`] variant.
+    KeyGeneration {
+        /// The private key the keys were generated from.
+        private_key: Cow<'s, SecretKey>,
+    },
+    /// A witness for the [`BfvProofStatement::ZeroDecryption`] variant.
+    ZeroDecryption {
+        /// The private key the public key was generated from.
+        private_key: Cow<'s, SecretKey>,
+    },
 }
 
 /// A BFV message, which is a SEAL plaintext and an optional coefficient bound.
@@ -161,6 +394,24 @@ type Z<const N: usize, B> = Zq<N, BarrettBackend<N, B>>;
 ///    the offsets are ordered in blocks `d, r, pk, e[0], e[1], sk, e`, with the size of each block
 ///    depending on the number of messages, statements, and public vs. private statements. This is
 ///    almost impossible to express via text, but should be easy to follow in the example below.
+/// 4. Homomorphic evaluation statements take up one row per ciphertext polynomial. An addition
+///    `c_out = c_a + c_b` has a zero row in `A`, with `c_out[i] - c_a[i] - c_b[i]` in `T`, and a
+///    relinearized product likewise has `c_out[i]` minus the recomputed product in `T`. A
+///    plaintext addition `c_out = c + m` uses the `d` and `r` blocks in its first row, with
+///    `c_out[i] - c[i]` in `T`. A plaintext multiplication `c_out = c * m` has `c[i]` in the
+///    message block and `-t * c[i]` in its `r` block, where the corresponding entry of `S` flags
+///    the coefficients of `m` that SEAL lifts to `m - t`.
+/// 5. Key generation statements take up one row for the public key and one per key switching key,
+///    and their columns come after all of the above in blocks `s, k, e` per statement, where `k`
+///    holds the function of `s` each set of key switching keys encrypts. The public key row is
+///    `p[1] * s + e = -p[0]`. SEAL encrypts `k` in key `i` of a set under only the `i`th modulus
+///    `q_i`, scaled by the special modulus `P`. Over the full key modulus, this is
+///    `c[1] * s + e - F_i * k = -c[0]`, where `F_i` is the CRT basis element that is `P` modulo
+///    `q_i` and `0` modulo `P` and the other data moduli.
+/// 6. Zero decryption statements take up one row for the public key and one per ciphertext, and
+///    share the key generation columns in blocks `s, e` per statement. The public key row is as
+///    above, and each ciphertext row is `c[1] * s + e = -c[0]`, where `e` is only bounded by the
+///    decryption noise.
 ///
 /// For example, if we have two public key statements and one private key statement for three
 /// separate messages:
@@ -209,13 +460,17 @@ type Z<const N: usize, B> = Zq<N, BarrettBackend<N, B>>;
 /// encryptions (public or private) of a single plaintext message, like we do for the delta scaling
 /// parameter. However, since the remainder is held in each [`BfvWitness`], I've gone with the less
 /// surprising implementation where we have a remainder witness for each statement.
+///
+/// # Errors
+/// Returns [`ProofError::MismatchedWitness`] if there isn't exactly one witness per statement, or
+/// a witness is a different variant than its statement.
 pub fn generate_prover_knowledge<P, B, const N: usize>(
     statements: &[BfvProofStatement<'_>],
     messages: &[BfvMessage],
     witness: &[BfvWitness<'_>],
     params: &P,
     ctx: &Context,
-) -> LogProofProverKnowledge<Z<N, B>>
+) -> Result<LogProofProverKnowledge<Z<N, B>>, ProofError>
 where
     B: BarrettConfig<N>,
     P: StatementParams,
@@ -227,9 +482,9 @@ where
     let LogProofVerifierKnowledge { a, t, bounds, f } =
         generate_verifier_knowledge(statements, &msg_bounds, params, ctx);
 
-    let s = compute_s(statements, messages, witness, params, ctx);
+    let s = compute_s(statements, messages, witness, params, ctx)?;
 
-    LogProofProverKnowledge::new(&a, &s, &t, &bounds, &f)
+    Ok(LogProofProverKnowledge::new(&a, &s, &t, &bounds, &f))
 }
 
 /// Generate only the [`LogProofVerifierKnowledge`] for a given set of [`BfvProofStatement`]s.
@@ -272,12 +527,14 @@ where
 
     let mut row = 0;
     for s in statements {
-        // m*d block
-        let msg_idx = s.message_id();
-        a.set(row, msg_idx, d.clone());
+        if s.is_public() || s.is_private() {
+            // m*d block
+            let msg_idx = s.message_id();
+            a.set(row, msg_idx, d.clone());
 
-        // r block
-        a.set(row, offsets.remainder, Polynomial::one());
+            // r block
+            a.set(row, offsets.remainder, Polynomial::one());
+        }
 
         match s {
             // sk, e blocks
@@ -311,6 +568,87 @@ where
 
                 row += 1;
             }
+            // no blocks; the rows of T must be zero
+            BfvProofStatement::Addition { .. } | BfvProofStatement::RelinearizedProduct { .. } => {
+                row += s.num_rows();
+            }
+            // m*d, r blocks for the first ciphertext polynomial only
+            BfvProofStatement::PlaintextAddition { message_id, .. } => {
+                a.set(row, *message_id, d.clone());
+                a.set(row, offsets.remainder, Polynomial::one());
+                offsets.inc_evaluation();
+
+                row += s.num_rows();
+            }
+            // m*c, h*(-t*c) blocks, where h flags the message coefficients that SEAL lifts to
+            // negative values
+            BfvProofStatement::PlaintextMultiplication {
+                message_id,
+                ciphertext,
+                ..
+            } => {
+                let t: Z<N, B> = Zq::from(params.plain_modulus());
+                let c = WithCtx(ctx, ciphertext).as_poly_vec();
+                for (i, c_i) in c.into_iter().enumerate() {
+                    a.set(row + i, offsets.remainder, (&c_i * t).neg());
+                    a.set(row + i, *message_id, c_i);
+                }
+                offsets.inc_evaluation();
+
+                row += s.num_rows();
+            }
+            // s, k, e blocks
+            BfvProofStatement::KeyGeneration {
+                public_key,
+                switching_keys,
+            } => {
+                let s_col = offsets.key_generation;
+                let mut e_col = s_col + 1 + switching_keys.len();
+
+                let p1 = WithCtx(ctx, public_key.as_ref())
+                    .as_poly_vec()
+                    .pop()
+                    .unwrap();
+                a.set(row, s_col, p1);
+                a.set(row, e_col, Polynomial::one());
+                row += 1;
+                e_col += 1;
+
+                let factors = key_switching_factors::<P, B, N>(params);
+                for (j, ksk) in switching_keys.iter().enumerate() {
+                    debug_assert_eq!(ksk.keys.len(), factors.len());
+
+                    for (key, factor) in ksk.keys.iter().zip(&factors) {
+                        let c1 = WithCtx(ctx, key).as_poly_vec().pop().unwrap();
+                        let factor = Polynomial {
+                            coeffs: vec![*factor],
+                        };
+                        a.set(row, s_col, c1);
+                        a.set(row, s_col + 1 + j, factor.neg());
+                        a.set(row, e_col, Polynomial::one());
+                        row += 1;
+                        e_col += 1;
+                    }
+                }
+                offsets.inc_key_generation(s);
+            }
+            // s, e blocks
+            BfvProofStatement::ZeroDecryption {
+                public_key,
+                ciphertexts,
+            } => {
+                let s_col = offsets.key_generation;
+                let c1s = std::iter::once(WithCtx(ctx, public_key.as_ref()).as_poly_vec())
+                    .chain(ciphertexts.iter().map(|c| WithCtx(ctx, c).as_poly_vec()))
+                    .map(|mut c| c.pop().unwrap());
+                for (i, c1) in c1s.enumerate() {
+                    a.set(row + i, s_col, c1);
+                    a.set(row + i, s_col + 1 + i, Polynomial::one());
+                }
+
+                row += s.num_rows();
+                offsets.inc_key_generation(s);
+            }
         }
     }
 
@@ -323,7 +661,7 @@ fn compute_s<P, B, const N: usize>(
     witness: &[BfvWitness<'_>],
     params: &P,
     ctx: &Context,
-) -> PolynomialMatrix<Z<N, B>>
+) -> Result<PolynomialMatrix<Z<N, B>>, ProofError>
 where
     B: BarrettConfig<N>,
     P: StatementParams,
@@ -338,13 +676,22 @@ where
     }
 
     // r_i, u_i, e_i, sk, e blocks
-    for (i, w) in witness.iter().enumerate() {
-        match w {
+    if witness.len() != statements.len() {
+        return Err(ProofError::MismatchedWitness(usize::min(
+            witness.len(),
+            statements.len(),
+        )));
+    }
+    for (i, (w, statement)) in witness.iter().zip(statements).enumerate() {
+        match (w, statement) {
             // sk, e
-            BfvWitness::PrivateKeyEncryption {
-                private_key,
-                components: SymmetricComponents { e, r },
-            } => {
+            (
+                BfvWitness::PrivateKeyEncryption {
+                    private_key,
+                    components: SymmetricComponents { e, r },
+                },
+                BfvProofStatement::PrivateKeyEncryption { .. },
+            ) => {
                 let r = r.as_poly();
                 let sk = WithCtx(ctx, private_key.as_ref()).as_poly();
                 let e = e.as_poly_vec().pop().unwrap();
@@ -354,7 +701,10 @@ where
                 offsets.inc_private();
             }
             // r_i, u_i, e_i
-            BfvWitness::PublicKeyEncryption(AsymmetricComponents { u, e, r }) => {
+            (
+                BfvWitness::PublicKeyEncryption(AsymmetricComponents { u, e, r }),
+                BfvProofStatement::PublicKeyEncryption { .. },
+            ) => {
                 let r = r.as_poly();
                 let u = u.as_poly_vec().pop().unwrap();
                 let mut e = e.as_poly_vec();
@@ -367,8 +717,14 @@ where
                 s.set(offsets.public_e_1, 0, e1);
                 offsets.inc_public();
             }
-            BfvWitness::Decryption { private_key } => {
-                let pt = &messages[statements[i].message_id()].plaintext;
+            (
+                BfvWitness::Decryption { private_key },
+                BfvProofStatement::Decryption {
+                    message_id,
+                    ciphertext,
+                },
+            ) => {
+                let pt = &messages[*message_id].plaintext;
                 let r = SymmetricEncryptor::new(ctx, private_key)
                     .unwrap()
                     .encrypt_symmetric_return_components(pt)
@@ -377,7 +733,7 @@ where
                     .r
                     .as_poly();
                 let sk = WithCtx(ctx, private_key.as_ref()).as_poly();
-                let ct = WithCtx(ctx, statements[i].ciphertext()).as_poly_vec();
+                let ct = WithCtx(ctx, ciphertext).as_poly_vec();
                 let m = pt.as_poly();
                 let delta = params.delta();
                 let e = &m * delta + &r - &ct[0] - &ct[1] * &sk;
@@ -393,10 +749,124 @@ where
                 s.set(offsets.private_e, 0, e.neg());
                 offsets.inc_private();
             }
+            (
+                BfvWitness::Evaluation,
+                BfvProofStatement::Addition { .. } | BfvProofStatement::RelinearizedProduct { .. },
+            ) => {}
+            // r, where SEAL adds round(q * m / t) = d * m + r to the first polynomial
+            (
+                BfvWitness::Evaluation,
+                BfvProofStatement::PlaintextAddition {
+                    message_id,
+                    ciphertext,
+                    result,
+                },
+            ) => {
+                let m = messages[*message_id].plaintext.as_poly();
+                let c0 = WithCtx(ctx, ciphertext).as_poly_vec().swap_remove(0);
+                let out0 = WithCtx(ctx, result).as_poly_vec().swap_remove(0);
+                let r = out0 - c0 - m * params.delta();
+                s.set(offsets.remainder, 0, r);
+                offsets.inc_evaluation();
+            }
+            // h, where SEAL multiplies by the message lifted to m - t * h
+            (
+                BfvWitness::Evaluation,
+                BfvProofStatement::PlaintextMultiplication { message_id, .. },
+            ) => {
+                let pt = &messages[*message_id].plaintext;
+                let threshold = params.plain_modulus().div_ceil(2);
+                let h = Polynomial {
+                    coeffs: strip_trailing_value(
+                        (0..pt.len())
+                            .map(|i| {
+                                if pt.get_coefficient(i) >= threshold {
+                                    Zq::one()
+                                } else {
+                                    Zq::zero()
+                                }
+                            })
+                            .collect::<Vec<_>>(),
+                        Zq::zero(),
+                    ),
+                };
+                s.set(offsets.remainder, 0, h);
+                offsets.inc_evaluation();
+            }
+            // s, k, e where each key satisfies c[1] * s + e - factor * k = -c[0]
+            (
+                BfvWitness::KeyGeneration { private_key },
+                BfvProofStatement::KeyGeneration {
+                    public_key,
+                    switching_keys,
+                },
+            ) => {
+                let sk: Polynomial<Z<N, B>> = WithCtx(ctx, private_key.as_ref()).as_poly();
+                let s_col = offsets.key_generation;
+                let mut e_col = s_col + 1 + switching_keys.len();
+
+                let pk = WithCtx(ctx, public_key.as_ref()).as_poly_vec();
+                let e = (&pk[1] * &sk + &pk[0])
+                    .vartime_div_rem_restricted_rhs(&f)
+                    .1
+                    .neg();
+                s.set(s_col, 0, sk.clone());
+                s.set(e_col, 0, e);
+                e_col += 1;
+
+                let factors = key_switching_factors::<P, B, N>(params);
+                for (j, ksk) in switching_keys.iter().enumerate() {
+                    let k = match ksk.target {
+                        KeySwitchingTarget::Relinearization => {
+                            (&sk * &sk).vartime_div_rem_restricted_rhs(&f).1
+                        }
+                        KeySwitchingTarget::Galois(g) => {
+                            apply_galois(&sk, g, params.degree() as usize)
+                        }
+                    };
+
+                    for (key, factor) in ksk.keys.iter().zip(&factors) {
+                        let c = WithCtx(ctx, key).as_poly_vec();
+                        let e = (&c[1] * &sk + &c[0] - &k * factor)
+                            .vartime_div_rem_restricted_rhs(&f)
+                            .1
+                            .neg();
+                        s.set(e_col, 0, e);
+                        e_col += 1;
+                    }
+
+                    s.set(s_col + 1 + j, 0, k);
+                }
+                offsets.inc_key_generation(statement);
+            }
+            // s, e where each row satisfies c[1] * s + e = -c[0]
+            (
+                BfvWitness::ZeroDecryption { private_key },
+                BfvProofStatement::ZeroDecryption {
+                    public_key,
+                    ciphertexts,
+                },
+            ) => {
+                let sk: Polynomial<Z<N, B>> = WithCtx(ctx, private_key.as_ref()).as_poly();
+                let s_col = offsets.key_generation;
+                s.set(s_col, 0, sk.clone());
+
+                let cs = std::iter::once(WithCtx(ctx, public_key.as_ref()).as_poly_vec())
+                    .chain(ciphertexts.iter().map(|c| WithCtx(ctx, c).as_poly_vec()));
+                for (j, c) in cs.enumerate() {
+                    let e = (&c[1] * &sk + &c[0])
+                        .vartime_div_rem_restricted_rhs(&f)
+                        .1
+                        .neg();
+                    s.set(s_col + 1 + j, 0, e);
+                }
+                offsets.inc_key_generation(statement);
+            }
+            _ => return Err(ProofError::MismatchedWitness(i)),
         }
     }
 
-    s
+    Ok(s)
 }
 
 fn compute_t<B, const N: usize>(
@@ -408,13 +878,62 @@ where
 {
     let rows = statements
         .iter()
-        .flat_map(|s| {
-            let mut c = WithCtx(ctx, s.ciphertext()).as_poly_vec();
-            // only include first ciphertext element for private statements
-            if s.is_private() {
-                c.pop().unwrap();
+        .flat_map(|s| match s {
+            BfvProofStatement::Addition { lhs, rhs, result } => {
+                let n = s.num_rows();
+                let lhs = padded_poly_vec(ctx, lhs, n);
+                let rhs = padded_poly_vec(ctx, rhs, n);
+                let result = padded_poly_vec(ctx, result, n);
+                (0..n)
+                    .map(|i| &result[i] - &lhs[i] - &rhs[i])
+                    .collect::<Vec<_>>()
+            }
+            BfvProofStatement::RelinearizedProduct {
+                product, result, ..
+            } => {
+                let n = s.num_rows();
+                let product = padded_poly_vec(ctx, product, n);
+                let result = padded_poly_vec(ctx, result, n);
+                (0..n).map(|i| &result[i] - &product[i]).collect::<Vec<_>>()
+            }
+            BfvProofStatement::PlaintextAddition {
+                ciphertext, result, ..
+            } => {
+                let n = s.num_rows();
+                let c = padded_poly_vec(ctx, ciphertext, n);
+                let result = padded_poly_vec(ctx, result, n);
+                (0..n).map(|i| &result[i] - &c[i]).collect::<Vec<_>>()
+            }
+            BfvProofStatement::PlaintextMultiplication { result, .. } => {
+                padded_poly_vec(ctx, result, s.num_rows())
+            }
+            BfvProofStatement::KeyGeneration {
+                public_key,
+                switching_keys,
+            } => std::iter::once(WithCtx(ctx, public_key.as_ref()).as_poly_vec())
+                .chain(
+                    switching_keys
+                        .iter()
+                        .flat_map(|ksk| &ksk.keys)
+                        .map(|key| WithCtx(ctx, key).as_poly_vec()),
+                )
+                .map(|mut c| c.swap_remove(0).neg())
+                .collect::<Vec<_>>(),
+            BfvProofStatement::ZeroDecryption {
+                public_key,
+                ciphertexts,
+            } => std::iter::once(WithCtx(ctx, public_key.as_ref()).as_poly_vec())
+                .chain(ciphertexts.iter().map(|c| WithCtx(ctx, c).as_poly_vec()))
+                .map(|mut c| c.swap_remove(0).neg())
+                .collect::<Vec<_>>(),
+            _ => {
+                let mut c = WithCtx(ctx, s.ciphertext()).as_poly_vec();
+                // only include first ciphertext element for private statements
+                if s.is_private() {
+                    c.pop().unwrap();
+                }
+                c
             }
-            c
         })
         .collect::<Vec<_>>();
     let t = PolynomialMatrix::from(rows);
@@ -426,6 +945,20 @@ where
     t
 }
 
+/// Get the polynomials of a ciphertext, padded with zeros to the given length.
+fn padded_poly_vec<B, const N: usize>(
+    ctx: &Context,
+    ciphertext: &Ciphertext,
+    len: usize,
+) -> Vec<Polynomial<Z<N, B>>>
+where
+    B: BarrettConfig<N>,
+{
+    let mut c = WithCtx(ctx, ciphertext).as_poly_vec();
+    c.resize(len, Polynomial::zero());
+    c
+}
+
 fn compute_bounds<P, B, const N: usize>(
     statements: &[BfvProofStatement<'_>],
     msg_bounds: &[Option<Bounds>],
@@ -445,13 +978,16 @@ where
     let u_bound = Bounds(vec![U_COEFFICIENT_BOUND; degree]);
     let e_bound = Bounds(vec![E_COEFFICIENT_BOUND; degree]);
     let s_bound = Bounds(vec![S_COEFFICIENT_BOUND; degree]);
-    // very liberal bound, the max that satisfies correctness
-    let delta_div_2_bits = params
-        .delta::<N, B>()
-        .into_bigint()
-        .div(NonZero::from_uint(Uint::from(2u8)))
-        .ceil_log2();
+    // The proof shows |e| <= 2^b, so take the largest b with 2^b <= floor(delta / 2). Any
+    // larger and noise past delta / 2 would pass, letting a nonzero plaintext decrypt to zero.
+    let delta_div_2_bits =
+        calculate_wide_delta(params.plain_modulus(), params.ciphertext_modulus())
+            .div(NonZero::from_uint(Uint::from(2u8)))
+            .log2();
     let decrypt_e_bound = Bounds(vec![delta_div_2_bits; degree]);
+    let lift_bound = Bounds(vec![LIFT_COEFFICIENT_BOUND; degree]);
+    // s^2 has coefficients of magnitude at most the degree
+    let relin_target_bound = Bounds(vec![(params.degree() + 1).ceil_log2(); degree]);
 
     // insert them
     for i in 0..IdxOffsets::num_messages(statements) {
@@ -466,7 +1002,13 @@ where
         );
     }
     for s in statements {
-        bounds.set(offsets.remainder, 0, r_bound.clone());
+        if s.has_remainder() {
+            let remainder_bound = match s {
+                BfvProofStatement::PlaintextMultiplication { .. } => &lift_bound,
+                _ => &r_bound,
+            };
+            bounds.set(offsets.remainder, 0, remainder_bound.clone());
+        }
         match s {
             BfvProofStatement::PrivateKeyEncryption { .. } => {
                 bounds.set(offsets.private_a, 0, s_bound.clone());
@@ -484,6 +1026,37 @@ where
                 bounds.set(offsets.private_e, 0, decrypt_e_bound.clone());
                 offsets.inc_private();
             }
+            BfvProofStatement::Addition { .. } | BfvProofStatement::RelinearizedProduct { .. } => {}
+            BfvProofStatement::PlaintextAddition { .. }
+            | BfvProofStatement::PlaintextMultiplication { .. } => {
+                offsets.inc_evaluation();
+            }
+            BfvProofStatement::KeyGeneration { switching_keys, .. } => {
+                let s_col = offsets.key_generation;
+                bounds.set(s_col, 0, s_bound.clone());
+                for (j, ksk) in switching_keys.iter().enumerate() {
+                    let target_bound = match ksk.target {
+                        KeySwitchingTarget::Relinearization => &relin_target_bound,
+                        // a permutation of s, up to sign
+                        KeySwitchingTarget::Galois(_) => &s_bound,
+                    };
+                    bounds.set(s_col + 1 + j, 0, target_bound.clone());
+                }
+                let e_col = s_col + 1 + switching_keys.len();
+                for k in 0..s.num_rows() {
+                    bounds.set(e_col + k, 0, e_bound.clone());
+                }
+                offsets.inc_key_generation(s);
+            }
+            BfvProofStatement::ZeroDecryption { .. } => {
+                let s_col = offsets.key_generation;
+                bounds.set(s_col, 0, s_bound.clone());
+                bounds.set(s_col + 1, 0, e_bound.clone());
+                for k in 1..s.num_rows() {
+                    bounds.set(s_col + 1 + k, 0, decrypt_e_bound.clone());
+                }
+                offsets.inc_key_generation(s);
+            }
         }
     }
     bounds
@@ -505,6 +1078,77 @@ where
     }
 }
 
+/// Compute the factor SEAL scales the target of the `i`th key in each set of key switching keys
+/// by. SEAL adds `P * k` to only the `i`th data modulus `q_i` of a key, where `P` is the special
+/// modulus, so over the full key modulus `P * q_0 * ... * q_{l-1}` the factor is the CRT basis
+/// element that is `P` modulo `q_i` and `0` modulo `P` and every other data modulus. This is
+/// `P * q_hat_i * (q_hat_i^-1 mod q_i)`, where `q_hat_i` is the product of the other data moduli.
+fn key_switching_factors<P, B, const N: usize>(params: &P) -> Vec<Z<N, B>>
+where
+    B: BarrettConfig<N>,
+    P: StatementParams,
+{
+    let qs = params.ciphertext_modulus();
+    let (special, data) = match qs.split_last() {
+        Some((special, data)) if !data.is_empty() => (*special, data),
+        // Without a special modulus, SEAL can't generate key switching keys.
+        _ => return vec![],
+    };
+
+    let mul_mod = |a: u64, b: u64, m: u64| ((a as u128 * b as u128) % m as u128) as u64;
+
+    data.iter()
+        .enumerate()
+        .map(|(i, q_i)| {
+            let others = || {
+                data.iter()
+                    .enumerate()
+                    .filter(move |(j, _)| *j != i)
+                    .map(|(_, q_j)| *q_j)
+            };
+
+            // Since q_i is prime, invert q_hat_i with Fermat's little theorem.
+            let q_hat_i = others().fold(1, |acc, q_j| mul_mod(acc, q_j % q_i, *q_i));
+            let mut q_hat_i_inv = 1;
+            let mut base = q_hat_i;
+            let mut exp = q_i - 2;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    q_hat_i_inv = mul_mod(q_hat_i_inv, base, *q_i);
+                }
+                base = mul_mod(base, base, *q_i);
+                exp >>= 1;
+            }
+
+            // Multiply out in the ring, so the factor is reduced however the ring relates to the
+            // key modulus.
+            others().fold(
+                Zq::from(q_hat_i_inv) * Zq::from(special),
+                |acc: Z<N, B>, q_j| acc * Zq::from(q_j),
+            )
+        })
+        .collect()
+}
+
+/// Apply the Galois automorphism `x -> x^g` for odd `g` to a polynomial in `R[x]/(x^n + 1)`.
+fn apply_galois<R: Ring>(poly: &Polynomial<R>, g: u64, degree: usize) -> Polynomial<R> {
+    let mut coeffs = vec![R::zero(); degree];
+
+    for (i, c) in poly.coeffs.iter().enumerate() {
+        // x^(i * g) reduced using x^n = -1
+        let j = ((i as u64 * g) % (2 * degree as u64)) as usize;
+        if j < degree {
+            coeffs[j] = c.clone();
+        } else {
+            coeffs[j - degree] = -c.clone();
+        }
+    }
+
+    Polynomial {
+        coeffs: strip_trailing_value(coeffs, R::zero()),
+    }
+}
+
 /// Represents the column offsets in `A` and the row offsets in `S` for the various fields.
 //
 // Hm. This could be an iterator that spits out the next ProofStatement with the appropriate indices.
@@ -519,8 +1163,15 @@ struct IdxOffsets {
     public_e_1: usize,
     /// The private key block occurs next.
     private_a: usize,
-    /// The private key statement's error component block occurs last.
+    /// The private key statement's error component block occurs next.
     private_e: usize,
+    /// The key generation statements' `s, k, e` blocks and the zero decryption statements' `s, e`
+    /// blocks occur last.
+    key_generation: usize,
+    /// The total number of rows in `A`.
+    rows: usize,
+    /// The total number of columns in `A`.
+    cols: usize,
 }
 
 impl IdxOffsets {
@@ -529,15 +1180,16 @@ impl IdxOffsets {
         let num_messages = Self::num_messages(statements);
         let num_public = Self::num_public(statements);
         let num_private = Self::num_private(statements);
-        let num_statements = statements.len();
+        let num_remainders = statements.iter().filter(|s| s.has_remainder()).count();
 
         // Offsets
         let remainder = num_messages;
-        let public_key = remainder + num_statements;
+        let public_key = remainder + num_remainders;
         let public_e_0 = public_key + num_public;
         let public_e_1 = public_e_0 + num_public;
         let private_a = public_e_1 + num_public;
         let private_e = private_a + num_private;
+        let key_generation = private_e + num_private;
 
         Self {
             remainder,
@@ -546,14 +1198,19 @@ impl IdxOffsets {
             public_e_1,
             private_a,
             private_e,
+            key_generation,
+            rows: statements.iter().map(|s| s.num_rows()).sum(),
+            cols: key_generation
+                + statements
+                    .iter()
+                    .map(|s| s.num_key_generation_cols())
+                    .sum::<usize>(),
         }
     }
 
     /// Return the (row, col) shape of A.
     fn a_shape(&self) -> (usize, usize) {
-        let num_private = self.private_e - self.private_a;
-        let num_public = self.public_e_0 - self.public_key;
-        (num_public * 2 + num_private, self.private_e + num_private)
+        (self.rows, self.cols)
     }
 
     /// Record that a private statement or witness has been inserted into `A` or `S`, respectively
@@ -573,11 +1230,23 @@ impl IdxOffsets {
         self.public_e_1 += 1;
     }
 
+    /// Record that an evaluation statement or witness has been inserted into `A` or `S`,
+    /// respectively bumping the indices.
+    fn inc_evaluation(&mut self) {
+        self.remainder += 1;
+    }
+
+    /// Record that a key generation statement or witness has been inserted into `A` or `S`,
+    /// respectively bumping the indices.
+    fn inc_key_generation(&mut self, statement: &BfvProofStatement<'_>) {
+        self.key_generation += statement.num_key_generation_cols();
+    }
+
     fn num_messages(statements: &[BfvProofStatement<'_>]) -> usize {
         statements
             .iter()
-            .fold(0usize, |max, s| usize::max(max, s.message_id()))
-            + 1
+            .filter_map(|s| s.try_message_id())
+            .fold(0usize, |max, id| usize::max(max, id + 1))
     }
 
     fn num_private(statements: &[BfvProofStatement<'_>]) -> usize {
@@ -585,7 +1254,7 @@ impl IdxOffsets {
     }
 
     fn num_public(statements: &[BfvProofStatement<'_>]) -> usize {
-        statements.len() - Self::num_private(statements)
+        statements.iter().filter(|s| s.is_public()).count()
     }
 }
 
@@ -622,37 +1291,17 @@ impl<const N: usize, B: BarrettConfig<N>> AsPolynomial<Z<N, B>> for WithCtx<'_,
 impl<const N: usize, B: BarrettConfig<N>> AsPolynomials<Z<N, B>> for PolynomialArray {
     fn as_poly_vec(&self) -> Vec<Polynomial<Z<N, B>>> {
         let chunk_size = self.coeff_modulus_size() as usize;
-        let bigint_values = self
+        let values = self
             .as_multiprecision_u64s()
             .unwrap()
             .chunks(chunk_size)
-            .map(|x| {
-                // If this is a plaintext, it likely only has one limb, so we need to extend
-                // it with zero limbs.
-                if x.len() < N {
-                    let mut words = [0; N];
-                    words[0..x.len()].copy_from_slice(x);
-                    Uint::<N>::from_words(words)
-                } else {
-                    // On the other hand, SEAL sometimes encodes a multiprecision integer
-                    // with more limbs than needed. The trailing limbs can be safely removed
-                    // since they are 0.
-                    let words = x[0..N].try_into().unwrap();
-                    Uint::<N>::from_words(words)
-                }
-            })
+            .map(reduce_words::<N, B>)
             .collect::<Vec<_>>();
 
-        bigint_values
+        values
             .chunks(self.poly_modulus_degree() as usize)
-            .map(|x| {
-                let leading_zeros_removed = strip_trailing_value(x.to_vec(), Uint::<N>::ZERO);
-                Polynomial {
-                    coeffs: leading_zeros_removed
-                        .iter()
-                        .map(|y| Zq::try_from(*y).unwrap())
-                        .collect::<Vec<_>>(),
-                }
+            .map(|x| Polynomial {
+                coeffs: strip_trailing_value(x.to_vec(), Zq::zero()),
             })
             .collect()
     }
@@ -715,11 +1364,15 @@ impl<T: Zero + Clone + Debug + PartialEq> MatrixSet<T> for Matrix<T> {
     }
 }
 
-fn calculate_ciphertext_modulus(qs: Vec<u64>) -> Uint<4> {
+/// The number of limbs used to hold the data modulus and delta before reducing them into the
+/// proof's ring. Wide enough for the 825-bit data modulus at lattice dimension 32768.
+const WIDE_LIMBS: usize = 16;
+
+fn calculate_ciphertext_modulus(qs: Vec<u64>) -> Uint<WIDE_LIMBS> {
     // Calculate the data coefficient modulus, which for fields with more
     // that one modulus in the coefficient modulus set is equal to the
     // product of all but the last moduli in the set.
-    let mut data_modulus = Uint::<4>::from_u8(1);
+    let mut data_modulus = Uint::<WIDE_LIMBS>::from_u8(1);
     if qs.len() == 1 {
         data_modulus = data_modulus
             .checked_mul(&Uint::<1>::from_u64(qs[0]))
@@ -732,15 +1385,47 @@ fn calculate_ciphertext_modulus(qs: Vec<u64>) -> Uint<4> {
     data_modulus
 }
 
-fn calculate_delta<const N: usize, B: BarrettConfig<N>>(p: u64, qs: Vec<u64>) -> Z<N, B> {
+fn calculate_wide_delta(p: u64, qs: Vec<u64>) -> Uint<WIDE_LIMBS> {
     let q_bigint = calculate_ciphertext_modulus(qs);
     let p_bigint = NonZero::new(Uint::from(p)).unwrap();
-    let delta = q_bigint.div_rem(&p_bigint).0;
+    q_bigint.div_rem(&p_bigint).0
+}
+
+fn calculate_delta<const N: usize, B: BarrettConfig<N>>(p: u64, qs: Vec<u64>) -> Z<N, B> {
+    // When the ring is one RNS group of a wider data modulus, delta exceeds the group's modulus,
+    // so reduce it rather than truncating.
+    let modulus = NonZero::new(extend_bigint::<WIDE_LIMBS, N>(&B::MODULUS)).unwrap();
+    let delta = calculate_wide_delta(p, qs).rem(&modulus);
     let limbs = delta.as_limbs().map(|l| l.into());
     let delta_uint = Uint::<N>::from_words(limbs[0..N].try_into().unwrap());
     Zq::try_from(delta_uint).unwrap()
 }
 
+/// Reduce the little-endian multiprecision integer `words` into `Z<N, B>`.
+fn reduce_words<const N: usize, B: BarrettConfig<N>>(words: &[u64]) -> Z<N, B> {
+    let (low, high) = words.split_at(words.len().min(N));
+
+    // SEAL often encodes a multiprecision integer with more limbs than needed, padding with
+    // zeros. In that case, the value usually already lies in the ring.
+    if high.iter().all(|w| *w == 0) {
+        let mut padded = [0; N];
+        padded[0..low.len()].copy_from_slice(low);
+
+        if let Ok(x) = Zq::try_from(Uint::<N>::from_words(padded)) {
+            return x;
+        }
+    }
+
+    // Otherwise, the ring is one RNS group of a wider modulus. Reduce with Horner's method in
+    // radix 2^64.
+    let radix = Zq::from(u64::MAX) + Zq::one();
+
+    words
+        .iter()
+        .rev()
+        .fold(Zq::zero(), |acc: Z<N, B>, w| acc * radix + Zq::from(*w))
+}
+
 fn strip_trailing_value<T>(mut v: Vec<T>, trim_value: T) -> Vec<T>
 where
     T: Eq,
@@ -757,12 +1442,17 @@ mod tests {
     use merlin::Transcript;
     use rand::Rng;
     use seal_fhe::{
-        BfvEncryptionParametersBuilder, CoefficientModulus, Encryptor, KeyGenerator, PlainModulus,
-        SecurityLevel, SymAsym,
+        BFVEvaluator, BfvEncryptionParametersBuilder, CoefficientModulus, Encryptor, Evaluator,
+        KeyGenerator, PlainModulus, SecurityLevel, SymAsym,
     };
 
+    use sunscreen_math::ring::RingModulus;
+
     use crate::{
-        rings::{ZqSeal128_1024, ZqSeal128_4096},
+        rings::{
+            SealQ128_1024, SealQ128_16384, ZqSeal128_1024, ZqSeal128_16384_2, ZqSeal128_4096,
+            ZqSeal128_8192,
+        },
         InnerProductVerifierKnowledge, LogProof, LogProofGenerators, ProofError,
     };
 
@@ -792,6 +1482,19 @@ mod tests {
         assert_eq!(delta.val.as_words(), &[53 * 53 / 4, 0]);
     }
 
+    #[test]
+    fn delta_calculation_reduces_into_rns_group() {
+        let qs = SealQ128_16384::Q.to_vec();
+        let wide_delta = calculate_wide_delta(65537, qs.clone());
+
+        // Delta is wider than the group's modulus, so it must be reduced rather than truncated.
+        let modulus: Uint<2> = ZqSeal128_16384_2::field_modulus();
+        assert!(Log2::log2(&wide_delta) > Log2::log2(&modulus));
+
+        let delta: ZqSeal128_16384_2 = calculate_delta(65537, qs);
+        assert_eq!(delta, reduce_words(wide_delta.as_words()));
+    }
+
     #[test]
     fn one_public_statement() {
         test_statements_with(1, 0, 0, 0)
@@ -832,6 +1535,398 @@ mod tests {
         ctx.prove_and_verify(&test_fixture).unwrap();
     }
 
+    #[test]
+    fn zero_decryption_statement() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture();
+        let pt = ctx.random_plaintext();
+        let ct = ctx.encryptor.encrypt(&pt).unwrap();
+        let zero = ctx.evaluator.sub_plain(&ct, &pt).unwrap();
+        test_fixture
+            .statements
+            .push(BfvProofStatement::ZeroDecryption {
+                public_key: Cow::Borrowed(&ctx.public_key),
+                ciphertexts: vec![zero],
+            });
+        test_fixture.witness.push(BfvWitness::ZeroDecryption {
+            private_key: Cow::Borrowed(&ctx.secret_key),
+        });
+
+        ctx.prove_and_verify(&test_fixture).unwrap();
+
+        let pk = generate_prover_knowledge(
+            &test_fixture.statements,
+            &test_fixture.messages,
+            &test_fixture.witness,
+            &ctx.params,
+            &ctx.ctx,
+        )
+        .unwrap();
+
+        // Claim a ciphertext of a nonzero plaintext decrypts to zero instead.
+        let mut other = pt.clone();
+        if other.len() == 0 {
+            other.resize(1);
+        }
+        other.set_coefficient(0, (other.get_coefficient(0) + 1) % 32);
+        let wrong = ctx.evaluator.sub_plain(&ct, &other).unwrap();
+        test_fixture.statements.pop();
+        test_fixture
+            .statements
+            .push(BfvProofStatement::ZeroDecryption {
+                public_key: Cow::Borrowed(&ctx.public_key),
+                ciphertexts: vec![wrong],
+            });
+        let msg_bounds = test_fixture
+            .messages
+            .iter()
+            .map(|m| m.bounds.clone())
+            .collect::<Vec<_>>();
+        let vk = generate_verifier_knowledge(
+            &test_fixture.statements,
+            &msg_bounds,
+            &ctx.params,
+            &ctx.ctx,
+        );
+
+        assert!(prove_and_verify_against(&pk, &vk).is_err());
+    }
+
+    #[test]
+    fn mismatched_witness_is_an_error() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture_with(1, 1, 0, 0);
+        let generate = |witness: &[BfvWitness<'_>]| {
+            generate_prover_knowledge::<_, SealQ128_1024, 1>(
+                &test_fixture.statements,
+                &test_fixture.messages,
+                witness,
+                &ctx.params,
+                &ctx.ctx,
+            )
+            .map(|_| ())
+        };
+
+        test_fixture.witness.swap(0, 1);
+        assert_eq!(
+            generate(&test_fixture.witness),
+            Err(ProofError::MismatchedWitness(0))
+        );
+        assert_eq!(
+            generate(&test_fixture.witness[..1]),
+            Err(ProofError::MismatchedWitness(1))
+        );
+    }
+
+    #[test]
+    fn addition_statement() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture_with(1, 1, 0, 0);
+        let lhs = test_fixture.statements[0].ciphertext().clone();
+        let rhs = test_fixture.statements[1].ciphertext().clone();
+        let result = ctx.evaluator.add(&lhs, &rhs).unwrap();
+        test_fixture
+            .statements
+            .push(BfvProofStatement::Addition { lhs, rhs, result });
+        test_fixture.witness.push(BfvWitness::Evaluation);
+
+        ctx.prove_and_verify(&test_fixture).unwrap();
+    }
+
+    #[test]
+    fn incorrect_addition_statement_fails() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture_with(1, 1, 0, 0);
+        let lhs = test_fixture.statements[0].ciphertext().clone();
+        let rhs = test_fixture.statements[1].ciphertext().clone();
+        let sum = ctx.evaluator.add(&lhs, &rhs).unwrap();
+        let difference = ctx.evaluator.sub(&lhs, &rhs).unwrap();
+        test_fixture.statements.push(BfvProofStatement::Addition {
+            lhs: lhs.clone(),
+            rhs: rhs.clone(),
+            result: sum,
+        });
+        test_fixture.witness.push(BfvWitness::Evaluation);
+        let pk = generate_prover_knowledge(
+            &test_fixture.statements,
+            &test_fixture.messages,
+            &test_fixture.witness,
+            &ctx.params,
+            &ctx.ctx,
+        )
+        .unwrap();
+
+        // Claim the difference is the sum instead.
+        test_fixture.statements.pop();
+        test_fixture.statements.push(BfvProofStatement::Addition {
+            lhs,
+            rhs,
+            result: difference,
+        });
+        let msg_bounds = test_fixture
+            .messages
+            .iter()
+            .map(|m| m.bounds.clone())
+            .collect::<Vec<_>>();
+        let vk = generate_verifier_knowledge(
+            &test_fixture.statements,
+            &msg_bounds,
+            &ctx.params,
+            &ctx.ctx,
+        );
+
+        assert!(prove_and_verify_against(&pk, &vk).is_err());
+    }
+
+    #[test]
+    fn relinearized_product_statement() {
+        // Relinearization needs a special modulus, which the 1024 modulus lacks.
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(64)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(4096, SecurityLevel::TC128).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::raw(32).unwrap())
+            .build()
+            .unwrap();
+        let ctx = Context::new_insecure(&params, false).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let public_key = gen.create_public_key();
+        let relin_keys = gen.create_relinearization_keys().unwrap();
+        let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+        let evaluator = BFVEvaluator::new(&ctx).unwrap();
+
+        let mut messages = vec![];
+        let mut statements = vec![];
+        let mut witness = vec![];
+        for (i, c) in [3, 7].into_iter().enumerate() {
+            let mut pt = Plaintext::new().unwrap();
+            pt.resize(1);
+            pt.set_coefficient(0, c);
+            let (ciphertext, components) = encryptor.encrypt_return_components(&pt).unwrap();
+            messages.push(BfvMessage {
+                plaintext: pt,
+                bounds: None,
+            });
+            statements.push(BfvProofStatement::PublicKeyEncryption {
+                message_id: i,
+                ciphertext,
+                public_key: Cow::Borrowed(&public_key),
+            });
+            witness.push(BfvWitness::PublicKeyEncryption(components));
+        }
+        let lhs = statements[0].ciphertext().clone();
+        let rhs = statements[1].ciphertext().clone();
+        let unrelinearized = evaluator.multiply(&lhs, &rhs).unwrap();
+        let product = evaluator.relinearize(&unrelinearized, &relin_keys).unwrap();
+        statements.push(BfvProofStatement::RelinearizedProduct {
+            lhs: lhs.clone(),
+            rhs: rhs.clone(),
+            product: product.clone(),
+            result: product.clone(),
+        });
+        witness.push(BfvWitness::Evaluation);
+
+        let pk: LogProofProverKnowledge<ZqSeal128_4096> =
+            generate_prover_knowledge(&statements, &messages, &witness, &params, &ctx).unwrap();
+
+        let gen: LogProofGenerators = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+        let mut p_t = Transcript::new(b"test");
+        let proof = LogProof::create(&mut p_t, &pk, &gen.g, &gen.h, &u);
+        let mut v_t = Transcript::new(b"test");
+
+        proof.verify(&mut v_t, &pk.vk, &gen.g, &gen.h, &u).unwrap();
+
+        // The unrelinearized product isn't the claimed result.
+        statements.pop();
+        statements.push(BfvProofStatement::RelinearizedProduct {
+            lhs,
+            rhs,
+            product,
+            result: evaluator.add(&unrelinearized, &unrelinearized).unwrap(),
+        });
+        let vk: LogProofVerifierKnowledge<ZqSeal128_4096> =
+            generate_verifier_knowledge(&statements, &[None, None], &params, &ctx);
+        let mut v_t = Transcript::new(b"test");
+
+        assert!(proof.verify(&mut v_t, &vk, &gen.g, &gen.h, &u).is_err());
+    }
+
+    #[test]
+    fn plaintext_addition_statement() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture_with(1, 0, 0, 0);
+        let ix = test_fixture.messages.len();
+        let pt = ctx.random_plaintext();
+        let ciphertext = test_fixture.statements[0].ciphertext().clone();
+        let result = ctx.evaluator.add_plain(&ciphertext, &pt).unwrap();
+        test_fixture.messages.push(BfvMessage {
+            plaintext: pt,
+            bounds: None,
+        });
+        test_fixture
+            .statements
+            .push(BfvProofStatement::PlaintextAddition {
+                message_id: ix,
+                ciphertext,
+                result,
+            });
+        test_fixture.witness.push(BfvWitness::Evaluation);
+
+        ctx.prove_and_verify(&test_fixture).unwrap();
+    }
+
+    #[test]
+    fn plaintext_multiplication_statement() {
+        let ctx = BFVTestContext::new();
+        let mut test_fixture = ctx.random_fixture_with(0, 1, 0, 0);
+        let ciphertext = test_fixture.statements[0].ciphertext().clone();
+
+        // Include coefficients above t / 2, which SEAL lifts to negative values.
+        let mut pt = Plaintext::new().unwrap();
+        pt.resize(4);
+        for (i, c) in [1, 31, 16, 17].into_iter().enumerate() {
+            pt.set_coefficient(i, c);
+        }
+        let ix = test_fixture.messages.len();
+        let result = ctx.evaluator.multiply_plain(&ciphertext, &pt).unwrap();
+        test_fixture.messages.push(BfvMessage {
+            plaintext: pt,
+            bounds: None,
+        });
+        test_fixture
+            .statements
+            .push(BfvProofStatement::PlaintextMultiplication {
+                message_id: ix,
+                ciphertext,
+                result,
+            });
+        test_fixture.witness.push(BfvWitness::Evaluation);
+
+        ctx.prove_and_verify(&test_fixture).unwrap();
+    }
+
+    #[test]
+    fn key_generation_statement() {
+        let (params, ctx, public_key, secret_key, switching_keys) =
+            key_generation_fixture(4096, true);
+
+        let statements = vec![BfvProofStatement::KeyGeneration {
+            public_key: Cow::Borrowed(&public_key),
+            switching_keys,
+        }];
+        let witness = vec![BfvWitness::KeyGeneration {
+            private_key: Cow::Borrowed(&secret_key),
+        }];
+
+        let pk: LogProofProverKnowledge<ZqSeal128_4096> =
+            generate_prover_knowledge(&statements, &[], &witness, &params, &ctx).unwrap();
+        assert_eq!(pk.vk.a.rows, 5);
+
+        let gen: LogProofGenerators = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+        let mut p_t = Transcript::new(b"test");
+        let proof = LogProof::create(&mut p_t, &pk, &gen.g, &gen.h, &u);
+        let mut v_t = Transcript::new(b"test");
+
+        proof.verify(&mut v_t, &pk.vk, &gen.g, &gen.h, &u).unwrap();
+    }
+
+    #[test]
+    fn key_generation_statement_with_more_moduli() {
+        // Four data moduli, so each relinearization key has a different CRT factor.
+        let (params, ctx, public_key, secret_key, switching_keys) =
+            key_generation_fixture(8192, false);
+
+        let statements = vec![BfvProofStatement::KeyGeneration {
+            public_key: Cow::Borrowed(&public_key),
+            switching_keys,
+        }];
+        let witness = vec![BfvWitness::KeyGeneration {
+            private_key: Cow::Borrowed(&secret_key),
+        }];
+
+        let pk: LogProofProverKnowledge<ZqSeal128_8192> =
+            generate_prover_knowledge(&statements, &[], &witness, &params, &ctx).unwrap();
+        assert_eq!(pk.vk.a.rows, 5);
+
+        let gen: LogProofGenerators = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+        let mut p_t = Transcript::new(b"test");
+        let proof = LogProof::create(&mut p_t, &pk, &gen.g, &gen.h, &u);
+        let mut v_t = Transcript::new(b"test");
+
+        proof.verify(&mut v_t, &pk.vk, &gen.g, &gen.h, &u).unwrap();
+    }
+
+    /// Generate degree 64 keys with the coefficient modulus SEAL uses at the given degree, along
+    /// with the relinearization keys and optionally the first set of Galois keys.
+    fn key_generation_fixture(
+        modulus_degree: u64,
+        with_galois_key: bool,
+    ) -> (
+        EncryptionParameters,
+        Context,
+        PublicKey,
+        SecretKey,
+        Vec<KeySwitchingKey>,
+    ) {
+        // Key switching needs a special modulus, which the 1024 modulus lacks.
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(64)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(modulus_degree, SecurityLevel::TC128).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::raw(32).unwrap())
+            .build()
+            .unwrap();
+        let ctx = Context::new_insecure(&params, false).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let public_key = gen.create_public_key();
+        let secret_key = gen.secret_key();
+
+        let relin_keys = gen.create_relinearization_keys().unwrap();
+        let mut switching_keys = vec![KeySwitchingKey {
+            target: KeySwitchingTarget::Relinearization,
+            keys: relin_keys.keys().unwrap(),
+        }];
+        if with_galois_key {
+            // Proving every Galois key is slow, so just take the first.
+            let galois_keys = gen.create_galois_keys().unwrap();
+            let (g, keys) = galois_keys.keys().unwrap().swap_remove(0);
+            switching_keys.push(KeySwitchingKey {
+                target: KeySwitchingTarget::Galois(g),
+                keys,
+            });
+        }
+
+        (params, ctx, public_key, secret_key, switching_keys)
+    }
+
+    #[test]
+    fn galois_automorphism() {
+        // x^3 - 2x -> x^9 - 2x^3 = x - 2x^3 in Z[x]/(x^4 + 1), since x^8 = 1
+        let poly = Polynomial {
+            coeffs: vec![
+                ZqSeal128_1024::zero(),
+                -ZqSeal128_1024::from(2),
+                ZqSeal128_1024::zero(),
+                ZqSeal128_1024::one(),
+            ],
+        };
+        let expected = Polynomial {
+            coeffs: vec![
+                ZqSeal128_1024::zero(),
+                ZqSeal128_1024::one(),
+                ZqSeal128_1024::zero(),
+                -ZqSeal128_1024::from(2),
+            ],
+        };
+
+        assert_eq!(apply_galois(&poly, 3, 4), expected);
+    }
+
     fn test_statements_with(
         num_public_statements: usize,
         num_private_statements: usize,
@@ -851,7 +1946,8 @@ mod tests {
             &test_fixture.witness,
             &ctx.params,
             &ctx.ctx,
-        );
+        )
+        .unwrap();
         let result = prove_and_verify(&prover_knowledge);
         if result.is_err() {
             panic!(
@@ -869,13 +1965,22 @@ mod tests {
     }
 
     fn prove_and_verify(pk: &LogProofProverKnowledge<ZqSeal128_1024>) -> Result<(), ProofError> {
+        prove_and_verify_against(pk, &pk.vk)
+    }
+
+    /// Prove with the given prover knowledge, but verify against possibly different verifier
+    /// knowledge.
+    fn prove_and_verify_against(
+        pk: &LogProofProverKnowledge<ZqSeal128_1024>,
+        vk: &LogProofVerifierKnowledge<ZqSeal128_1024>,
+    ) -> Result<(), ProofError> {
         let gen: LogProofGenerators = LogProofGenerators::new(pk.vk.l() as usize);
         let u = InnerProductVerifierKnowledge::get_u();
         let mut p_t = Transcript::new(b"test");
         let proof = LogProof::create(&mut p_t, pk, &gen.g, &gen.h, &u);
         let mut v_t = Transcript::new(b"test");
 
-        proof.verify(&mut v_t, &pk.vk, &gen.g, &gen.h, &u)
+        proof.verify(&mut v_t, vk, &gen.g, &gen.h, &u)
     }
 
     struct TestFixture<'p, 's> {
@@ -890,6 +1995,7 @@ mod tests {
         public_key: PublicKey,
         secret_key: SecretKey,
         encryptor: Encryptor<SymAsym>,
+        evaluator: BFVEvaluator,
     }
 
     impl BFVTestContext {
@@ -909,6 +2015,7 @@ mod tests {
             let secret_key = gen.secret_key();
             let encryptor =
                 Encryptor::with_public_and_secret_key(&ctx, &public_key, &secret_key).unwrap();
+            let evaluator = BFVEvaluator::new(&ctx).unwrap();
 
             BFVTestContext {
                 ctx,
@@ -916,6 +2023,7 @@ mod tests {
                 public_key,
                 secret_key,
                 encryptor,
+                evaluator,
             }
         }
 
@@ -1046,7 +2154,8 @@ mod tests {
                 &fixture.witness,
                 &self.params,
                 &self.ctx,
-            );
+            )
+            .unwrap();
             prove_and_verify(&pk)
         }
     }
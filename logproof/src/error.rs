@@ -1,6 +1,6 @@
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 /**
- * An error that occurred when verifying a proof.
+ * An error that occurred when creating or verifying a proof.
  */
 pub enum ProofError {
     /**
@@ -14,4 +14,11 @@ pub enum ProofError {
      */
     #[error("The proof is malformed")]
     MalformedProof,
+
+    /**
+     * The witness at the given index doesn't match its statement, or there isn't one witness per
+     * statement.
+     */
+    #[error("Witness {0} doesn't match its statement")]
+    MismatchedWitness(usize),
 }
@@ -16,7 +16,7 @@ use sunscreen_math::{RistrettoPointVec, ScalarVec};
 
 use crate::error::ProofError;
 use crate::{linear_algebra::InnerProduct, math::rand256};
-use crate::{math::parallel_multiscalar_multiplication, transcript::LogProofTranscript};
+use crate::{math::parallel_multiscalar_multiplication, transcript::LogProofTranscript, wire};
 
 #[derive(Debug, Clone)]
 /**
@@ -172,6 +172,9 @@ impl InnerProductProof {
         h: &[RistrettoPoint],
         u: &RistrettoPoint,
     ) -> Self {
+        let now = Instant::now();
+        let total = now;
+
         let mut pk = pk.clone();
 
         assert_eq!(pk.v_1.len(), pk.v_2.len());
@@ -199,6 +202,10 @@ impl InnerProductProof {
         let a = transcript.challenge_point(b"a");
         let t_prime = vk.t + a * vk.x;
 
+        trace!("Prefold {}s", now.elapsed().as_secs_f64());
+
+        let now = Instant::now();
+
         let (g, h, _t_pprime, v_1, v_2, rho_prime) = InnerProductProof::folding_prover(
             transcript,
             &pk,
@@ -211,6 +218,10 @@ impl InnerProductProof {
             u,
         );
 
+        trace!("Fold {}s", now.elapsed().as_secs_f64());
+
+        let now = Instant::now();
+
         debug_assert_eq!(
             _t_pprime,
             g * v_1 + h * v_2 + a * (v_1 * v_2) + u * rho_prime
@@ -237,6 +248,9 @@ impl InnerProductProof {
             g * z_1 + h * z_2 + a * (c.invert() * z_1 * z_2) + u * tau
         );
 
+        trace!("Post fold {}s", now.elapsed().as_secs_f64());
+        trace!("Prove time {}s", total.elapsed().as_secs_f64());
+
         Self {
             w: w.compress(),
             w_prime: w_prime.compress(),
@@ -316,6 +330,72 @@ impl InnerProductProof {
         }
     }
 
+    /**
+     * Appends the canonical binary encoding of this proof to `bytes`.
+     *
+     * # Remarks
+     * See [`crate::wire`] for how individual elements are encoded.
+     */
+    pub fn write_to(&self, bytes: &mut Vec<u8>) {
+        assert_eq!(self.t_1.len(), self.t_minus1.len());
+
+        // t_1 and t_minus1 always have the same length, so write it once.
+        wire::write_len(bytes, self.t_1.len());
+
+        for (t_1, t_minus1) in self.t_1.iter().zip(self.t_minus1.iter()) {
+            wire::write_compressed_point(bytes, t_1);
+            wire::write_compressed_point(bytes, t_minus1);
+        }
+
+        wire::write_compressed_point(bytes, &self.w);
+        wire::write_compressed_point(bytes, &self.w_prime);
+        wire::write_scalar(bytes, &self.z_1);
+        wire::write_scalar(bytes, &self.z_2);
+        wire::write_scalar(bytes, &self.tau);
+    }
+
+    /**
+     * Reads a proof written by [`InnerProductProof::write_to`] from the
+     * front of `bytes`, returning it and the remaining bytes.
+     */
+    pub fn read_from(bytes: &[u8]) -> Result<(Self, &[u8]), ProofError> {
+        let (rounds, mut rest) = wire::read_len(bytes)?;
+
+        if rounds > rest.len() / 64 {
+            return Err(ProofError::MalformedProof);
+        }
+
+        let mut t_1 = Vec::with_capacity(rounds);
+        let mut t_minus1 = Vec::with_capacity(rounds);
+
+        for _ in 0..rounds {
+            let (p, r) = wire::read_compressed_point(rest)?;
+            t_1.push(p);
+            let (p, r) = wire::read_compressed_point(r)?;
+            t_minus1.push(p);
+            rest = r;
+        }
+
+        let (w, rest) = wire::read_compressed_point(rest)?;
+        let (w_prime, rest) = wire::read_compressed_point(rest)?;
+        let (z_1, rest) = wire::read_scalar(rest)?;
+        let (z_2, rest) = wire::read_scalar(rest)?;
+        let (tau, rest) = wire::read_scalar(rest)?;
+
+        Ok((
+            Self {
+                t_1,
+                t_minus1,
+                w,
+                w_prime,
+                z_1,
+                z_2,
+                tau,
+            },
+            rest,
+        ))
+    }
+
     fn mad_scalar_point(
         v_1: &[RistrettoPoint],
         v_2: &[RistrettoPoint],
@@ -536,7 +616,10 @@ impl InnerProductProof {
                 Self::fold_verifier(transcript, &t, &t_1, &t_minus1, g_t, g_b, h_t, h_b);
 
             let mad = |x: &[Scalar], y: &[Scalar], z: Scalar| {
-                x.iter().zip(y.iter()).map(|(a, b)| a + b * z).collect()
+                x.par_iter()
+                    .zip(y.par_iter())
+                    .map(|(a, b)| a + b * z)
+                    .collect()
             };
 
             // Prover needs to collapse vectors and update rho.
@@ -64,6 +64,7 @@ pub mod rings;
  */
 pub mod math;
 mod transcript;
+pub mod wire;
 
 pub use merlin::Transcript;
 pub use transcript::LogProofTranscript;
@@ -169,15 +169,18 @@ where
         assert_eq!(self.rows, rhs.rows);
         assert_eq!(self.cols, rhs.cols);
 
-        let mut c = Matrix::new(self.rows, self.cols);
+        let data = self
+            .data
+            .par_iter()
+            .zip(rhs.data.par_iter())
+            .map(|(a, b)| a - b)
+            .collect();
 
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                c[(i, j)] = &self[(i, j)] - &rhs[(i, j)];
-            }
+        Self::Output {
+            rows: self.rows,
+            cols: self.cols,
+            data,
         }
-
-        c
     }
 }
 
@@ -223,7 +226,7 @@ where
 
         let data = self
             .data
-            .iter()
+            .par_iter()
             .map(|y| {
                 let coeffs = y.coeffs.iter().map(|c| c * x_inv).collect();
 
@@ -245,7 +248,7 @@ where
     pub fn scalar_mul_q(&self, x: &Zq<N, B>) -> Self {
         let data = self
             .data
-            .iter()
+            .par_iter()
             .map(|y| {
                 let coeffs = y.coeffs.iter().map(|c| *c * x).collect();
 
@@ -265,7 +268,7 @@ where
      * matrix of the evaluations for each element.
      */
     pub fn evaluate(&self, point: &Zq<N, B>) -> Matrix<Zq<N, B>> {
-        let data = self.data.iter().map(|x| x.evaluate(point)).collect();
+        let data = self.data.par_iter().map(|x| x.evaluate(point)).collect();
 
         Matrix {
             rows: self.rows,
@@ -297,6 +300,82 @@ where
     }
 }
 
+impl<R> PolynomialMatrix<R>
+where
+    R: Ring,
+{
+    /**
+     * Computes `self * rhs`, with each element reduced modulo `x^n + 1`.
+     *
+     * # Remarks
+     * Each product of elements uses [`Polynomial::mul_negacyclic`], which
+     * is much faster for large `n` than computing the full product and
+     * dividing by `x^n + 1`.
+     *
+     * # Panics
+     * If `self.cols != rhs.rows`.
+     */
+    pub fn mul_negacyclic(&self, rhs: &Self, n: usize) -> Self {
+        assert_eq!(self.cols, rhs.rows);
+
+        let data = (0..self.rows * rhs.cols)
+            .into_par_iter()
+            .map(|i| {
+                let row = i / rhs.cols;
+                let col = i % rhs.cols;
+
+                (0..self.cols).fold(Polynomial::zero(), |acc, k| {
+                    acc + self[(row, k)].mul_negacyclic(&rhs[(k, col)], n)
+                })
+            })
+            .collect();
+
+        Self {
+            rows: self.rows,
+            cols: rhs.cols,
+            data,
+        }
+    }
+
+    /**
+     * Divides each element by `x^n + 1`, returning the matrices of
+     * quotients and remainders.
+     *
+     * # Remarks
+     * Unlike [`Polynomial::vartime_div_rem_restricted_rhs`], this takes
+     * time linear in the length of each element.
+     */
+    pub fn div_rem_negacyclic(&self, n: usize) -> (Self, Self) {
+        let (q, r): (Vec<_>, Vec<_>) = self
+            .data
+            .par_iter()
+            .map(|x| {
+                let mut rem = x.coeffs.clone();
+                let mut quot = vec![R::zero(); rem.len().saturating_sub(n)];
+
+                // x^i = x^(i - n) * (x^n + 1) - x^(i - n)
+                for i in (n..rem.len()).rev() {
+                    let c = std::mem::replace(&mut rem[i], R::zero());
+                    rem[i - n] = rem[i - n].clone() - c.clone();
+                    quot[i - n] = quot[i - n].clone() + c;
+                }
+
+                rem.truncate(n);
+
+                (Polynomial { coeffs: quot }, Polynomial { coeffs: rem })
+            })
+            .unzip();
+
+        let matrix = |data| Self {
+            rows: self.rows,
+            cols: self.cols,
+            data,
+        };
+
+        (matrix(q), matrix(r))
+    }
+}
+
 impl<F> From<Vec<F>> for Matrix<F>
 where
     F: Zero + Clone,
@@ -867,6 +946,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn negacyclic_ops_match_division() {
+        type R = ZqSeal128_8192;
+
+        // Large enough for the NTT.
+        let n = 1024;
+        let random_poly = |len: usize| Polynomial {
+            coeffs: (0..len)
+                .map(|_| R::from(rand::random::<u64>()))
+                .collect::<Vec<_>>(),
+        };
+
+        let a = PolynomialMatrix::from(vec![
+            vec![random_poly(n), random_poly(n - 1)],
+            vec![random_poly(3), random_poly(n)],
+        ]);
+        let s = PolynomialMatrix::from(vec![vec![random_poly(n)], vec![random_poly(n)]]);
+
+        let mut f = Polynomial {
+            coeffs: vec![R::zero(); n + 1],
+        };
+        f.coeffs[0] = R::one();
+        f.coeffs[n] = R::one();
+
+        let product = &a * &s;
+        let (q, r) = product.div_rem_negacyclic(n);
+
+        assert_eq!(q, &product / &f);
+        assert_eq!(r, product.map(|x| x.vartime_div_rem_restricted_rhs(&f).1));
+        assert_eq!(a.mul_negacyclic(&s, n), r);
+    }
+
     #[test]
     fn can_multiply_matrix() {
         type Fp = ZqRistretto;
@@ -44,11 +44,18 @@ use crate::{
     },
     rings::{FieldFrom, FieldInto, ZqRistretto},
     transcript::LogProofTranscript,
-    ProofError,
+    wire, ProofError,
 };
 
 type MatrixPoly<Q> = Matrix<Polynomial<Q>>;
 
+/**
+ * The number of coefficients each rayon task converts to 2's complement.
+ * Converting a single coefficient is cheap, so batching amortizes the
+ * per-task overhead.
+ */
+const TWOS_COMPLEMENT_CHUNK_LEN: usize = 4096;
+
 /**
  * Bounds on the coefficients in the secret S (specified in number of bits).
  */
@@ -344,10 +351,7 @@ where
         assert_eq!(a.rows, t.rows);
         assert_eq!(s.cols, t.cols);
 
-        debug_assert_eq!(
-            (a * s).map(|x| x.vartime_div_rem_restricted_rhs(f).1),
-            t.clone()
-        );
+        debug_assert_eq!(mul_mod_f(a, s, f), t.clone());
 
         let vk = VerifierKnowledge::new(a.clone(), t.clone(), f.clone(), bounds.clone());
 
@@ -438,6 +442,39 @@ impl LogProof {
         half_rho: &Scalar,
         shared_indices: &[(usize, usize)],
     ) -> Self
+    where
+        Q: Ring + ModSwitch<ZqRistretto> + Mul<Q, Output = Q> + CryptoHash + RingModulus<4> + Ord,
+    {
+        Self::create_with_shared_blinding(
+            transcript,
+            pk,
+            g,
+            h,
+            u,
+            half_rho,
+            half_rho,
+            shared_indices,
+        )
+    }
+
+    /// Like [`LogProof::create_with_shared`], but with independent blinding
+    /// factors for the shared and unshared commitments.
+    ///
+    /// # Remarks
+    /// Use this when several proofs share the same commitment `w_shared`,
+    /// so reusing `shared_rho` doesn't also reuse the blinding on the rest of
+    /// each witness.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_shared_blinding<Q>(
+        transcript: &mut Transcript,
+        pk: &ProverKnowledge<Q>,
+        g: &[RistrettoPoint],
+        h: &[RistrettoPoint],
+        u: &RistrettoPoint,
+        shared_rho: &Scalar,
+        unshared_rho: &Scalar,
+        shared_indices: &[(usize, usize)],
+    ) -> Self
     where
         Q: Ring + ModSwitch<ZqRistretto> + Mul<Q, Output = Q> + CryptoHash + RingModulus<4> + Ord,
     {
@@ -475,13 +512,17 @@ impl LogProof {
         );
         }
 
+        let total = Instant::now();
+
         let b_serialized = LogProof::serialize_bounds(&b);
         let b_slices = vk.b_slices();
 
         transcript.linear_relation_domain_separator();
         transcript.append_linear_relation_knowledge(vk);
 
+        let now = Instant::now();
         let (r_2, r_1) = Self::compute_factors(&vk.a, &pk.s, &vk.t, &vk.f);
+        trace!("Compute factors {}s", now.elapsed().as_secs_f64());
 
         // In debug mode, assert that AS + qR_1 + fR_2 == T over Z[X].
         // Note we use FpRistretto for Z[X], which should be large enough
@@ -491,6 +532,7 @@ impl LogProof {
             linear_relation::assert_factors(pk, f, &r_2, &r_1);
         }
 
+        let now = Instant::now();
         let s_serialized: Vec<ZqRistretto> = Self::serialize(&pk.s, d as usize);
         let r_1_serialized = Self::serialize(&r_1, (2 * d - 1) as usize);
         let r_2_serialized = Self::serialize(&r_2, (d - 1) as usize);
@@ -517,24 +559,29 @@ impl LogProof {
         // inverts the bits. Bitwise NOT does the same thing.
         let s_2 = !s_1.clone();
 
+        trace!("Serialize {}s", now.elapsed().as_secs_f64());
+
+        let now = Instant::now();
         let (s_1_shared, h_shared, s_1_unshared, h_unshared) =
             Self::split_shared_and_unshared_bits(shared_indices, &b_slices, &s_1, h);
 
-        let w_shared = Self::make_shared_commitment(&s_1_shared, half_rho, &h_shared, u);
+        let w_shared = Self::make_shared_commitment(&s_1_shared, shared_rho, &h_shared, u);
         let w_unshared =
-            Self::make_unshared_commitment(&s_1_unshared, &s_2, half_rho, g, &h_unshared, u);
+            Self::make_unshared_commitment(&s_1_unshared, &s_2, unshared_rho, g, &h_unshared, u);
         let w = w_shared + w_unshared;
+        trace!("Commit {}s", now.elapsed().as_secs_f64());
 
         if cfg!(debug_assertions) {
-            let w_prime =
-                Self::make_commitment(&s_1, &s_2, &(Scalar::from(2u64) * half_rho), g, h, u);
+            let w_prime = Self::make_commitment(&s_1, &s_2, &(shared_rho + unshared_rho), g, h, u);
             assert_eq!(w, w_prime);
         }
 
         transcript.append_point(b"w_shared", &w_shared.compress());
         transcript.append_point(b"w", &w.compress());
 
+        let now = Instant::now();
         let (alpha, beta, gamma, phi, psi) = Self::create_challenges(&pk.vk, transcript);
+        trace!("Create challenges {}s", now.elapsed().as_secs_f64());
 
         if cfg!(debug_assertions) {
             linear_relation::assert_eval(pk, &r_1, &r_2, &alpha);
@@ -588,14 +635,22 @@ impl LogProof {
             );
         }
 
+        let now = Instant::now();
         let g_prime = Self::compute_g_prime(g, &phi);
+        trace!("g_prime {}s", now.elapsed().as_secs_f64());
 
+        let now = Instant::now();
         let v = Self::compute_v(vk, alpha, &beta, &gamma);
+        trace!("v {}s", now.elapsed().as_secs_f64());
 
+        let now = Instant::now();
         let t = Self::compute_t(&w, &g_prime, h, &phi, &psi, &v);
+        trace!("t {}s", now.elapsed().as_secs_f64());
 
+        let now = Instant::now();
         let v_1 = Self::compute_v1(&v, &phi, &s_2, &psi);
         let v_2 = Self::compute_v2(&s_1, &psi);
+        trace!("v_1, v_2 {}s", now.elapsed().as_secs_f64());
 
         if cfg!(debug_assertions) {
             let g_a = parallel_multiscalar_multiplication(
@@ -638,13 +693,15 @@ impl LogProof {
             Self::compute_x(vk, &gamma, &alpha, &beta, &phi, &psi, &v)
         );
 
-        // The half_rho blinding factor needs to be doubled because by making a
-        // shared and unshared commitment with half_rho blinding factor in each,
-        // the total blinding factor is the sum of the two blinding factors.
-        let rho = half_rho + half_rho;
+        // By making a shared and unshared commitment with a blinding factor in
+        // each, the total blinding factor is the sum of the two blinding factors.
+        let rho = shared_rho + unshared_rho;
 
+        let now = Instant::now();
         let inner_product_proof =
             Self::create_inner_product_proof(transcript, &v_1, &v_2, &rho, &t, &g_prime, h, u);
+        trace!("Inner product proof {}s", now.elapsed().as_secs_f64());
+        trace!("Prove time {}s", total.elapsed().as_secs_f64());
 
         Self {
             w_shared,
@@ -653,6 +710,64 @@ impl LogProof {
         }
     }
 
+    /**
+     * Appends the canonical binary encoding of this proof to `bytes`.
+     *
+     * # Remarks
+     * See [`crate::wire`] for how individual elements are encoded.
+     */
+    pub fn write_to(&self, bytes: &mut Vec<u8>) {
+        wire::write_point(bytes, &self.w_shared);
+        wire::write_point(bytes, &self.w);
+        self.inner_product_proof.write_to(bytes);
+    }
+
+    /**
+     * Reads a proof written by [`LogProof::write_to`] from the front of
+     * `bytes`, returning it and the remaining bytes.
+     */
+    pub fn read_from(bytes: &[u8]) -> Result<(Self, &[u8]), ProofError> {
+        let (w_shared, rest) = wire::read_point(bytes)?;
+        let (w, rest) = wire::read_point(rest)?;
+        let (inner_product_proof, rest) = InnerProductProof::read_from(rest)?;
+
+        Ok((
+            Self {
+                w_shared,
+                w,
+                inner_product_proof,
+            },
+            rest,
+        ))
+    }
+
+    /**
+     * Serialize this proof into its canonical binary encoding.
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_to(&mut bytes);
+
+        bytes
+    }
+
+    /**
+     * Deserialize a proof from its canonical binary encoding.
+     *
+     * # Remarks
+     * Returns [`ProofError::MalformedProof`] if `bytes` isn't a valid
+     * encoding or contains trailing data.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
+        let (proof, rest) = Self::read_from(bytes)?;
+
+        if !rest.is_empty() {
+            return Err(ProofError::MalformedProof);
+        }
+
+        Ok(proof)
+    }
+
     /**
      * Verify the given proof holds.
      */
@@ -777,7 +892,10 @@ impl LogProof {
     }
 
     fn compute_v2(s_1: &BitSlice, psi: &Scalar) -> Vec<Scalar> {
-        s_1.iter().map(|x| Self::bool_to_scalar(*x) + psi).collect()
+        (0..s_1.len())
+            .into_par_iter()
+            .map(|i| Self::bool_to_scalar(s_1[i]) + psi)
+            .collect()
     }
 
     fn bool_to_scalar(x: bool) -> Scalar {
@@ -795,8 +913,8 @@ impl LogProof {
         psi: &Scalar,
     ) -> RistrettoPoint {
         let v_plus_phi = v
-            .iter()
-            .zip(phi.iter())
+            .par_iter()
+            .zip(phi.par_iter())
             .map(|(v, phi)| v + psi * phi)
             .collect::<Vec<Scalar>>();
 
@@ -1087,13 +1205,24 @@ impl LogProof {
         let as_q = a * s;
         let t_as_q = t - &as_q;
 
-        // f should evenly divide (t - as).
-        debug_assert_eq!(
-            t_as_q.map(|x| x.vartime_div_rem_restricted_rhs(f).1),
-            Matrix::new(t.rows, t.cols)
-        );
+        // f should evenly divide (t - as). The quotient needs the full product, but dividing by
+        // x^n + 1 is cheap.
+        let r_2 = match negacyclic_degree(f) {
+            Some(n) => {
+                let (r_2, rem) = t_as_q.div_rem_negacyclic(n);
+                debug_assert_eq!(rem, Matrix::new(t.rows, t.cols));
+
+                r_2
+            }
+            None => {
+                debug_assert_eq!(
+                    t_as_q.map(|x| x.vartime_div_rem_restricted_rhs(f).1),
+                    Matrix::new(t.rows, t.cols)
+                );
 
-        let r_2 = &t_as_q / f;
+                &t_as_q / f
+            }
+        };
 
         debug_assert_eq!(t - r_2.map(|x| x * f), a * s);
 
@@ -1171,14 +1300,21 @@ impl LogProof {
     where
         B: ArithmeticBackend<N>,
     {
-        let mut bitvec = BitVec::with_capacity(values.len() * log_b as usize);
+        let chunks = values
+            .par_chunks(TWOS_COMPLEMENT_CHUNK_LEN)
+            .map(|chunk| {
+                let mut bitvec = BitVec::with_capacity(chunk.len() * log_b as usize);
+
+                // This code should not feature timing side-channels.
+                for value in chunk {
+                    LogProof::to_2s_complement_single(value, log_b, &mut bitvec);
+                }
 
-        // This code should not feature timing side-channels.
-        for value in values.iter() {
-            LogProof::to_2s_complement_single(value, log_b, &mut bitvec);
-        }
+                bitvec
+            })
+            .collect::<Vec<_>>();
 
-        bitvec
+        Self::concat_bits(chunks, values.len() * log_b as usize)
     }
 
     /**
@@ -1198,11 +1334,33 @@ impl LogProof {
         // Make sure we have an equal number of values and bounds to serialize
         assert_eq!(values.len(), log_b.len());
 
-        let mut bitvec = BitVec::with_capacity(log_b.iter().sum::<u32>() as usize);
+        let chunks = values
+            .par_chunks(TWOS_COMPLEMENT_CHUNK_LEN)
+            .zip(log_b.par_chunks(TWOS_COMPLEMENT_CHUNK_LEN))
+            .map(|(values, log_b)| {
+                let mut bitvec = BitVec::with_capacity(log_b.iter().sum::<u32>() as usize);
+
+                // This code should not feature timing side-channels.
+                for (value, bound) in zip(values.iter(), log_b.iter()) {
+                    LogProof::to_2s_complement_single(value, *bound, &mut bitvec);
+                }
+
+                bitvec
+            })
+            .collect::<Vec<_>>();
+
+        Self::concat_bits(chunks, log_b.iter().sum::<u32>() as usize)
+    }
 
-        // This code should not feature timing side-channels.
-        for (value, bound) in zip(values.iter(), log_b.iter()) {
-            LogProof::to_2s_complement_single(value, *bound, &mut bitvec);
+    /**
+     * Concatenates the bit vectors produced by each parallel chunk of a
+     * 2's complement conversion, preserving their order.
+     */
+    fn concat_bits(chunks: Vec<BitVec>, len: usize) -> BitVec {
+        let mut bitvec = BitVec::with_capacity(len);
+
+        for chunk in chunks {
+            bitvec.extend_from_bitslice(chunk.as_bitslice());
         }
 
         bitvec
@@ -1256,27 +1414,24 @@ impl LogProof {
     where
         Q: Ring + ModSwitch<ZqRistretto>,
     {
-        let mut result = vec![];
-
-        for i in 0..x.rows {
-            for j in 0..x.cols {
-                let poly = &x[(i, j)];
-
-                for c in poly.coeffs.iter().take(d) {
-                    result.push(c.mod_switch_signed());
-                }
-
+        // Matrix data is stored in row-major order, so serializing each
+        // polynomial in parallel and concatenating preserves the layout.
+        x.as_slice()
+            .par_iter()
+            .flat_map_iter(|poly| {
                 for c in poly.coeffs.iter().skip(d) {
                     debug_assert_eq!(*c, Q::zero(), "polynomial exceeds expected degree");
                 }
 
-                for _ in poly.coeffs.len()..d {
-                    result.push(ZqRistretto::zero());
-                }
-            }
-        }
+                let padding = d.saturating_sub(poly.coeffs.len());
 
-        result
+                poly.coeffs
+                    .iter()
+                    .take(d)
+                    .map(|c| c.mod_switch_signed())
+                    .chain(std::iter::repeat_n(ZqRistretto::zero(), padding))
+            })
+            .collect()
     }
 
     fn split_shared_and_unshared_bits(
@@ -1320,6 +1475,34 @@ impl LogProof {
     }
 }
 
+/**
+ * Returns `n` if `f` is `x^n + 1`, as it is for BFV statements.
+ */
+fn negacyclic_degree<Q: Ring>(f: &Polynomial<Q>) -> Option<usize> {
+    if f.vartime_is_zero() {
+        return None;
+    }
+
+    let n = f.vartime_degree();
+    let is_negacyclic = n > 0
+        && f.coeffs[0] == Q::one()
+        && f.coeffs[n] == Q::one()
+        && f.coeffs[1..n].iter().all(|c| *c == Q::zero());
+
+    is_negacyclic.then_some(n)
+}
+
+/**
+ * Computes `a * s` in `Z_q[X]/f`, using a negacyclic NTT when `f` is
+ * `x^n + 1`.
+ */
+fn mul_mod_f<Q: Ring>(a: &MatrixPoly<Q>, s: &MatrixPoly<Q>, f: &Polynomial<Q>) -> MatrixPoly<Q> {
+    match negacyclic_degree(f) {
+        Some(n) => a.mul_negacyclic(s, n),
+        None => (a * s).map(|x| x.vartime_div_rem_restricted_rhs(f).1),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::ops::Div;
@@ -1603,6 +1786,44 @@ mod test {
         transcripts_match(4);
     }
 
+    #[test]
+    fn can_roundtrip_proof_bytes() {
+        type Fq = ZqSeal128_8192;
+
+        let LatticeProblem { a, s, t, f, b } = test_lattice::<Fq>(2);
+
+        let pk = ProverKnowledge::new(&a, &s, &t, &b, &f);
+
+        let gens = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = inner_product::VerifierKnowledge::get_u();
+
+        let proof = LogProof::create(&mut Transcript::new(b"test"), &pk, &gens.g, &gens.h, &u);
+        let bytes = proof.to_bytes();
+
+        // w_shared, w, the round count, 2 points per folding round, w, w'
+        // and 3 scalars.
+        let rounds = (pk.vk.l() as usize).next_power_of_two().ilog2() as usize;
+        assert_eq!(bytes.len(), 32 * 2 + 4 + 64 * rounds + 32 * 2 + 32 * 3);
+
+        let proof = LogProof::from_bytes(&bytes).unwrap();
+
+        proof
+            .verify(&mut Transcript::new(b"test"), &pk.vk, &gens.g, &gens.h, &u)
+            .unwrap();
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+
+        assert_eq!(
+            LogProof::from_bytes(&trailing).unwrap_err(),
+            ProofError::MalformedProof
+        );
+        assert_eq!(
+            LogProof::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            ProofError::MalformedProof
+        );
+    }
+
     #[test]
     fn can_compute_b_1() {
         type Fq = ZqSeal128_8192;
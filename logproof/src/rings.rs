@@ -27,6 +27,217 @@ pub struct RistrettoConfig {}
  */
 pub type ZqRistretto = Zq<4, BarrettBackend<4, RistrettoConfig>>;
 
+/**
+ * The q modulus SEAL BFV uses with 128-bit security and lattice dimension
+ * 32768.
+ *
+ * # Remarks
+ *
+ * SEAL uses Q =
+ * 0x7fffffffe90001 * 0x7fffffffbf0001 * 0x7fffffffbd0001 * 0x7fffffffba0001 *
+ * 0x7fffffffaa0001 * 0x7fffffffa50001 * 0x7fffffff9f0001 * 0x7fffffff7e0001 *
+ * 0x7fffffff770001 * 0x7fffffff380001 * 0x7fffffff330001 * 0x7fffffff2d0001 *
+ * 0x7fffffff170001 * 0x7fffffff150001 * 0x7ffffffef00001 * 0x7ffffffecd0001
+ *
+ * This can be derived by running
+ * `CoefficientModulus::bfv_default(32768, SecurityLevel::TC128)`
+ * or by running the underlying SEAL function
+ * `CoeffModulus::BFVDefault(32768, sec_level_type::tc128)`
+ *
+ * The data modulus (every prime but the last) is 825 bits, which doesn't fit
+ * in the Ristretto scalar field. Proofs over these parameters are therefore
+ * split into [`GROUPS`](Self::GROUPS), each proven in its own ring
+ * ([`SealQ128_32768_0`] through [`SealQ128_32768_4`]). Since the groups are
+ * coprime, a relation holding modulo each group's product holds modulo the
+ * data modulus by the Chinese remainder theorem.
+ */
+pub struct SealQ128_32768 {}
+impl SealQ128_32768 {
+    /// The SEAL modulus chain
+    pub const Q: &'static [u64] = &[
+        0x7fffffffe90001,
+        0x7fffffffbf0001,
+        0x7fffffffbd0001,
+        0x7fffffffba0001,
+        0x7fffffffaa0001,
+        0x7fffffffa50001,
+        0x7fffffff9f0001,
+        0x7fffffff7e0001,
+        0x7fffffff770001,
+        0x7fffffff380001,
+        0x7fffffff330001,
+        0x7fffffff2d0001,
+        0x7fffffff170001,
+        0x7fffffff150001,
+        0x7ffffffef00001,
+        0x7ffffffecd0001,
+    ];
+
+    /// The data modulus primes, partitioned into the groups proven
+    /// separately.
+    pub const GROUPS: &'static [&'static [u64]] = &[
+        SealQ128_32768_0::Q,
+        SealQ128_32768_1::Q,
+        SealQ128_32768_2::Q,
+        SealQ128_32768_3::Q,
+        SealQ128_32768_4::Q,
+    ];
+}
+
+/**
+ * The first RNS group of [`SealQ128_32768`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "46768052381402955559697894210635902022651417526273",
+    num_limbs = 3
+)]
+pub struct SealQ128_32768_0 {}
+impl SealQ128_32768_0 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x7fffffffe90001, 0x7fffffffbf0001, 0x7fffffffbd0001];
+}
+
+/**
+ * The second RNS group of [`SealQ128_32768`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "46768052373576461122500881741873941255294588354561",
+    num_limbs = 3
+)]
+pub struct SealQ128_32768_1 {}
+impl SealQ128_32768_1 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x7fffffffba0001, 0x7fffffffaa0001, 0x7fffffffa50001];
+}
+
+/**
+ * The third RNS group of [`SealQ128_32768`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "46768052363623201893698796800587715339069781442561",
+    num_limbs = 3
+)]
+pub struct SealQ128_32768_2 {}
+impl SealQ128_32768_2 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x7fffffff9f0001, 0x7fffffff7e0001, 0x7fffffff770001];
+}
+
+/**
+ * The fourth RNS group of [`SealQ128_32768`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "46768052342185412790483995721748498973451647188993",
+    num_limbs = 3
+)]
+pub struct SealQ128_32768_3 {}
+impl SealQ128_32768_3 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x7fffffff380001, 0x7fffffff330001, 0x7fffffff2d0001];
+}
+
+/**
+ * The fifth RNS group of [`SealQ128_32768`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "46768052331636659424537967070067124590805753004033",
+    num_limbs = 3
+)]
+pub struct SealQ128_32768_4 {}
+impl SealQ128_32768_4 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x7fffffff170001, 0x7fffffff150001, 0x7ffffffef00001];
+}
+
+/**
+ * The q modulus SEAL BFV uses with 128-bit security and lattice dimension
+ * 16384.
+ *
+ * # Remarks
+ *
+ * SEAL uses Q =
+ * 0xfffffffd8001 * 0xfffffffa0001 * 0xfffffff00001 * 0x1fffffff68001 *
+ * 0x1fffffff50001 * 0x1ffffffee8001 * 0x1ffffffea0001 * 0x1ffffffe88001 *
+ * 0x1ffffffe48001
+ *
+ * This can be derived by running
+ * `CoefficientModulus::bfv_default(16384, SecurityLevel::TC128)`
+ * or by running the underlying SEAL function
+ * `CoeffModulus::BFVDefault(16384, sec_level_type::tc128)`
+ *
+ * As with [`SealQ128_32768`], the 389-bit data modulus is too wide for the
+ * Ristretto scalar field, so proofs are split across
+ * [`GROUPS`](Self::GROUPS) ([`SealQ128_16384_0`] through
+ * [`SealQ128_16384_2`]).
+ */
+pub struct SealQ128_16384 {}
+impl SealQ128_16384 {
+    /// The SEAL modulus chain
+    pub const Q: &'static [u64] = &[
+        0xfffffffd8001,
+        0xfffffffa0001,
+        0xfffffff00001,
+        0x1fffffff68001,
+        0x1fffffff50001,
+        0x1ffffffee8001,
+        0x1ffffffea0001,
+        0x1ffffffe88001,
+        0x1ffffffe48001,
+    ];
+
+    /// The data modulus primes, partitioned into the groups proven
+    /// separately.
+    pub const GROUPS: &'static [&'static [u64]] = &[
+        SealQ128_16384_0::Q,
+        SealQ128_16384_1::Q,
+        SealQ128_16384_2::Q,
+    ];
+}
+
+/**
+ * The first RNS group of [`SealQ128_16384`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "22300745071319587974466878971304454742573057",
+    num_limbs = 3
+)]
+pub struct SealQ128_16384_0 {}
+impl SealQ128_16384_0 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0xfffffffd8001, 0xfffffffa0001, 0xfffffff00001];
+}
+
+/**
+ * The second RNS group of [`SealQ128_16384`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "178405960799016814493009085271400774888980481",
+    num_limbs = 3
+)]
+pub struct SealQ128_16384_1 {}
+impl SealQ128_16384_1 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x1fffffff68001, 0x1fffffff50001, 0x1ffffffee8001];
+}
+
+/**
+ * The third RNS group of [`SealQ128_16384`].
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(modulus = "316912648378404767787008557057", num_limbs = 2)]
+pub struct SealQ128_16384_2 {}
+impl SealQ128_16384_2 {
+    /// The primes in this group
+    pub const Q: &'static [u64] = &[0x1ffffffea0001, 0x1ffffffe88001];
+}
+
 /**
  * The configuration type for q modulus SEAL BFV uses with 128-bit security
  * an lattice dimension 8192.
@@ -124,6 +335,62 @@ impl SealQ128_1024 {
     pub const Q: &'static [u64] = &[0x7e00001];
 }
 
+#[allow(unused)]
+/**
+ * The ring for the first RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 32768.
+ */
+pub type ZqSeal128_32768_0 = Zq<3, BarrettBackend<3, SealQ128_32768_0>>;
+
+#[allow(unused)]
+/**
+ * The ring for the second RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 32768.
+ */
+pub type ZqSeal128_32768_1 = Zq<3, BarrettBackend<3, SealQ128_32768_1>>;
+
+#[allow(unused)]
+/**
+ * The ring for the third RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 32768.
+ */
+pub type ZqSeal128_32768_2 = Zq<3, BarrettBackend<3, SealQ128_32768_2>>;
+
+#[allow(unused)]
+/**
+ * The ring for the fourth RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 32768.
+ */
+pub type ZqSeal128_32768_3 = Zq<3, BarrettBackend<3, SealQ128_32768_3>>;
+
+#[allow(unused)]
+/**
+ * The ring for the fifth RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 32768.
+ */
+pub type ZqSeal128_32768_4 = Zq<3, BarrettBackend<3, SealQ128_32768_4>>;
+
+#[allow(unused)]
+/**
+ * The ring for the first RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 16384.
+ */
+pub type ZqSeal128_16384_0 = Zq<3, BarrettBackend<3, SealQ128_16384_0>>;
+
+#[allow(unused)]
+/**
+ * The ring for the second RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 16384.
+ */
+pub type ZqSeal128_16384_1 = Zq<3, BarrettBackend<3, SealQ128_16384_1>>;
+
+#[allow(unused)]
+/**
+ * The ring for the third RNS group of SEAL's BFV data modulus with 128-bit
+ * security and a poly degree of 16384.
+ */
+pub type ZqSeal128_16384_2 = Zq<2, BarrettBackend<2, SealQ128_16384_2>>;
+
 #[allow(unused)]
 /**
  * The field SEAL's BFV scheme uses with 128-bit security and a poly degree
@@ -243,6 +510,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crypto_bigint::CheckedMul;
     use sunscreen_math::{poly::Polynomial, ring::RingModulus};
 
     use crate::math::Log2;
@@ -351,6 +619,55 @@ mod tests {
         assert_eq!(Log2::log2(&modulus), 173);
     }
 
+    #[test]
+    fn rns_groups_partition_data_modulus() {
+        fn product(primes: &[u64]) -> Uint<4> {
+            primes.iter().fold(Uint::<4>::ONE, |acc, p| {
+                acc.checked_mul(&Uint::<4>::from_u64(*p)).unwrap()
+            })
+        }
+
+        for (q, groups) in [
+            (SealQ128_16384::Q, SealQ128_16384::GROUPS),
+            (SealQ128_32768::Q, SealQ128_32768::GROUPS),
+        ] {
+            assert_eq!(groups.concat(), q[..q.len() - 1]);
+        }
+
+        assert_eq!(
+            product(SealQ128_16384_0::Q),
+            ZqSeal128_16384_0::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_16384_1::Q),
+            ZqSeal128_16384_1::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_16384_2::Q),
+            ZqSeal128_16384_2::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_32768_0::Q),
+            ZqSeal128_32768_0::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_32768_1::Q),
+            ZqSeal128_32768_1::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_32768_2::Q),
+            ZqSeal128_32768_2::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_32768_3::Q),
+            ZqSeal128_32768_3::field_modulus()
+        );
+        assert_eq!(
+            product(SealQ128_32768_4::Q),
+            ZqSeal128_32768_4::field_modulus()
+        );
+    }
+
     #[test]
     fn can_convert_fp_ristretto_to_scalar() {
         let x = ZqRistretto::from(42);
@@ -87,5 +87,5 @@ where
         public_key: Cow::Borrowed(&public_key),
     };
     let witness = BfvWitness::PublicKeyEncryption(components);
-    generate_prover_knowledge(&[statement], &[message], &[witness], &params, &ctx)
+    generate_prover_knowledge(&[statement], &[message], &[witness], &params, &ctx).unwrap()
 }
@@ -0,0 +1,217 @@
+//! Helpers for the canonical binary encoding of proofs.
+//!
+//! # Remarks
+//! Unlike the serde representation, this encoding is explicit and stable
+//! across releases:
+//! * Ristretto points are encoded as their 32 byte compressed form.
+//! * Scalars are encoded as their 32 byte canonical little-endian form.
+//!   Decoding rejects non-canonical scalars.
+//! * Lengths are encoded as big-endian `u32`s.
+
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+
+use crate::ProofError;
+
+/**
+ * Appends the compressed encoding of `point` to `bytes`.
+ */
+pub fn write_point(bytes: &mut Vec<u8>, point: &RistrettoPoint) {
+    write_compressed_point(bytes, &point.compress());
+}
+
+/**
+ * Appends an already compressed point to `bytes`.
+ */
+pub fn write_compressed_point(bytes: &mut Vec<u8>, point: &CompressedRistretto) {
+    bytes.extend_from_slice(point.as_bytes());
+}
+
+/**
+ * Appends the canonical encoding of `scalar` to `bytes`.
+ */
+pub fn write_scalar(bytes: &mut Vec<u8>, scalar: &Scalar) {
+    bytes.extend_from_slice(scalar.as_bytes());
+}
+
+/**
+ * Appends `len` to `bytes` as a big-endian `u32`.
+ *
+ * # Panics
+ * If `len` doesn't fit in a `u32`.
+ */
+pub fn write_len(bytes: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("Length exceeds u32::MAX");
+
+    bytes.extend_from_slice(&len.to_be_bytes());
+}
+
+/**
+ * Appends the length of `points` followed by each point's compressed
+ * encoding.
+ */
+pub fn write_points(bytes: &mut Vec<u8>, points: &[RistrettoPoint]) {
+    write_len(bytes, points.len());
+
+    for p in points {
+        write_point(bytes, p);
+    }
+}
+
+/**
+ * Reads `N` bytes from the front of `bytes`, returning them and the
+ * remaining bytes.
+ */
+pub fn read_array<const N: usize>(bytes: &[u8]) -> Result<([u8; N], &[u8]), ProofError> {
+    if bytes.len() < N {
+        return Err(ProofError::MalformedProof);
+    }
+
+    let (val, rest) = bytes.split_at(N);
+
+    // Can't fail, as we just checked the length.
+    Ok((val.try_into().unwrap(), rest))
+}
+
+/**
+ * Reads a big-endian `u32` length.
+ */
+pub fn read_len(bytes: &[u8]) -> Result<(usize, &[u8]), ProofError> {
+    let (val, rest) = read_array::<4>(bytes)?;
+
+    Ok((u32::from_be_bytes(val) as usize, rest))
+}
+
+/**
+ * Reads a compressed point without decompressing it.
+ */
+pub fn read_compressed_point(bytes: &[u8]) -> Result<(CompressedRistretto, &[u8]), ProofError> {
+    let (val, rest) = read_array::<32>(bytes)?;
+
+    Ok((CompressedRistretto(val), rest))
+}
+
+/**
+ * Reads and decompresses a point.
+ *
+ * # Remarks
+ * Returns [`ProofError::MalformedProof`] if the bytes aren't a valid
+ * compressed point.
+ */
+pub fn read_point(bytes: &[u8]) -> Result<(RistrettoPoint, &[u8]), ProofError> {
+    let (val, rest) = read_compressed_point(bytes)?;
+    let val = val.decompress().ok_or(ProofError::MalformedProof)?;
+
+    Ok((val, rest))
+}
+
+/**
+ * Reads a scalar, rejecting non-canonical encodings.
+ */
+pub fn read_scalar(bytes: &[u8]) -> Result<(Scalar, &[u8]), ProofError> {
+    let (val, rest) = read_array::<32>(bytes)?;
+    let val = Scalar::from_canonical_bytes(val).ok_or(ProofError::MalformedProof)?;
+
+    Ok((val, rest))
+}
+
+/**
+ * Reads a length-prefixed list of points as written by [`write_points`].
+ */
+pub fn read_points(bytes: &[u8]) -> Result<(Vec<RistrettoPoint>, &[u8]), ProofError> {
+    let (len, mut rest) = read_len(bytes)?;
+
+    // Don't trust the length to size the allocation; a malicious length
+    // would otherwise let a tiny message request a huge buffer.
+    if len > rest.len() / 32 {
+        return Err(ProofError::MalformedProof);
+    }
+
+    let mut points = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let (p, r) = read_point(rest)?;
+        points.push(p);
+        rest = r;
+    }
+
+    Ok((points, rest))
+}
+
+/**
+ * Reads a length-prefixed list of compressed points without decompressing
+ * them.
+ */
+pub fn read_compressed_points(
+    bytes: &[u8],
+) -> Result<(Vec<CompressedRistretto>, &[u8]), ProofError> {
+    let (len, mut rest) = read_len(bytes)?;
+
+    if len > rest.len() / 32 {
+        return Err(ProofError::MalformedProof);
+    }
+
+    let mut points = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let (p, r) = read_compressed_point(rest)?;
+        points.push(p);
+        rest = r;
+    }
+
+    Ok((points, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    use super::*;
+
+    #[test]
+    fn can_roundtrip_points_and_scalars() {
+        let points = vec![
+            RISTRETTO_BASEPOINT_POINT,
+            RISTRETTO_BASEPOINT_POINT * Scalar::from(7u64),
+        ];
+        let scalar = Scalar::from(42u64);
+
+        let mut bytes = vec![];
+        write_points(&mut bytes, &points);
+        write_scalar(&mut bytes, &scalar);
+
+        assert_eq!(bytes.len(), 4 + 2 * 32 + 32);
+        assert_eq!(&bytes[0..4], &[0, 0, 0, 2]);
+
+        let (actual_points, rest) = read_points(&bytes).unwrap();
+        let (actual_scalar, rest) = read_scalar(rest).unwrap();
+
+        assert_eq!(actual_points, points);
+        assert_eq!(actual_scalar, scalar);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_encodings() {
+        // Non-canonical scalar
+        assert_eq!(
+            read_scalar(&[0xFF; 32]).unwrap_err(),
+            ProofError::MalformedProof
+        );
+
+        // Truncated input
+        assert_eq!(
+            read_point(&[0; 31]).unwrap_err(),
+            ProofError::MalformedProof
+        );
+
+        // Length exceeds the remaining data
+        let mut bytes = vec![];
+        write_len(&mut bytes, 1000);
+        write_point(&mut bytes, &RISTRETTO_BASEPOINT_POINT);
+
+        assert_eq!(read_points(&bytes).unwrap_err(), ProofError::MalformedProof);
+    }
+}
@@ -464,6 +464,7 @@ impl AsRef<SecretKey> for SecretKey {
  * CKKS scheme, where relinearization is much more computationally costly than
  * multiplications and additions.
  */
+#[derive(Debug)]
 pub struct RelinearizationKeys {
     handle: *mut c_void,
 }
@@ -487,6 +488,19 @@ impl RelinearizationKeys {
         Ok(Self { handle })
     }
 
+    /**
+     * Returns the key switching keys for relinearizing the `s^2` term of a size 3
+     * ciphertext. There is one key per modulus in the ciphertext modulus, excluding
+     * the special modulus.
+     */
+    pub fn keys(&self) -> Result<Vec<PublicKey>> {
+        // SEAL stores the keys for s^k at index k - 2.
+        Ok(kswitch_key_lists(self.handle)?
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
     /**
      * Returns the key as a byte array.
      */
@@ -650,6 +664,59 @@ impl GaloisKeys {
 
         Ok(Self { handle })
     }
+
+    /**
+     * Returns the key switching keys for each Galois element these keys
+     * support, along with that element. Each set of keys switches from
+     * `s(x^g)` to `s` and has one key per modulus in the ciphertext modulus,
+     * excluding the special modulus.
+     */
+    pub fn keys(&self) -> Result<Vec<(u64, Vec<PublicKey>)>> {
+        // SEAL stores the keys for Galois element g at index (g - 1) / 2 and
+        // leaves the lists for elements that weren't generated empty.
+        Ok(kswitch_key_lists(self.handle)?
+            .into_iter()
+            .enumerate()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(i, keys)| (2 * i as u64 + 1, keys))
+            .collect())
+    }
+}
+
+/**
+ * Copies the lists of keys out of a SEAL `KSwitchKeys` object.
+ */
+fn kswitch_key_lists(handle: *mut c_void) -> Result<Vec<Vec<PublicKey>>> {
+    let mut num_lists: u64 = 0;
+
+    convert_seal_error(unsafe { bindgen::KSwitchKeys_RawSize(handle, &mut num_lists) })?;
+
+    (0..num_lists)
+        .map(|index| {
+            let mut count: u64 = 0;
+
+            convert_seal_error(unsafe {
+                bindgen::KSwitchKeys_GetKeyList(handle, index, &mut count, null_mut())
+            })?;
+
+            let mut keys: Vec<*mut c_void> = vec![null_mut(); count as usize];
+
+            convert_seal_error(unsafe {
+                bindgen::KSwitchKeys_GetKeyList(handle, index, &mut count, keys.as_mut_ptr())
+            })?;
+
+            // The returned keys point into the KSwitchKeys object, so copy them.
+            keys.into_iter()
+                .map(|key| {
+                    let mut handle: *mut c_void = null_mut();
+
+                    convert_seal_error(unsafe { bindgen::PublicKey_Create2(key, &mut handle) })?;
+
+                    Ok(PublicKey { handle })
+                })
+                .collect()
+        })
+        .collect()
 }
 
 impl PartialEq for GaloisKeys {
@@ -831,6 +898,35 @@ mod tests {
         gen.create_galois_keys().unwrap();
     }
 
+    #[test]
+    fn can_get_key_switching_keys() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        // One key per data modulus.
+        let relin_keys = gen.create_relinearization_keys().unwrap();
+        assert_eq!(relin_keys.keys().unwrap().len(), 4);
+
+        let galois_keys = gen.create_galois_keys().unwrap();
+        let galois_keys = galois_keys.keys().unwrap();
+
+        assert!(!galois_keys.is_empty());
+
+        for (g, keys) in galois_keys {
+            assert_eq!(g % 2, 1);
+            assert_eq!(keys.len(), 4);
+        }
+    }
+
     #[test]
     fn can_init_from_existing_secret_key() {
         let params = BfvEncryptionParametersBuilder::new()
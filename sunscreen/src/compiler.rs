@@ -467,25 +467,8 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
         let linked_zkp_programs = zkp_data.linked_zkp_program_fns.iter().map(|prog| {
             // As long as we've properly maintained invariants internal to the compiler, linked
             // programs should only be present when params are available.
-            let params = params.expect("no params; please file a bug!").clone();
-            // Note: this is currently unsupported because determining the exact arbitrary
-            // plaintext modulus from the dynamic length of a linked ZKP input in general is not
-            // possible (as x.ilog2() is not injective). We may support this in the future.
-            if !params.plain_modulus.is_power_of_two() {
-                return Err(Error::unsupported(
-                    "Plaintext modulus must be a power of two for ZKP programs with #[linked] arguments.",
-                ));
-            }
-            let result = prog.build(params.plain_modulus)?;
-            let result = zkp::compile(&result);
-            let metadata = ZkpProgramMetadata {
-                params: Some(params),
-                signature: prog.signature(),
-            };
-            let compiled_program = CompiledZkpProgram {
-                zkp_program_fn: result,
-                metadata,
-            };
+            let params = params.expect("no params; please file a bug!");
+            let compiled_program = compile_linked_zkp_program(prog.as_ref(), params)?;
             Ok((prog.name().to_owned(), compiled_program))
         });
         let zkp_programs = zkp_data
@@ -512,6 +495,32 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
     }
 }
 
+/// Compile a ZKP program with inputs linked from FHE programs using `params`.
+pub(crate) fn compile_linked_zkp_program<B: FieldSpec>(
+    prog: &dyn ZkpProgramFn<B, Link = Linked>,
+    params: &Params,
+) -> Result<CompiledZkpProgram> {
+    // Note: this is currently unsupported because determining the exact arbitrary
+    // plaintext modulus from the dynamic length of a linked ZKP input in general is not
+    // possible (as x.ilog2() is not injective). We may support this in the future.
+    if !params.plain_modulus.is_power_of_two() {
+        return Err(Error::unsupported(
+            "Plaintext modulus must be a power of two for ZKP programs with #[linked] arguments.",
+        ));
+    }
+    let result = prog.build(params.plain_modulus)?;
+    let result = zkp::compile(&result);
+    let metadata = ZkpProgramMetadata {
+        params: Some(params.clone()),
+        signature: prog.signature(),
+    };
+
+    Ok(CompiledZkpProgram {
+        zkp_program_fn: result,
+        metadata,
+    })
+}
+
 impl<B> ZkpCompiler<B>
 where
     B: FieldSpec,
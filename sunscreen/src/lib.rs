@@ -73,7 +73,10 @@ pub use sunscreen_runtime::{
 };
 #[cfg(feature = "bulletproofs")]
 pub use sunscreen_zkp_backend::bulletproofs;
-pub use sunscreen_zkp_backend::{Error as ZkpError, Proof, Result as ZkpResult, ZkpBackend};
+pub use sunscreen_zkp_backend::{
+    Commitment, CommitmentOpening, Error as ZkpError, Proof, Result as ZkpResult,
+    UnsatisfiedConstraint, ZkpBackend,
+};
 pub use zkp::{invoke_gadget, ZkpProgramFn, ZkpProgramFnExt};
 
 #[derive(Clone)]
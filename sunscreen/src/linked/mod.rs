@@ -24,9 +24,17 @@
 //! encryption](crate::FheRuntime::encrypt), while also opting to _share_ a message with a linked
 //! ZKP program. Under the hood, we'll handle the complicated bits of generating a linear relation
 //! for SDLP and sharing the secrets with the [`zkp_program`](crate::zkp_program).
+//!
+//! For the common case of proving an encrypted value lies in a range, [`RangeProofExt`] provides
+//! built-in proofs that don't require writing a ZKP program at all.
+mod range;
+
+pub use range::*;
+
 pub use logproof::Bounds;
 pub use sunscreen_runtime::{
-    ExistingMessage, LinkWithZkp, LinkedMessage, LinkedProof, LinkedProofBuilder,
+    ExistingMessage, LinkWithTfhe, LinkWithZkp, LinkedMessage, LinkedProof, LinkedProofBuilder,
     LinkedProofVerificationBuilder, Message, MessageRef, Sdlp, SdlpBuilder,
-    SdlpVerificationBuilder,
+    SdlpVerificationBuilder, TfheLinkedMessage, TfheLinkedProofBuilder,
+    TfheLinkedProofVerificationBuilder,
 };
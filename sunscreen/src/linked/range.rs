@@ -0,0 +1,215 @@
+use std::{
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+
+use crypto_bigint::Uint;
+use sunscreen_runtime::LinkWithZkp;
+use sunscreen_zkp_backend::bulletproofs::{BulletproofsBackend, BulletproofsFieldSpec};
+
+use crate as sunscreen;
+use crate::{
+    compiler::compile_linked_zkp_program,
+    types::{
+        bfv::{Signed, Unsigned128, Unsigned64},
+        zkp::{
+            AsFieldElement, BfvSigned, BfvUnsigned128, BfvUnsigned64, BigInt, BulletproofsField,
+            ConstrainCmp, Field, FieldSpec,
+        },
+        TryIntoPlaintext, TypeName,
+    },
+    zkp::Linked,
+    zkp_program, Ciphertext, CompiledZkpProgram, FheZkpRuntime, Params, PublicKey, Result,
+    ZkpProgramFn,
+};
+
+use super::LinkedProof;
+
+/// Prove that an encrypted `Signed` lies in `[lo, hi]`. Since `lo <= x` and `x <= hi` as field
+/// elements with 64-bit differences, `x` is an integer in the range, so the decrypted value is
+/// too.
+#[zkp_program]
+fn signed_range<F: FieldSpec>(
+    #[linked] x: BfvSigned<F>,
+    #[public] lo: Field<F>,
+    #[public] hi: Field<F>,
+) {
+    let x = x.into_field_elem();
+    x.constrain_ge_bounded(lo, 64);
+    x.constrain_le_bounded(hi, 64);
+}
+
+/// Prove that an encrypted `Unsigned64` lies in `[lo, hi]`.
+#[zkp_program]
+fn unsigned64_range<F: FieldSpec>(
+    #[linked] x: BfvUnsigned64<F>,
+    #[public] lo: Field<F>,
+    #[public] hi: Field<F>,
+) {
+    let x = x.into_field_elem();
+    x.constrain_ge_bounded(lo, 64);
+    x.constrain_le_bounded(hi, 64);
+}
+
+/// Prove that an encrypted `Unsigned128` lies in `[lo, hi]`.
+#[zkp_program]
+fn unsigned128_range<F: FieldSpec>(
+    #[linked] x: BfvUnsigned128<F>,
+    #[public] lo: Field<F>,
+    #[public] hi: Field<F>,
+) {
+    let x = x.into_field_elem();
+    x.constrain_ge_bounded(lo, 128);
+    x.constrain_le_bounded(hi, 128);
+}
+
+/// The range-check programs of one type, compiled for each set of parameters they've been used
+/// with.
+type ProgramCache = Mutex<Vec<(Params, Arc<CompiledZkpProgram>)>>;
+
+fn cached_program(
+    cache: &ProgramCache,
+    params: &Params,
+    prog: &dyn ZkpProgramFn<BulletproofsFieldSpec, Link = Linked>,
+) -> Result<Arc<CompiledZkpProgram>> {
+    let mut cache = cache.lock().unwrap();
+
+    if let Some((_, program)) = cache.iter().find(|(p, _)| p == params) {
+        return Ok(program.clone());
+    }
+
+    let program = Arc::new(compile_linked_zkp_program(prog, params)?);
+    cache.push((params.clone(), program.clone()));
+
+    Ok(program)
+}
+
+/// A plaintext type whose encryptions can carry a built-in proof that the encrypted value lies
+/// in a range, without writing a [`zkp_program`](crate::zkp_program). See [`RangeProofExt`].
+pub trait RangeProvable: LinkWithZkp + TryIntoPlaintext + TypeName {
+    /// Convert this value to the field element the range-check program decodes it to.
+    fn to_field(&self) -> BulletproofsField;
+
+    /// Get the range-check ZKP program for this type, compiled for `params`.
+    ///
+    /// Programs are compiled on first use and cached for later calls with the same parameters.
+    fn range_program(params: &Params) -> Result<Arc<CompiledZkpProgram>>;
+}
+
+impl RangeProvable for Signed {
+    fn to_field(&self) -> BulletproofsField {
+        BulletproofsField::from(i64::from(*self))
+    }
+
+    fn range_program(params: &Params) -> Result<Arc<CompiledZkpProgram>> {
+        static PROGRAMS: ProgramCache = Mutex::new(Vec::new());
+        cached_program(&PROGRAMS, params, &signed_range)
+    }
+}
+
+impl RangeProvable for Unsigned64 {
+    fn to_field(&self) -> BulletproofsField {
+        BulletproofsField::from(BigInt(Uint::from(*self).resize()))
+    }
+
+    fn range_program(params: &Params) -> Result<Arc<CompiledZkpProgram>> {
+        static PROGRAMS: ProgramCache = Mutex::new(Vec::new());
+        cached_program(&PROGRAMS, params, &unsigned64_range)
+    }
+}
+
+impl RangeProvable for Unsigned128 {
+    fn to_field(&self) -> BulletproofsField {
+        BulletproofsField::from(BigInt(Uint::from(*self).resize()))
+    }
+
+    fn range_program(params: &Params) -> Result<Arc<CompiledZkpProgram>> {
+        static PROGRAMS: ProgramCache = Mutex::new(Vec::new());
+        cached_program(&PROGRAMS, params, &unsigned128_range)
+    }
+}
+
+/// Built-in proofs that an encrypted value lies in a range, e.g. that an encrypted balance is
+/// non-negative.
+///
+/// These encrypt the value and link it to a range-check ZKP program, like you would with a
+/// [`LinkedProofBuilder`](super::LinkedProofBuilder) and your own
+/// [`zkp_program`](crate::zkp_program), but the program is compiled and cached for you.
+///
+/// ```no_run
+/// # use sunscreen::{bulletproofs::BulletproofsBackend, linked::RangeProofExt, types::bfv::Signed, FheZkpRuntime, Params, Result};
+/// # fn example(params: &Params) -> Result<()> {
+/// let runtime = FheZkpRuntime::new(params, &BulletproofsBackend::new())?;
+/// let (public_key, _private_key) = runtime.generate_keys()?;
+///
+/// let non_negative = Signed::from(0)..=Signed::from(i64::MAX);
+/// let (balance, proof) =
+///     runtime.encrypt_with_range_proof(&Signed::from(100), non_negative.clone(), &public_key)?;
+///
+/// runtime.verify_range_proof::<Signed>(&balance, non_negative, &public_key, &proof)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait RangeProofExt {
+    /// Encrypt `value` under `public_key`, returning the ciphertext and a [`LinkedProof`] that it
+    /// is a valid encryption of a value in `range`.
+    fn encrypt_with_range_proof<P: RangeProvable>(
+        &self,
+        value: &P,
+        range: RangeInclusive<P>,
+        public_key: &PublicKey,
+    ) -> Result<(Ciphertext, LinkedProof)>;
+
+    /// Verify a proof from [`RangeProofExt::encrypt_with_range_proof`] that `ciphertext` is a
+    /// valid encryption under `public_key` of a value in `range`.
+    fn verify_range_proof<P: RangeProvable>(
+        &self,
+        ciphertext: &Ciphertext,
+        range: RangeInclusive<P>,
+        public_key: &PublicKey,
+        proof: &LinkedProof,
+    ) -> Result<()>;
+}
+
+impl RangeProofExt for FheZkpRuntime<BulletproofsBackend> {
+    fn encrypt_with_range_proof<P: RangeProvable>(
+        &self,
+        value: &P,
+        range: RangeInclusive<P>,
+        public_key: &PublicKey,
+    ) -> Result<(Ciphertext, LinkedProof)> {
+        let program = P::range_program(self.params())?;
+        let mut builder = self.linkedproof_builder();
+
+        let (ciphertext, link) = builder.encrypt_returning_link(value, public_key)?;
+        let proof = builder
+            .zkp_program(&program)?
+            .linked_input(link)
+            .public_input(range.start().to_field())
+            .public_input(range.end().to_field())
+            .build()?;
+
+        Ok((ciphertext, proof))
+    }
+
+    fn verify_range_proof<P: RangeProvable>(
+        &self,
+        ciphertext: &Ciphertext,
+        range: RangeInclusive<P>,
+        public_key: &PublicKey,
+        proof: &LinkedProof,
+    ) -> Result<()> {
+        let program = P::range_program(self.params())?;
+        let mut builder = self.linkedproof_verification_builder();
+
+        builder.encrypt_returning_link::<P>(ciphertext, public_key)?;
+        builder
+            .zkp_program(&program)?
+            .proof(proof.clone())
+            .public_input(range.start().to_field())
+            .public_input(range.end().to_field())
+            .verify()?;
+
+        Ok(())
+    }
+}
@@ -7,6 +7,10 @@ use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
 use super::ToUInt;
 
 #[derive(Clone, Copy)]
+/**
+ * Computes the quotient and remainder of `x / m`, where `x` is interpreted
+ * as a signed value, and proves they're correct.
+ */
 pub struct SignedModulus {
     field_modulus: BigInt,
     max_remainder_bits: usize,
@@ -134,6 +138,12 @@ pub struct Inverse {
 }
 
 impl Inverse {
+    /**
+     * Creates a new [`Inverse`] gadget.
+     *
+     * # Panics
+     * * If `field_modulus == 0`
+     */
     pub fn new(field_modulus: BigInt) -> Self {
         if field_modulus == BigInt::ZERO {
             panic!("Field modulus cannot be zero.");
@@ -0,0 +1,419 @@
+use petgraph::stable_graph::NodeIndex;
+use subtle::{Choice, ConditionallySelectable};
+use sunscreen_zkp_backend::{BigInt, Error as ZkpError, Gadget, Result as ZkpResult};
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
+
+use super::{Poseidon, ToUInt};
+
+/**
+ * Domain tag hashed with each leaf to form its leaf node. Leaf nodes are
+ * `Poseidon(LEAF_DOMAIN, leaf)`, while unused leaf slots hold the zero
+ * sentinel, so no leaf value (including zero) can prove membership at an
+ * unused index.
+ */
+const LEAF_DOMAIN: u32 = 1;
+
+fn leaf_domain() -> BigInt {
+    BigInt::from(LEAF_DOMAIN)
+}
+
+/**
+ * A Merkle tree over field elements using the [`Poseidon`] hash, computed
+ * outside of a ZKP program. Use this to build the tree whose root a
+ * [`MerkleMembership`] proof verifies against.
+ *
+ * # Remarks
+ * The tree always has `2^depth` leaves. Each leaf is hashed with a domain
+ * tag before entering the tree. Slots not filled by [`MerkleTree::new`]
+ * hold a zero sentinel instead, which no hashed leaf can equal, so even a
+ * zero leaf can't prove membership at an unused index. The subtrees formed
+ * by unused slots are never materialized, so sparse trees with large
+ * depths are cheap.
+ */
+pub struct MerkleTree {
+    hasher: Poseidon,
+    depth: usize,
+    // layers[0] holds the hashed leaves and layers[depth] the root. Each layer
+    // only stores nodes with at least one non-padding descendant.
+    layers: Vec<Vec<BigInt>>,
+    // empty_subtrees[i] is the root of a subtree of height i with no
+    // leaves.
+    empty_subtrees: Vec<BigInt>,
+}
+
+impl MerkleTree {
+    /**
+     * Builds a Merkle tree with `2^depth` leaves, the first of which are
+     * `leaves`.
+     *
+     * # Panics
+     * * If `depth` is zero or greater than 63.
+     * * If `leaves.len() > 2^depth`.
+     * * If any leaf is not less than `field_modulus`.
+     */
+    pub fn new(field_modulus: BigInt, depth: usize, leaves: &[BigInt]) -> Self {
+        if depth == 0 || depth > 63 {
+            panic!("Merkle tree depth must be in [1, 63].");
+        }
+
+        if leaves.len() as u64 > 1 << depth {
+            panic!("Too many leaves for a Merkle tree of depth {depth}.");
+        }
+
+        let hasher = Poseidon::new(field_modulus);
+
+        let mut empty_subtrees = vec![BigInt::ZERO];
+
+        for i in 0..depth {
+            empty_subtrees.push(hasher.hash(&empty_subtrees[i], &empty_subtrees[i]));
+        }
+
+        let mut layers = vec![leaves
+            .iter()
+            .map(|leaf| hasher.hash(&leaf_domain(), leaf))
+            .collect::<Vec<_>>()];
+
+        for i in 0..depth {
+            let layer = layers[i]
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&empty_subtrees[i]);
+
+                    hasher.hash(&pair[0], right)
+                })
+                .collect();
+
+            layers.push(layer);
+        }
+
+        Self {
+            hasher,
+            depth,
+            layers,
+            empty_subtrees,
+        }
+    }
+
+    /**
+     * The root of this tree.
+     */
+    pub fn root(&self) -> BigInt {
+        self.node(self.depth, 0)
+    }
+
+    /**
+     * The number of levels between the leaves and the root.
+     */
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /**
+     * Returns the siblings on the path from the leaf at `index` to the
+     * root, starting with the leaf's sibling. Pass these as the sibling
+     * inputs to [`MerkleMembership`].
+     *
+     * # Panics
+     * * If `index >= 2^depth`.
+     */
+    pub fn path(&self, index: u64) -> Vec<BigInt> {
+        if index >= 1 << self.depth {
+            panic!("Leaf index {index} out of range.");
+        }
+
+        (0..self.depth)
+            .map(|level| self.node(level, ((index >> level) ^ 0x1) as usize))
+            .collect()
+    }
+
+    /**
+     * Hashes a leaf up to the root along the given `path`, as a
+     * [`MerkleMembership`] proof would.
+     */
+    pub fn root_from_path(&self, leaf: &BigInt, index: u64, path: &[BigInt]) -> BigInt {
+        compute_root(&self.hasher, leaf, &BigInt::from(index), path)
+    }
+
+    fn node(&self, level: usize, index: usize) -> BigInt {
+        self.layers[level]
+            .get(index)
+            .copied()
+            .unwrap_or(self.empty_subtrees[level])
+    }
+}
+
+fn compute_root(hasher: &Poseidon, leaf: &BigInt, index: &BigInt, path: &[BigInt]) -> BigInt {
+    let mut cur = hasher.hash(&leaf_domain(), leaf);
+
+    for (i, sibling) in path.iter().enumerate() {
+        // When the bit is set, the current node is the right child.
+        let is_right = Choice::from(index.bit_vartime(i) as u8);
+
+        let left = BigInt::conditional_select(&cur, sibling, is_right);
+        let right = BigInt::conditional_select(sibling, &cur, is_right);
+
+        cur = hasher.hash(&left, &right);
+    }
+
+    cur
+}
+
+#[derive(Clone)]
+/**
+ * Proves a private leaf belongs to a [`MerkleTree`] with a given root
+ * without revealing which leaf it is. The circuit size is logarithmic in
+ * the number of leaves, which makes it suitable for allowlists with
+ * millions of entries.
+ *
+ * Gadget inputs are, in order:
+ * * the leaf
+ * * the leaf's index in the tree
+ * * the root
+ * * the `depth` siblings from [`MerkleTree::path`]
+ *
+ * This gadget has no outputs.
+ */
+pub struct MerkleMembership {
+    hasher: Poseidon,
+    depth: usize,
+}
+
+impl MerkleMembership {
+    /**
+     * Creates a new [`MerkleMembership`] gadget for trees of the given
+     * depth over the field with the given modulus.
+     *
+     * # Panics
+     * * If `depth` is zero or greater than 63.
+     */
+    pub fn new(field_modulus: BigInt, depth: usize) -> Self {
+        if depth == 0 || depth > 63 {
+            panic!("Merkle tree depth must be in [1, 63].");
+        }
+
+        Self {
+            hasher: Poseidon::new(field_modulus),
+            depth,
+        }
+    }
+}
+
+impl Gadget for MerkleMembership {
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let leaf = gadget_inputs[0];
+        let index = gadget_inputs[1];
+        let root = gadget_inputs[2];
+        let path = &gadget_inputs[3..];
+
+        if compute_root(&self.hasher, &leaf, &index, path) != root {
+            return Err(ZkpError::gadget_error(
+                "Merkle path does not lead to the given root.",
+            ));
+        }
+
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let leaf = gadget_inputs[0];
+        let index = gadget_inputs[1];
+        let root = gadget_inputs[2];
+        let path = &gadget_inputs[3..];
+
+        // The index's bits say whether each node on the path is a left or
+        // right child. ToUInt proves the bits are binary.
+        let bits = invoke_gadget(ToUInt::new(self.depth), &[index]);
+
+        let domain = with_zkp_ctx(|ctx| ctx.add_constant(&leaf_domain()));
+
+        let mut cur = invoke_gadget(self.hasher.clone(), &[domain, leaf])[0];
+
+        for (bit, sibling) in bits.iter().zip(path) {
+            // left = cur + bit * (sibling - cur)
+            // right = sibling - bit * (sibling - cur)
+            let (left, right) = with_zkp_ctx(|ctx| {
+                let diff = ctx.add_subtraction(*sibling, cur);
+                let swap = ctx.add_multiplication(*bit, diff);
+
+                (
+                    ctx.add_addition(cur, swap),
+                    ctx.add_subtraction(*sibling, swap),
+                )
+            });
+
+            cur = invoke_gadget(self.hasher.clone(), &[left, right])[0];
+        }
+
+        with_zkp_ctx(|ctx| {
+            let diff = ctx.add_subtraction(cur, root);
+            ctx.add_constraint(diff, &BigInt::ZERO);
+        });
+
+        vec![]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        3 + self.depth
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, FieldSpec, ZkpBackend};
+
+    use crate::types::zkp::Field;
+    use crate::{self as sunscreen, invoke_gadget, Compiler};
+
+    use super::*;
+
+    const DEPTH: usize = 4;
+
+    fn field_modulus() -> BigInt {
+        <BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS
+    }
+
+    #[test]
+    fn sparse_tree_matches_full_tree() {
+        let leaves = (0..5u32).map(|x| BigInt::from(100 + x)).collect::<Vec<_>>();
+
+        let sparse = MerkleTree::new(field_modulus(), DEPTH, &leaves);
+
+        let hasher = Poseidon::new(field_modulus());
+
+        let mut layer = leaves
+            .iter()
+            .map(|x| hasher.hash(&leaf_domain(), x))
+            .collect::<Vec<_>>();
+        layer.resize(1 << DEPTH, BigInt::ZERO);
+
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| hasher.hash(&pair[0], &pair[1]))
+                .collect();
+        }
+
+        assert_eq!(sparse.root(), layer[0]);
+    }
+
+    #[test]
+    fn zero_leaf_is_not_padding() {
+        let leaves = (0..5u32).map(|x| BigInt::from(100 + x)).collect::<Vec<_>>();
+
+        let sparse = MerkleTree::new(field_modulus(), DEPTH, &leaves);
+
+        let mut padded = leaves.clone();
+        padded.resize(1 << DEPTH, BigInt::ZERO);
+
+        let padded = MerkleTree::new(field_modulus(), DEPTH, &padded);
+
+        assert_ne!(sparse.root(), padded.root());
+
+        for i in 5..(1 << DEPTH) {
+            assert_ne!(
+                sparse.root_from_path(&BigInt::ZERO, i, &sparse.path(i)),
+                sparse.root()
+            );
+        }
+    }
+
+    #[test]
+    fn path_leads_to_root() {
+        let leaves = (0..11u32)
+            .map(|x| BigInt::from(100 + x))
+            .collect::<Vec<_>>();
+
+        let tree = MerkleTree::new(field_modulus(), DEPTH, &leaves);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let i = i as u64;
+
+            assert_eq!(tree.root_from_path(leaf, i, &tree.path(i)), tree.root());
+        }
+
+        assert_ne!(
+            tree.root_from_path(&leaves[0], 1, &tree.path(1)),
+            tree.root()
+        );
+    }
+
+    #[test]
+    fn can_prove_membership() {
+        #[zkp_program]
+        fn membership<F: FieldSpec>(
+            leaf: Field<F>,
+            index: Field<F>,
+            path: [Field<F>; DEPTH],
+            #[public] root: Field<F>,
+        ) {
+            let mut inputs = vec![leaf.ids[0], index.ids[0], root.ids[0]];
+            inputs.extend(path.iter().map(|x| x.ids[0]));
+
+            invoke_gadget(MerkleMembership::new(F::FIELD_MODULUS, DEPTH), &inputs);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(membership)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(membership).unwrap();
+
+        type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+        let leaves = (0..11u32)
+            .map(|x| BigInt::from(100 + x))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(field_modulus(), DEPTH, &leaves);
+        let root = BpField::from(tree.root());
+
+        let test_case = |leaf: u32, index: u64, expect_pass: bool| {
+            let path = tree.path(index);
+            let path: [BpField; DEPTH] = std::array::from_fn(|i| BpField::from(path[i]));
+
+            let result = runtime
+                .proof_builder(prog)
+                .private_input(BpField::from(leaf))
+                .private_input(BpField::from(index))
+                .private_input(path)
+                .public_input(root)
+                .prove();
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verification_builder(prog)
+                .proof(&proof)
+                .public_input(root)
+                .verify()
+                .unwrap();
+        };
+
+        test_case(100, 0, true);
+        test_case(107, 7, true);
+        test_case(110, 10, true);
+        test_case(107, 6, false);
+        test_case(111, 11, false);
+        test_case(0, 11, false);
+        test_case(0, 15, false);
+    }
+}
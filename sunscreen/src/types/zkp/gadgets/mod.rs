@@ -1,5 +1,9 @@
 mod arithmetic;
 mod binary;
+mod merkle;
+mod poseidon;
 
 pub use arithmetic::*;
 pub use binary::*;
+pub use merkle::*;
+pub use poseidon::*;
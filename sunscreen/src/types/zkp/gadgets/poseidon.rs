@@ -0,0 +1,454 @@
+use crypto_bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
+    NonZero, U512,
+};
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Error as ZkpError, Gadget, Result as ZkpResult};
+
+use crate::zkp::{with_zkp_ctx, ZkpContextOps};
+
+/**
+ * The number of field elements in the Poseidon state. One element is
+ * capacity, the remaining two are rate, which makes the permutation a
+ * 2-to-1 compression function suitable for Merkle trees.
+ */
+pub const POSEIDON_WIDTH: usize = 3;
+
+/**
+ * The number of full S-box rounds. Half are applied before the partial
+ * rounds and half after.
+ */
+const FULL_ROUNDS: usize = 8;
+
+/**
+ * The number of partial S-box rounds, where only the first state element
+ * passes through the S-box.
+ */
+const PARTIAL_ROUNDS: usize = 57;
+
+/**
+ * The number of state elements the circuit re-materializes as hidden
+ * inputs after every partial round.
+ */
+const HIDDEN_PER_PARTIAL_ROUND: usize = POSEIDON_WIDTH - 1;
+
+type Residue = DynResidue<{ U512::LIMBS }>;
+
+/**
+ * The Grain LFSR the Poseidon reference implementation uses to derive round
+ * constants, so the constants are "nothing up my sleeve" numbers.
+ */
+struct GrainLfsr {
+    // Bit i holds the i-th oldest bit of the 80-bit register.
+    state: u128,
+}
+
+impl GrainLfsr {
+    fn new(field_bits: usize) -> Self {
+        let mut state = 0u128;
+        let mut pos = 0;
+
+        let mut push = |value: u64, width: usize| {
+            for k in (0..width).rev() {
+                state |= (((value >> k) & 0x1) as u128) << pos;
+                pos += 1;
+            }
+        };
+
+        // Prime field
+        push(1, 2);
+        // x^alpha S-box
+        push(0, 4);
+        push(field_bits as u64, 12);
+        push(POSEIDON_WIDTH as u64, 12);
+        push(FULL_ROUNDS as u64, 10);
+        push(PARTIAL_ROUNDS as u64, 10);
+        push((1 << 30) - 1, 30);
+
+        let mut lfsr = Self { state };
+
+        for _ in 0..160 {
+            lfsr.clock();
+        }
+
+        lfsr
+    }
+
+    fn clock(&mut self) -> bool {
+        let s = self.state;
+        let bit = ((s >> 62) ^ (s >> 51) ^ (s >> 38) ^ (s >> 23) ^ (s >> 13) ^ s) & 0x1;
+
+        self.state = (s >> 1) | (bit << 79);
+
+        bit == 1
+    }
+
+    fn next_bit(&mut self) -> bool {
+        // Self-shrinking: emit the second bit of each pair only when the
+        // first bit is set.
+        loop {
+            let select = self.clock();
+            let bit = self.clock();
+
+            if select {
+                return bit;
+            }
+        }
+    }
+
+    /**
+     * Samples a field element by rejection, reading `field_bits` bits
+     * most-significant first.
+     */
+    fn next_field_element(&mut self, field_modulus: &BigInt, field_bits: usize) -> BigInt {
+        loop {
+            let mut x = U512::ZERO;
+
+            for _ in 0..field_bits {
+                x = x
+                    .shl_vartime(1)
+                    .wrapping_add(&U512::from_u8(self.next_bit() as u8));
+            }
+
+            if x < field_modulus.0 {
+                return BigInt(x);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+/**
+ * The Poseidon hash function over a prime field, with a width 3 state, the
+ * `x^5` S-box, 8 full rounds and 57 partial rounds. This offers 128-bit
+ * security for fields of roughly 255 bits, such as the Ristretto scalar
+ * field used by Bulletproofs.
+ *
+ * As a [`Gadget`], this takes 2 field elements and outputs their hash.
+ * The same function can be computed outside a ZKP program with
+ * [`Poseidon::hash`], e.g. to build a [`MerkleTree`](super::MerkleTree).
+ *
+ * # Remarks
+ * Round constants are generated with the Grain LFSR exactly as in the
+ * Poseidon reference implementation. The MDS matrix is the Cauchy matrix
+ * `M[i][j] = 1 / (i + j + 3)`.
+ *
+ * To keep linear combinations from growing with every partial round, the
+ * circuit asks the prover for the 2 state elements that bypass the S-box
+ * after each partial round as hidden inputs and constrains them equal to
+ * their computed values.
+ */
+pub struct Poseidon {
+    field_modulus: BigInt,
+    params: DynResidueParams<{ U512::LIMBS }>,
+    round_constants: Vec<[Residue; POSEIDON_WIDTH]>,
+    mds: [[Residue; POSEIDON_WIDTH]; POSEIDON_WIDTH],
+}
+
+impl Poseidon {
+    /**
+     * Creates a new [`Poseidon`] hash over the field with the given
+     * modulus. Pass `F::FIELD_MODULUS` for the [`FieldSpec`](crate::types::zkp::FieldSpec)
+     * of your backend.
+     *
+     * # Panics
+     * * If `field_modulus` is even.
+     * * If `x^5` is not a permutation of the field (i.e. `5` divides
+     *   `field_modulus - 1`).
+     */
+    pub fn new(field_modulus: BigInt) -> Self {
+        if !field_modulus.bit_vartime(0) {
+            panic!("Poseidon requires an odd prime field modulus.");
+        }
+
+        let five = NonZero::from_uint(U512::from_u8(5));
+
+        if field_modulus.wrapping_sub(&U512::ONE).rem(&five) == U512::ZERO {
+            panic!("x^5 is not a permutation of the given field.");
+        }
+
+        let params = DynResidueParams::new(&field_modulus.0);
+        let field_bits = field_modulus.bits();
+
+        let mut lfsr = GrainLfsr::new(field_bits);
+
+        let round_constants = (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|_| {
+                std::array::from_fn(|_| {
+                    let c = lfsr.next_field_element(&field_modulus, field_bits);
+
+                    Residue::new(&c.0, params)
+                })
+            })
+            .collect();
+
+        let mds = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let x = Residue::new(&U512::from_u64((i + j + POSEIDON_WIDTH) as u64), params);
+
+                x.invert().0
+            })
+        });
+
+        Self {
+            field_modulus,
+            params,
+            round_constants,
+            mds,
+        }
+    }
+
+    /**
+     * Hashes `left` and `right` into a single field element.
+     *
+     * # Panics
+     * * If either input is not less than the field modulus.
+     */
+    pub fn hash(&self, left: &BigInt, right: &BigInt) -> BigInt {
+        assert!(
+            *left < self.field_modulus && *right < self.field_modulus,
+            "Poseidon inputs must be reduced modulo the field modulus."
+        );
+
+        let (out, _) = self.hash_with_trace(left, right);
+
+        out
+    }
+
+    /**
+     * Computes the hash along with the state elements the circuit
+     * re-materializes after each partial round.
+     */
+    fn hash_with_trace(&self, left: &BigInt, right: &BigInt) -> (BigInt, Vec<BigInt>) {
+        let mut state = [
+            Residue::zero(self.params),
+            Residue::new(&left.0, self.params),
+            Residue::new(&right.0, self.params),
+        ];
+
+        let mut trace = Vec::with_capacity(PARTIAL_ROUNDS * HIDDEN_PER_PARTIAL_ROUND);
+
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            for (s, c) in state.iter_mut().zip(constants) {
+                *s += c;
+            }
+
+            if Self::is_full_round(round) {
+                for s in state.iter_mut() {
+                    *s = Self::sbox(s);
+                }
+            } else {
+                state[0] = Self::sbox(&state[0]);
+            }
+
+            state = std::array::from_fn(|i| {
+                state
+                    .iter()
+                    .zip(&self.mds[i])
+                    .fold(Residue::zero(self.params), |acc, (s, m)| acc + *s * m)
+            });
+
+            if !Self::is_full_round(round) {
+                trace.extend(state[1..].iter().map(|x| BigInt(x.retrieve())));
+            }
+        }
+
+        (BigInt(state[1].retrieve()), trace)
+    }
+
+    fn sbox(x: &Residue) -> Residue {
+        let x2 = x.square();
+        let x4 = x2.square();
+
+        x4 * x
+    }
+
+    fn is_full_round(round: usize) -> bool {
+        !(FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round)
+    }
+}
+
+impl Gadget for Poseidon {
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let left = gadget_inputs[0];
+        let right = gadget_inputs[1];
+
+        if left >= self.field_modulus || right >= self.field_modulus {
+            return Err(ZkpError::gadget_error(
+                "Poseidon inputs must be reduced modulo the field modulus.",
+            ));
+        }
+
+        let (_, trace) = self.hash_with_trace(&left, &right);
+
+        Ok(trace)
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let mut hidden_inputs = hidden_inputs.iter();
+
+        let out = with_zkp_ctx(|ctx| {
+            let mut state = [
+                ctx.add_constant(&BigInt::ZERO),
+                gadget_inputs[0],
+                gadget_inputs[1],
+            ];
+
+            let mds = self
+                .mds
+                .map(|row| row.map(|m| ctx.add_constant(&BigInt(m.retrieve()))));
+
+            for (round, constants) in self.round_constants.iter().enumerate() {
+                for (s, c) in state.iter_mut().zip(constants) {
+                    let c = ctx.add_constant(&BigInt(c.retrieve()));
+                    *s = ctx.add_addition(*s, c);
+                }
+
+                let sbox_count = if Self::is_full_round(round) {
+                    POSEIDON_WIDTH
+                } else {
+                    1
+                };
+
+                for s in state.iter_mut().take(sbox_count) {
+                    let x2 = ctx.add_multiplication(*s, *s);
+                    let x4 = ctx.add_multiplication(x2, x2);
+                    *s = ctx.add_multiplication(x4, *s);
+                }
+
+                state = std::array::from_fn(|i| {
+                    let mut acc = ctx.add_multiplication(state[0], mds[i][0]);
+
+                    for (s, m) in state.iter().zip(&mds[i]).skip(1) {
+                        let term = ctx.add_multiplication(*s, *m);
+                        acc = ctx.add_addition(acc, term);
+                    }
+
+                    acc
+                });
+
+                if !Self::is_full_round(round) {
+                    for s in state.iter_mut().skip(1) {
+                        let hidden = *hidden_inputs.next().unwrap();
+
+                        let diff = ctx.add_subtraction(*s, hidden);
+                        ctx.add_constraint(diff, &BigInt::ZERO);
+
+                        *s = hidden;
+                    }
+                }
+            }
+
+            state[1]
+        });
+
+        vec![out]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        2
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        PARTIAL_ROUNDS * HIDDEN_PER_PARTIAL_ROUND
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, FieldSpec, ZkpBackend};
+
+    use crate::types::zkp::Field;
+    use crate::{self as sunscreen, invoke_gadget, Compiler};
+
+    use super::*;
+
+    #[test]
+    fn grain_matches_reference_constants() {
+        // The first BN254 round constant produced by the Poseidon
+        // reference implementation for t = 3, R_F = 8, R_P = 57.
+        let bn254 = BigInt::from_be_hex(
+            "000000000000000000000000000000000000000000000000000000000000000030644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        );
+
+        let mut lfsr = GrainLfsr::new(254);
+
+        assert_eq!(
+            lfsr.next_field_element(&bn254, 254),
+            BigInt::from_be_hex(
+                "00000000000000000000000000000000000000000000000000000000000000000ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+            )
+        );
+    }
+
+    #[test]
+    fn native_hash_is_deterministic() {
+        let field_modulus = <BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS;
+
+        let poseidon = Poseidon::new(field_modulus);
+
+        let a = BigInt::from(1u32);
+        let b = BigInt::from(2u32);
+
+        assert_eq!(
+            poseidon.hash(&a, &b),
+            Poseidon::new(field_modulus).hash(&a, &b)
+        );
+        assert_ne!(poseidon.hash(&a, &b), poseidon.hash(&b, &a));
+        assert!(poseidon.hash(&a, &b) < field_modulus);
+    }
+
+    #[test]
+    fn can_prove_poseidon_preimage() {
+        #[zkp_program]
+        fn preimage<F: FieldSpec>(a: Field<F>, b: Field<F>, #[public] digest: Field<F>) {
+            let out = invoke_gadget(Poseidon::new(F::FIELD_MODULUS), &[a.ids[0], b.ids[0]]);
+
+            ProgramNode::<Field<F>>::new(&out).constrain_eq(digest);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(preimage)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(preimage).unwrap();
+
+        type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+        let poseidon = Poseidon::new(<BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS);
+        let digest = poseidon.hash(&BigInt::from(42u32), &BigInt::from(1337u32));
+
+        let test_case = |a: u32, b: u32, expect_pass: bool| {
+            let result = runtime.prove(
+                prog,
+                vec![BpField::from(a), BpField::from(b)],
+                vec![BpField::from(digest)],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(prog, &proof, vec![BpField::from(digest)], vec![])
+                .unwrap();
+        };
+
+        test_case(42, 1337, true);
+        test_case(1337, 42, false);
+    }
+}
@@ -1,9 +1,14 @@
 #[cfg(feature = "linkedproofs")]
 mod bfv_plaintext;
 mod field;
-mod gadgets;
+/**
+ * Reusable [`Gadget`] implementations for ZKP programs.
+ */
+pub mod gadgets;
 mod program_node;
 mod rns_polynomial;
+#[cfg(feature = "linkedproofs")]
+mod tfhe_message;
 
 #[cfg(feature = "linkedproofs")]
 pub use bfv_plaintext::*;
@@ -13,6 +18,8 @@ pub use program_node::*;
 pub use rns_polynomial::*;
 use sunscreen_compiler_common::TypeName;
 pub use sunscreen_zkp_backend::{BigInt, FieldSpec, Gadget};
+#[cfg(feature = "linkedproofs")]
+pub use tfhe_message::*;
 
 pub use sunscreen_runtime::{ToNativeFields, ZkpProgramInputTrait};
 
@@ -68,6 +68,12 @@ pub trait CreateZkpProgramInput {
      * Creates a constant program input of type T.
      */
     fn constant_input() -> Self;
+
+    /**
+     * Creates a private program input of type T that the verifier only
+     * sees as a commitment.
+     */
+    fn committed_input() -> Self;
 }
 
 /// Trait for adding FHE-linked inputs to a ZKP program.
@@ -114,6 +120,10 @@ where
     fn public_input() -> Self {
         [0; N].map(|_| T::public_input())
     }
+
+    fn committed_input() -> Self {
+        [0; N].map(|_| T::committed_input())
+    }
 }
 
 impl<T, const N: usize> CreateLinkedZkpProgramInput for [T; N]
@@ -158,6 +168,21 @@ where
 
         Self::new(&ids)
     }
+
+    fn committed_input() -> Self {
+        let mut ids = Vec::with_capacity(T::NUM_NATIVE_FIELD_ELEMENTS);
+
+        for _ in 0..T::NUM_NATIVE_FIELD_ELEMENTS {
+            ids.push(with_zkp_ctx(|ctx| {
+                let input = ctx.add_private_input();
+                ctx.add_commitment(input);
+
+                input
+            }));
+        }
+
+        Self::new(&ids)
+    }
 }
 
 impl<T> CreateLinkedZkpProgramInput for ProgramNode<T>
@@ -0,0 +1,56 @@
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_runtime::LinkWithTfhe;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    types::zkp::ProgramNode,
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use super::{AsFieldElement, Field, NumFieldElements, ToNativeFields};
+
+use crate as sunscreen;
+
+/// A TFHE message of `BITS` plaintext bits that has been linked to a ZKP program with a
+/// [`TfheLinkedProofBuilder`](sunscreen_runtime::TfheLinkedProofBuilder).
+///
+/// Declare it as a `#[private]` argument preceding the program's other private arguments, and
+/// use the [`AsFieldElement::into_field_elem`] method to decode the message into a field element.
+#[derive(Debug, Clone, TypeName)]
+pub struct TfheMessage<F: FieldSpec, const BITS: usize> {
+    data: Vec<Field<F>>,
+}
+
+impl<F: FieldSpec, const BITS: usize> NumFieldElements for TfheMessage<F, BITS> {
+    // The two's complement expansion of the message carries an additional sign bit.
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = BITS + 1;
+}
+
+impl<F: FieldSpec, const BITS: usize> LinkWithTfhe for TfheMessage<F, BITS> {
+    const PLAINTEXT_BITS: u32 = BITS as u32;
+}
+
+impl<F: FieldSpec, const BITS: usize> ToNativeFields for TfheMessage<F, BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.data.iter().map(|x| x.val).collect()
+    }
+}
+
+impl<F: FieldSpec, const BITS: usize> AsFieldElement<F> for ProgramNode<TfheMessage<F, BITS>> {
+    type Output = ProgramNode<Field<F>>;
+
+    fn into_field_elem(self) -> Self::Output {
+        // The SDLP proves the bits are the two's complement expansion of some `m` with
+        // `|m| < 2^BITS`, which decrypts to `m mod 2^BITS`. That's exactly the low `BITS` bits, so
+        // we drop the sign bit.
+        ProgramNode::new(&[with_zkp_ctx(|ctx| {
+            let mut x = ctx.add_constant(&BigInt::ZERO);
+            for (i, b) in self.ids.iter().take(BITS).enumerate() {
+                let pow = ctx.add_constant(&(BigInt::ONE << i));
+                let mul = ctx.add_multiplication(pow, *b);
+                x = ctx.add_addition(x, mul);
+            }
+            x
+        })])
+    }
+}
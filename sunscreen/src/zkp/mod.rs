@@ -12,6 +12,9 @@ use std::sync::Arc;
 use std::vec;
 use std::{any::Any, cell::RefCell};
 
+mod profile;
+pub use profile::*;
+
 mod sealed {
     pub trait Sealed {}
     impl Sealed for super::Linked {}
@@ -47,6 +50,19 @@ pub trait ZkpProgramFn<F: FieldSpec> {
     /// Create a circuit from this specification.
     fn build(&self, linked_input: <Self::Link as Link>::Input) -> Result<ZkpFrontendCompilation>;
 
+    /// Create a circuit from this specification, retaining the context it
+    /// was built in.
+    ///
+    /// The default implementation wraps [`build`](Self::build)'s graph in a
+    /// fresh context, so [`profile`](ZkpProgramFnExt::profile) can't
+    /// attribute its nodes to source lines. `#[zkp_program]` overrides this.
+    fn build_context(&self, linked_input: <Self::Link as Link>::Input) -> Result<ZkpContext> {
+        let mut context = ZkpContext::new(ZkpData::new());
+        context.graph = self.build(linked_input)?;
+
+        Ok(context)
+    }
+
     /// Gets the call signature for this program.
     fn signature(&self) -> CallSignature;
 
@@ -188,6 +204,37 @@ pub trait ZkpProgramFnExt {
     {
         self.runtime_with(B::default())
     }
+
+    /// Break down which source lines of this `#[zkp_program]` contribute
+    /// the most constraints.
+    ///
+    /// ```rust
+    /// use sunscreen::{
+    ///     bulletproofs::BulletproofsBackend,
+    ///     zkp_program, types::zkp::{Field, FieldSpec},
+    ///     ZkpProgramFnExt
+    /// };
+    ///
+    /// #[zkp_program]
+    /// fn cube<F: FieldSpec>(x: Field<F>, #[public] y: Field<F>) {
+    ///     let x_2 = x * x;
+    ///     (x_2 * x).constrain_eq(y);
+    /// }
+    /// # fn main() -> Result<(), sunscreen::Error> {
+    /// let profile = cube.profile::<BulletproofsBackend>()?;
+    /// assert_eq!(profile.multiplications(), 2);
+    /// println!("{profile}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn profile<B: ZkpBackend>(&self) -> Result<ConstraintProfile>
+    where
+        Self: ZkpProgramFn<B::Field, Link = NotLinked>,
+    {
+        let context = self.build_context(())?;
+
+        Ok(ConstraintProfile::new(self.name(), &context))
+    }
 }
 
 use std::fmt::Debug;
@@ -222,6 +269,8 @@ pub enum Operation {
     Mul,
     /// Negation.
     Neg,
+    /// Binds its operand to the input commitment with the given positional index.
+    Commit(usize),
 }
 
 impl Hash for Operation {
@@ -259,6 +308,10 @@ impl Hash for Operation {
                 state.write_u8(10);
                 x.hash(state);
             }
+            Self::Commit(x) => {
+                state.write_u8(11);
+                state.write_usize(*x);
+            }
         }
     }
 }
@@ -271,6 +324,7 @@ impl PartialEq for Operation {
             (Self::HiddenInput(x), Self::HiddenInput(y)) => x == y,
             (Self::Constraint(x), Self::Constraint(y)) => x == y,
             (Self::Constant(x), Self::Constant(y)) => x == y,
+            (Self::Commit(x), Self::Commit(y)) => x == y,
             (Self::InvokeGadget(x), Self::InvokeGadget(y)) => x.type_id() == y.type_id(),
             (Self::Add, Self::Add) => true,
             (Self::Sub, Self::Sub) => true,
@@ -292,6 +346,7 @@ impl Debug for Operation {
             Self::HiddenInput(x) => write!(f, "HiddenInput({x})"),
             Self::Constraint(x) => write!(f, "Constraint({x:#?})"),
             Self::Constant(x) => write!(f, "Constant({x:#?})"),
+            Self::Commit(x) => write!(f, "Commit({x})"),
             Self::InvokeGadget(g) => write!(f, "InvokeGadget({})", g.debug_name()),
             Self::Add => write!(f, "Add"),
             Self::Sub => write!(f, "Sub"),
@@ -311,7 +366,7 @@ impl OperationTrait for Operation {
     }
 
     fn is_unary(&self) -> bool {
-        matches!(self, Operation::Neg)
+        matches!(self, Operation::Neg | Operation::Commit(_))
     }
 
     fn is_unordered(&self) -> bool {
@@ -358,19 +413,26 @@ impl Operation {
     pub fn is_hidden_input(&self) -> bool {
         matches!(self, Operation::HiddenInput(_))
     }
+
+    /// Whether or not this operation binds a value to an input commitment.
+    pub fn is_commit(&self) -> bool {
+        matches!(self, Operation::Commit(_))
+    }
 }
 
 /**
  * An implementation detail of a ZKP program. During compilation, it
- * tracks how many public and private inputs have been added.
+ * tracks how many public, private, and committed inputs have been added.
  */
 pub struct ZkpData {
     next_public_input: usize,
     next_private_input: usize,
     next_constant_input: usize,
+    next_commitment: usize,
     // A lookup table to reuse constant nodes. Reduces the size
     // of the graph.
     constant_map: HashMap<BigInt, NodeIndex>,
+    origins: OriginTracker,
 }
 
 impl ZkpData {
@@ -382,7 +444,9 @@ impl ZkpData {
             next_private_input: 0,
             next_public_input: 0,
             next_constant_input: 0,
+            next_commitment: 0,
             constant_map: HashMap::new(),
+            origins: OriginTracker::default(),
         }
     }
 }
@@ -434,6 +498,11 @@ pub trait ZkpContextOps {
      */
     fn add_hidden_input(&mut self, gadget_arg_id: usize) -> NodeIndex;
 
+    /**
+     * Add a node binding `input` to the next input commitment.
+     */
+    fn add_commitment(&mut self, input: NodeIndex) -> NodeIndex;
+
     /**
      * Add an addition to this context
      */
@@ -496,6 +565,13 @@ impl ZkpContextOps for ZkpContext {
         self.add_node(Operation::HiddenInput(gadget_arg_id))
     }
 
+    fn add_commitment(&mut self, input: NodeIndex) -> NodeIndex {
+        let node = self.add_unary_operation(Operation::Commit(self.data.next_commitment), input);
+        self.data.next_commitment += 1;
+
+        node
+    }
+
     fn add_addition(&mut self, left: NodeIndex, right: NodeIndex) -> NodeIndex {
         self.add_binary_operation(Operation::Add, left, right)
     }
@@ -589,6 +665,7 @@ pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
                 Operation::Sub => JitOperation::Sub,
                 Operation::Constraint(x) => JitOperation::Constraint(x),
                 Operation::Constant(x) => JitOperation::Constant(x),
+                Operation::Commit(x) => JitOperation::Commit(x),
             };
 
             NodeInfo { operation }
@@ -626,6 +703,9 @@ pub fn invoke_gadget<G: Gadget>(g: G, gadget_inputs: &[NodeIndex]) -> Vec<NodeIn
     let mut hidden_inputs = vec![];
 
     with_zkp_ctx(|ctx| {
+        let next_node = ctx.graph.node_count();
+        ctx.data.origins.enter_gadget(next_node, g.debug_name());
+
         let gadget = ctx.add_invoke_gadget(&g);
 
         for i in 0..hidden_inputs_count {
@@ -640,5 +720,12 @@ pub fn invoke_gadget<G: Gadget>(g: G, gadget_inputs: &[NodeIndex]) -> Vec<NodeIn
         }
     });
 
-    g.gen_circuit(gadget_inputs, &hidden_inputs)
+    let outputs = g.gen_circuit(gadget_inputs, &hidden_inputs);
+
+    with_zkp_ctx(|ctx| {
+        let next_node = ctx.graph.node_count();
+        ctx.data.origins.exit_gadget(next_node);
+    });
+
+    outputs
 }
@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use petgraph::{stable_graph::NodeIndex, Direction};
+
+use super::{with_zkp_ctx, Operation, ZkpContext};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/**
+ * A location in the source of a [`zkp_program`](crate::zkp_program).
+ */
+pub struct SourceSpan {
+    /**
+     * The source file.
+     */
+    pub file: &'static str,
+
+    /**
+     * The 1-based line number.
+     */
+    pub line: u32,
+
+    /**
+     * The 1-based column number.
+     */
+    pub column: u32,
+}
+
+impl Display for SourceSpan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/**
+ * Where a node in a ZKP program came from.
+ */
+pub(crate) struct NodeOrigin {
+    pub span: Option<SourceSpan>,
+    pub gadget: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Default)]
+/**
+ * Tracks the [`NodeOrigin`] of each node added to a ZKP program's graph.
+ */
+pub(crate) struct OriginTracker {
+    current: NodeOrigin,
+    gadget_depth: usize,
+
+    // Runs of consecutive node indices sharing an origin, keyed by the
+    // first index in the run.
+    runs: Vec<(usize, NodeOrigin)>,
+}
+
+impl OriginTracker {
+    /**
+     * Attributes nodes from index `next_node` onward to the given span.
+     */
+    pub fn set_span(&mut self, next_node: usize, span: SourceSpan) {
+        // Nodes created inside a gadget belong to the statement that
+        // invoked it.
+        if self.gadget_depth == 0 {
+            self.current.span = Some(span);
+            self.push_run(next_node);
+        }
+    }
+
+    /**
+     * Attributes nodes from index `next_node` onward to the given gadget,
+     * unless a gadget is already being invoked.
+     */
+    pub fn enter_gadget(&mut self, next_node: usize, name: &'static str) {
+        if self.gadget_depth == 0 {
+            self.current.gadget = Some(name);
+            self.push_run(next_node);
+        }
+
+        self.gadget_depth += 1;
+    }
+
+    /**
+     * Finishes a gadget invocation started with [`Self::enter_gadget`].
+     */
+    pub fn exit_gadget(&mut self, next_node: usize) {
+        self.gadget_depth -= 1;
+
+        if self.gadget_depth == 0 {
+            self.current.gadget = None;
+            self.push_run(next_node);
+        }
+    }
+
+    /**
+     * Gets the origin of the node at the given index.
+     */
+    pub fn origin(&self, node: NodeIndex) -> NodeOrigin {
+        let run = self
+            .runs
+            .partition_point(|(start, _)| *start <= node.index());
+
+        match run {
+            0 => NodeOrigin::default(),
+            i => self.runs[i - 1].1,
+        }
+    }
+
+    fn push_run(&mut self, next_node: usize) {
+        let origin = self.current;
+
+        match self.runs.last_mut() {
+            Some((_, last)) if *last == origin => {}
+            Some((start, last)) if *start == next_node => *last = origin,
+            _ => self.runs.push((next_node, origin)),
+        }
+    }
+}
+
+/**
+ * Records the source location of the statement about to run in the current
+ * [`zkp_program`](crate::zkp_program). The `zkp_program` macro inserts
+ * calls to this before each statement.
+ *
+ * # Remarks
+ * For internal use only.
+ */
+#[doc(hidden)]
+pub fn set_source_span(file: &'static str, line: u32, column: u32) {
+    with_zkp_ctx(|ctx| {
+        let next_node = ctx.graph.node_count();
+
+        ctx.data
+            .origins
+            .set_span(next_node, SourceSpan { file, line, column })
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * The cost of the nodes created by one source location, possibly inside
+ * a gadget.
+ */
+pub struct ProfileEntry {
+    /**
+     * The source location in the ZKP program, or [`None`] for nodes
+     * created outside of any statement.
+     */
+    pub span: Option<SourceSpan>,
+
+    /**
+     * The outermost gadget invoked at `span` that created these nodes, if
+     * any.
+     */
+    pub gadget: Option<&'static str>,
+
+    /**
+     * The number of multiplication gates between two non-constant values.
+     */
+    pub multiplications: usize,
+
+    /**
+     * The number of linear constraints, including those fixing public
+     * inputs and binding committed inputs.
+     */
+    pub linear_constraints: usize,
+
+    /**
+     * The number of allocated variables (inputs and gadget hidden inputs).
+     */
+    pub variables: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * A breakdown of where a ZKP program's constraints come from. Create one
+ * with [`ZkpProgramFnExt::profile`](crate::ZkpProgramFnExt::profile).
+ *
+ * # Remarks
+ * Costs follow the Bulletproofs R1CS arithmetization. Multiplying by a
+ * constant and adding are free, each multiplication of two non-constant
+ * values costs a gate, and every 2 allocated variables cost a gate.
+ */
+pub struct ConstraintProfile {
+    name: String,
+    entries: Vec<ProfileEntry>,
+}
+
+impl ConstraintProfile {
+    /**
+     * Computes the profile for a ZKP program built in the given context.
+     */
+    pub(crate) fn new(name: &str, ctx: &ZkpContext) -> Self {
+        let graph = &ctx.graph;
+
+        // Whether each node is a compile-time constant rather than a linear
+        // combination of variables.
+        let mut is_constant = HashMap::<NodeIndex, bool>::new();
+        let mut entries = HashMap::<NodeOrigin, ProfileEntry>::new();
+
+        // Operands are always created before the nodes that use them, so
+        // index order is a topological order.
+        let mut nodes = graph.node_indices().collect::<Vec<_>>();
+        nodes.sort();
+
+        for id in nodes {
+            let operands = graph
+                .neighbors_directed(id, Direction::Incoming)
+                .collect::<Vec<_>>();
+
+            let all_constant = operands.iter().all(|x| is_constant[x]);
+
+            let (constant, multiplications, linear_constraints, variables) =
+                match &graph[id].operation {
+                    Operation::Constant(_) | Operation::ConstantInput(_) => (true, 0, 0, 0),
+                    Operation::PrivateInput(_) | Operation::HiddenInput(_) => (false, 0, 0, 1),
+                    Operation::PublicInput(_) => (false, 0, 1, 1),
+                    Operation::Add | Operation::Sub | Operation::Neg => (all_constant, 0, 0, 0),
+                    Operation::Mul => {
+                        let any_constant = operands.iter().any(|x| is_constant[x]);
+
+                        (all_constant, usize::from(!any_constant), 0, 0)
+                    }
+                    Operation::Constraint(_) => {
+                        let count = operands.iter().filter(|x| !is_constant[x]).count();
+
+                        (false, 0, count, 0)
+                    }
+                    Operation::Commit(_) => (false, 0, 1, 0),
+                    Operation::InvokeGadget(_) => (false, 0, 0, 0),
+                };
+
+            is_constant.insert(id, constant);
+
+            if multiplications + linear_constraints + variables == 0 {
+                continue;
+            }
+
+            let origin = ctx.data.origins.origin(id);
+
+            let entry = entries.entry(origin).or_insert_with(|| ProfileEntry {
+                span: origin.span,
+                gadget: origin.gadget,
+                multiplications: 0,
+                linear_constraints: 0,
+                variables: 0,
+            });
+
+            entry.multiplications += multiplications;
+            entry.linear_constraints += linear_constraints;
+            entry.variables += variables;
+        }
+
+        let mut entries = entries.into_values().collect::<Vec<_>>();
+
+        entries.sort_by(|a, b| {
+            b.multiplications
+                .cmp(&a.multiplications)
+                .then(b.linear_constraints.cmp(&a.linear_constraints))
+                .then(b.variables.cmp(&a.variables))
+                .then(a.span.cmp(&b.span))
+                .then(a.gadget.cmp(&b.gadget))
+        });
+
+        Self {
+            name: name.to_owned(),
+            entries,
+        }
+    }
+
+    /**
+     * The name of the profiled ZKP program.
+     */
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /**
+     * The cost of each source location, most expensive first.
+     */
+    pub fn entries(&self) -> &[ProfileEntry] {
+        &self.entries
+    }
+
+    /**
+     * The total number of multiplication gates between two non-constant
+     * values.
+     */
+    pub fn multiplications(&self) -> usize {
+        self.entries.iter().map(|x| x.multiplications).sum()
+    }
+
+    /**
+     * The total number of linear constraints.
+     */
+    pub fn linear_constraints(&self) -> usize {
+        self.entries.iter().map(|x| x.linear_constraints).sum()
+    }
+
+    /**
+     * The total number of allocated variables.
+     */
+    pub fn variables(&self) -> usize {
+        self.entries.iter().map(|x| x.variables).sum()
+    }
+
+    /**
+     * The number of multipliers in the circuit, which determines proving
+     * time and proof size. This is [`Self::multiplications`] plus one gate
+     * for every 2 allocated variables.
+     */
+    pub fn multipliers(&self) -> usize {
+        self.multiplications() + (self.variables() + 1) / 2
+    }
+}
+
+impl Display for ConstraintProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Constraint profile for {} ({} multipliers)",
+            self.name,
+            self.multipliers()
+        )?;
+        writeln!(f, "{:>10} {:>10} {:>10}  location", "mul", "linear", "vars")?;
+
+        for entry in &self.entries {
+            let span = entry
+                .span
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| "<unknown>".to_owned());
+
+            write!(
+                f,
+                "{:>10} {:>10} {:>10}  {span}",
+                entry.multiplications, entry.linear_constraints, entry.variables
+            )?;
+
+            if let Some(gadget) = entry.gadget {
+                write!(f, " ({gadget})")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        writeln!(
+            f,
+            "{:>10} {:>10} {:>10}  total",
+            self.multiplications(),
+            self.linear_constraints(),
+            self.variables()
+        )
+    }
+}
@@ -1,22 +1,25 @@
 #[cfg(feature = "linkedproofs")]
 mod linked_tests {
     use lazy_static::lazy_static;
-    use logproof::rings::{SealQ128_1024, SealQ128_4096};
+    use logproof::rings::{SealQ128_1024, SealQ128_16384, SealQ128_4096};
     use num::Rational64;
+    use serde::{Deserialize, Serialize};
     use sunscreen::types::bfv::{Rational, Signed, Unsigned64};
     use sunscreen::types::zkp::{
         AsFieldElement, BfvRational, BfvSigned, BulletproofsField, ConstrainFresh,
     };
     use sunscreen::types::Cipher;
-    use sunscreen::PlainModulusConstraint;
     use sunscreen::{
         fhe_program,
+        linked::RangeProofExt,
         types::zkp::{ConstrainCmp, Field, FieldSpec},
         zkp_program, zkp_var, Compiler,
     };
+    use sunscreen::{Ciphertext, PlainModulusConstraint, PublicKey};
     use sunscreen_fhe_program::SchemeType;
     use sunscreen_runtime::{
-        FheZkpRuntime, LinkedProofBuilder, LinkedProofVerificationBuilder, Params,
+        FheZkpRuntime, LinkedProof, LinkedProofBuilder, LinkedProofVerificationBuilder, Params,
+        Sdlp, SdlpParams,
     };
     use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
 
@@ -97,6 +100,237 @@ mod linked_tests {
         }
     }
 
+    #[test]
+    fn linked_proof_wire_format_roundtrips() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&TEST_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(valid_transaction)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let valid_transaction_zkp = app.get_zkp_program(valid_transaction).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (ct, tx_msg) = proof_builder
+            .encrypt_returning_link(&Signed::from(5), &public_key)
+            .unwrap();
+        let lp = proof_builder
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .linked_input(tx_msg)
+            .public_input(BulletproofsField::from(10i64))
+            .build()
+            .unwrap();
+
+        let bytes = lp.to_bytes().unwrap();
+
+        // The version 1 header: magic, version, proof kind, parameters id. Proofs made by
+        // earlier releases begin with these bytes, so they must never change.
+        assert_eq!(&bytes[..8], &[b'S', b'N', b'L', b'P', 0, 1, 1, 1]);
+
+        // A linked proof isn't a solo SDLP.
+        assert!(Sdlp::from_bytes(&bytes).is_err());
+        assert!(LinkedProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let lp = LinkedProof::from_bytes(&bytes).unwrap();
+        assert_eq!(lp.params(), SdlpParams::SealQ128_1024);
+        assert_eq!(lp.to_bytes().unwrap(), bytes);
+
+        let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+        verify_builder
+            .encrypt_returning_link::<Signed>(&ct, &public_key)
+            .unwrap();
+        verify_builder
+            .proof(lp)
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .public_input(BulletproofsField::from(10i64))
+            .verify()
+            .unwrap();
+    }
+
+    /// A linked proof encoded with version 1 of the wire format, along with the statement it's
+    /// about. Run `generate_linked_proof_v1_fixture` to recreate it.
+    const LINKED_PROOF_V1_FIXTURE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/linked_proof_v1.bin"
+    );
+
+    #[derive(Serialize, Deserialize)]
+    struct LinkedProofFixture {
+        public_key: PublicKey,
+        ciphertext: Ciphertext,
+        proof: Vec<u8>,
+    }
+
+    #[test]
+    #[ignore = "writes the checked in fixture; run it only when the wire format version changes"]
+    fn generate_linked_proof_v1_fixture() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&TEST_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(valid_transaction)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let valid_transaction_zkp = app.get_zkp_program(valid_transaction).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (ciphertext, tx_msg) = proof_builder
+            .encrypt_returning_link(&Signed::from(5), &public_key)
+            .unwrap();
+        let lp = proof_builder
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .linked_input(tx_msg)
+            .public_input(BulletproofsField::from(10i64))
+            .build()
+            .unwrap();
+
+        let fixture = LinkedProofFixture {
+            public_key,
+            ciphertext,
+            proof: lp.to_bytes().unwrap(),
+        };
+
+        std::fs::write(
+            LINKED_PROOF_V1_FIXTURE,
+            bincode::serialize(&fixture).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs tests/fixtures/linked_proof_v1.bin; run generate_linked_proof_v1_fixture first"]
+    fn linked_proof_v1_fixture_verifies() {
+        let fixture = std::fs::read(LINKED_PROOF_V1_FIXTURE)
+            .expect("Missing fixture; run generate_linked_proof_v1_fixture with --ignored");
+        let fixture: LinkedProofFixture = bincode::deserialize(&fixture).unwrap();
+
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&TEST_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(valid_transaction)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let valid_transaction_zkp = app.get_zkp_program(valid_transaction).unwrap();
+
+        // Proofs written by this release must decode and re-encode to the same bytes.
+        let lp = LinkedProof::from_bytes(&fixture.proof).unwrap();
+        assert_eq!(lp.params(), SdlpParams::SealQ128_1024);
+        assert_eq!(lp.to_bytes().unwrap(), fixture.proof);
+
+        let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+        verify_builder
+            .encrypt_returning_link::<Signed>(&fixture.ciphertext, &fixture.public_key)
+            .unwrap();
+        verify_builder
+            .proof(lp)
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .public_input(BulletproofsField::from(10i64))
+            .verify()
+            .unwrap();
+    }
+
+    #[test]
+    fn linked_proofs_are_bound_to_context() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&TEST_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(valid_transaction)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let valid_transaction_zkp = app.get_zkp_program(valid_transaction).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (ct, tx_msg) = proof_builder
+            .encrypt_returning_link(&Signed::from(5), &public_key)
+            .unwrap();
+        let lp = proof_builder
+            .context(b"app-a")
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .linked_input(tx_msg)
+            .public_input(BulletproofsField::from(10i64))
+            .build()
+            .unwrap();
+
+        let verify = |lp: LinkedProof, context: &[u8]| {
+            let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+            verify_builder
+                .encrypt_returning_link::<Signed>(&ct, &public_key)
+                .unwrap();
+            verify_builder
+                .proof(lp)
+                .context(context)
+                .zkp_program(valid_transaction_zkp)
+                .unwrap()
+                .public_input(BulletproofsField::from(10i64))
+                .verify()
+        };
+
+        verify(lp.clone(), b"app-a").unwrap();
+        assert!(verify(lp.clone(), b"app-b").is_err());
+        assert!(verify(lp, b"").is_err());
+    }
+
+    #[test]
+    fn linked_proofs_reject_split_data_moduli() {
+        // The SDLP for the lattice dimension 16384 modulus is split into groups, which a linked
+        // proof can't share with its bulletproof. A small lattice dimension keeps the test fast.
+        let params = Params {
+            coeff_modulus: SealQ128_16384::Q.to_vec(),
+            ..*TEST_PARAMS
+        };
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&params)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(valid_transaction)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let valid_transaction_zkp = app.get_zkp_program(valid_transaction).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (_ct, tx_msg) = proof_builder
+            .encrypt_returning_link(&Signed::from(5), &public_key)
+            .unwrap();
+        let result = proof_builder
+            .zkp_program(valid_transaction_zkp)
+            .unwrap()
+            .linked_input(tx_msg)
+            .public_input(BulletproofsField::from(10i64))
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(sunscreen_runtime::Error::BuilderError(_))
+        ));
+
+        // The SDLP alone can still be proven.
+        assert_eq!(
+            proof_builder.build_sdlp().unwrap().params(),
+            SdlpParams::SealQ128_16384
+        );
+    }
+
     #[test]
     fn test_invalid_transaction_example() {
         let app = Compiler::new()
@@ -684,4 +918,47 @@ mod linked_tests {
             Err(sunscreen::RuntimeError::ArgumentMismatch(_))
         ));
     }
+
+    #[test]
+    fn builtin_signed_range_proofs() {
+        let rt = FheZkpRuntime::new(&TEST_PARAMS, &BulletproofsBackend::new()).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+        let range = Signed::from(-5)..=Signed::from(10);
+
+        for x in [-5, 10] {
+            let (ct, proof) = rt
+                .encrypt_with_range_proof(&Signed::from(x), range.clone(), &public_key)
+                .unwrap();
+            rt.verify_range_proof(&ct, range.clone(), &public_key, &proof)
+                .unwrap();
+        }
+
+        for x in [-6, 11] {
+            assert!(rt
+                .encrypt_with_range_proof(&Signed::from(x), range.clone(), &public_key)
+                .is_err());
+        }
+
+        // The proof doesn't hold for a range that excludes the value.
+        let (ct, proof) = rt
+            .encrypt_with_range_proof(&Signed::from(7), range, &public_key)
+            .unwrap();
+        let narrower = Signed::from(0)..=Signed::from(5);
+        assert!(rt
+            .verify_range_proof(&ct, narrower, &public_key, &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn builtin_unsigned_range_proof() {
+        let rt = FheZkpRuntime::new(&TEST_PARAMS, &BulletproofsBackend::new()).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+        let range = Unsigned64::from(1)..=Unsigned64::from(u64::MAX);
+
+        let (ct, proof) = rt
+            .encrypt_with_range_proof(&Unsigned64::from(42), range.clone(), &public_key)
+            .unwrap();
+        rt.verify_range_proof(&ct, range, &public_key, &proof)
+            .unwrap();
+    }
 }
@@ -1,15 +1,22 @@
 #[cfg(feature = "linkedproofs")]
 mod sdlp_tests {
     use lazy_static::lazy_static;
-    use logproof::rings::SealQ128_1024;
+    use logproof::rings::{SealQ128_1024, SealQ128_16384, SealQ128_32768, SealQ128_4096};
     use sunscreen::{
-        types::bfv::{Fractional, Signed, Unsigned64},
-        FheProgramFnExt,
+        types::{
+            bfv::{Batched, Fractional, Signed, Unsigned64},
+            Cipher,
+        },
+        CompiledFheProgram, FheProgramFnExt, FheProgramInput,
     };
     use sunscreen_compiler_macros::fhe_program;
     use sunscreen_fhe_program::SchemeType;
 
-    use sunscreen_runtime::{FheRuntime, Params, SdlpBuilder, SdlpVerificationBuilder};
+    use logproof::ProofError;
+    use sunscreen_runtime::{
+        Error, FheRuntime, LinkedProofError, Params, Sdlp, SdlpBuilder, SdlpParams,
+        SdlpVerificationBuilder,
+    };
 
     lazy_static! {
         static ref TEST_PARAMS: Params = Params {
@@ -55,6 +62,279 @@ mod sdlp_tests {
         logproof_vk_builder.proof(sdlp).verify().unwrap();
     }
 
+    #[test]
+    fn prove_statements_split_across_modulus_groups() {
+        // The data modulus for lattice dimension 16384 doesn't fit in the Ristretto scalar
+        // field, so the SDLP is proven separately modulo each group of primes. A small lattice
+        // dimension keeps the test fast.
+        let params = Params {
+            lattice_dimension: 128,
+            coeff_modulus: SealQ128_16384::Q.to_vec(),
+            plain_modulus: 32,
+            scheme_type: SchemeType::Bfv,
+            security_level: sunscreen::SecurityLevel::TC128,
+        };
+        let rt = FheRuntime::new(&params).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+        let ct_sym = logproof_builder
+            .encrypt_symmetric(&Unsigned64::from(3), &private_key)
+            .unwrap();
+
+        let sdlp = logproof_builder.build().unwrap();
+        assert_eq!(sdlp.params(), SdlpParams::SealQ128_16384);
+        assert_eq!(sdlp.params().groups(), 3);
+
+        let sdlp = Sdlp::from_bytes(&sdlp.to_bytes()).unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        logproof_vk_builder.encrypt_symmetric(&ct_sym).unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        // The proof doesn't verify against a different ciphertext.
+        let other_ct = rt.encrypt(Signed::from(3), &public_key).unwrap();
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&other_ct, &public_key).unwrap();
+        logproof_vk_builder.encrypt_symmetric(&ct_sym).unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+
+        // Decryption noise is bounded by delta, which is wider than any one group.
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+        logproof_builder
+            .decrypt_returning_msg::<Signed>(&ct, &private_key)
+            .unwrap();
+        assert!(matches!(
+            logproof_builder.build(),
+            Err(Error::BuilderError(_))
+        ));
+    }
+
+    /// Prove encryptions under the full lattice dimension for `coeff_modulus`, whose SDLP is split
+    /// into groups.
+    fn prove_statements_at_full_dimension(
+        lattice_dimension: u64,
+        coeff_modulus: &[u64],
+        expected_params: SdlpParams,
+    ) {
+        let params = Params {
+            lattice_dimension,
+            coeff_modulus: coeff_modulus.to_vec(),
+            ..*TEST_PARAMS
+        };
+        let rt = FheRuntime::new(&params).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+        let ct_sym = logproof_builder
+            .encrypt_symmetric(&Unsigned64::from(3), &private_key)
+            .unwrap();
+
+        let sdlp = logproof_builder.build().unwrap();
+        assert_eq!(sdlp.params(), expected_params);
+
+        let sdlp = Sdlp::from_bytes(&sdlp.to_bytes()).unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        logproof_vk_builder.encrypt_symmetric(&ct_sym).unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        let other_ct = rt.encrypt(Signed::from(3), &public_key).unwrap();
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&other_ct, &public_key).unwrap();
+        logproof_vk_builder.encrypt_symmetric(&ct_sym).unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+    }
+
+    #[test]
+    fn prove_statements_at_lattice_dimension_16384() {
+        prove_statements_at_full_dimension(16384, SealQ128_16384::Q, SdlpParams::SealQ128_16384);
+    }
+
+    #[test]
+    fn prove_statements_at_lattice_dimension_32768() {
+        prove_statements_at_full_dimension(32768, SealQ128_32768::Q, SdlpParams::SealQ128_32768);
+    }
+
+    #[test]
+    fn proofs_are_bound_to_context() {
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+
+        logproof_builder.context(b"app-a");
+        let sdlp = logproof_builder.build().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        logproof_vk_builder
+            .proof(sdlp.clone())
+            .context(b"app-a")
+            .verify()
+            .unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        assert!(logproof_vk_builder
+            .proof(sdlp)
+            .context(b"app-b")
+            .verify()
+            .is_err());
+    }
+
+    #[test]
+    fn sdlp_wire_format_roundtrips() {
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+
+        let sdlp = logproof_builder.build().unwrap();
+        assert_eq!(sdlp.params(), SdlpParams::SealQ128_1024);
+
+        let bytes = sdlp.to_bytes();
+
+        // The version 1 header: magic, version, proof kind, parameters id. Proofs made by
+        // earlier releases begin with these bytes, so they must never change.
+        assert_eq!(&bytes[..8], &[b'S', b'N', b'L', b'P', 0, 1, 0, 1]);
+
+        let sdlp = Sdlp::from_bytes(&bytes).unwrap();
+        assert_eq!(sdlp.to_bytes(), bytes);
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        logproof_vk_builder.proof(sdlp).verify().unwrap();
+    }
+
+    #[test]
+    fn sdlp_wire_format_rejects_bad_encodings() {
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+
+        let bytes = logproof_builder.build().unwrap().to_bytes();
+
+        let with_byte = |i: usize, b: u8| {
+            let mut bytes = bytes.clone();
+            bytes[i] = b;
+            Sdlp::from_bytes(&bytes)
+        };
+
+        assert!(matches!(
+            with_byte(0, b'X'),
+            Err(Error::LinkedProofError(LinkedProofError::BadMagic))
+        ));
+        assert!(matches!(
+            with_byte(5, 2),
+            Err(Error::LinkedProofError(
+                LinkedProofError::UnsupportedVersion(2)
+            ))
+        ));
+        assert!(matches!(
+            with_byte(6, 1),
+            Err(Error::LinkedProofError(
+                LinkedProofError::UnexpectedProofKind(1)
+            ))
+        ));
+        assert!(matches!(
+            with_byte(7, 0),
+            Err(Error::LinkedProofError(LinkedProofError::UnknownParams(0)))
+        ));
+        assert!(matches!(
+            Sdlp::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::LogProofError(ProofError::MalformedProof))
+        ));
+
+        // A proof claiming different parameters decodes, but fails verification.
+        let sdlp = with_byte(7, SdlpParams::SealQ128_8192.id()).unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        assert!(matches!(
+            logproof_vk_builder.proof(sdlp).verify(),
+            Err(Error::LinkedProofError(LinkedProofError::ParamsMismatch(
+                SdlpParams::SealQ128_8192,
+                SdlpParams::SealQ128_1024
+            )))
+        ));
+    }
+
+    #[test]
+    fn prove_keys() {
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        let (other_public_key, _) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        logproof_builder
+            .prove_keys(&public_key, &private_key)
+            .unwrap();
+        let ct = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+
+        let sdlp = logproof_builder.build().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.keys(&public_key).unwrap();
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.keys(&other_public_key).unwrap();
+        logproof_vk_builder.encrypt(&ct, &public_key).unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+    }
+
+    #[test]
+    fn prove_keys_with_key_switching_keys() {
+        // The 1024 modulus lacks a special modulus, so its keys have no relinearization or Galois
+        // keys.
+        let params = Params {
+            coeff_modulus: SealQ128_4096::Q.to_vec(),
+            ..*TEST_PARAMS
+        };
+        let rt = FheRuntime::new(&params).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        assert!(public_key.relin_key.is_some());
+        assert!(public_key.galois_key.is_some());
+
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+        logproof_builder
+            .prove_keys(&public_key, &private_key)
+            .unwrap();
+        let sdlp = logproof_builder.build().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.keys(&public_key).unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        // Keys from another secret don't verify.
+        let (other_public_key, _) = rt.generate_keys().unwrap();
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.keys(&other_public_key).unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+    }
+
     #[test]
     fn prove_linked_statements() {
         let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
@@ -117,4 +397,182 @@ mod sdlp_tests {
             .unwrap();
         logproof_vk_builder.proof(sdlp).verify().unwrap();
     }
+
+    #[test]
+    fn prove_linear_evaluation() {
+        #[fhe_program(scheme = "bfv")]
+        fn scale(x: Cipher<Signed>, k: Signed) -> Cipher<Signed> {
+            x * k
+        }
+
+        #[fhe_program(scheme = "bfv")]
+        fn sum(x: Cipher<Signed>, y: Cipher<Signed>) -> Cipher<Signed> {
+            x + y
+        }
+
+        #[fhe_program(scheme = "bfv")]
+        fn shift(x: Cipher<Signed>, c: Signed) -> Cipher<Signed> {
+            x + c
+        }
+
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+
+        // The client's inputs
+        let x = rt.encrypt(Signed::from(3), &public_key).unwrap();
+        let y = rt.encrypt(Signed::from(-5), &public_key).unwrap();
+
+        // The server's private plaintexts
+        let k = Signed::from(7);
+        let c = Signed::from(1);
+
+        let run = |program: &CompiledFheProgram, args: Vec<FheProgramInput>| {
+            rt.run(program, args, &public_key).unwrap().remove(0)
+        };
+        let scaled = run(&scale.compile().unwrap(), vec![x.clone().into(), k.into()]);
+        let summed = run(
+            &sum.compile().unwrap(),
+            vec![scaled.clone().into(), y.clone().into()],
+        );
+        let shifted = run(
+            &shift.compile().unwrap(),
+            vec![summed.clone().into(), c.into()],
+        );
+
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+        logproof_builder.multiply_plain(&x, &k, &scaled).unwrap();
+        logproof_builder.add(&scaled, &y, &summed).unwrap();
+        logproof_builder.add_plain(&summed, &c, &shifted).unwrap();
+        let sdlp = logproof_builder.build().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.multiply_plain(&x, &scaled).unwrap();
+        logproof_vk_builder.add(&scaled, &y, &summed).unwrap();
+        logproof_vk_builder.add_plain(&summed, &shifted).unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        // The proof doesn't hold for a different computation.
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.multiply_plain(&x, &scaled).unwrap();
+        logproof_vk_builder.add(&y, &y, &summed).unwrap();
+        logproof_vk_builder.add_plain(&summed, &shifted).unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+
+        let result: Signed = rt.decrypt(&shifted, &private_key).unwrap();
+        assert_eq!(result, Signed::from(3 * 7 - 5 + 1));
+    }
+
+    #[test]
+    fn prove_relinearized_product() {
+        #[fhe_program(scheme = "bfv")]
+        fn product(x: Cipher<Signed>, y: Cipher<Signed>) -> Cipher<Signed> {
+            x * y
+        }
+
+        // Relinearization needs a special modulus, which the 1024 modulus lacks.
+        let params = Params {
+            coeff_modulus: SealQ128_4096::Q.to_vec(),
+            ..*TEST_PARAMS
+        };
+        let rt = FheRuntime::new(&params).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        let mut logproof_builder = SdlpBuilder::new(&rt);
+
+        let x = logproof_builder
+            .encrypt(&Signed::from(3), &public_key)
+            .unwrap();
+        let y = logproof_builder
+            .encrypt(&Signed::from(-5), &public_key)
+            .unwrap();
+        let args: Vec<FheProgramInput> = vec![x.clone().into(), y.clone().into()];
+        let z = rt
+            .run(&product.compile().unwrap(), args, &public_key)
+            .unwrap()
+            .remove(0);
+        logproof_builder.multiply(&x, &y, &public_key, &z).unwrap();
+        let sdlp = logproof_builder.build().unwrap();
+
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&x, &public_key).unwrap();
+        logproof_vk_builder.encrypt(&y, &public_key).unwrap();
+        logproof_vk_builder
+            .multiply(&x, &y, &public_key, &z)
+            .unwrap();
+        logproof_vk_builder.proof(sdlp.clone()).verify().unwrap();
+
+        // The proof doesn't hold for a different product.
+        let mut logproof_vk_builder = SdlpVerificationBuilder::new(&rt);
+        logproof_vk_builder.encrypt(&x, &public_key).unwrap();
+        logproof_vk_builder.encrypt(&y, &public_key).unwrap();
+        logproof_vk_builder
+            .multiply(&x, &x, &public_key, &z)
+            .unwrap();
+        assert!(logproof_vk_builder.proof(sdlp).verify().is_err());
+
+        let result: Signed = rt.decrypt(&z, &private_key).unwrap();
+        assert_eq!(result, Signed::from(-15));
+    }
+
+    #[test]
+    fn prove_decryption() {
+        #[fhe_program(scheme = "bfv")]
+        fn scale(x: Cipher<Fractional<64>>, k: Fractional<64>) -> Cipher<Fractional<64>> {
+            x * k
+        }
+
+        let rt = FheRuntime::new(&TEST_PARAMS).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+        let (other_public_key, _) = rt.generate_keys().unwrap();
+
+        // After some arithmetic, the decrypted polynomial isn't a fresh encoding of its value.
+        let x = rt
+            .encrypt(Fractional::<64>::from(3.25), &public_key)
+            .unwrap();
+        let k = Fractional::<64>::from(2.0);
+        let args: Vec<FheProgramInput> = vec![x.into(), k.into()];
+        let ct = rt
+            .run(&scale.compile().unwrap(), args, &public_key)
+            .unwrap()
+            .remove(0);
+
+        let (value, proof) = rt
+            .decrypt_with_proof::<Fractional<64>>(&ct, &private_key, &public_key)
+            .unwrap();
+        assert_eq!(value, Fractional::<64>::from(6.5));
+        rt.verify_decryption(&ct, &value, &public_key, &proof)
+            .unwrap();
+
+        // The proof doesn't hold for another value or another key.
+        let other_value = Fractional::<64>::from(6.0);
+        assert!(matches!(
+            rt.verify_decryption(&ct, &other_value, &public_key, &proof),
+            Err(Error::LinkedProofError(
+                LinkedProofError::DecryptedValueMismatch
+            ))
+        ));
+        assert!(rt
+            .verify_decryption(&ct, &value, &other_public_key, &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn prove_batched_decryption() {
+        // Batching needs a prime plain modulus that is 1 modulo twice the lattice dimension.
+        let params = Params {
+            plain_modulus: 257,
+            ..TEST_PARAMS.clone()
+        };
+        let rt = FheRuntime::new(&params).unwrap();
+        let (public_key, private_key) = rt.generate_keys().unwrap();
+
+        let data = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+        let ct = rt.encrypt(data, &public_key).unwrap();
+
+        let (value, proof) = rt
+            .decrypt_with_proof::<Batched<4>>(&ct, &private_key, &public_key)
+            .unwrap();
+        assert_eq!(value, data);
+        rt.verify_decryption(&ct, &value, &public_key, &proof)
+            .unwrap();
+    }
 }
@@ -0,0 +1,77 @@
+#[cfg(feature = "linkedproofs")]
+mod tfhe_keys_tests {
+    use sunscreen_runtime::{TfheKeyProofBuilder, TfheKeyProofVerificationBuilder};
+    use sunscreen_tfhe::{
+        high_level::keygen, GlweDef, GlweDimension, GlweSize, LweDef, LweDimension,
+        PolynomialDegree, RadixCount, RadixDecomposition, RadixLog, GLWE_1_1024_128, LWE_512_80,
+    };
+
+    const GLWE: GlweDef = GlweDef {
+        dim: GlweDimension {
+            size: GlweSize(1),
+            polynomial_degree: PolynomialDegree(8),
+        },
+        ..GLWE_1_1024_128
+    };
+
+    const LWE: LweDef = LweDef {
+        dim: LweDimension(4),
+        ..LWE_512_80
+    };
+
+    const RADIX: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(2),
+        radix_log: RadixLog(8),
+    };
+
+    #[test]
+    fn can_prove_bootstrap_and_keyswitch_keys() {
+        let lwe_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&GLWE);
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &LWE, &GLWE, &RADIX);
+        let ksk = keygen::generate_ksk(
+            glwe_sk.to_lwe_secret_key(),
+            &lwe_sk,
+            &GLWE.as_lwe_def(),
+            &LWE,
+            &RADIX,
+        );
+
+        let mut builder = TfheKeyProofBuilder::<u64>::new(&GLWE);
+        let glwe_key = builder.glwe_secret_key(&glwe_sk);
+        let lwe_key = builder.lwe_secret_key(&lwe_sk);
+        let sdlp = builder
+            .bootstrap_key(&bsk, lwe_key, glwe_key, &LWE, &RADIX)
+            .keyswitch_key_from_glwe(&ksk, glwe_key, lwe_key, &LWE, &RADIX)
+            .context(b"keys")
+            .build()
+            .unwrap();
+
+        let verify = |ksk| {
+            let mut builder = TfheKeyProofVerificationBuilder::<u64>::new(&GLWE);
+            let glwe_key = builder.glwe_secret_key();
+            let lwe_key = builder.lwe_secret_key();
+            builder
+                .bootstrap_key(&bsk, lwe_key, glwe_key, &LWE, &RADIX)
+                .keyswitch_key_from_glwe(ksk, glwe_key, lwe_key, &LWE, &RADIX)
+                .context(b"keys")
+                .proof(sdlp.clone())
+                .verify()
+        };
+
+        verify(&ksk).unwrap();
+
+        // The proof doesn't verify for a keyswitch key from a different GLWE key.
+        let other_glwe_sk = keygen::generate_binary_glwe_sk(&GLWE);
+        let other_ksk = keygen::generate_ksk(
+            other_glwe_sk.to_lwe_secret_key(),
+            &lwe_sk,
+            &GLWE.as_lwe_def(),
+            &LWE,
+            &RADIX,
+        );
+
+        assert!(verify(&other_ksk).is_err());
+    }
+}
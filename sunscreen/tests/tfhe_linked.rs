@@ -0,0 +1,126 @@
+#[cfg(feature = "linkedproofs")]
+mod tfhe_linked_tests {
+    use sunscreen::types::zkp::{
+        AsFieldElement, BulletproofsField, ConstrainCmp, Field, FieldSpec, TfheMessage,
+    };
+    use sunscreen::{zkp_program, zkp_var, Compiler};
+    use sunscreen_runtime::{
+        LinkedProof, SdlpParams, TfheLinkedProofBuilder, TfheLinkedProofVerificationBuilder,
+    };
+    use sunscreen_tfhe::{
+        entities::LweCiphertext, high_level::keygen, LweDef, LweDimension, PlaintextBits,
+        LWE_512_80,
+    };
+    use sunscreen_zkp_backend::bulletproofs::{BulletproofsBackend, BulletproofsFieldSpec};
+
+    const BITS: PlaintextBits = PlaintextBits(4);
+
+    const LWE: LweDef = LweDef {
+        dim: LweDimension(4),
+        ..LWE_512_80
+    };
+
+    type Message = TfheMessage<BulletproofsFieldSpec, 4>;
+
+    #[zkp_program]
+    fn in_range<F: FieldSpec>(
+        #[private] x: TfheMessage<F, 4>,
+        #[private] y: TfheMessage<F, 4>,
+        #[public] max: Field<F>,
+    ) {
+        let lower_bound = zkp_var!(1);
+
+        let x = x.into_field_elem();
+        let y = y.into_field_elem();
+
+        lower_bound.constrain_le_bounded(x, 8);
+        max.constrain_ge_bounded(x + y, 8);
+    }
+
+    #[test]
+    fn can_link_lwe_encryptions_with_range_proof() {
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(in_range)
+            .compile()
+            .unwrap();
+        let in_range_zkp = app.get_zkp_program(in_range).unwrap();
+
+        let sk = keygen::generate_binary_lwe_sk(&LWE);
+        let pk = keygen::generate_lwe_pk(&sk, &LWE);
+
+        let max = BulletproofsField::from(20u32);
+
+        let mut builder = TfheLinkedProofBuilder::<u64>::new(&LWE, BITS);
+        let (ct_x, x) = builder.encrypt_returning_link(15, &pk).unwrap();
+        let (ct_y, y) = builder.encrypt_symmetric_returning_link(5, &sk).unwrap();
+        let lp = builder
+            .zkp_program(in_range_zkp)
+            .linked_input::<Message>(x)
+            .unwrap()
+            .linked_input::<Message>(y)
+            .unwrap()
+            .public_input(max)
+            .context(b"tfhe")
+            .build()
+            .unwrap();
+
+        assert_eq!(lp.params(), SdlpParams::Torus64);
+
+        let lp = LinkedProof::from_bytes(&lp.to_bytes().unwrap()).unwrap();
+
+        let verify = |ct_x: &LweCiphertext<u64>, ct_y: &LweCiphertext<u64>, lp: LinkedProof| {
+            TfheLinkedProofVerificationBuilder::<u64>::new(&LWE, BITS)
+                .encrypt(ct_x, &pk)
+                .encrypt_symmetric(ct_y)
+                .proof(lp)
+                .zkp_program(in_range_zkp)
+                .public_input(max)
+                .context(b"tfhe")
+                .verify()
+        };
+
+        verify(&ct_x, &ct_y, lp.clone()).unwrap();
+
+        // The proof is about these ciphertexts and no others.
+        assert!(verify(&ct_y, &ct_x, lp).is_err());
+    }
+
+    #[test]
+    fn cannot_link_messages_violating_constraints() {
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(in_range)
+            .compile()
+            .unwrap();
+        let in_range_zkp = app.get_zkp_program(in_range).unwrap();
+
+        let sk = keygen::generate_binary_lwe_sk(&LWE);
+
+        for (x, y) in [(0, 5), (15, 6)] {
+            let mut builder = TfheLinkedProofBuilder::<u64>::new(&LWE, BITS);
+            let (_, x) = builder.encrypt_symmetric_returning_link(x, &sk).unwrap();
+            let (_, y) = builder.encrypt_symmetric_returning_link(y, &sk).unwrap();
+            builder
+                .zkp_program(in_range_zkp)
+                .linked_input::<Message>(x)
+                .unwrap()
+                .linked_input::<Message>(y)
+                .unwrap()
+                .public_input(BulletproofsField::from(20u32));
+
+            assert!(builder.build().is_err());
+        }
+
+        // Messages must fit in the plaintext bits.
+        let mut builder = TfheLinkedProofBuilder::<u64>::new(&LWE, BITS);
+        assert!(builder.encrypt_symmetric_returning_link(16, &sk).is_err());
+
+        // Linked inputs must decode the same number of plaintext bits.
+        let (_, x) = builder.encrypt_symmetric_returning_link(15, &sk).unwrap();
+        assert!(matches!(
+            builder.linked_input::<TfheMessage<BulletproofsFieldSpec, 3>>(x),
+            Err(sunscreen_runtime::Error::BuilderError(_))
+        ));
+    }
+}
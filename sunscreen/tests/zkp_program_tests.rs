@@ -1,4 +1,7 @@
-use sunscreen::{types::zkp::Field, zkp_program, Compiler, Runtime};
+use sunscreen::{
+    types::zkp::{BigInt, ConstrainCmp, Field},
+    zkp_program, Compiler, Runtime, ZkpProgramFnExt,
+};
 use sunscreen_runtime::{TypeNameInstance, ZkpProgramInput};
 use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, FieldSpec, ZkpBackend};
 
@@ -218,3 +221,260 @@ fn builder_methods_work() {
         .verify()
         .unwrap();
 }
+
+#[test]
+fn can_use_committed_inputs() {
+    #[zkp_program]
+    fn factor<F: FieldSpec>(#[committed] x: Field<F>, y: Field<F>, #[public] z: Field<F>) {
+        (x * y).constrain_eq(z);
+    }
+
+    #[zkp_program]
+    fn sum<F: FieldSpec>(#[committed] xs: [Field<F>; 2], #[public] z: Field<F>) {
+        (xs[0] + xs[1]).constrain_eq(z);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(factor)
+        .zkp_program(sum)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let factor_program = app.get_zkp_program(factor).unwrap();
+    let sum_program = app.get_zkp_program(sum).unwrap();
+
+    let x = BPField::from(6);
+    let blinding = BigInt::from(1234u32);
+    let commitment = runtime.commit(x, &[blinding]).unwrap();
+
+    let proof = runtime
+        .proof_builder(factor_program)
+        .committed_input(x, &[blinding])
+        .private_input(BPField::from(7))
+        .public_input(BPField::from(42))
+        .prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(factor_program)
+        .proof(&proof)
+        .committed_input(&commitment)
+        .public_input(BPField::from(42))
+        .verify()
+        .unwrap();
+
+    // A commitment to a different value must not verify.
+    let wrong_commitment = runtime.commit(BPField::from(7), &[blinding]).unwrap();
+
+    assert!(runtime
+        .verification_builder(factor_program)
+        .proof(&proof)
+        .committed_input(&wrong_commitment)
+        .public_input(BPField::from(42))
+        .verify()
+        .is_err());
+
+    // Omitting the commitment is an error.
+    assert!(runtime
+        .verification_builder(factor_program)
+        .proof(&proof)
+        .public_input(BPField::from(42))
+        .verify()
+        .is_err());
+
+    // Each native field element gets its own blinding factor and commitment.
+    let xs = [BPField::from(6), BPField::from(9)];
+    let blindings = [BigInt::from(1234u32), BigInt::from(5678u32)];
+
+    let commitments = runtime.commit(xs, &blindings).unwrap();
+    assert_eq!(commitments.len(), 2);
+    assert_eq!(commitments[0], commitment[0]);
+
+    let proof = runtime
+        .proof_builder(sum_program)
+        .committed_input(xs, &blindings)
+        .public_input(BPField::from(15))
+        .prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(sum_program)
+        .proof(&proof)
+        .committed_input(&commitments)
+        .public_input(BPField::from(15))
+        .verify()
+        .unwrap();
+
+    // The wrong number of blinding factors is an error.
+    assert!(runtime
+        .proof_builder(sum_program)
+        .committed_input(xs, &blindings[..1])
+        .public_input(BPField::from(15))
+        .prove()
+        .is_err());
+}
+
+#[test]
+fn debug_prove_reports_unsatisfied_constraints() {
+    use sunscreen_runtime::Error;
+    use sunscreen_zkp_backend::{Error as ZkpError, ExecOperation};
+
+    #[zkp_program]
+    fn factor<F: FieldSpec>(a: Field<F>, b: Field<F>, #[public] n: Field<F>) {
+        (a * b).constrain_eq(n);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(factor)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(factor).unwrap();
+
+    let proof = runtime
+        .proof_builder(program)
+        .private_input(BPField::from(3))
+        .private_input(BPField::from(5))
+        .public_input(BPField::from(15))
+        .debug_prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(program)
+        .proof(&proof)
+        .public_input(BPField::from(15))
+        .verify()
+        .unwrap();
+
+    let result = runtime.debug_prove(
+        program,
+        vec![BPField::from(3), BPField::from(4)],
+        vec![BPField::from(15)],
+        vec![],
+    );
+
+    let Err(Error::ZkpError(ZkpError::UnsatisfiedConstraint(constraint))) = result else {
+        panic!("Expected an unsatisfied constraint");
+    };
+
+    // 3 * 4 - 15 != 0
+    assert_eq!(constraint.expected, BigInt::ZERO);
+    assert_ne!(constraint.actual, BigInt::ZERO);
+    assert!(constraint
+        .trace
+        .iter()
+        .any(|x| x.operation == ExecOperation::Mul && x.value == BigInt::from_u32(12)));
+    assert!(constraint.to_string().contains("but it equals -3"));
+}
+
+#[test]
+fn can_profile_constraints() {
+    #[zkp_program]
+    fn cube_in_range<F: FieldSpec>(x: Field<F>, #[public] y: Field<F>) {
+        let x_3 = x * x * x;
+        x_3.constrain_eq(y);
+        x.constrain_le_bounded(y, 8);
+    }
+
+    let profile = cube_in_range.profile::<BulletproofsBackend>().unwrap();
+
+    assert_eq!(profile.name(), "cube_in_range");
+    assert_eq!(profile.multiplications(), 10);
+    assert_eq!(profile.linear_constraints(), 11);
+    assert_eq!(profile.variables(), 10);
+    assert_eq!(profile.multipliers(), 15);
+
+    // The range check's bit decomposition dominates the circuit.
+    let range_check = &profile.entries()[0];
+
+    assert!(range_check.gadget.unwrap().ends_with("ToUInt"));
+    assert_eq!(range_check.multiplications, 8);
+    assert_eq!(range_check.linear_constraints, 9);
+    assert_eq!(range_check.variables, 8);
+
+    let cube = &profile.entries()[1];
+
+    assert_eq!(cube.gadget, None);
+    assert_eq!(cube.multiplications, 2);
+    assert!(cube.span.unwrap().file.ends_with("zkp_program_tests.rs"));
+
+    let constrain_eq = profile
+        .entries()
+        .iter()
+        .find(|x| x.span.unwrap().line == cube.span.unwrap().line + 1)
+        .unwrap();
+
+    assert_eq!(constrain_eq.multiplications, 0);
+    assert_eq!(constrain_eq.linear_constraints, 1);
+
+    // The estimate must match the circuit Bulletproofs actually builds.
+    // Bulletproofs also adds 2 constraints per multiplication gate to bind
+    // its operands.
+    let program = cube_in_range.compile::<BulletproofsBackend>().unwrap();
+
+    let metrics = BulletproofsBackend::new()
+        .metrics(
+            &program.zkp_program_fn,
+            &[BigInt::from_u32(2)],
+            &[BigInt::from_u32(8)],
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(metrics.multipliers, profile.multipliers());
+    assert_eq!(
+        metrics.constraints,
+        profile.linear_constraints() + 2 * profile.multiplications()
+    );
+}
+
+#[test]
+fn proofs_are_bound_to_context() {
+    #[zkp_program]
+    fn square<F: FieldSpec>(x: Field<F>, #[public] y: Field<F>) {
+        (x * x).constrain_eq(y);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(square)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(square).unwrap();
+
+    let proof = runtime
+        .proof_builder(program)
+        .private_input(BPField::from(4))
+        .public_input(BPField::from(16))
+        .context(b"app-a")
+        .prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(program)
+        .proof(&proof)
+        .public_input(BPField::from(16))
+        .context(b"app-a")
+        .verify()
+        .unwrap();
+
+    for context in [&b"app-b"[..], &[]] {
+        let result = runtime
+            .verification_builder(program)
+            .proof(&proof)
+            .public_input(BPField::from(16))
+            .context(context)
+            .verify();
+
+        assert!(result.is_err());
+    }
+}
@@ -4,7 +4,10 @@ use sunscreen_compiler_common::macros::{
     create_program_node, emit_signature, extract_fn_arguments, ExtractFnArgumentsError,
 };
 use syn::{
-    parse_macro_input, spanned::Spanned, Generics, ItemFn, Path, ReturnType, Type, TypeParamBound,
+    parse_macro_input, parse_quote_spanned,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Block, ExprConst, Generics, Item, ItemFn, Path, ReturnType, Stmt, Type, TypeParamBound,
 };
 
 use crate::{
@@ -15,6 +18,7 @@ use crate::{
 enum ArgumentKind {
     Linked,
     Private,
+    Committed,
     Public,
     Constant,
 }
@@ -91,7 +95,8 @@ fn get_generic_arg(generics: &Generics) -> Result<(Ident, Path)> {
 fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenStream> {
     let zkp_program_name = &input_fn.sig.ident;
     let vis = &input_fn.vis;
-    let body = &input_fn.block;
+    let mut body = input_fn.block.clone();
+    SpanMarker.visit_block_mut(&mut body);
     let inputs = &input_fn.sig.inputs;
     let ret = &input_fn.sig.output;
 
@@ -108,6 +113,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
     };
 
     let mut is_linked = false;
+    let mut is_committed = false;
     let mut public_seen = false;
     let mut constant_seen = false;
     let mut private_seen = false;
@@ -146,6 +152,16 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
                                 }
                                 private_seen = true;
                             },
+                            Some("committed") => {
+                                if public_seen || constant_seen {
+                                    return Err(Error::compile_error(attr.path().span(),
+                                        "#[committed] arguments must be specified before #[public] and #[constant] arguments"
+                                    ));
+                                }
+                                arg_kind = ArgumentKind::Committed;
+                                is_committed = true;
+                                private_seen = true;
+                            },
                             Some("public") => {
                                 if constant_seen {
                                     return Err(Error::compile_error(attr.path().span(),
@@ -161,14 +177,14 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
                             },
                             _ => {
                                 return Err(Error::compile_error(attr.path().span(), &format!(
-                                    "Expected #[linked], #[private], #[public] or #[constant], found {}",
+                                    "Expected #[linked], #[private], #[committed], #[public] or #[constant], found {}",
                                     attr.path().to_token_stream()
                                 )));
                             }
                         }
                     },
                     [_, attr, ..] => {
-                        return Err(Error::compile_error(attr.span(), "ZKP program arguments may only have one attribute (#[linked], #[private], #[public] or #[constant])."));
+                        return Err(Error::compile_error(attr.span(), "ZKP program arguments may only have one attribute (#[linked], #[private], #[committed], #[public] or #[constant])."));
                     }
                 };
 
@@ -181,6 +197,13 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
         Err(ExtractFnArgumentsError::IllegalType(s)) => Err(Error::compile_error(s, "ZKP program arguments must be an array or named struct type"))?,
     };
 
+    if is_linked && is_committed {
+        return Err(Error::compile_error(
+            inputs.span(),
+            "ZKP programs may not have both #[linked] and #[committed] arguments",
+        ));
+    }
+
     let argument_types = unwrapped_inputs
         .iter()
         .map(|(_, t, _)| (**t).clone())
@@ -194,11 +217,18 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
         let (input_type, input_arg) = match t.0 {
             ArgumentKind::Linked => ("linked_input", Some(&build_arg)),
             ArgumentKind::Private => ("private_input", None),
+            ArgumentKind::Committed => ("committed_input", None),
             ArgumentKind::Public => ("public_input", None),
             ArgumentKind::Constant => ("constant_input", None),
         };
 
-        create_program_node(&t.2.to_string(), t.1, input_type, input_arg)
+        let marker = span_marker(t.2.span());
+        let node = create_program_node(&t.2.to_string(), t.1, input_type, input_arg);
+
+        quote! {
+            #marker
+            #node
+        }
     });
 
     let zkp_program_struct_name =
@@ -233,6 +263,10 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
             type Link = #associated_link_type;
 
             fn build(&self, linked_input: <Self::Link as sunscreen::zkp::Link>::Input) -> sunscreen::Result<sunscreen::zkp::ZkpFrontendCompilation> {
+                Ok(<Self as sunscreen::ZkpProgramFn<#generic_ident>>::build_context(self, linked_input)?.graph)
+            }
+
+            fn build_context(&self, linked_input: <Self::Link as sunscreen::zkp::Link>::Input) -> sunscreen::Result<sunscreen::zkp::ZkpContext> {
                 use std::cell::RefCell;
                 use std::mem::transmute;
                 use sunscreen::{Error, INDEX_ARENA, Result, types::{zkp::{ProgramNode, CreateLinkedZkpProgramInput, CreateZkpProgramInput, ConstrainEq, IntoProgramNode}, TypeName}, zkp::{CURRENT_ZKP_CTX, ZkpContext, ZkpData}};
@@ -271,7 +305,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
                     ctx.swap(&RefCell::new(None));
                 });
 
-                Ok(context.graph)
+                Ok(context)
             }
 
             fn name(&self) -> &str {
@@ -294,3 +328,40 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
         #vis const #zkp_program_name: #zkp_program_struct_name = #zkp_program_struct_name;
     })
 }
+
+/**
+ * Emits a statement recording the source location of `span` so the
+ * constraints generated after it can be attributed to it.
+ */
+fn span_marker(span: Span) -> Stmt {
+    parse_quote_spanned! {span=>
+        sunscreen::zkp::set_source_span(file!(), line!(), column!());
+    }
+}
+
+/**
+ * Inserts a [`span_marker`] before every statement in a ZKP program's body,
+ * including those in nested blocks.
+ */
+struct SpanMarker;
+
+impl VisitMut for SpanMarker {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        visit_mut::visit_block_mut(self, block);
+
+        let stmts = std::mem::take(&mut block.stmts);
+
+        for stmt in stmts {
+            if !matches!(stmt, Stmt::Item(_)) {
+                block.stmts.push(span_marker(stmt.span()));
+            }
+
+            block.stmts.push(stmt);
+        }
+    }
+
+    // Nested items and const blocks don't run in the ZKP program's context.
+    fn visit_item_mut(&mut self, _item: &mut Item) {}
+
+    fn visit_expr_const_mut(&mut self, _expr: &mut ExprConst) {}
+}
@@ -5,6 +5,9 @@ use sunscreen_math_macros::refify_binary_op;
 
 use crate::{ring::Ring, One, Zero};
 
+pub(crate) mod ntt;
+pub use ntt::NTT_THRESHOLD;
+
 #[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 /// A polynomial over the ring `T`.
 ///
@@ -155,6 +158,56 @@ where
 
         (q, rem)
     }
+
+    /// Computes `self * rhs` using schoolbook multiplication, regardless of whether `R` supports
+    /// a faster algorithm.
+    pub fn mul_schoolbook(&self, rhs: &Self) -> Self {
+        // TODO: Fix vartime
+        if self.coeffs.is_empty() || rhs.coeffs.is_empty() {
+            return Self::zero();
+        }
+
+        let mut out_coeffs = vec![R::zero(); (self.coeffs.len() - 1) + (rhs.coeffs.len() - 1) + 1];
+
+        for i in 0..self.coeffs.len() {
+            for j in 0..rhs.coeffs.len() {
+                let a = self.coeffs.get(i).unwrap_or(&R::zero()).clone();
+                let b = rhs.coeffs.get(j).unwrap_or(&R::zero()).clone();
+
+                out_coeffs[i + j] = a * b + &out_coeffs[i + j];
+            }
+        }
+
+        Polynomial { coeffs: out_coeffs }
+    }
+
+    /// Computes `self * rhs` modulo `x^n + 1`. The result has exactly `n` coefficients.
+    ///
+    /// # Remarks
+    /// When `n` is a power of two and neither operand has more than `n` coefficients, this
+    /// uses [`Ring::fast_negacyclic_mul`] if `R` supports it. Otherwise, this multiplies the
+    /// polynomials and then reduces the product.
+    pub fn mul_negacyclic(&self, rhs: &Self, n: usize) -> Self {
+        if n.is_power_of_two() && self.coeffs.len() <= n && rhs.coeffs.len() <= n {
+            if let Some(coeffs) = R::fast_negacyclic_mul(&self.coeffs, &rhs.coeffs, n) {
+                return Self { coeffs };
+            }
+        }
+
+        let product = self * rhs;
+        let mut coeffs = vec![R::zero(); n];
+
+        for (i, c) in product.coeffs.into_iter().enumerate() {
+            // x^n = -1
+            if (i / n) % 2 == 0 {
+                coeffs[i % n] = coeffs[i % n].clone() + c;
+            } else {
+                coeffs[i % n] = coeffs[i % n].clone() - c;
+            }
+        }
+
+        Self { coeffs }
+    }
 }
 
 impl<T> Index<usize> for Polynomial<T>
@@ -233,23 +286,14 @@ where
     type Output = Polynomial<T>;
 
     fn mul(self, rhs: &Polynomial<T>) -> Self::Output {
-        // TODO: Fix vartime
         if self.coeffs.is_empty() || rhs.coeffs.is_empty() {
             return Self::Output::zero();
         }
 
-        let mut out_coeffs = vec![T::zero(); (self.coeffs.len() - 1) + (rhs.coeffs.len() - 1) + 1];
-
-        for i in 0..self.coeffs.len() {
-            for j in 0..rhs.coeffs.len() {
-                let a = self.coeffs.get(i).unwrap_or(&T::zero()).clone();
-                let b = rhs.coeffs.get(j).unwrap_or(&T::zero()).clone();
-
-                out_coeffs[i + j] = a * b + &out_coeffs[i + j];
-            }
+        match T::fast_poly_mul(&self.coeffs, &rhs.coeffs) {
+            Some(coeffs) => Polynomial { coeffs },
+            None => self.mul_schoolbook(rhs),
         }
-
-        Polynomial { coeffs: out_coeffs }
     }
 }
 
@@ -320,7 +364,7 @@ mod tests {
     use crate::{
         self as sunscreen_math,
         poly::Polynomial,
-        ring::{BarrettBackend, Zq},
+        ring::{BarrettBackend, Ring, Zq},
         One, Zero,
     };
 
@@ -382,6 +426,53 @@ mod tests {
         assert_eq!(a * b, expected);
     }
 
+    #[test]
+    fn can_mul_large_polynomials() {
+        #[derive(BarrettConfig)]
+        #[barrett_config(modulus = "1234", num_limbs = 1)]
+        struct Cfg;
+
+        type R = Zq<1, BarrettBackend<1, Cfg>>;
+        type TestPoly = Polynomial<Zq<1, BarrettBackend<1, Cfg>>>;
+
+        let random_poly = |len| TestPoly {
+            coeffs: (0..len)
+                .map(|_| R::from(Uniform::from(0..1234u64).sample(&mut thread_rng())))
+                .collect(),
+        };
+
+        // Large enough to use an NTT.
+        let a = random_poly(super::NTT_THRESHOLD + 5);
+        let b = random_poly(2 * super::NTT_THRESHOLD);
+
+        assert_eq!(&a * &b, a.mul_schoolbook(&b));
+
+        let n = 4 * super::NTT_THRESHOLD;
+        let product = a.mul_negacyclic(&b, n);
+
+        assert_eq!(product.coeffs.len(), n);
+        assert_eq!(product, (&a * &b).vartime_div_rem_restricted_rhs(&f(n)).1);
+
+        // Falls back to reducing the product for small degrees.
+        let n = 64;
+        let small = TestPoly {
+            coeffs: a.coeffs[..n].to_vec(),
+        };
+
+        assert_eq!(
+            small.mul_negacyclic(&small, n),
+            (&small * &small).vartime_div_rem_restricted_rhs(&f(n)).1
+        );
+    }
+
+    fn f<R: Ring>(n: usize) -> Polynomial<R> {
+        let mut coeffs = vec![R::zero(); n + 1];
+        coeffs[0] = R::one();
+        coeffs[n] = R::one();
+
+        Polynomial { coeffs }
+    }
+
     #[test]
     fn can_get_poly_degree_constant_coeff() {
         #[derive(BarrettConfig)]
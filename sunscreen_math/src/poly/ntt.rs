@@ -0,0 +1,541 @@
+//! Polynomial multiplication over [`Zq`] using number theoretic transforms.
+//!
+//! An NTT needs a prime modulus with roots of unity of the right order, which `q` generally
+//! lacks. Instead, we lift the coefficients to integers, compute their product exactly modulo
+//! enough NTT-friendly primes that the result can't wrap, and recombine the residues with the
+//! Chinese remainder theorem before reducing modulo `q`.
+
+use crypto_bigint::{Uint, Word};
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+
+use crate::{
+    ring::{ArithmeticBackend, Zq},
+    Zero,
+};
+
+/// The smallest operand length for which [`Zq`] polynomial multiplication uses an NTT rather than
+/// schoolbook multiplication.
+pub const NTT_THRESHOLD: usize = 128;
+
+/// The log2 of the largest supported transform length.
+const MAX_LOG_LEN: u32 = 32;
+
+/// The number of primes we generate, which supports moduli up to about 1900 bits.
+const NUM_PRIMES: usize = 64;
+
+/// A prime `p = c * 2^32 + 1` in `[2^61, 2^62)` and a primitive `2^32`th root of unity mod `p`.
+#[derive(Debug, Clone, Copy)]
+struct NttPrime {
+    p: u64,
+    root: u64,
+}
+
+lazy_static! {
+    static ref PRIMES: Vec<NttPrime> = find_primes(NUM_PRIMES);
+}
+
+/// Computes the product of `lhs` and `rhs`.
+///
+/// # Panics
+/// If either operand is empty.
+pub(crate) fn mul<const N: usize, B: ArithmeticBackend<N>>(
+    lhs: &[Zq<N, B>],
+    rhs: &[Zq<N, B>],
+) -> Vec<Zq<N, B>> {
+    let out_len = lhs.len() + rhs.len() - 1;
+    let len = out_len.next_power_of_two();
+
+    // Each output coefficient is a sum of at most min(lhs, rhs) products of values below q.
+    let terms = usize::min(lhs.len(), rhs.len());
+    let primes = primes_for::<N, B>(terms);
+
+    let lhs = lift(lhs);
+    let rhs = lift(rhs);
+
+    let residues = primes
+        .par_iter()
+        .map(|prime| {
+            let ntt = Ntt::new(prime, len);
+            let mut a = residues_mod(&lhs, prime.p, len);
+            let mut b = residues_mod(&rhs, prime.p, len);
+
+            ntt.forward(&mut a);
+            ntt.forward(&mut b);
+            ntt.pointwise_mul(&mut a, &b);
+            ntt.inverse(&mut a);
+
+            a.truncate(out_len);
+            a
+        })
+        .collect::<Vec<_>>();
+
+    reconstruct(primes, &residues)
+}
+
+/// Computes the product of `lhs` and `rhs` modulo `x^n + 1`.
+///
+/// # Panics
+/// If `n` isn't a power of two or either operand has more than `n` coefficients.
+pub(crate) fn mul_negacyclic<const N: usize, B: ArithmeticBackend<N>>(
+    lhs: &[Zq<N, B>],
+    rhs: &[Zq<N, B>],
+    n: usize,
+) -> Vec<Zq<N, B>> {
+    assert!(n.is_power_of_two());
+    assert!(lhs.len() <= n && rhs.len() <= n);
+
+    // Wrapping around x^n + 1 negates terms, so the exact coefficients lie in
+    // (-n (q - 1)^2, n (q - 1)^2). Shift them by this offset to make them non-negative.
+    let primes = primes_for::<N, B>(2 * n);
+    let q_minus_1 = B::MODULUS.wrapping_sub(&Uint::ONE);
+
+    let lhs = lift(lhs);
+    let rhs = lift(rhs);
+
+    let residues = primes
+        .par_iter()
+        .map(|prime| {
+            let p = prime.p;
+            let ntt = Ntt::new(prime, n);
+
+            // Twisting by powers of a 2n-th root psi turns the negacyclic convolution into a
+            // cyclic one.
+            let psi = pow_mod(prime.root, (1 << MAX_LOG_LEN) / (2 * n as u64), p);
+            let psi_inv = inv_mod(psi, p);
+
+            let mut a = residues_mod(&lhs, p, n);
+            let mut b = residues_mod(&rhs, p, n);
+            twist(&mut a, psi, p);
+            twist(&mut b, psi, p);
+
+            ntt.forward(&mut a);
+            ntt.forward(&mut b);
+            ntt.pointwise_mul(&mut a, &b);
+            ntt.inverse(&mut a);
+
+            twist(&mut a, psi_inv, p);
+
+            let q_minus_1 = uint_mod(&q_minus_1, p);
+            let offset = mul_mod(n as u64 % p, mul_mod(q_minus_1, q_minus_1, p), p);
+
+            for x in a.iter_mut() {
+                *x = add_mod(*x, offset, p);
+            }
+
+            a
+        })
+        .collect::<Vec<_>>();
+
+    // (q - 1)^2 = 1 mod q, so the offset is n mod q.
+    let offset = Zq::from(n as u64);
+
+    reconstruct(primes, &residues)
+        .into_iter()
+        .map(|x| x - offset)
+        .collect()
+}
+
+/// Returns enough primes to exactly represent sums of `terms` products of two values below `q`.
+fn primes_for<const N: usize, B: ArithmeticBackend<N>>(terms: usize) -> &'static [NttPrime] {
+    let bits = 2 * B::MODULUS.bits() + (usize::BITS - terms.leading_zeros()) as usize;
+
+    // Every prime is at least 2^61.
+    let count = (bits + 60) / 61;
+
+    assert!(
+        count <= PRIMES.len(),
+        "modulus too large for NTT multiplication"
+    );
+
+    &PRIMES[..count]
+}
+
+/// Converts coefficients to integers in `[0, q)`.
+fn lift<const N: usize, B: ArithmeticBackend<N>>(coeffs: &[Zq<N, B>]) -> Vec<Uint<N>> {
+    coeffs.iter().map(|x| x.into_bigint()).collect()
+}
+
+/// Reduces the integers modulo `p`, padding with zeros to `len`.
+fn residues_mod<const N: usize>(values: &[Uint<N>], p: u64, len: usize) -> Vec<u64> {
+    let mut out = values.iter().map(|x| uint_mod(x, p)).collect::<Vec<_>>();
+    out.resize(len, 0);
+    out
+}
+
+/// Recombines the residues of each coefficient modulo `primes` into a value in `Zq`, using
+/// Garner's algorithm.
+fn reconstruct<const N: usize, B: ArithmeticBackend<N>>(
+    primes: &[NttPrime],
+    residues: &[Vec<u64>],
+) -> Vec<Zq<N, B>> {
+    // inv[i] = (p_0 * ... * p_{i-1})^-1 mod p_i
+    let inv = primes
+        .iter()
+        .enumerate()
+        .map(|(i, prime)| {
+            let prod = primes[..i]
+                .iter()
+                .fold(1, |acc, p_j| mul_mod(acc, p_j.p % prime.p, prime.p));
+            inv_mod(prod, prime.p)
+        })
+        .collect::<Vec<_>>();
+
+    // radix[i] = p_0 * ... * p_{i-1} mod q
+    let radix = primes
+        .iter()
+        .scan(Zq::<N, B>::from(1u64), |acc, prime| {
+            let cur = *acc;
+            *acc = cur * Zq::from(prime.p);
+            Some(cur)
+        })
+        .collect::<Vec<_>>();
+
+    let len = residues[0].len();
+
+    (0..len)
+        .into_par_iter()
+        .map(|c| {
+            // The mixed radix digits v_i where x = v_0 + v_1 p_0 + v_2 p_0 p_1 + ...
+            let mut digits = Vec::with_capacity(primes.len());
+
+            for (i, prime) in primes.iter().enumerate() {
+                let p = prime.p;
+                let mut partial = 0;
+                let mut prod = 1;
+
+                for (digit, p_j) in digits.iter().zip(primes) {
+                    partial = add_mod(partial, mul_mod(*digit, prod, p), p);
+                    prod = mul_mod(prod, p_j.p % p, p);
+                }
+
+                digits.push(mul_mod(sub_mod(residues[i][c], partial, p), inv[i], p));
+            }
+
+            digits
+                .iter()
+                .zip(&radix)
+                .fold(Zq::zero(), |acc, (digit, radix)| {
+                    acc + Zq::from(*digit) * radix
+                })
+        })
+        .collect()
+}
+
+/// Precomputed twiddle factors for a transform of one length modulo one prime.
+struct Ntt {
+    p: u64,
+    roots: Vec<u64>,
+    inv_roots: Vec<u64>,
+    len_inv: u64,
+}
+
+impl Ntt {
+    fn new(prime: &NttPrime, len: usize) -> Self {
+        assert!(len.is_power_of_two() && len.trailing_zeros() <= MAX_LOG_LEN);
+
+        let p = prime.p;
+        let w = pow_mod(prime.root, (1 << MAX_LOG_LEN) / len as u64, p);
+
+        Self {
+            p,
+            roots: powers(w, len / 2, p),
+            inv_roots: powers(inv_mod(w, p), len / 2, p),
+            len_inv: inv_mod(len as u64 % p, p),
+        }
+    }
+
+    fn forward(&self, a: &mut [u64]) {
+        self.transform(a, &self.roots);
+    }
+
+    fn inverse(&self, a: &mut [u64]) {
+        self.transform(a, &self.inv_roots);
+
+        for x in a.iter_mut() {
+            *x = mul_mod(*x, self.len_inv, self.p);
+        }
+    }
+
+    fn pointwise_mul(&self, a: &mut [u64], b: &[u64]) {
+        for (x, y) in a.iter_mut().zip(b) {
+            *x = mul_mod(*x, *y, self.p);
+        }
+    }
+
+    /// An iterative radix 2 Cooley-Tukey transform.
+    fn transform(&self, a: &mut [u64], roots: &[u64]) {
+        let len = a.len();
+        let p = self.p;
+
+        bit_reverse(a);
+
+        let mut half = 1;
+
+        while half < len {
+            let step = len / (2 * half);
+
+            for chunk in a.chunks_exact_mut(2 * half) {
+                let (lo, hi) = chunk.split_at_mut(half);
+
+                for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let t = mul_mod(*v, roots[j * step], p);
+                    *v = sub_mod(*u, t, p);
+                    *u = add_mod(*u, t, p);
+                }
+            }
+
+            half *= 2;
+        }
+    }
+}
+
+fn bit_reverse(a: &mut [u64]) {
+    let bits = a.len().trailing_zeros();
+
+    if bits == 0 {
+        return;
+    }
+
+    for i in 0..a.len() {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+}
+
+/// Multiplies `a[i]` by `w^i`.
+fn twist(a: &mut [u64], w: u64, p: u64) {
+    let mut cur = 1;
+
+    for x in a.iter_mut() {
+        *x = mul_mod(*x, cur, p);
+        cur = mul_mod(cur, w, p);
+    }
+}
+
+fn powers(w: u64, count: usize, p: u64) -> Vec<u64> {
+    let mut out = Vec::with_capacity(count);
+    let mut cur = 1;
+
+    for _ in 0..count {
+        out.push(cur);
+        cur = mul_mod(cur, w, p);
+    }
+
+    out
+}
+
+fn uint_mod<const N: usize>(x: &Uint<N>, p: u64) -> u64 {
+    x.as_words().iter().rev().fold(0, |acc, word| {
+        ((((acc as u128) << Word::BITS) | *word as u128) % p as u128) as u64
+    })
+}
+
+#[inline(always)]
+fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    // p < 2^62, so this can't overflow.
+    let x = a + b;
+
+    if x >= p {
+        x - p
+    } else {
+        x
+    }
+}
+
+#[inline(always)]
+fn sub_mod(a: u64, b: u64, p: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + p - b
+    }
+}
+
+#[inline(always)]
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut out = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = mul_mod(out, base, p);
+        }
+
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+
+    out
+}
+
+/// Inverts `a` modulo the prime `p`.
+fn inv_mod(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+/// Deterministic Miller-Rabin, which is exact for 64-bit integers with these bases.
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    for b in BASES {
+        if n % b == 0 {
+            return n == b;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    'witness: for b in BASES {
+        let mut x = pow_mod(b, d, n);
+
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Finds the `count` largest primes of the form `c * 2^32 + 1` below `2^62`.
+fn find_primes(count: usize) -> Vec<NttPrime> {
+    let mut primes = Vec::with_capacity(count);
+    let mut c = (1u64 << (62 - MAX_LOG_LEN)) - 1;
+
+    while primes.len() < count {
+        let p = (c << MAX_LOG_LEN) + 1;
+        c -= 1;
+
+        if !is_prime(p) {
+            continue;
+        }
+
+        // Raising a quadratic non-residue g to (p - 1) / 2^32 gives an element whose 2^31st
+        // power is g^((p - 1) / 2) = -1, so it's a primitive 2^32nd root.
+        let g = (2..)
+            .find(|g| pow_mod(*g, (p - 1) / 2, p) == p - 1)
+            .unwrap();
+
+        primes.push(NttPrime {
+            p,
+            root: pow_mod(g, (p - 1) >> MAX_LOG_LEN, p),
+        });
+    }
+
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{distributions::Uniform, prelude::Distribution, thread_rng};
+    use sunscreen_math_macros::BarrettConfig;
+
+    use super::*;
+    use crate::{self as sunscreen_math, poly::Polynomial, ring::BarrettBackend};
+
+    #[derive(BarrettConfig)]
+    #[barrett_config(
+        modulus = "421249101157150430150591791601812858371395928330411389778873040897",
+        num_limbs = 4
+    )]
+    struct Cfg;
+
+    type R = Zq<4, BarrettBackend<4, Cfg>>;
+
+    fn random_poly(len: usize) -> Polynomial<R> {
+        let dist = Uniform::from(0..u64::MAX);
+
+        Polynomial {
+            coeffs: (0..len)
+                .map(|_| {
+                    let x = R::from(dist.sample(&mut thread_rng()));
+                    // Fill all the limbs.
+                    x * x * x * x
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn primes_are_ntt_friendly() {
+        for prime in PRIMES.iter() {
+            assert!(is_prime(prime.p));
+            assert_eq!(
+                pow_mod(prime.root, 1 << (MAX_LOG_LEN - 1), prime.p),
+                prime.p - 1
+            );
+        }
+    }
+
+    #[test]
+    fn ntt_round_trips() {
+        let ntt = Ntt::new(&PRIMES[0], 16);
+        let a = (0..16).collect::<Vec<u64>>();
+        let mut b = a.clone();
+
+        ntt.forward(&mut b);
+        assert_ne!(a, b);
+
+        ntt.inverse(&mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn mul_matches_schoolbook() {
+        for (l, r) in [(1, 1), (3, 70), (100, 100), (257, 130)] {
+            let a = random_poly(l);
+            let b = random_poly(r);
+
+            let expected = a.mul_schoolbook(&b);
+            let actual = Polynomial {
+                coeffs: mul(&a.coeffs, &b.coeffs),
+            };
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn mul_negacyclic_matches_schoolbook() {
+        for (l, r, n) in [(1, 1, 1), (4, 4, 4), (100, 37, 128), (256, 256, 256)] {
+            let a = random_poly(l);
+            let b = random_poly(r);
+
+            let product = a.mul_schoolbook(&b);
+            let mut expected = vec![R::zero(); n];
+
+            for (i, c) in product.coeffs.into_iter().enumerate() {
+                if i < n {
+                    expected[i] = expected[i] + c;
+                } else {
+                    expected[i - n] = expected[i - n] - c;
+                }
+            }
+
+            let actual = mul_negacyclic(&a.coeffs, &b.coeffs, n);
+
+            assert_eq!(actual, expected);
+        }
+    }
+}
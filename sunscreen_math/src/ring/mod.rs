@@ -9,6 +9,8 @@ use std::{
     ops::{Add, Mul, Neg, Sub},
 };
 use subtle::{Choice, ConditionallySelectable};
+
+use crate::poly::{ntt, NTT_THRESHOLD};
 use sunscreen_math_macros::refify_binary_op;
 
 mod barrett;
@@ -32,6 +34,22 @@ pub trait Ring:
     + Sync
     + Send
 {
+    /// Multiplies the polynomials with coefficients `lhs` and `rhs`, neither of which is empty,
+    /// if this ring has a faster algorithm than schoolbook multiplication for operands of these
+    /// sizes. Otherwise, returns `None`.
+    ///
+    /// # Remarks
+    /// [`Polynomial`](crate::poly::Polynomial) multiplication calls this before falling back to
+    /// schoolbook multiplication.
+    fn fast_poly_mul(_lhs: &[Self], _rhs: &[Self]) -> Option<Vec<Self>> {
+        None
+    }
+
+    /// Like [`Ring::fast_poly_mul`], but computes the product modulo `x^n + 1`, returning `n`
+    /// coefficients. `n` is a power of two and neither operand has more than `n` coefficients.
+    fn fast_negacyclic_mul(_lhs: &[Self], _rhs: &[Self], _n: usize) -> Option<Vec<Self>> {
+        None
+    }
 }
 
 /**
@@ -582,7 +600,15 @@ impl<const N: usize, B: ArithmeticBackend<N>> Neg for &Zq<N, B> {
 
 impl<const N: usize, B: ArithmeticBackend<N>> Eq for Zq<N, B> {}
 
-impl<const N: usize, B: ArithmeticBackend<N>> Ring for Zq<N, B> {}
+impl<const N: usize, B: ArithmeticBackend<N>> Ring for Zq<N, B> {
+    fn fast_poly_mul(lhs: &[Self], rhs: &[Self]) -> Option<Vec<Self>> {
+        (usize::min(lhs.len(), rhs.len()) >= NTT_THRESHOLD).then(|| ntt::mul(lhs, rhs))
+    }
+
+    fn fast_negacyclic_mul(lhs: &[Self], rhs: &[Self], n: usize) -> Option<Vec<Self>> {
+        (n >= NTT_THRESHOLD).then(|| ntt::mul_negacyclic(lhs, rhs, n))
+    }
+}
 
 impl<const N: usize, B: ArithmeticBackend<N>> Zq<N, B> {}
 
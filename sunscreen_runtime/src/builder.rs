@@ -1,6 +1,6 @@
 //! This module contains various builders for ZKPs, SDLPs, and linked proofs.
 
-use sunscreen_zkp_backend::{Proof, ZkpBackend};
+use sunscreen_zkp_backend::{BigInt, Commitment, CommitmentOpening, Proof, ZkpBackend};
 
 use crate::{marker, CompiledZkpProgram, GenericRuntime, Params, Result, ZkpProgramInput};
 
@@ -17,7 +17,7 @@ pub enum BuilderError {
 }
 
 impl BuilderError {
-    fn user_error(msg: impl Into<String>) -> crate::Error {
+    pub(crate) fn user_error(msg: impl Into<String>) -> crate::Error {
         Self::InvalidUsage(Box::new(msg.into())).into()
     }
 }
@@ -32,6 +32,8 @@ pub struct ProofBuilder<'r, 'p, T: marker::Zkp, B: ZkpBackend> {
     private_inputs: Vec<ZkpProgramInput>,
     public_inputs: Vec<ZkpProgramInput>,
     constant_inputs: Vec<ZkpProgramInput>,
+    committed_inputs: Vec<(ZkpProgramInput, Vec<BigInt>)>,
+    context: Vec<u8>,
 }
 
 impl<'r, 'p, T: marker::Zkp, B: ZkpBackend> ProofBuilder<'r, 'p, T, B> {
@@ -48,6 +50,8 @@ impl<'r, 'p, T: marker::Zkp, B: ZkpBackend> ProofBuilder<'r, 'p, T, B> {
             private_inputs: vec![],
             public_inputs: vec![],
             constant_inputs: vec![],
+            committed_inputs: vec![],
+            context: vec![],
         }
     }
 
@@ -102,15 +106,69 @@ impl<'r, 'p, T: marker::Zkp, B: ZkpBackend> ProofBuilder<'r, 'p, T, B> {
         self
     }
 
+    /// Add a `#[committed]` input to the proof builder. Call this in the same order as the
+    /// program's private and committed arguments.
+    ///
+    /// `blindings` must contain a uniformly random field element for each of the input's
+    /// native field elements. The verifier needs the commitments from
+    /// [`runtime.commit()`][GenericRuntime::commit] with the same input and blindings.
+    pub fn committed_input(
+        mut self,
+        input: impl Into<ZkpProgramInput>,
+        blindings: &[BigInt],
+    ) -> Self {
+        let input = input.into();
+
+        self.private_inputs.push(input.clone());
+        self.committed_inputs.push((input, blindings.to_owned()));
+        self
+    }
+
+    /// Bind the proof to application-defined domain separation data, such as a session or
+    /// chain ID. The verifier must pass the same data to [`VerificationBuilder::context`], so
+    /// the proof can't be replayed in another application.
+    pub fn context(mut self, context: &[u8]) -> Self {
+        self.context = context.to_owned();
+        self
+    }
+
     /// Generate a proof; see [`runtime.prove()`][GenericRuntime::prove].
     pub fn prove(self) -> Result<Proof> {
-        self.runtime.prove(
+        let commitment_openings = self.commitment_openings()?;
+
+        self.runtime.prove_with_context(
             self.program,
             self.private_inputs,
             self.public_inputs,
             self.constant_inputs,
+            &commitment_openings,
+            &self.context,
         )
     }
+
+    /// Generate a proof, describing any unsatisfied constraint on failure; see
+    /// [`runtime.debug_prove()`][GenericRuntime::debug_prove].
+    pub fn debug_prove(self) -> Result<Proof> {
+        let commitment_openings = self.commitment_openings()?;
+
+        self.runtime.debug_prove_with_context(
+            self.program,
+            self.private_inputs,
+            self.public_inputs,
+            self.constant_inputs,
+            &commitment_openings,
+            &self.context,
+        )
+    }
+
+    fn commitment_openings(&self) -> Result<Vec<CommitmentOpening>> {
+        Ok(self
+            .committed_inputs
+            .iter()
+            .map(|(input, blindings)| self.runtime.commitment_openings(input.clone(), blindings))
+            .collect::<Result<Vec<Vec<CommitmentOpening>>>>()?
+            .concat())
+    }
 }
 
 /// A builder for verifying a proof.
@@ -123,6 +181,8 @@ pub struct VerificationBuilder<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> {
     proof: Option<&'a Proof>,
     constant_inputs: Vec<ZkpProgramInput>,
     public_inputs: Vec<ZkpProgramInput>,
+    commitments: Vec<Commitment>,
+    context: Vec<u8>,
 }
 
 impl<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> VerificationBuilder<'r, 'p, 'a, T, B> {
@@ -140,6 +200,8 @@ impl<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> VerificationBuilder<'r, 'p, 'a,
             proof: None,
             public_inputs: vec![],
             constant_inputs: vec![],
+            commitments: vec![],
+            context: vec![],
         }
     }
 
@@ -183,6 +245,20 @@ impl<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> VerificationBuilder<'r, 'p, 'a,
         self
     }
 
+    /// Add the commitments to a `#[committed]` input to the verification builder, as returned
+    /// by [`runtime.commit()`][GenericRuntime::commit]. Call this in the same order as the
+    /// program's committed arguments.
+    pub fn committed_input(mut self, commitments: &[Commitment]) -> Self {
+        self.commitments.extend_from_slice(commitments);
+        self
+    }
+
+    /// Set the domain separation data the proof was bound to with [`ProofBuilder::context`].
+    pub fn context(mut self, context: &[u8]) -> Self {
+        self.context = context.to_owned();
+        self
+    }
+
     /// Verify that `self.proof` satisfies `self.program`; see
     /// [`runtime.verify()`][GenericRuntime::verify].
     ///
@@ -196,11 +272,13 @@ impl<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> VerificationBuilder<'r, 'p, 'a,
                 "You must supply a proof to the verification builder before calling `verify`",
             )
         })?;
-        self.runtime.verify(
+        self.runtime.verify_with_context(
             self.program,
             proof,
             self.public_inputs,
             self.constant_inputs,
+            &self.commitments,
+            &self.context,
         )
     }
 }
@@ -219,11 +297,19 @@ mod linked {
     use std::{borrow::Cow, sync::Arc};
 
     use logproof::{
-        bfv_statement::{self, BfvMessage, BfvProofStatement, BfvWitness, StatementParams},
+        bfv_statement::{
+            self, BfvMessage, BfvProofStatement, BfvWitness, KeySwitchingKey, KeySwitchingTarget,
+            StatementParams,
+        },
         math::Log2,
-        rings::{SealQ128_1024, SealQ128_2048, SealQ128_4096, SealQ128_8192},
+        rings::{
+            SealQ128_1024, SealQ128_16384, SealQ128_16384_0, SealQ128_16384_1, SealQ128_16384_2,
+            SealQ128_2048, SealQ128_32768, SealQ128_32768_0, SealQ128_32768_1, SealQ128_32768_2,
+            SealQ128_32768_3, SealQ128_32768_4, SealQ128_4096, SealQ128_8192,
+        },
         Bounds, LogProofProverKnowledge, LogProofVerifierKnowledge,
     };
+    use seal_fhe::{BFVEvaluator, Ciphertext as SealCiphertext, Evaluator};
     use sunscreen_compiler_common::{Type, TypeName};
     use sunscreen_math::ring::{BarrettBackend, BarrettConfig, Zq};
     use sunscreen_zkp_backend::{
@@ -235,7 +321,7 @@ mod linked {
         marker, Ciphertext, CompiledZkpProgram, Fhe, FheRuntime, FheZkp, FheZkpRuntime,
         GenericRuntime, LinkedProof, NumCiphertexts, Params, Plaintext, PrivateKey, PublicKey,
         Result, Sdlp, SdlpProverKnowledge, SdlpVerifierKnowledge, TryFromPlaintext,
-        TryIntoPlaintext, ZkpProgramInput,
+        TryIntoPlaintext, WithContext, ZkpProgramInput,
     };
 
     /// All FHE plaintext types can be used in a [`Sdlp`]. This trait indicates further that a
@@ -348,6 +434,198 @@ mod linked {
         Private(&'a PrivateKey),
     }
 
+    /// The operations between a ciphertext and a plaintext that can be added to the logproof.
+    #[derive(Clone, Copy)]
+    enum PlainOp {
+        Add,
+        Multiply,
+    }
+
+    impl PlainOp {
+        fn statement<'k>(
+            self,
+            message_id: usize,
+            ciphertext: &WithContext<SealCiphertext>,
+            result: &WithContext<SealCiphertext>,
+        ) -> BfvProofStatement<'k> {
+            let ciphertext = ciphertext.data.clone();
+            let result = result.data.clone();
+            match self {
+                PlainOp::Add => BfvProofStatement::PlaintextAddition {
+                    message_id,
+                    ciphertext,
+                    result,
+                },
+                PlainOp::Multiply => BfvProofStatement::PlaintextMultiplication {
+                    message_id,
+                    ciphertext,
+                    result,
+                },
+            }
+        }
+    }
+
+    /// Add the statements that `result` is the sum of `lhs` and `rhs`, which must all have the
+    /// same type.
+    fn push_addition(
+        statements: &mut Vec<BfvProofStatement<'_>>,
+        lhs: &Ciphertext,
+        rhs: &Ciphertext,
+        result: &Ciphertext,
+    ) -> Result<usize> {
+        check_same_type(lhs, result)?;
+        check_same_type(rhs, result)?;
+
+        let lhs = lhs.inner_as_seal_ciphertext()?;
+        let rhs = rhs.inner_as_seal_ciphertext()?;
+        let result = result.inner_as_seal_ciphertext()?;
+
+        for ((lhs, rhs), result) in lhs.iter().zip(rhs).zip(result) {
+            statements.push(BfvProofStatement::Addition {
+                lhs: lhs.data.clone(),
+                rhs: rhs.data.clone(),
+                result: result.data.clone(),
+            });
+        }
+
+        Ok(result.len())
+    }
+
+    /// Add the statements that `result` is the relinearized product of `lhs` and `rhs` under the
+    /// relinearization keys of `public_key`. The ciphertexts must all have the same type.
+    ///
+    /// The product is computed here, so malformed operands are rejected before either party
+    /// generates their knowledge.
+    fn push_relinearized_product<M: marker::Fhe, Z>(
+        runtime: &GenericRuntime<M, Z>,
+        statements: &mut Vec<BfvProofStatement<'_>>,
+        lhs: &Ciphertext,
+        rhs: &Ciphertext,
+        public_key: &PublicKey,
+        result: &Ciphertext,
+    ) -> Result<usize> {
+        check_same_type(lhs, result)?;
+        check_same_type(rhs, result)?;
+
+        let relin_key = public_key.relin_key.as_ref().ok_or_else(|| {
+            BuilderError::user_error("The public key has no relinearization keys")
+        })?;
+
+        let lhs = lhs.inner_as_seal_ciphertext()?;
+        let rhs = rhs.inner_as_seal_ciphertext()?;
+        let result = result.inner_as_seal_ciphertext()?;
+        if lhs.len() != result.len() || rhs.len() != result.len() {
+            return Err(BuilderError::user_error(
+                "The operands have a different number of components than the result",
+            ));
+        }
+
+        let evaluator = BFVEvaluator::new(runtime.context())?;
+        for ((lhs, rhs), result) in lhs.iter().zip(rhs).zip(result) {
+            let product = evaluator
+                .multiply(&lhs.data, &rhs.data)
+                .and_then(|p| evaluator.relinearize(&p, &relin_key.data))
+                .map_err(|_| {
+                    BuilderError::user_error("The operands can't be multiplied and relinearized")
+                })?;
+
+            statements.push(BfvProofStatement::RelinearizedProduct {
+                lhs: lhs.data.clone(),
+                rhs: rhs.data.clone(),
+                product,
+                result: result.data.clone(),
+            });
+        }
+
+        Ok(result.len())
+    }
+
+    /// Create the statement that every key in `public_key` was generated from the same secret.
+    fn key_generation_statement(public_key: &PublicKey) -> Result<BfvProofStatement<'_>> {
+        let mut switching_keys = vec![];
+
+        if let Some(relin_key) = &public_key.relin_key {
+            switching_keys.push(KeySwitchingKey {
+                target: KeySwitchingTarget::Relinearization,
+                keys: relin_key.data.keys()?,
+            });
+        }
+
+        if let Some(galois_key) = &public_key.galois_key {
+            for (g, keys) in galois_key.data.keys()? {
+                switching_keys.push(KeySwitchingKey {
+                    target: KeySwitchingTarget::Galois(g),
+                    keys,
+                });
+            }
+        }
+
+        Ok(BfvProofStatement::KeyGeneration {
+            public_key: Cow::Borrowed(&public_key.public_key.data),
+            switching_keys,
+        })
+    }
+
+    /// Create the statement that `ciphertext` decrypts to `plaintext` under the secret key of
+    /// `public_key`. Subtracting the plaintext from each inner ciphertext leaves ciphertexts that
+    /// decrypt to zero.
+    fn zero_decryption_statement<'k, M: marker::Fhe, Z>(
+        runtime: &GenericRuntime<M, Z>,
+        ciphertext: &Ciphertext,
+        plaintext: &Plaintext,
+        public_key: &'k PublicKey,
+    ) -> Result<BfvProofStatement<'k>> {
+        let expected_type = Type {
+            is_encrypted: true,
+            ..plaintext.data_type.clone()
+        };
+        if expected_type != ciphertext.data_type {
+            return Err(crate::Error::type_mismatch(
+                &expected_type,
+                &ciphertext.data_type,
+            ));
+        }
+
+        let cts = ciphertext.inner_as_seal_ciphertext()?;
+        let pts = plaintext.inner.as_seal_plaintext()?;
+        if cts.len() != pts.len() {
+            return Err(BuilderError::user_error(
+                "The plaintext has a different number of components than the ciphertext",
+            ));
+        }
+
+        let evaluator = BFVEvaluator::new(runtime.context())?;
+        let ciphertexts = cts
+            .iter()
+            .zip(pts)
+            .map(|(ct, pt)| {
+                // The statement only relates the first two polynomials to the secret key.
+                if ct.data.num_polynomials() != 2 {
+                    return Err(BuilderError::user_error(
+                        "Decryption proofs require relinearized ciphertexts",
+                    ));
+                }
+                Ok(evaluator.sub_plain(&ct.data, &pt.data)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(BfvProofStatement::ZeroDecryption {
+            public_key: Cow::Borrowed(&public_key.public_key.data),
+            ciphertexts,
+        })
+    }
+
+    fn check_same_type(expected: &Ciphertext, actual: &Ciphertext) -> Result<()> {
+        if expected.data_type != actual.data_type {
+            return Err(crate::Error::type_mismatch(
+                &expected.data_type,
+                &actual.data_type,
+            ));
+        }
+
+        Ok(())
+    }
+
     impl<Z> Msg<Z> {
         fn pt_typed(&self) -> &PlaintextTyped {
             match self {
@@ -402,6 +680,7 @@ mod linked {
         messages: Vec<BfvMessage>,
         witness: Vec<BfvWitness<'k>>,
         custom_bounds: Vec<((usize, usize), Bounds)>,
+        context: Vec<u8>,
 
         // linked proof fields
         compiled_zkp_program: Option<&'z CompiledZkpProgram>,
@@ -442,6 +721,11 @@ mod linked {
         }
 
         /// Build the [`LinkedProof`].
+        ///
+        /// # Remarks
+        /// Linked proofs support only coefficient moduli whose SDLP isn't split into groups (see
+        /// [`SdlpParams::groups`](crate::SdlpParams::groups)), i.e. lattice dimensions up to 8192.
+        /// For larger moduli, this returns a [`BuilderError`].
         pub fn build(&mut self) -> Result<LinkedProof> {
             self.build_linkedproof()
         }
@@ -456,6 +740,7 @@ mod linked {
                 messages: vec![],
                 witness: vec![],
                 custom_bounds: vec![],
+                context: vec![],
                 compiled_zkp_program: None,
                 linked_inputs: vec![],
                 private_inputs: vec![],
@@ -669,6 +954,226 @@ mod linked {
             Ok((p, Message(msg_internal)))
         }
 
+        /// Decrypt a ciphertext, adding a statement that it decrypts to the returned plaintext
+        /// under the secret key of `public_key`.
+        ///
+        /// Unlike [`Self::decrypt_returning_msg`], the plaintext isn't hidden: share it with the
+        /// verifier, who passes it to [`LogProofVerificationBuilder::decrypt_revealing`]. The
+        /// statement also shows `public_key` was generated from `private_key`, so the verifier
+        /// knows which key decrypted the ciphertext.
+        pub fn decrypt_revealing<P>(
+            &mut self,
+            ciphertext: &Ciphertext,
+            private_key: &'k PrivateKey,
+            public_key: &'k PublicKey,
+        ) -> Result<(P, Plaintext)>
+        where
+            P: TryFromPlaintext + TypeName,
+        {
+            let plaintext =
+                self.runtime
+                    .decrypt_map_components::<P>(ciphertext, private_key, |_, _| ())?;
+            let p = P::try_from_plaintext(&plaintext, self.runtime.params())?;
+
+            self.statements.push(zero_decryption_statement(
+                self.runtime,
+                ciphertext,
+                &plaintext,
+                public_key,
+            )?);
+            self.witness.push(BfvWitness::ZeroDecryption {
+                private_key: Cow::Borrowed(&private_key.0.data),
+            });
+
+            Ok((p, plaintext))
+        }
+
+        /// Add a statement that the public key in `public_key` was generated from `private_key`,
+        /// and that its relinearization and Galois keys are RLWE samples under that same short
+        /// secret. The verifier only needs the public key.
+        ///
+        /// # Remarks
+        /// This authenticates the public key, but **not** the relinearization or Galois keys. The
+        /// secret each of them encrypts is a free witness that is only bounded, since `s^2` and
+        /// `s(x^g)` aren't linear functions of `s` in the ring. A dishonest prover can publish
+        /// evaluation keys encrypting any short polynomial, so results the verifier computes with
+        /// them may decrypt incorrectly even though this proof verifies.
+        ///
+        /// Proving every Galois key is expensive, so consider removing the ones your FHE programs
+        /// don't use from `public_key` first.
+        pub fn prove_keys(
+            &mut self,
+            public_key: &'k PublicKey,
+            private_key: &'k PrivateKey,
+        ) -> Result<()> {
+            self.statements.push(key_generation_statement(public_key)?);
+            self.witness.push(BfvWitness::KeyGeneration {
+                private_key: Cow::Borrowed(&private_key.0.data),
+            });
+            Ok(())
+        }
+
+        /// Add a statement that `result` is the sum of the ciphertexts `lhs` and `rhs`, as computed
+        /// by an FHE program run with [the runtime](`crate::GenericRuntime::run`).
+        ///
+        /// # Remarks
+        /// All three ciphertexts are public, so this statement proves nothing on its own. Use it
+        /// alongside statements about the operands, e.g. to show that the sum of two fresh
+        /// encryptions was computed correctly.
+        pub fn add(
+            &mut self,
+            lhs: &Ciphertext,
+            rhs: &Ciphertext,
+            result: &Ciphertext,
+        ) -> Result<()> {
+            let count = push_addition(&mut self.statements, lhs, rhs, result)?;
+            self.witness
+                .extend((0..count).map(|_| BfvWitness::Evaluation));
+            Ok(())
+        }
+
+        /// Add a statement that `result` is the product of the ciphertexts `lhs` and `rhs`,
+        /// relinearized with the relinearization keys of `public_key`, as computed by an FHE
+        /// program run with [the runtime](`crate::GenericRuntime::run`).
+        ///
+        /// # Remarks
+        /// As with [`Self::add`], all the ciphertexts are public, so the verifier simply recomputes
+        /// the product. The statement binds it to the other statements in the proof.
+        pub fn multiply(
+            &mut self,
+            lhs: &Ciphertext,
+            rhs: &Ciphertext,
+            public_key: &'k PublicKey,
+            result: &Ciphertext,
+        ) -> Result<()> {
+            let count = push_relinearized_product(
+                self.runtime,
+                &mut self.statements,
+                lhs,
+                rhs,
+                public_key,
+                result,
+            )?;
+            self.witness
+                .extend((0..count).map(|_| BfvWitness::Evaluation));
+            Ok(())
+        }
+
+        /// Add a statement that `result` is the sum of `ciphertext` and a plaintext, as computed by
+        /// an FHE program run with [the runtime](`crate::GenericRuntime::run`). The plaintext
+        /// remains private.
+        pub fn add_plain<P>(
+            &mut self,
+            ciphertext: &Ciphertext,
+            plaintext: &P,
+            result: &Ciphertext,
+        ) -> Result<()>
+        where
+            P: TryIntoPlaintext + TypeName,
+        {
+            self.evaluate_plain_internal(PlainOp::Add, ciphertext, plaintext, result)?;
+            Ok(())
+        }
+
+        /// Add a statement that `result` is the sum of `ciphertext` and a plaintext, returning the
+        /// message to optionally be [encrypted](`Self::reencrypt`), that is, _shared_ with another
+        /// logproof statement.
+        pub fn add_plain_returning_msg<P>(
+            &mut self,
+            ciphertext: &Ciphertext,
+            plaintext: &P,
+            result: &Ciphertext,
+        ) -> Result<Message>
+        where
+            P: TryIntoPlaintext + TypeName,
+        {
+            self.evaluate_plain_internal(PlainOp::Add, ciphertext, plaintext, result)
+        }
+
+        /// Add a statement that `result` is the product of `ciphertext` and a plaintext, as
+        /// computed by an FHE program run with [the runtime](`crate::GenericRuntime::run`). The
+        /// plaintext remains private.
+        pub fn multiply_plain<P>(
+            &mut self,
+            ciphertext: &Ciphertext,
+            plaintext: &P,
+            result: &Ciphertext,
+        ) -> Result<()>
+        where
+            P: TryIntoPlaintext + TypeName,
+        {
+            self.evaluate_plain_internal(PlainOp::Multiply, ciphertext, plaintext, result)?;
+            Ok(())
+        }
+
+        /// Add a statement that `result` is the product of `ciphertext` and a plaintext, returning
+        /// the message to optionally be [encrypted](`Self::reencrypt`), that is, _shared_ with
+        /// another logproof statement.
+        pub fn multiply_plain_returning_msg<P>(
+            &mut self,
+            ciphertext: &Ciphertext,
+            plaintext: &P,
+            result: &Ciphertext,
+        ) -> Result<Message>
+        where
+            P: TryIntoPlaintext + TypeName,
+        {
+            self.evaluate_plain_internal(PlainOp::Multiply, ciphertext, plaintext, result)
+        }
+
+        fn evaluate_plain_internal<P>(
+            &mut self,
+            op: PlainOp,
+            ciphertext: &Ciphertext,
+            plaintext: &P,
+            result: &Ciphertext,
+        ) -> Result<Message>
+        where
+            P: TryIntoPlaintext + TypeName,
+        {
+            let plaintext_typed = self.plaintext_typed(plaintext)?;
+            let expected_type = Type {
+                is_encrypted: true,
+                ..plaintext_typed.type_name.clone()
+            };
+            if expected_type != ciphertext.data_type {
+                return Err(crate::Error::type_mismatch(
+                    &expected_type,
+                    &ciphertext.data_type,
+                ));
+            }
+            check_same_type(ciphertext, result)?;
+
+            let plaintexts = plaintext_typed.plaintext.inner_as_seal_plaintext()?;
+            let ciphertexts = ciphertext.inner_as_seal_ciphertext()?;
+            let results = result.inner_as_seal_ciphertext()?;
+            if plaintexts.len() != ciphertexts.len() {
+                return Err(BuilderError::user_error(
+                    "The plaintext's length does not match the ciphertext. This is likely a type mismatch.",
+                ));
+            }
+
+            let idx_start = self.messages.len();
+            for ((pt, ct), result) in plaintexts.iter().zip(ciphertexts).zip(results) {
+                let message_id = self.messages.len();
+                self.messages.push(BfvMessage {
+                    plaintext: pt.data.clone(),
+                    bounds: None,
+                });
+                self.statements.push(op.statement(message_id, ct, result));
+                self.witness.push(BfvWitness::Evaluation);
+            }
+            let idx_end = self.messages.len();
+
+            let msg_internal = MessageInternal {
+                id: idx_start,
+                pt: Arc::new(plaintext_typed),
+                len: idx_end - idx_start,
+                zkp_type: (),
+            };
+            Ok(Message(msg_internal))
+        }
+
         fn encrypt_asymmetric_internal<T>(
             &mut self,
             message: Msg<T>,
@@ -758,22 +1263,65 @@ mod linked {
             self
         }
 
+        /// Bind the proof to application-defined domain separation data, such as a session or
+        /// chain ID. The verifier must pass the same data to
+        /// [`LogProofVerificationBuilder::context`], so the proof can't be replayed in another
+        /// application.
+        pub fn context(&mut self, context: &[u8]) -> &mut Self {
+            self.context = context.to_owned();
+            self
+        }
+
         /// Build the [`Sdlp`] for the statements added to this builder.
         fn build_logproof(&self) -> Result<Sdlp> {
-            Sdlp::create(&self.build_sdlp_pk()?)
+            let groups = sdlp_groups(self.runtime.params(), &self.statements)?;
+
+            // Build each group's knowledge lazily, so only one group is in memory at a time.
+            Sdlp::create(
+                (0..groups).map(|group| self.build_sdlp_pk(group)),
+                &self.context,
+            )
         }
 
-        fn build_sdlp_pk(&self) -> Result<SdlpProverKnowledge> {
+        /// Build the [`SdlpProverKnowledge`] for the given group of the data modulus. See
+        /// [`sdlp_groups`].
+        fn build_sdlp_pk(&self, group: usize) -> Result<SdlpProverKnowledge> {
             let params = self.runtime.params();
-            let mut pk: SdlpProverKnowledge = match &params.coeff_modulus[..] {
-                SealQ128_1024::Q => Ok(self.build_sdlp_pk_generic::<1, SealQ128_1024>()?.into()),
-                SealQ128_2048::Q => Ok(self.build_sdlp_pk_generic::<1, SealQ128_2048>()?.into()),
-                SealQ128_4096::Q => Ok(self.build_sdlp_pk_generic::<2, SealQ128_4096>()?.into()),
-                SealQ128_8192::Q => Ok(self.build_sdlp_pk_generic::<3, SealQ128_8192>()?.into()),
-                _ => Err(BuilderError::UnsupportedParameters(Box::new(
-                    params.clone(),
-                ))),
-            }?;
+            let mut pk: SdlpProverKnowledge = match (&params.coeff_modulus[..], group) {
+                (SealQ128_1024::Q, 0) => self.build_sdlp_pk_generic::<1, SealQ128_1024>()?.into(),
+                (SealQ128_2048::Q, 0) => self.build_sdlp_pk_generic::<1, SealQ128_2048>()?.into(),
+                (SealQ128_4096::Q, 0) => self.build_sdlp_pk_generic::<2, SealQ128_4096>()?.into(),
+                (SealQ128_8192::Q, 0) => self.build_sdlp_pk_generic::<3, SealQ128_8192>()?.into(),
+                (SealQ128_16384::Q, 0) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_16384_0>()?.into()
+                }
+                (SealQ128_16384::Q, 1) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_16384_1>()?.into()
+                }
+                (SealQ128_16384::Q, 2) => {
+                    self.build_sdlp_pk_generic::<2, SealQ128_16384_2>()?.into()
+                }
+                (SealQ128_32768::Q, 0) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_32768_0>()?.into()
+                }
+                (SealQ128_32768::Q, 1) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_32768_1>()?.into()
+                }
+                (SealQ128_32768::Q, 2) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_32768_2>()?.into()
+                }
+                (SealQ128_32768::Q, 3) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_32768_3>()?.into()
+                }
+                (SealQ128_32768::Q, 4) => {
+                    self.build_sdlp_pk_generic::<3, SealQ128_32768_4>()?.into()
+                }
+                _ => {
+                    return Err(
+                        BuilderError::UnsupportedParameters(Box::new(params.clone())).into(),
+                    )
+                }
+            };
 
             // Add the custom bounds, if any
             let bounds = pk.bounds_mut();
@@ -795,7 +1343,7 @@ mod linked {
                 &self.witness,
                 params,
                 ctx,
-            ))
+            )?)
         }
     }
 
@@ -917,7 +1465,9 @@ mod linked {
         /// Output a [`LinkedProof`] from the encryption statements and ZKP program and inputs added to
         /// this builder.
         fn build_linkedproof(&self) -> Result<crate::linked::LinkedProof> {
-            let sdlp = self.build_sdlp_pk()?;
+            check_linkable(self.runtime.params(), &self.statements)?;
+
+            let sdlp = self.build_sdlp_pk(0)?;
             let program = self.compiled_zkp_program.ok_or_else(|| {
                 BuilderError::user_error("Cannot build linked proof without a compiled ZKP program. Use the `.zkp_program()` method")
             })?;
@@ -940,10 +1490,57 @@ mod linked {
                 self.private_inputs.clone(),
                 self.public_inputs.clone(),
                 self.constant_inputs.clone(),
+                &self.context,
             )
         }
     }
 
+    /// The number of groups of the data modulus an SDLP over `params` proves separately. See
+    /// [`SdlpParams::groups`](crate::SdlpParams::groups).
+    fn sdlp_groups(params: &Params, statements: &[BfvProofStatement<'_>]) -> Result<usize> {
+        let groups = match &params.coeff_modulus[..] {
+            SealQ128_1024::Q | SealQ128_2048::Q | SealQ128_4096::Q | SealQ128_8192::Q => 1,
+            SealQ128_16384::Q => SealQ128_16384::GROUPS.len(),
+            SealQ128_32768::Q => SealQ128_32768::GROUPS.len(),
+            _ => return Err(BuilderError::UnsupportedParameters(Box::new(params.clone())).into()),
+        };
+
+        // The decryption noise is bounded by delta, which is wider than any one group.
+        if groups > 1
+            && statements.iter().any(|s| {
+                matches!(
+                    s,
+                    BfvProofStatement::Decryption { .. } | BfvProofStatement::ZeroDecryption { .. }
+                )
+            })
+        {
+            return Err(BuilderError::user_error(format!(
+                "Decryption statements aren't supported for lattice dimension {}",
+                params.lattice_dimension
+            )));
+        }
+
+        Ok(groups)
+    }
+
+    /// Fail if a linked proof over `params` would split its SDLP into groups of the data modulus.
+    ///
+    /// The group proofs of a split SDLP are tied together by a commitment to all of `S`, while the
+    /// R1CS bulletproof shares a commitment to just the linked messages. Linking a split SDLP
+    /// would need a further argument that these commitments agree on the linked messages, so it
+    /// isn't supported.
+    fn check_linkable(params: &Params, statements: &[BfvProofStatement<'_>]) -> Result<()> {
+        let groups = sdlp_groups(params, statements)?;
+
+        if groups != 1 {
+            return Err(BuilderError::user_error(format!(
+                "Linked proofs aren't supported for coefficient moduli whose SDLP is split into {groups} groups"
+            )));
+        }
+
+        Ok(())
+    }
+
     fn mk_bounds<P: LinkWithZkp>(params: &Params) -> Bounds {
         let mut bounds = vec![params.plain_modulus.ceil_log2(); P::DEGREE_BOUND];
         bounds.resize(params.lattice_dimension as usize, 0);
@@ -987,6 +1584,7 @@ mod linked {
         message_bounds: Vec<Option<Bounds>>,
         sdlp: Option<Sdlp>,
         custom_bounds: Vec<((usize, usize), Bounds)>,
+        context: Vec<u8>,
 
         // linked proof fields
         compiled_zkp_program: Option<&'z CompiledZkpProgram>,
@@ -1013,13 +1611,16 @@ mod linked {
 
         /// Verify the SDLP.
         pub fn verify(&mut self) -> Result<()> {
-            let vk = self.build_sdlp_vk()?;
-            let sdlp = self.sdlp.as_mut().ok_or_else(|| {
+            let groups = sdlp_groups(self.runtime.params(), &self.statements)?;
+            let sdlp = self.sdlp.as_ref().ok_or_else(|| {
                 BuilderError::user_error(
                     "You must supply a proof to the verification builder before calling `verify`. Use the `.proof()` method.",
                 )
             })?;
-            sdlp.verify(&vk)
+            sdlp.verify(
+                (0..groups).map(|group| self.build_sdlp_vk(group)),
+                &self.context,
+            )
         }
     }
 
@@ -1041,7 +1642,9 @@ mod linked {
 
         /// Verify the linked proof.
         pub fn verify(&mut self) -> Result<()> {
-            let vk = self.build_sdlp_vk()?;
+            check_linkable(self.runtime.params(), &self.statements)?;
+
+            let vk = self.build_sdlp_vk(0)?;
             let linkedproof = self.linkedproof.as_mut().ok_or_else(|| {
                 BuilderError::user_error(
                     "You must supply a proof to the verification builder before calling `verify`. Use the `.proof()` method.",
@@ -1055,6 +1658,7 @@ mod linked {
                 program,
                 self.public_inputs.drain(0..).collect(),
                 self.constant_inputs.drain(0..).collect(),
+                &self.context,
             )
         }
     }
@@ -1066,6 +1670,7 @@ mod linked {
                 statements: vec![],
                 message_bounds: vec![],
                 custom_bounds: vec![],
+                context: vec![],
                 compiled_zkp_program: None,
                 public_inputs: vec![],
                 constant_inputs: vec![],
@@ -1227,6 +1832,119 @@ mod linked {
             Ok(())
         }
 
+        /// Add verifier knowledge for [`LogProofBuilder::decrypt_revealing`], where `plaintext` is
+        /// the plaintext the prover shared.
+        pub fn decrypt_revealing(
+            &mut self,
+            ciphertext: &Ciphertext,
+            plaintext: &Plaintext,
+            public_key: &'k PublicKey,
+        ) -> Result<()> {
+            self.statements.push(zero_decryption_statement(
+                self.runtime,
+                ciphertext,
+                plaintext,
+                public_key,
+            )?);
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::prove_keys`]. See its remarks: this
+        /// doesn't authenticate the relinearization or Galois keys in `public_key`.
+        pub fn keys(&mut self, public_key: &'k PublicKey) -> Result<()> {
+            self.statements.push(key_generation_statement(public_key)?);
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::add`].
+        pub fn add(
+            &mut self,
+            lhs: &Ciphertext,
+            rhs: &Ciphertext,
+            result: &Ciphertext,
+        ) -> Result<()> {
+            push_addition(&mut self.statements, lhs, rhs, result)?;
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::multiply`].
+        pub fn multiply(
+            &mut self,
+            lhs: &Ciphertext,
+            rhs: &Ciphertext,
+            public_key: &'k PublicKey,
+            result: &Ciphertext,
+        ) -> Result<()> {
+            push_relinearized_product(
+                self.runtime,
+                &mut self.statements,
+                lhs,
+                rhs,
+                public_key,
+                result,
+            )?;
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::add_plain`].
+        pub fn add_plain(&mut self, ciphertext: &Ciphertext, result: &Ciphertext) -> Result<()> {
+            self.evaluate_plain_internal(PlainOp::Add, ciphertext, result, None)?;
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::add_plain_returning_msg`].
+        pub fn add_plain_returning_msg(
+            &mut self,
+            ciphertext: &Ciphertext,
+            result: &Ciphertext,
+        ) -> Result<MessageRef> {
+            self.evaluate_plain_internal(PlainOp::Add, ciphertext, result, None)
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::multiply_plain`].
+        pub fn multiply_plain(
+            &mut self,
+            ciphertext: &Ciphertext,
+            result: &Ciphertext,
+        ) -> Result<()> {
+            self.evaluate_plain_internal(PlainOp::Multiply, ciphertext, result, None)?;
+            Ok(())
+        }
+
+        /// Add verifier knowledge for [`LogProofBuilder::multiply_plain_returning_msg`].
+        pub fn multiply_plain_returning_msg(
+            &mut self,
+            ciphertext: &Ciphertext,
+            result: &Ciphertext,
+        ) -> Result<MessageRef> {
+            self.evaluate_plain_internal(PlainOp::Multiply, ciphertext, result, None)
+        }
+
+        fn evaluate_plain_internal(
+            &mut self,
+            op: PlainOp,
+            ciphertext: &Ciphertext,
+            result: &Ciphertext,
+            bounds: Option<Bounds>,
+        ) -> Result<MessageRef> {
+            check_same_type(ciphertext, result)?;
+
+            let start_idx = self.message_bounds.len();
+            let ciphertexts = ciphertext.inner_as_seal_ciphertext()?;
+            let results = result.inner_as_seal_ciphertext()?;
+            for (ct, result) in ciphertexts.iter().zip(results) {
+                let message_id = self.message_bounds.len();
+                self.statements.push(op.statement(message_id, ct, result));
+                self.message_bounds.push(bounds.clone());
+            }
+            let end_idx = self.message_bounds.len();
+
+            Ok(MessageRef {
+                id: start_idx,
+                len: end_idx - start_idx,
+            })
+        }
+
         /// Customize bounds for a given entry in the secret `S`. Note these custom bounds must
         /// match those provided during the proof generation.
         ///
@@ -1238,18 +1956,52 @@ mod linked {
             self
         }
 
-        /// Build the [`SdlpVerifierKnowledge`] for the statements added to this builder.
-        pub(crate) fn build_sdlp_vk(&self) -> Result<SdlpVerifierKnowledge> {
+        /// Supply the domain separation data the prover bound the proof to with
+        /// [`LogProofBuilder::context`]. Verification fails if it doesn't match.
+        pub fn context(&mut self, context: &[u8]) -> &mut Self {
+            self.context = context.to_owned();
+            self
+        }
+
+        /// Build the [`SdlpVerifierKnowledge`] for the statements added to this builder, for the
+        /// given group of the data modulus. See [`sdlp_groups`].
+        pub(crate) fn build_sdlp_vk(&self, group: usize) -> Result<SdlpVerifierKnowledge> {
             let params = self.runtime.params();
-            let mut vk: SdlpVerifierKnowledge = match &params.coeff_modulus[..] {
-                SealQ128_1024::Q => Ok(self.build_sdlp_vk_generic::<1, SealQ128_1024>()?.into()),
-                SealQ128_2048::Q => Ok(self.build_sdlp_vk_generic::<1, SealQ128_2048>()?.into()),
-                SealQ128_4096::Q => Ok(self.build_sdlp_vk_generic::<2, SealQ128_4096>()?.into()),
-                SealQ128_8192::Q => Ok(self.build_sdlp_vk_generic::<3, SealQ128_8192>()?.into()),
-                _ => Err(BuilderError::UnsupportedParameters(Box::new(
-                    params.clone(),
-                ))),
-            }?;
+            let mut vk: SdlpVerifierKnowledge = match (&params.coeff_modulus[..], group) {
+                (SealQ128_1024::Q, 0) => self.build_sdlp_vk_generic::<1, SealQ128_1024>()?.into(),
+                (SealQ128_2048::Q, 0) => self.build_sdlp_vk_generic::<1, SealQ128_2048>()?.into(),
+                (SealQ128_4096::Q, 0) => self.build_sdlp_vk_generic::<2, SealQ128_4096>()?.into(),
+                (SealQ128_8192::Q, 0) => self.build_sdlp_vk_generic::<3, SealQ128_8192>()?.into(),
+                (SealQ128_16384::Q, 0) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_16384_0>()?.into()
+                }
+                (SealQ128_16384::Q, 1) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_16384_1>()?.into()
+                }
+                (SealQ128_16384::Q, 2) => {
+                    self.build_sdlp_vk_generic::<2, SealQ128_16384_2>()?.into()
+                }
+                (SealQ128_32768::Q, 0) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_32768_0>()?.into()
+                }
+                (SealQ128_32768::Q, 1) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_32768_1>()?.into()
+                }
+                (SealQ128_32768::Q, 2) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_32768_2>()?.into()
+                }
+                (SealQ128_32768::Q, 3) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_32768_3>()?.into()
+                }
+                (SealQ128_32768::Q, 4) => {
+                    self.build_sdlp_vk_generic::<3, SealQ128_32768_4>()?.into()
+                }
+                _ => {
+                    return Err(
+                        BuilderError::UnsupportedParameters(Box::new(params.clone())).into(),
+                    )
+                }
+            };
 
             // Add the custom bounds, if any
             let bounds = vk.bounds_mut();
@@ -13,6 +13,10 @@ mod metadata;
 mod run;
 mod runtime;
 mod serialization;
+#[cfg(feature = "linkedproofs")]
+mod tfhe_keys;
+#[cfg(feature = "linkedproofs")]
+mod tfhe_linked;
 
 use std::sync::Arc;
 
@@ -29,6 +33,10 @@ pub use metadata::*;
 pub use run::*;
 pub use runtime::*;
 pub use serialization::WithContext;
+#[cfg(feature = "linkedproofs")]
+pub use tfhe_keys::*;
+#[cfg(feature = "linkedproofs")]
+pub use tfhe_linked::*;
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, Eq)]
 /**
@@ -3,48 +3,242 @@
 use std::{ops::Range, time::Instant};
 
 use bitvec::vec::BitVec;
-use bulletproofs::{BulletproofGens, GeneratorsChain, PedersenGens};
+use bulletproofs::{r1cs::R1CSProof, BulletproofGens, GeneratorsChain, PedersenGens};
 use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
 use log::trace;
 use logproof::{
     linear_algebra::Matrix,
     math::rand256,
-    rings::{ZqSeal128_1024, ZqSeal128_2048, ZqSeal128_4096, ZqSeal128_8192},
-    Bounds, InnerProductVerifierKnowledge, LogProof, LogProofGenerators, LogProofProverKnowledge,
-    LogProofVerifierKnowledge, ProofError,
+    rings::{
+        SealQ128_16384, SealQ128_32768, ZqSeal128_1024, ZqSeal128_16384_0, ZqSeal128_16384_1,
+        ZqSeal128_16384_2, ZqSeal128_2048, ZqSeal128_32768_0, ZqSeal128_32768_1, ZqSeal128_32768_2,
+        ZqSeal128_32768_3, ZqSeal128_32768_4, ZqSeal128_4096, ZqSeal128_8192,
+    },
+    wire, Bounds, InnerProductVerifierKnowledge, LogProof, LogProofGenerators,
+    LogProofProverKnowledge, LogProofVerifierKnowledge, ProofError,
 };
 use merlin::Transcript;
 use paste::paste;
 use seq_macro::seq;
 use serde::{Deserialize, Serialize};
 use sunscreen_compiler_common::Type;
+use sunscreen_tfhe::zkp::{Zq32, Zq64};
 use sunscreen_zkp_backend::{
     bulletproofs::{
         BulletproofProverParameters, BulletproofVerifierParameters, BulletproofsBackend,
+        BulletproofsR1CSProof,
     },
     BigInt, Proof, ZkpBackend,
 };
 
-use crate::{CompiledZkpProgram, Result, TypeNameInstance, ZkpProgramInput, ZkpRuntime};
+use crate::{
+    BuilderError, CompiledZkpProgram, Plaintext, Result, TypeNameInstance, ZkpProgramInput,
+    ZkpRuntime,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 /// SDLP proof
+///
+/// # Remarks
+/// The serde representation of this type isn't stable across releases. In particular, it gained
+/// the [`SdlpParams`] the proof was created under, so proofs serialized with serde by earlier
+/// releases won't deserialize. Use [`Sdlp::to_bytes`] for a versioned encoding.
 pub struct Sdlp {
-    proof: LogProof,
+    params: SdlpParams,
+    /// One proof per RNS group of the data modulus. See [`SdlpParams::groups`].
+    proofs: Vec<LogProof>,
     g: Vec<RistrettoPoint>,
     h: Vec<RistrettoPoint>,
     u: RistrettoPoint,
 }
 
+/// Identifies the ciphertext modulus an [`Sdlp`] was created under.
+///
+/// The identifier is part of an encoded proof, so verifiers can reject proofs made for different
+/// parameters before attempting to verify them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SdlpParams {
+    /// The 128-bit secure coefficient modulus for lattice dimension 1024.
+    SealQ128_1024,
+
+    /// The 128-bit secure coefficient modulus for lattice dimension 2048.
+    SealQ128_2048,
+
+    /// The 128-bit secure coefficient modulus for lattice dimension 4096.
+    SealQ128_4096,
+
+    /// The 128-bit secure coefficient modulus for lattice dimension 8192.
+    SealQ128_8192,
+
+    /// The 128-bit secure coefficient modulus for lattice dimension 16384.
+    SealQ128_16384,
+
+    /// The 128-bit secure coefficient modulus for lattice dimension 32768.
+    SealQ128_32768,
+
+    /// The 32-bit discrete torus of TFHE ciphertexts.
+    Torus32,
+
+    /// The 64-bit discrete torus of TFHE ciphertexts.
+    Torus64,
+}
+
+impl SdlpParams {
+    /// The identifier written in the header of an encoded proof.
+    ///
+    /// These values are part of the wire format and must never be reassigned.
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::SealQ128_1024 => 1,
+            Self::SealQ128_2048 => 2,
+            Self::SealQ128_4096 => 3,
+            Self::SealQ128_8192 => 4,
+            Self::SealQ128_16384 => 5,
+            Self::SealQ128_32768 => 6,
+            Self::Torus32 => 7,
+            Self::Torus64 => 8,
+        }
+    }
+
+    /// The number of proofs an [`Sdlp`] under these parameters contains.
+    ///
+    /// When the data modulus is too wide for the Ristretto scalar field, it's partitioned into
+    /// groups of primes and the statements are proven modulo each group in turn. The proofs
+    /// commit to the same witness `S`, so together they prove the statements modulo the whole
+    /// data modulus.
+    pub fn groups(&self) -> usize {
+        match self {
+            Self::SealQ128_1024
+            | Self::SealQ128_2048
+            | Self::SealQ128_4096
+            | Self::SealQ128_8192
+            | Self::Torus32
+            | Self::Torus64 => 1,
+            Self::SealQ128_16384 => SealQ128_16384::GROUPS.len(),
+            Self::SealQ128_32768 => SealQ128_32768::GROUPS.len(),
+        }
+    }
+}
+
+impl TryFrom<u8> for SdlpParams {
+    type Error = LinkedProofError;
+
+    fn try_from(id: u8) -> std::result::Result<Self, Self::Error> {
+        Ok(match id {
+            1 => Self::SealQ128_1024,
+            2 => Self::SealQ128_2048,
+            3 => Self::SealQ128_4096,
+            4 => Self::SealQ128_8192,
+            5 => Self::SealQ128_16384,
+            6 => Self::SealQ128_32768,
+            7 => Self::Torus32,
+            8 => Self::Torus64,
+            _ => return Err(LinkedProofError::UnknownParams(id)),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+/// A proof that a ciphertext decrypts to a published value, created with
+/// [`FheRuntime::decrypt_with_proof`](crate::FheRuntime::decrypt_with_proof) and checked with
+/// [`FheRuntime::verify_decryption`](crate::FheRuntime::verify_decryption).
+pub struct DecryptionProof {
+    /// The decrypted plaintext. A fresh encoding of the value may differ from the decrypted
+    /// polynomial (e.g. for fractional values after some arithmetic), so the proof carries the
+    /// polynomial itself.
+    pub(crate) plaintext: Plaintext,
+    pub(crate) sdlp: Sdlp,
+}
+
+impl DecryptionProof {
+    /// The decrypted plaintext this proof is about.
+    pub fn plaintext(&self) -> &Plaintext {
+        &self.plaintext
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 /// R1CS BP proof and associated information for verification
 struct BP {
     proof: Proof,
-    verifier_parameters: BulletproofVerifierParameters,
+    /// The number of Bulletproof generators the proof was created with.
+    gens_capacity: usize,
+    /// The SDLP generators the Bulletproof shares, which replace its first generators.
+    shared_generators: Vec<RistrettoPoint>,
+}
+
+impl BP {
+    /// The largest generator capacity a decoded proof may claim. The verifier derives this many
+    /// generators, so an unbounded capacity would let a tiny proof demand unbounded work.
+    const MAX_GENS_CAPACITY: usize = 1 << 20;
+
+    fn new_verifier_parameters(
+        gens_capacity: usize,
+        shared_generators: &[RistrettoPoint],
+    ) -> BulletproofVerifierParameters {
+        BulletproofVerifierParameters::new(
+            PedersenGens::default(),
+            new_single_party_with_shared_generators(gens_capacity, shared_generators),
+            shared_generators.len(),
+        )
+    }
+
+    fn verifier_parameters(&self) -> BulletproofVerifierParameters {
+        Self::new_verifier_parameters(self.gens_capacity, &self.shared_generators)
+    }
+
+    /// Appends the length-prefixed [`R1CSProof::to_bytes`] encoding of the proof, then the
+    /// generator capacity (u32 BE), then the shared generators as a length-prefixed list of
+    /// compressed points.
+    fn write_to(&self, bytes: &mut Vec<u8>) -> Result<()> {
+        let proof = match &self.proof {
+            Proof::Bulletproofs(proof) => proof.0.to_bytes(),
+            Proof::Custom { .. } => return Err(ProofError::MalformedProof.into()),
+        };
+
+        wire::write_len(bytes, proof.len());
+        bytes.extend_from_slice(&proof);
+        wire::write_len(bytes, self.gens_capacity);
+        wire::write_points(bytes, &self.shared_generators);
+
+        Ok(())
+    }
+
+    fn read_from(bytes: &[u8]) -> Result<(Self, &[u8])> {
+        let (len, rest) = wire::read_len(bytes)?;
+
+        if rest.len() < len {
+            return Err(ProofError::MalformedProof.into());
+        }
+
+        let (proof, rest) = rest.split_at(len);
+        let proof = R1CSProof::from_bytes(proof).map_err(|_| ProofError::MalformedProof)?;
+
+        let (gens_capacity, rest) = wire::read_len(rest)?;
+        let (shared_generators, rest) = wire::read_points(rest)?;
+
+        // Each shared generator replaces one of the `2 * gens_capacity` generators.
+        if gens_capacity > Self::MAX_GENS_CAPACITY || shared_generators.len() > 2 * gens_capacity {
+            return Err(ProofError::MalformedProof.into());
+        }
+
+        Ok((
+            Self {
+                proof: Proof::Bulletproofs(Box::new(BulletproofsR1CSProof(proof))),
+                gens_capacity,
+                shared_generators,
+            },
+            rest,
+        ))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 /// A linked proof between an SDLP and R1CS BP
+///
+/// # Remarks
+/// As with [`Sdlp`], the serde representation of this type isn't stable across releases. Use
+/// [`LinkedProof::to_bytes`] for a versioned encoding.
 pub struct LinkedProof {
     sdlp: Sdlp,
     bp: BP,
@@ -57,9 +251,81 @@ pub enum LinkedProofError {
     #[error("SDLP proof error: {0:?}")]
     LogproofProofError(#[from] ProofError),
 
-    /// The commitment to the shared inputs in the SDLP and R1CS BP do not match.
+    /// The commitments to the shared inputs in the SDLP and R1CS BP, or in the proofs for each
+    /// group of a split SDLP, do not match.
     #[error("Shared commitments are not equal")]
     SharedCommitmentsNotEqual,
+
+    /// The encoded proof doesn't begin with the expected magic bytes.
+    #[error("Not an encoded Sunscreen proof")]
+    BadMagic,
+
+    /// The encoded proof has a format version this release can't read.
+    #[error("Unsupported proof format version {0}")]
+    UnsupportedVersion(u16),
+
+    /// The encoded proof is a different kind of proof than requested.
+    #[error("Unexpected proof kind {0}")]
+    UnexpectedProofKind(u8),
+
+    /// The encoded proof names parameters this release doesn't know.
+    #[error("Unknown parameters identifier {0}")]
+    UnknownParams(u8),
+
+    /// The proof was created under different parameters than the verifier's.
+    #[error("Proof parameters {0:?} don't match the verifier's parameters {1:?}")]
+    ParamsMismatch(SdlpParams, SdlpParams),
+
+    /// The decryption proof is for a different value than the claimed one.
+    #[error("The decryption proof is for a different value")]
+    DecryptedValueMismatch,
+}
+
+/// The magic bytes beginning every encoded [`Sdlp`] and [`LinkedProof`].
+const WIRE_MAGIC: [u8; 4] = *b"SNLP";
+
+/// The current version of the proof wire format. Bump this whenever the encoding changes, and
+/// keep decoding older versions.
+const WIRE_VERSION: u16 = 1;
+
+/// The proof kind byte of an encoded [`Sdlp`].
+const WIRE_KIND_SDLP: u8 = 0;
+
+/// The proof kind byte of an encoded [`LinkedProof`].
+const WIRE_KIND_LINKED: u8 = 1;
+
+/// Writes the header shared by all encoded proofs:
+///
+/// | magic (4 bytes) | version (u16 BE) | kind (u8) | params id (u8) |
+fn write_header(bytes: &mut Vec<u8>, kind: u8, params: SdlpParams) {
+    bytes.extend_from_slice(&WIRE_MAGIC);
+    bytes.extend_from_slice(&WIRE_VERSION.to_be_bytes());
+    bytes.push(kind);
+    bytes.push(params.id());
+}
+
+/// Reads the header written by [`write_header`], checking the magic, version and kind.
+fn read_header(bytes: &[u8], kind: u8) -> Result<(SdlpParams, &[u8])> {
+    let (magic, rest) = wire::read_array::<4>(bytes)?;
+
+    if magic != WIRE_MAGIC {
+        return Err(LinkedProofError::BadMagic.into());
+    }
+
+    let (version, rest) = wire::read_array::<2>(rest)?;
+    let version = u16::from_be_bytes(version);
+
+    if version != WIRE_VERSION {
+        return Err(LinkedProofError::UnsupportedVersion(version).into());
+    }
+
+    let ([actual_kind, params], rest) = wire::read_array::<2>(rest)?;
+
+    if actual_kind != kind {
+        return Err(LinkedProofError::UnexpectedProofKind(actual_kind).into());
+    }
+
+    Ok((SdlpParams::try_from(params)?, rest))
 }
 
 /// Generate a set of generators for a single party where some of the
@@ -93,6 +359,19 @@ fn new_single_party_with_shared_generators(
     BulletproofGens::new_from_generators(vec![g], vec![h]).unwrap()
 }
 
+/// Create a transcript with the given label, binding the user's domain separation data if there
+/// is any.
+fn new_transcript(label: &'static [u8], context: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(label);
+
+    // Omitting empty contexts keeps proofs without one unchanged.
+    if !context.is_empty() {
+        transcript.append_message(b"context", context);
+    }
+
+    transcript
+}
+
 impl LinkedProof {
     const TRANSCRIPT_LABEL: &'static [u8] = b"linked-sdlp-and-r1cs-bp";
     /// This function creates a linked proof.
@@ -110,6 +389,8 @@ impl LinkedProof {
     /// * `private_inputs`: The private inputs to the ZKP program, not including the shared values
     /// * `public_inputs`: The public inputs to the ZKP program
     /// * `constant_inputs`: The constant inputs to the ZKP program
+    /// * `context`: Domain separation data the verifier must also supply
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn create<I>(
         prover_knowledge: &SdlpProverKnowledge,
         shared_indices: &[(usize, usize)],
@@ -118,13 +399,14 @@ impl LinkedProof {
         private_inputs: Vec<I>,
         public_inputs: Vec<I>,
         constant_inputs: Vec<I>,
+        context: &[u8],
     ) -> Result<Self>
     where
         I: Into<ZkpProgramInput> + Clone,
     {
         type Rt = ZkpRuntime<BulletproofsBackend>;
         let backend = BulletproofsBackend::new();
-        let mut transcript = Transcript::new(Self::TRANSCRIPT_LABEL);
+        let mut transcript = new_transcript(Self::TRANSCRIPT_LABEL, context);
 
         let vk = prover_knowledge.vk();
         let shared_inputs_binary = shared_indices
@@ -159,7 +441,8 @@ impl LinkedProof {
         );
 
         let sdlp_package = Sdlp {
-            proof: sdlp_proof,
+            params: vk.params(),
+            proofs: vec![sdlp_proof],
             g: gens.g,
             h: gens.h,
             u,
@@ -194,17 +477,10 @@ impl LinkedProof {
             &constant_inputs_bigint,
         )?;
 
-        let bulletproof_gens =
-            new_single_party_with_shared_generators(2 * constraint_count, &shared_gens.clone());
+        let gens_capacity = 2 * constraint_count;
+        let verifier_parameters = BP::new_verifier_parameters(gens_capacity, &shared_gens);
 
-        let verifier_parameters = BulletproofVerifierParameters::new(
-            PedersenGens::default(),
-            bulletproof_gens.clone(),
-            shared_gens.len(),
-        );
-
-        let prover_parameters =
-            BulletproofProverParameters::new(verifier_parameters.clone(), half_rho);
+        let prover_parameters = BulletproofProverParameters::new(verifier_parameters, half_rho);
 
         trace!("Starting BP JIT (prover)...");
         let now = Instant::now();
@@ -226,7 +502,8 @@ impl LinkedProof {
 
         let bp_package = BP {
             proof: bp_proof,
-            verifier_parameters,
+            gens_capacity,
+            shared_generators: shared_gens,
         };
 
         Ok(Self {
@@ -244,6 +521,7 @@ impl LinkedProof {
     /// * `program`: The compiled ZKP program to verify
     /// * `public_inputs`: The public inputs to the ZKP program
     /// * `constant_inputs`: The constant inputs to the ZKP program
+    /// * `context`: The domain separation data the prover supplied
     ///
     pub(crate) fn verify<I>(
         &self,
@@ -251,17 +529,26 @@ impl LinkedProof {
         program: &CompiledZkpProgram,
         public_inputs: Vec<I>,
         constant_inputs: Vec<I>,
+        context: &[u8],
     ) -> Result<()>
     where
         I: Into<ZkpProgramInput> + Clone,
     {
         let runtime = ZkpRuntime::new(BulletproofsBackend::new())?;
 
-        let mut transcript = Transcript::new(Self::TRANSCRIPT_LABEL);
+        let mut transcript = new_transcript(Self::TRANSCRIPT_LABEL, context);
+
+        self.sdlp.check_params(&sdlp_vk.params())?;
+
+        // Linked proofs share their witness with a single R1CS bulletproof, so they never split
+        // the data modulus into groups.
+        if self.sdlp.proofs.len() != 1 {
+            return Err(ProofError::MalformedProof.into());
+        }
 
         sdlp_vk
             .verify(
-                &self.sdlp.proof,
+                &self.sdlp.proofs[0],
                 &mut transcript,
                 &self.sdlp.g,
                 &self.sdlp.h,
@@ -274,7 +561,7 @@ impl LinkedProof {
             &self.bp.proof,
             public_inputs,
             constant_inputs,
-            &self.bp.verifier_parameters,
+            &self.bp.verifier_parameters(),
             &mut transcript,
         )?;
 
@@ -282,13 +569,48 @@ impl LinkedProof {
             let b = b.clone();
             let a_i1_shared = (*b).0.A_I1_shared();
 
-            if a_i1_shared != self.sdlp.proof.w_shared.compress() {
+            if a_i1_shared != self.sdlp.proofs[0].w_shared.compress() {
                 return Err(LinkedProofError::SharedCommitmentsNotEqual.into());
             }
         }
 
         Ok(())
     }
+
+    /// The parameters this proof was created under.
+    pub fn params(&self) -> SdlpParams {
+        self.sdlp.params
+    }
+
+    /// Serialize this proof into the versioned wire format.
+    ///
+    /// The encoding is the common proof header, then the SDLP body (see [`Sdlp::to_bytes`]), then
+    /// the length (u32 BE) and canonical encoding of the R1CS bulletproof (see
+    /// [`R1CSProof::to_bytes`]), then the number of Bulletproof generators (u32 BE) and the
+    /// generators shared with the SDLP as a length-prefixed list of compressed points. The
+    /// verifier derives the remaining generators itself.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+
+        write_header(&mut bytes, WIRE_KIND_LINKED, self.sdlp.params);
+        self.sdlp.write_body(&mut bytes);
+        self.bp.write_to(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Deserialize a proof written by [`LinkedProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (params, rest) = read_header(bytes, WIRE_KIND_LINKED)?;
+        let (sdlp, rest) = Sdlp::read_body(params, rest)?;
+        let (bp, rest) = BP::read_from(rest)?;
+
+        if !rest.is_empty() {
+            return Err(ProofError::MalformedProof.into());
+        }
+
+        Ok(Self { sdlp, bp })
+    }
 }
 
 impl Sdlp {
@@ -298,14 +620,80 @@ impl Sdlp {
     /// properties of those underlying values.
     ///
     /// The [builder methods](`crate::SdlpBuilder`) offer an easier way to construct this proof.
-    pub(crate) fn create(prover_knowledge: &SdlpProverKnowledge) -> Result<Self> {
-        let mut transcript = Transcript::new(Self::TRANSCRIPT_LABEL);
-        let gen = LogProofGenerators::new(prover_knowledge.vk().l() as usize);
+    ///
+    /// `prover_knowledge` yields the knowledge for each group of the data modulus (see
+    /// [`SdlpParams::groups`]). Groups are consumed and proven one at a time, so only one group's
+    /// knowledge need be in memory at once.
+    pub(crate) fn create<I>(prover_knowledge: I, context: &[u8]) -> Result<Self>
+    where
+        I: IntoIterator<Item = Result<SdlpProverKnowledge>>,
+    {
+        let mut transcript = new_transcript(Self::TRANSCRIPT_LABEL, context);
         let u = InnerProductVerifierKnowledge::get_u();
-        let proof = prover_knowledge.create_logproof(&mut transcript, &gen.g, &gen.h, &u);
+
+        // Every group proof commits to all of `S` with the same blinding, so their shared
+        // commitments are equal exactly when they prove statements about the same witness.
+        let shared_rho = Scalar::from_bits(rand256());
+
+        let mut params = None;
+        let mut proofs = vec![];
+        let mut gen: Option<LogProofGenerators> = None;
+
+        for pk in prover_knowledge {
+            let pk = pk?;
+            let group_params = pk.params();
+
+            if matches!(params, Some(p) if p != group_params) {
+                return Err(BuilderError::user_error(
+                    "Every group of an SDLP must be proven under the same parameters",
+                ));
+            }
+
+            let group_gen = LogProofGenerators::new(pk.l() as usize);
+            let proof = if group_params.groups() == 1 {
+                pk.create_logproof(&mut transcript, &group_gen.g, &group_gen.h, &u)
+            } else {
+                pk.create_split_logproof(
+                    &mut transcript,
+                    &group_gen.g,
+                    &group_gen.h,
+                    &u,
+                    &shared_rho,
+                )
+            };
+
+            trace!(
+                "Proved SDLP group {}/{}",
+                proofs.len() + 1,
+                group_params.groups()
+            );
+
+            proofs.push(proof);
+            params = Some(group_params);
+
+            // The generators are prefix-stable, so the longest set serves every group.
+            if gen.as_ref().map_or(true, |g| g.g.len() < group_gen.g.len()) {
+                gen = Some(group_gen);
+            }
+        }
+
+        let (Some(params), Some(gen)) = (params, gen) else {
+            return Err(BuilderError::user_error(
+                "An SDLP must prove at least one group",
+            ));
+        };
+
+        if proofs.len() != params.groups() {
+            return Err(BuilderError::user_error(format!(
+                "An SDLP under {params:?} must prove {} groups, but {} were given",
+                params.groups(),
+                proofs.len()
+            )));
+        }
 
         Ok(Self {
-            proof,
+            params,
+            proofs,
             g: gen.g,
             h: gen.h,
             u,
@@ -313,27 +701,169 @@ impl Sdlp {
     }
 
     /// This function verifies a solo SDLP.
-    pub(crate) fn verify(&self, vk: &SdlpVerifierKnowledge) -> Result<()> {
-        let mut transcript = Transcript::new(Self::TRANSCRIPT_LABEL);
+    ///
+    /// `verifier_knowledge` yields the verifier knowledge for each group of the data modulus, in
+    /// the same order the prover used.
+    pub(crate) fn verify<I>(&self, verifier_knowledge: I, context: &[u8]) -> Result<()>
+    where
+        I: IntoIterator<Item = Result<SdlpVerifierKnowledge>>,
+    {
+        let mut transcript = new_transcript(Self::TRANSCRIPT_LABEL, context);
+        let mut num_vks = 0;
+
+        for vk in verifier_knowledge {
+            let vk = vk?;
+            self.check_params(&vk.params())?;
+
+            let proof = self.proofs.get(num_vks).ok_or(ProofError::MalformedProof)?;
+            let l = vk.l() as usize;
+
+            if self.g.len() < l || self.h.len() < l {
+                return Err(ProofError::MalformedProof.into());
+            }
+
+            vk.verify(proof, &mut transcript, &self.g[..l], &self.h[..l], &self.u)?;
+            num_vks += 1;
+        }
+
+        if num_vks != self.proofs.len() {
+            return Err(ProofError::MalformedProof.into());
+        }
+
+        // Each group proof is only sound modulo its group. They compose into a proof modulo the
+        // whole data modulus only if they're about the same witness.
+        if self
+            .proofs
+            .windows(2)
+            .any(|p| p[0].w_shared != p[1].w_shared)
+        {
+            return Err(LinkedProofError::SharedCommitmentsNotEqual.into());
+        }
+
+        Ok(())
+    }
+
+    /// Fail if this proof was created under different parameters than `params`, or doesn't have
+    /// one proof per group.
+    fn check_params(&self, params: &SdlpParams) -> Result<()> {
+        if self.params != *params {
+            return Err(LinkedProofError::ParamsMismatch(self.params, *params).into());
+        }
 
-        vk.verify(&self.proof, &mut transcript, &self.g, &self.h, &self.u)?;
+        if self.proofs.len() != params.groups() {
+            return Err(ProofError::MalformedProof.into());
+        }
 
         Ok(())
     }
+
+    /// The parameters this proof was created under.
+    pub fn params(&self) -> SdlpParams {
+        self.params
+    }
+
+    /// Serialize this proof into the versioned wire format.
+    ///
+    /// The encoding is a header of magic bytes, the format version (u16 BE), the proof kind and
+    /// the [`SdlpParams::id`], followed by the body: the canonical encoding of the first
+    /// [`LogProof`], then the generators `g` and `h` as length-prefixed lists of compressed
+    /// points, then the compressed point `u`, then the remaining [`SdlpParams::groups`] - 1
+    /// proofs. See [`logproof::wire`] for how each element is encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        write_header(&mut bytes, WIRE_KIND_SDLP, self.params);
+        self.write_body(&mut bytes);
+
+        bytes
+    }
+
+    /// Deserialize a proof written by [`Sdlp::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (params, rest) = read_header(bytes, WIRE_KIND_SDLP)?;
+        let (sdlp, rest) = Self::read_body(params, rest)?;
+
+        if !rest.is_empty() {
+            return Err(ProofError::MalformedProof.into());
+        }
+
+        Ok(sdlp)
+    }
+
+    fn write_body(&self, bytes: &mut Vec<u8>) {
+        // The group proofs after the first go last, so proofs with a single group keep the same
+        // encoding.
+        let (first, rest) = self.proofs.split_first().unwrap();
+
+        first.write_to(bytes);
+        wire::write_points(bytes, &self.g);
+        wire::write_points(bytes, &self.h);
+        wire::write_point(bytes, &self.u);
+
+        for proof in rest {
+            proof.write_to(bytes);
+        }
+    }
+
+    fn read_body(params: SdlpParams, bytes: &[u8]) -> Result<(Self, &[u8])> {
+        let (proof, rest) = LogProof::read_from(bytes)?;
+        let (g, rest) = wire::read_points(rest)?;
+        let (h, rest) = wire::read_points(rest)?;
+        let (u, mut rest) = wire::read_point(rest)?;
+
+        let mut proofs = vec![proof];
+
+        for _ in 1..params.groups() {
+            let (proof, r) = LogProof::read_from(rest)?;
+            proofs.push(proof);
+            rest = r;
+        }
+
+        Ok((
+            Self {
+                params,
+                proofs,
+                g,
+                h,
+                u,
+            },
+            rest,
+        ))
+    }
 }
 
-/// The prover knowledge of an [`Sdlp`].
-pub(crate) struct SdlpProverKnowledge(pub(crate) SdlpProverKnowledgeInternal);
+// The knowledge types are nominally `pub` so the public TFHE builders can bound on conversions
+// into them without tripping `private_bounds`, but this module keeps them out of the public API.
+mod knowledge {
+    use serde::{Deserialize, Serialize};
 
-/// The verifier knowledge of an [`Sdlp`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct SdlpVerifierKnowledge(pub(crate) SdlpVerifierKnowledgeInternal);
+    use super::{SdlpProverKnowledgeInternal, SdlpVerifierKnowledgeInternal};
+
+    /// The prover knowledge of an [`Sdlp`](super::Sdlp).
+    pub struct SdlpProverKnowledge(pub(crate) SdlpProverKnowledgeInternal);
+
+    /// The verifier knowledge of an [`Sdlp`](super::Sdlp).
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SdlpVerifierKnowledge(pub(crate) SdlpVerifierKnowledgeInternal);
+}
+
+pub(crate) use knowledge::{SdlpProverKnowledge, SdlpVerifierKnowledge};
 
 pub(crate) enum SdlpProverKnowledgeInternal {
     LP1(LogProofProverKnowledge<ZqSeal128_1024>),
     LP2(LogProofProverKnowledge<ZqSeal128_2048>),
     LP3(LogProofProverKnowledge<ZqSeal128_4096>),
     LP4(LogProofProverKnowledge<ZqSeal128_8192>),
+    LP5(LogProofProverKnowledge<ZqSeal128_16384_0>),
+    LP6(LogProofProverKnowledge<ZqSeal128_16384_1>),
+    LP7(LogProofProverKnowledge<ZqSeal128_16384_2>),
+    LP8(LogProofProverKnowledge<ZqSeal128_32768_0>),
+    LP9(LogProofProverKnowledge<ZqSeal128_32768_1>),
+    LP10(LogProofProverKnowledge<ZqSeal128_32768_2>),
+    LP11(LogProofProverKnowledge<ZqSeal128_32768_3>),
+    LP12(LogProofProverKnowledge<ZqSeal128_32768_4>),
+    LP13(LogProofProverKnowledge<Zq32>),
+    LP14(LogProofProverKnowledge<Zq64>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -342,6 +872,16 @@ pub(crate) enum SdlpVerifierKnowledgeInternal {
     LP2(LogProofVerifierKnowledge<ZqSeal128_2048>),
     LP3(LogProofVerifierKnowledge<ZqSeal128_4096>),
     LP4(LogProofVerifierKnowledge<ZqSeal128_8192>),
+    LP5(LogProofVerifierKnowledge<ZqSeal128_16384_0>),
+    LP6(LogProofVerifierKnowledge<ZqSeal128_16384_1>),
+    LP7(LogProofVerifierKnowledge<ZqSeal128_16384_2>),
+    LP8(LogProofVerifierKnowledge<ZqSeal128_32768_0>),
+    LP9(LogProofVerifierKnowledge<ZqSeal128_32768_1>),
+    LP10(LogProofVerifierKnowledge<ZqSeal128_32768_2>),
+    LP11(LogProofVerifierKnowledge<ZqSeal128_32768_3>),
+    LP12(LogProofVerifierKnowledge<ZqSeal128_32768_4>),
+    LP13(LogProofVerifierKnowledge<Zq32>),
+    LP14(LogProofVerifierKnowledge<Zq64>),
 }
 
 macro_rules! impl_from {
@@ -365,10 +905,20 @@ impl_from!(ZqSeal128_1024, LP1);
 impl_from!(ZqSeal128_2048, LP2);
 impl_from!(ZqSeal128_4096, LP3);
 impl_from!(ZqSeal128_8192, LP4);
+impl_from!(ZqSeal128_16384_0, LP5);
+impl_from!(ZqSeal128_16384_1, LP6);
+impl_from!(ZqSeal128_16384_2, LP7);
+impl_from!(ZqSeal128_32768_0, LP8);
+impl_from!(ZqSeal128_32768_1, LP9);
+impl_from!(ZqSeal128_32768_2, LP10);
+impl_from!(ZqSeal128_32768_3, LP11);
+impl_from!(ZqSeal128_32768_4, LP12);
+impl_from!(Zq32, LP13);
+impl_from!(Zq64, LP14);
 
 macro_rules! seq_zq {
     ($block:tt) => (
-        seq!(N in 1..=4 {
+        seq!(N in 1..=14 {
             #[allow(unused_braces)]
             $block
         })
@@ -376,6 +926,39 @@ macro_rules! seq_zq {
 }
 
 impl SdlpProverKnowledge {
+    /// Get the parameters this knowledge is defined over.
+    pub(crate) fn params(&self) -> SdlpParams {
+        match &self.0 {
+            SdlpProverKnowledgeInternal::LP1(_) => SdlpParams::SealQ128_1024,
+            SdlpProverKnowledgeInternal::LP2(_) => SdlpParams::SealQ128_2048,
+            SdlpProverKnowledgeInternal::LP3(_) => SdlpParams::SealQ128_4096,
+            SdlpProverKnowledgeInternal::LP4(_) => SdlpParams::SealQ128_8192,
+            SdlpProverKnowledgeInternal::LP5(_)
+            | SdlpProverKnowledgeInternal::LP6(_)
+            | SdlpProverKnowledgeInternal::LP7(_) => SdlpParams::SealQ128_16384,
+            SdlpProverKnowledgeInternal::LP8(_)
+            | SdlpProverKnowledgeInternal::LP9(_)
+            | SdlpProverKnowledgeInternal::LP10(_)
+            | SdlpProverKnowledgeInternal::LP11(_)
+            | SdlpProverKnowledgeInternal::LP12(_) => SdlpParams::SealQ128_32768,
+            SdlpProverKnowledgeInternal::LP13(_) => SdlpParams::Torus32,
+            SdlpProverKnowledgeInternal::LP14(_) => SdlpParams::Torus64,
+        }
+    }
+
+    /// Get the length in bits of the binary expansion of the serialized secret * vectors.
+    ///
+    /// Delegate to [`LogProofVerifierKnowledge::l`].
+    pub(crate) fn l(&self) -> u32 {
+        seq_zq!({
+            match &self.0 {
+                #(
+                    SdlpProverKnowledgeInternal::LP~N(pk) => pk.vk.l(),
+                )*
+            }
+        })
+    }
+
     /// Get the binary expansion of a component of the witness matrix `S`.
     ///
     /// Delegation to [`LogProofProverKnowledge::s_binary_by_index`].
@@ -438,6 +1021,47 @@ impl SdlpProverKnowledge {
         })
     }
 
+    /// Create a `LogProof` for one group of a split SDLP, sharing all of `S`.
+    ///
+    /// The shared commitment is blinded by `shared_rho`, which must be the same for every group.
+    /// The rest of the commitment gets fresh blinding.
+    ///
+    /// Delegation to [`LogProof::create_with_shared_blinding`].
+    pub fn create_split_logproof(
+        &self,
+        transcript: &mut Transcript,
+        g: &[RistrettoPoint],
+        h: &[RistrettoPoint],
+        u: &RistrettoPoint,
+        shared_rho: &Scalar,
+    ) -> LogProof {
+        let unshared_rho = Scalar::from_bits(rand256());
+
+        seq_zq!({
+            match &self.0 {
+                #(
+                    SdlpProverKnowledgeInternal::LP~N(pk) => {
+                        let bounds = &pk.vk.bounds;
+                        let shared_indices = (0..bounds.rows)
+                            .flat_map(|i| (0..bounds.cols).map(move |j| (i, j)))
+                            .collect::<Vec<_>>();
+
+                        LogProof::create_with_shared_blinding(
+                            transcript,
+                            pk,
+                            g,
+                            h,
+                            u,
+                            shared_rho,
+                            &unshared_rho,
+                            &shared_indices,
+                        )
+                    }
+                )*
+            }
+        })
+    }
+
     /// Create a `LogProof` without sharing.
     ///
     /// Delegation to [`LogProof::create`].
@@ -459,6 +1083,26 @@ impl SdlpProverKnowledge {
 }
 
 impl SdlpVerifierKnowledge {
+    /// Get the parameters this knowledge is defined over.
+    pub fn params(&self) -> SdlpParams {
+        match &self.0 {
+            SdlpVerifierKnowledgeInternal::LP1(_) => SdlpParams::SealQ128_1024,
+            SdlpVerifierKnowledgeInternal::LP2(_) => SdlpParams::SealQ128_2048,
+            SdlpVerifierKnowledgeInternal::LP3(_) => SdlpParams::SealQ128_4096,
+            SdlpVerifierKnowledgeInternal::LP4(_) => SdlpParams::SealQ128_8192,
+            SdlpVerifierKnowledgeInternal::LP5(_)
+            | SdlpVerifierKnowledgeInternal::LP6(_)
+            | SdlpVerifierKnowledgeInternal::LP7(_) => SdlpParams::SealQ128_16384,
+            SdlpVerifierKnowledgeInternal::LP8(_)
+            | SdlpVerifierKnowledgeInternal::LP9(_)
+            | SdlpVerifierKnowledgeInternal::LP10(_)
+            | SdlpVerifierKnowledgeInternal::LP11(_)
+            | SdlpVerifierKnowledgeInternal::LP12(_) => SdlpParams::SealQ128_32768,
+            SdlpVerifierKnowledgeInternal::LP13(_) => SdlpParams::Torus32,
+            SdlpVerifierKnowledgeInternal::LP14(_) => SdlpParams::Torus64,
+        }
+    }
+
     /// Get the length in bits of the binary expansion of the serialized secret * vectors.
     ///
     /// Delegate to [`LogProofVerifierKnowledge::l`].
@@ -516,3 +1160,56 @@ impl SdlpVerifierKnowledge {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use logproof::{
+        linear_algebra::Matrix, math::make_poly, rings::ZqSeal128_1024, Bounds,
+        LogProofProverKnowledge,
+    };
+
+    use super::*;
+
+    /// An [`Sdlp`] encoded with version 1 of the wire format. Run `generate_sdlp_v1_fixture` to
+    /// recreate it.
+    const SDLP_V1_FIXTURE: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sdlp_v1.bin");
+
+    /// A small fixed statement `A·s = t mod x^8 + 1`, so the fixture can be checked without SEAL.
+    fn fixture_knowledge() -> LogProofProverKnowledge<ZqSeal128_1024> {
+        let a = Matrix::from([[
+            make_poly(&[1, 2, 3, 4, 5, 6, 7, 8]),
+            make_poly(&[9, 0, 0, 0, 0, 0, 0, 1]),
+        ]]);
+        let s = Matrix::from([
+            [make_poly(&[1, -1, 0, 1, 0, 0, 1, -1])],
+            [make_poly(&[0, 1, 1, -1, 0, -1, 0, 1])],
+        ]);
+        let f = make_poly(&[1, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let t = (&a * &s).map(|x| x.vartime_div_rem_restricted_rhs(&f).1);
+        let bounds = Matrix::from([[Bounds(vec![1; 8])], [Bounds(vec![1; 8])]]);
+
+        LogProofProverKnowledge::new(&a, &s, &t, &bounds, &f)
+    }
+
+    #[test]
+    #[ignore = "writes the checked in fixture; run it only when the wire format version changes"]
+    fn generate_sdlp_v1_fixture() {
+        let sdlp = Sdlp::create([Ok(fixture_knowledge().into())], &[]).unwrap();
+
+        std::fs::write(SDLP_V1_FIXTURE, sdlp.to_bytes()).unwrap();
+    }
+
+    #[test]
+    fn sdlp_v1_fixture_verifies() {
+        let fixture = std::fs::read(SDLP_V1_FIXTURE).unwrap();
+
+        // Proofs written by this release must decode and re-encode to the same bytes.
+        let sdlp = Sdlp::from_bytes(&fixture).unwrap();
+        assert_eq!(sdlp.params(), SdlpParams::SealQ128_1024);
+        assert_eq!(sdlp.to_bytes(), fixture);
+
+        let vk = fixture_knowledge().vk.into();
+        sdlp.verify([Ok(vk)], &[]).unwrap();
+    }
+}
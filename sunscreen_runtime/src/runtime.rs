@@ -24,7 +24,13 @@ use seal_fhe::{
 };
 
 pub use sunscreen_compiler_common::{Type, TypeName};
+use sunscreen_zkp_backend::find_unsatisfied_constraint;
+use sunscreen_zkp_backend::jit_prover_unchecked;
 use sunscreen_zkp_backend::BigInt;
+use sunscreen_zkp_backend::Commitment;
+use sunscreen_zkp_backend::CommitmentOpening;
+use sunscreen_zkp_backend::Error as ZkpError;
+use sunscreen_zkp_backend::FieldSpec;
 use sunscreen_zkp_backend::Proof;
 use sunscreen_zkp_backend::ZkpBackend;
 
@@ -685,6 +691,128 @@ where
         public_inputs: Vec<I>,
         constant_inputs: Vec<I>,
     ) -> Result<Proof>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.prove_with_commitments(program, private_inputs, public_inputs, constant_inputs, &[])
+    }
+
+    /**
+     * Prove the given `inputs` satisfy `program`, which has `#[committed]`
+     * arguments.
+     *
+     * # Remarks
+     * Committed arguments go in `private_inputs` at their position in the
+     * program's signature. `commitment_openings` holds an opening for each
+     * native field element of the committed arguments, in order. See
+     * [`Self::commitment_openings`] to create these.
+     *
+     * It's typically more convenient to use
+     * [`ProofBuilder::committed_input`], which does this for you.
+     */
+    pub fn prove_with_commitments<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+        commitment_openings: &[CommitmentOpening],
+    ) -> Result<Proof>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.prove_with_context(
+            program,
+            private_inputs,
+            public_inputs,
+            constant_inputs,
+            commitment_openings,
+            &[],
+        )
+    }
+
+    /**
+     * Prove the given `inputs` satisfy `program`, binding the proof to
+     * the given application-defined `context` (e.g. a session or chain
+     * ID). The proof only verifies under the same `context`, so it can't
+     * be replayed in another application.
+     *
+     * # Remarks
+     * See [`Self::prove_with_commitments`] for `commitment_openings`.
+     *
+     * It's typically more convenient to use [`ProofBuilder::context`].
+     */
+    pub fn prove_with_context<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+        commitment_openings: &[CommitmentOpening],
+        context: &[u8],
+    ) -> Result<Proof>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        let [private_inputs, public_inputs, constant_inputs] = Self::collect_and_validate_zkp_args(
+            [private_inputs, public_inputs, constant_inputs],
+            program,
+        )?;
+
+        self.prove_native(
+            program,
+            private_inputs,
+            public_inputs,
+            constant_inputs,
+            commitment_openings,
+            context,
+        )
+    }
+
+    /**
+     * Like [`Self::prove`], but when the inputs don't satisfy `program`,
+     * runs the program directly to find out why. The returned error is
+     * then a [`ZkpError::UnsatisfiedConstraint`] describing which
+     * constraint failed, the values on both sides, and the chain of
+     * operations that computed them.
+     *
+     * # Remarks
+     * Only use this while developing ZKP programs. Debugging doesn't run
+     * in constant time and its errors reveal private inputs.
+     */
+    pub fn debug_prove<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+    ) -> Result<Proof>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.debug_prove_with_context(
+            program,
+            private_inputs,
+            public_inputs,
+            constant_inputs,
+            &[],
+            &[],
+        )
+    }
+
+    /**
+     * Like [`Self::prove_with_context`], but reports unsatisfied
+     * constraints in the same way as [`Self::debug_prove`].
+     */
+    pub fn debug_prove_with_context<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+        commitment_openings: &[CommitmentOpening],
+        context: &[u8],
+    ) -> Result<Proof>
     where
         I: Into<ZkpProgramInput>,
     {
@@ -693,6 +821,50 @@ where
             program,
         )?;
 
+        let result = self.prove_native(
+            program,
+            private_inputs.clone(),
+            public_inputs.clone(),
+            constant_inputs.clone(),
+            commitment_openings,
+            context,
+        );
+
+        match result {
+            Err(Error::ZkpError(ZkpError::UnsatisfiableConstraint(_))) => {
+                let to_field = |x: &[BigInt]| {
+                    x.iter()
+                        .map(|x| <B::Field as FieldSpec>::BackendField::try_from(*x))
+                        .collect::<std::result::Result<Vec<_>, ZkpError>>()
+                };
+
+                let prog = jit_prover_unchecked::<B::Field>(
+                    &program.zkp_program_fn,
+                    &to_field(&private_inputs)?,
+                    &to_field(&public_inputs)?,
+                    &to_field(&constant_inputs)?,
+                )?;
+
+                let inputs = [public_inputs, private_inputs].concat();
+
+                match find_unsatisfied_constraint::<B::Field>(&prog, &inputs)? {
+                    Some(x) => Err(ZkpError::UnsatisfiedConstraint(Box::new(x)).into()),
+                    None => result,
+                }
+            }
+            result => result,
+        }
+    }
+
+    fn prove_native(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<BigInt>,
+        public_inputs: Vec<BigInt>,
+        constant_inputs: Vec<BigInt>,
+        commitment_openings: &[CommitmentOpening],
+        context: &[u8],
+    ) -> Result<Proof> {
         let backend = &self.zkp_backend;
 
         trace!("Starting JIT (prover)...");
@@ -712,7 +884,57 @@ where
 
         trace!("Starting backend prove...");
 
-        Ok(backend.prove(&prog, &inputs)?)
+        Ok(backend.prove_with_context(&prog, &inputs, commitment_openings, context)?)
+    }
+
+    /**
+     * Creates the openings for a `#[committed]` argument with the given
+     * value. `blindings` must contain a uniformly random field element for
+     * each of `input`'s native field elements.
+     */
+    pub fn commitment_openings(
+        &self,
+        input: impl Into<ZkpProgramInput>,
+        blindings: &[BigInt],
+    ) -> Result<Vec<CommitmentOpening>> {
+        let values = input.into().0.to_native_fields();
+
+        if values.len() != blindings.len() {
+            return Err(ZkpError::inputs_mismatch(&format!(
+                "Expected {} blinding factors, received {}",
+                values.len(),
+                blindings.len()
+            ))
+            .into());
+        }
+
+        Ok(values
+            .into_iter()
+            .zip(blindings)
+            .map(|(value, blinding)| CommitmentOpening::new(value, *blinding))
+            .collect())
+    }
+
+    /**
+     * Commits to the value of a `#[committed]` argument, returning a
+     * commitment for each of `input`'s native field elements. The verifier
+     * passes these to [`VerificationBuilder::committed_input`].
+     *
+     * # Remarks
+     * `blindings` must contain a uniformly random field element for each
+     * of `input`'s native field elements. Reusing the same blindings for
+     * the same value yields the same commitments, which lets multiple
+     * proofs refer to the same hidden value.
+     */
+    pub fn commit(
+        &self,
+        input: impl Into<ZkpProgramInput>,
+        blindings: &[BigInt],
+    ) -> Result<Vec<Commitment>> {
+        self.commitment_openings(input, blindings)?
+            .iter()
+            .map(|x| Ok(self.zkp_backend.commit(x)?))
+            .collect()
     }
 
     /// Create a proof builder.
@@ -747,6 +969,57 @@ where
         public_inputs: Vec<I>,
         constant_inputs: Vec<I>,
     ) -> Result<()>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.verify_with_commitments(program, proof, public_inputs, constant_inputs, &[])
+    }
+
+    /**
+     * Verify that the given `proof` satisfies the given `program`, which
+     * has `#[committed]` arguments.
+     *
+     * # Remarks
+     * `commitments` holds a commitment for each native field element of
+     * the committed arguments, in order. See [`Self::commit`].
+     */
+    pub fn verify_with_commitments<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        proof: &Proof,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+        commitments: &[Commitment],
+    ) -> Result<()>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.verify_with_context(
+            program,
+            proof,
+            public_inputs,
+            constant_inputs,
+            commitments,
+            &[],
+        )
+    }
+
+    /**
+     * Verify that the given `proof` satisfies the given `program` and was
+     * created with [`Self::prove_with_context`] using the same `context`.
+     *
+     * # Remarks
+     * See [`Self::verify_with_commitments`] for `commitments`.
+     */
+    pub fn verify_with_context<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        proof: &Proof,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+        commitments: &[Commitment],
+        context: &[u8],
+    ) -> Result<()>
     where
         I: Into<ZkpProgramInput>,
     {
@@ -765,7 +1038,7 @@ where
         trace!("Verifier JIT time {}s", now.elapsed().as_secs_f64());
         trace!("Starting backend verify...");
 
-        Ok(backend.verify(&prog, proof)?)
+        Ok(backend.verify_with_context(&prog, proof, commitments, context)?)
     }
 
     /**
@@ -973,6 +1246,59 @@ impl FheRuntime {
     pub fn sdlp_verification_builder<'k>(&self) -> crate::SdlpVerificationBuilder<'_, 'k> {
         crate::SdlpVerificationBuilder::new(self)
     }
+
+    /// Decrypt the given ciphertext into the type `P`, along with a proof that third parties can
+    /// check with [`FheRuntime::verify_decryption`] without the private key.
+    ///
+    /// # Remarks
+    /// The proof shows that the ciphertext decrypts to the value under the secret key of
+    /// `public_key`, so the verifier needs `public_key` and the prover must supply it here too.
+    /// The ciphertext must be relinearized.
+    pub fn decrypt_with_proof<P>(
+        &self,
+        ciphertext: &Ciphertext,
+        private_key: &PrivateKey,
+        public_key: &PublicKey,
+    ) -> Result<(P, crate::DecryptionProof)>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        let mut builder = self.sdlp_builder();
+        let (value, plaintext) =
+            builder.decrypt_revealing::<P>(ciphertext, private_key, public_key)?;
+        let sdlp = builder.build()?;
+
+        Ok((value, crate::DecryptionProof { plaintext, sdlp }))
+    }
+
+    /// Verify a proof from [`FheRuntime::decrypt_with_proof`] that `ciphertext` decrypts to
+    /// `value` under the secret key of `public_key`.
+    pub fn verify_decryption<P>(
+        &self,
+        ciphertext: &Ciphertext,
+        value: &P,
+        public_key: &PublicKey,
+        proof: &crate::DecryptionProof,
+    ) -> Result<()>
+    where
+        P: TryFromPlaintext + TypeName + PartialEq,
+    {
+        let expected_type = P::type_name();
+        if proof.plaintext.data_type != expected_type {
+            return Err(Error::type_mismatch(
+                &expected_type,
+                &proof.plaintext.data_type,
+            ));
+        }
+
+        if P::try_from_plaintext(&proof.plaintext, self.params())? != *value {
+            return Err(crate::LinkedProofError::DecryptedValueMismatch.into());
+        }
+
+        let mut builder = self.sdlp_verification_builder();
+        builder.decrypt_revealing(ciphertext, &proof.plaintext, public_key)?;
+        builder.proof(proof.sdlp.clone()).verify()
+    }
 }
 
 /**
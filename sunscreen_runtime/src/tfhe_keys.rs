@@ -0,0 +1,460 @@
+//! Builders for [`Sdlp`]s that TFHE evaluation keys are well formed.
+//!
+//! The proof shows each bootstrap key and keyswitch key encrypts the claimed secret key under
+//! binary secret keys. Keys are registered with the builder and referred to by handle, so
+//! statements sharing a handle prove they use the same key. A keyswitch key may switch from a
+//! GLWE secret key reinterpreted as an LWE key, as when keyswitching after a bootstrap.
+
+use logproof::{LogProofProverKnowledge, LogProofVerifierKnowledge};
+use sunscreen_tfhe::{
+    entities::{BootstrapKey, GlweSecretKey, LweKeyswitchKey, LweSecretKey},
+    zkp::{
+        generate_glwe_sdlp_prover_knowledge, generate_glwe_sdlp_verifier_knowledge,
+        glwe_sdlp_key_indices, GlweKeyIndices, GlweProofStatement, GlweWitness, TorusZq,
+    },
+    GlweDef, LweDef, PlaintextBits, RadixDecomposition, TorusOps,
+};
+
+use crate::{BuilderError, Result, Sdlp, SdlpProverKnowledge, SdlpVerifierKnowledge};
+
+// Key statements don't encrypt messages, so the plaintext bits don't affect the relation.
+const PLAINTEXT_BITS: PlaintextBits = PlaintextBits(1);
+
+/// A GLWE secret key registered with a [`TfheKeyProofBuilder`] or
+/// [`TfheKeyProofVerificationBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TfheGlweKeyId {
+    id: usize,
+}
+
+/// An LWE secret key registered with a [`TfheKeyProofBuilder`] or
+/// [`TfheKeyProofVerificationBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TfheLweKeyId {
+    id: usize,
+}
+
+/// A statement about an evaluation key, as known to both the prover and the verifier.
+enum Statement<'k, S: TorusOps> {
+    BootstrapKey {
+        lwe_key: TfheLweKeyId,
+        glwe_key: TfheGlweKeyId,
+        bootstrap_key: &'k BootstrapKey<S>,
+        lwe: LweDef,
+        radix: RadixDecomposition,
+    },
+    KeyswitchKey {
+        from: KeyswitchSource,
+        to_lwe_key: TfheLweKeyId,
+        keyswitch_key: &'k LweKeyswitchKey<S>,
+        from_lwe: LweDef,
+        to_lwe: LweDef,
+        radix: RadixDecomposition,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum KeyswitchSource {
+    Lwe(TfheLweKeyId),
+    Glwe(TfheGlweKeyId),
+}
+
+/// The statements with the LWE keys extracted from GLWE keys given IDs after the registered
+/// LWE keys.
+struct Statements<'k, S: TorusOps> {
+    glwe: GlweDef,
+    statements: Vec<Statement<'k, S>>,
+    num_lwe_keys: usize,
+}
+
+impl<'k, S: TorusOps + TorusZq> Statements<'k, S> {
+    fn new(glwe: &GlweDef) -> Self {
+        Self {
+            glwe: *glwe,
+            statements: vec![],
+            num_lwe_keys: 0,
+        }
+    }
+
+    fn extracted_keys(&self) -> Vec<TfheGlweKeyId> {
+        let mut keys = vec![];
+
+        for s in &self.statements {
+            if let Statement::KeyswitchKey {
+                from: KeyswitchSource::Glwe(key),
+                ..
+            } = s
+            {
+                if !keys.contains(key) {
+                    keys.push(*key);
+                }
+            }
+        }
+
+        keys
+    }
+
+    fn proof_statements(&self) -> Result<Vec<GlweProofStatement<'_, S>>> {
+        if self.statements.is_empty() {
+            return Err(BuilderError::user_error(
+                "Cannot build a key proof without any keys.",
+            ));
+        }
+
+        let extracted_keys = self.extracted_keys();
+
+        let statements = self
+            .statements
+            .iter()
+            .map(|s| match s {
+                Statement::BootstrapKey {
+                    lwe_key,
+                    glwe_key,
+                    bootstrap_key,
+                    lwe,
+                    radix,
+                } => GlweProofStatement::BootstrapKey {
+                    lwe_secret_key_id: lwe_key.id,
+                    glwe_secret_key_id: glwe_key.id,
+                    bootstrap_key,
+                    lwe,
+                    radix,
+                },
+                Statement::KeyswitchKey {
+                    from,
+                    to_lwe_key,
+                    keyswitch_key,
+                    from_lwe,
+                    to_lwe,
+                    radix,
+                } => {
+                    let (from_lwe_secret_key_id, from_glwe_secret_key_id) = match from {
+                        KeyswitchSource::Lwe(key) => (key.id, None),
+                        KeyswitchSource::Glwe(key) => (
+                            self.num_lwe_keys
+                                + extracted_keys.iter().position(|k| k == key).unwrap(),
+                            Some(key.id),
+                        ),
+                    };
+
+                    GlweProofStatement::LweKeyswitchKey {
+                        from_lwe_secret_key_id,
+                        from_glwe_secret_key_id,
+                        to_lwe_secret_key_id: to_lwe_key.id,
+                        keyswitch_key,
+                        from_lwe,
+                        to_lwe,
+                        radix,
+                    }
+                }
+            })
+            .collect();
+
+        Ok(statements)
+    }
+}
+
+/// A builder for an [`Sdlp`] that TFHE bootstrap and keyswitch keys are well formed.
+///
+/// `S` is the torus the keys are defined over, either `u32` or `u64`.
+pub struct TfheKeyProofBuilder<'k, S: TorusOps> {
+    statements: Statements<'k, S>,
+    glwe_keys: Vec<&'k GlweSecretKey<S>>,
+    lwe_keys: Vec<&'k LweSecretKey<S>>,
+    context: Vec<u8>,
+}
+
+impl<'k, S> TfheKeyProofBuilder<'k, S>
+where
+    S: TorusOps + TorusZq,
+    LogProofProverKnowledge<S::Zq>: Into<SdlpProverKnowledge>,
+{
+    /// Create a new [`TfheKeyProofBuilder`] for keys under the GLWE parameters `glwe`.
+    pub fn new(glwe: &GlweDef) -> Self {
+        Self {
+            statements: Statements::new(glwe),
+            glwe_keys: vec![],
+            lwe_keys: vec![],
+            context: vec![],
+        }
+    }
+
+    /// Register a GLWE secret key the proof's keys are built from.
+    pub fn glwe_secret_key(&mut self, key: &'k GlweSecretKey<S>) -> TfheGlweKeyId {
+        self.glwe_keys.push(key);
+
+        TfheGlweKeyId {
+            id: self.glwe_keys.len() - 1,
+        }
+    }
+
+    /// Register an LWE secret key the proof's keys are built from.
+    pub fn lwe_secret_key(&mut self, key: &'k LweSecretKey<S>) -> TfheLweKeyId {
+        self.lwe_keys.push(key);
+        self.statements.num_lwe_keys += 1;
+
+        TfheLweKeyId {
+            id: self.lwe_keys.len() - 1,
+        }
+    }
+
+    /// Add the statement that `bootstrap_key` encrypts `lwe_key` under `glwe_key`.
+    pub fn bootstrap_key(
+        &mut self,
+        bootstrap_key: &'k BootstrapKey<S>,
+        lwe_key: TfheLweKeyId,
+        glwe_key: TfheGlweKeyId,
+        lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        self.statements.statements.push(Statement::BootstrapKey {
+            lwe_key,
+            glwe_key,
+            bootstrap_key,
+            lwe: *lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Add the statement that `keyswitch_key` switches from `from_lwe_key` to `to_lwe_key`.
+    pub fn keyswitch_key(
+        &mut self,
+        keyswitch_key: &'k LweKeyswitchKey<S>,
+        from_lwe_key: TfheLweKeyId,
+        to_lwe_key: TfheLweKeyId,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        self.statements.statements.push(Statement::KeyswitchKey {
+            from: KeyswitchSource::Lwe(from_lwe_key),
+            to_lwe_key,
+            keyswitch_key,
+            from_lwe: *from_lwe,
+            to_lwe: *to_lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Add the statement that `keyswitch_key` switches from `from_glwe_key`, reinterpreted as
+    /// an LWE key, to `to_lwe_key`.
+    pub fn keyswitch_key_from_glwe(
+        &mut self,
+        keyswitch_key: &'k LweKeyswitchKey<S>,
+        from_glwe_key: TfheGlweKeyId,
+        to_lwe_key: TfheLweKeyId,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        let from_lwe = self.statements.glwe.as_lwe_def();
+
+        self.statements.statements.push(Statement::KeyswitchKey {
+            from: KeyswitchSource::Glwe(from_glwe_key),
+            to_lwe_key,
+            keyswitch_key,
+            from_lwe,
+            to_lwe: *to_lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Bind the proof to application data, such as a session identifier or the statement's
+    /// purpose. The verifier must supply the same data to
+    /// [`TfheKeyProofVerificationBuilder::context`].
+    pub fn context(&mut self, context: &[u8]) -> &mut Self {
+        self.context = context.to_owned();
+        self
+    }
+
+    /// The rows of the proof's witness holding each registered secret key, indexed by the
+    /// handles' registration order. Keys extracted from a GLWE key follow the registered LWE
+    /// keys.
+    pub fn key_indices(&self) -> Result<GlweKeyIndices> {
+        let statements = self.statements.proof_statements()?;
+
+        glwe_sdlp_key_indices(&statements, &self.statements.glwe)
+            .map_err(|e| BuilderError::user_error(e.to_string()))
+    }
+
+    /// Build the [`Sdlp`].
+    pub fn build(&self) -> Result<Sdlp> {
+        let statements = self.statements.proof_statements()?;
+
+        let extracted_keys = self
+            .statements
+            .extracted_keys()
+            .iter()
+            .map(|k| {
+                self.glwe_keys
+                    .get(k.id)
+                    .map(|key| key.to_lwe_secret_key().to_owned())
+                    .ok_or_else(|| BuilderError::user_error("Unknown GLWE secret key handle"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let lwe_keys = self
+            .lwe_keys
+            .iter()
+            .copied()
+            .chain(extracted_keys.iter())
+            .collect::<Vec<_>>();
+
+        let pk = generate_glwe_sdlp_prover_knowledge(
+            &statements,
+            &GlweWitness {
+                glwe_secret_keys: &self.glwe_keys,
+                lwe_secret_keys: &lwe_keys,
+                messages: &[],
+                public_randomness: &[],
+            },
+            &self.statements.glwe,
+            PLAINTEXT_BITS,
+        )
+        .map_err(|e| BuilderError::user_error(e.to_string()))?;
+
+        Sdlp::create(std::iter::once(Ok(pk.into())), &self.context)
+    }
+}
+
+/// A builder for verifying an [`Sdlp`] created by a [`TfheKeyProofBuilder`].
+///
+/// Register the keys and add the statements in the same order as the prover.
+pub struct TfheKeyProofVerificationBuilder<'k, S: TorusOps> {
+    statements: Statements<'k, S>,
+    num_glwe_keys: usize,
+    context: Vec<u8>,
+    sdlp: Option<Sdlp>,
+}
+
+impl<'k, S> TfheKeyProofVerificationBuilder<'k, S>
+where
+    S: TorusOps + TorusZq,
+    LogProofVerifierKnowledge<S::Zq>: Into<SdlpVerifierKnowledge>,
+{
+    /// Create a new [`TfheKeyProofVerificationBuilder`] for keys under the GLWE parameters
+    /// `glwe`.
+    pub fn new(glwe: &GlweDef) -> Self {
+        Self {
+            statements: Statements::new(glwe),
+            num_glwe_keys: 0,
+            context: vec![],
+            sdlp: None,
+        }
+    }
+
+    /// Register the GLWE secret key for [`TfheKeyProofBuilder::glwe_secret_key`].
+    pub fn glwe_secret_key(&mut self) -> TfheGlweKeyId {
+        self.num_glwe_keys += 1;
+
+        TfheGlweKeyId {
+            id: self.num_glwe_keys - 1,
+        }
+    }
+
+    /// Register the LWE secret key for [`TfheKeyProofBuilder::lwe_secret_key`].
+    pub fn lwe_secret_key(&mut self) -> TfheLweKeyId {
+        self.statements.num_lwe_keys += 1;
+
+        TfheLweKeyId {
+            id: self.statements.num_lwe_keys - 1,
+        }
+    }
+
+    /// Add the statement for [`TfheKeyProofBuilder::bootstrap_key`].
+    pub fn bootstrap_key(
+        &mut self,
+        bootstrap_key: &'k BootstrapKey<S>,
+        lwe_key: TfheLweKeyId,
+        glwe_key: TfheGlweKeyId,
+        lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        self.statements.statements.push(Statement::BootstrapKey {
+            lwe_key,
+            glwe_key,
+            bootstrap_key,
+            lwe: *lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Add the statement for [`TfheKeyProofBuilder::keyswitch_key`].
+    pub fn keyswitch_key(
+        &mut self,
+        keyswitch_key: &'k LweKeyswitchKey<S>,
+        from_lwe_key: TfheLweKeyId,
+        to_lwe_key: TfheLweKeyId,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        self.statements.statements.push(Statement::KeyswitchKey {
+            from: KeyswitchSource::Lwe(from_lwe_key),
+            to_lwe_key,
+            keyswitch_key,
+            from_lwe: *from_lwe,
+            to_lwe: *to_lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Add the statement for [`TfheKeyProofBuilder::keyswitch_key_from_glwe`].
+    pub fn keyswitch_key_from_glwe(
+        &mut self,
+        keyswitch_key: &'k LweKeyswitchKey<S>,
+        from_glwe_key: TfheGlweKeyId,
+        to_lwe_key: TfheLweKeyId,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> &mut Self {
+        let from_lwe = self.statements.glwe.as_lwe_def();
+
+        self.statements.statements.push(Statement::KeyswitchKey {
+            from: KeyswitchSource::Glwe(from_glwe_key),
+            to_lwe_key,
+            keyswitch_key,
+            from_lwe,
+            to_lwe: *to_lwe,
+            radix: *radix,
+        });
+        self
+    }
+
+    /// Set the domain separation data the prover bound the proof to with
+    /// [`TfheKeyProofBuilder::context`].
+    pub fn context(&mut self, context: &[u8]) -> &mut Self {
+        self.context = context.to_owned();
+        self
+    }
+
+    /// Set the proof to verify.
+    pub fn proof(&mut self, proof: Sdlp) -> &mut Self {
+        self.sdlp = Some(proof);
+        self
+    }
+
+    /// Verify the proof.
+    pub fn verify(&mut self) -> Result<()> {
+        let statements = self.statements.proof_statements()?;
+
+        let vk = generate_glwe_sdlp_verifier_knowledge(
+            &statements,
+            &self.statements.glwe,
+            PLAINTEXT_BITS,
+        )
+        .map_err(|e| BuilderError::user_error(e.to_string()))?;
+
+        let sdlp = self.sdlp.as_ref().ok_or_else(|| {
+            BuilderError::user_error(
+                "You must supply a proof to the verification builder before calling `verify`. Use the `.proof()` method.",
+            )
+        })?;
+
+        sdlp.verify(std::iter::once(Ok(vk.into())), &self.context)
+    }
+}
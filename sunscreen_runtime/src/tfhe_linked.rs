@@ -0,0 +1,475 @@
+//! Builders for [`LinkedProof`]s about TFHE LWE encryptions.
+//!
+//! These mirror the [BFV linked proof builders](crate::LinkedProofBuilder): the SDLP proves that
+//! each [`LweCiphertext`] is a valid encryption of some message, and the R1CS bulletproof proves
+//! that the linked messages satisfy the constraints of a ZKP program.
+//!
+//! Each linked message is passed to the ZKP program as a private input holding the two's
+//! complement binary expansion of the message, which is `plaintext_bits + 1` bits wide (see
+//! `sunscreen::types::zkp::TfheMessage`). Linked
+//! messages are always the first private inputs to the program, in the order they were
+//! [linked](TfheLinkedProofBuilder::linked_input).
+
+use logproof::{LogProofProverKnowledge, LogProofVerifierKnowledge};
+use sunscreen_compiler_common::{Type, TypeName};
+use sunscreen_tfhe::{
+    entities::{LweCiphertext, LwePublicKey, LweSecretKey, TlwePublicEncRandomness},
+    zkp::{
+        generate_tfhe_sdlp_prover_knowledge, generate_tfhe_sdlp_verifier_knowledge, ProofStatement,
+        TorusZq, Witness,
+    },
+    LweDef, PlaintextBits, Torus, TorusOps,
+};
+
+use crate::{
+    BuilderError, CompiledZkpProgram, LinkedProof, Result, SdlpProverKnowledge,
+    SdlpVerifierKnowledge, ZkpProgramInput,
+};
+
+/// An encryption of a message under a TFHE [`LweSecretKey`] or [`LwePublicKey`], as known to
+/// both the prover and the verifier.
+///
+/// Each encryption proves a distinct message, so the message id of a statement is its index.
+enum Statement<'k, S: TorusOps> {
+    PrivateKeyEncryption {
+        ciphertext: LweCiphertext<S>,
+    },
+    PublicKeyEncryption {
+        ciphertext: LweCiphertext<S>,
+        public_key: &'k LwePublicKey<S>,
+    },
+}
+
+impl<'k, S: TorusOps + TorusZq> Statement<'k, S> {
+    fn as_proof_statement(&self, message_id: usize) -> ProofStatement<'_, 'k, S> {
+        match self {
+            Self::PrivateKeyEncryption { ciphertext } => ProofStatement::PrivateKeyEncryption {
+                message_id,
+                ciphertext,
+            },
+            Self::PublicKeyEncryption {
+                ciphertext,
+                public_key,
+            } => ProofStatement::PublicKeyEncryption {
+                message_id,
+                ciphertext,
+                public_key: *public_key,
+            },
+        }
+    }
+}
+
+/// The randomness behind a [`Statement`], known only to the prover.
+enum Randomness<'k, S: TorusOps> {
+    PrivateKeyEncryption {
+        randomness: Torus<S>,
+        private_key: &'k LweSecretKey<S>,
+    },
+    PublicKeyEncryption {
+        randomness: TlwePublicEncRandomness<S>,
+    },
+}
+
+impl<'k, S: TorusOps + TorusZq> Randomness<'k, S> {
+    fn as_witness(&self) -> Witness<'k, '_, S> {
+        match self {
+            Self::PrivateKeyEncryption {
+                randomness,
+                private_key,
+            } => Witness::PrivateKeyEncryption {
+                randomness: *randomness,
+                private_key: *private_key,
+            },
+            Self::PublicKeyEncryption { randomness } => Witness::PublicKeyEncryption { randomness },
+        }
+    }
+}
+
+fn proof_statements<'a, 'k, S: TorusOps + TorusZq>(
+    statements: &'a [Statement<'k, S>],
+) -> Vec<ProofStatement<'a, 'k, S>> {
+    statements
+        .iter()
+        .enumerate()
+        .map(|(id, s)| s.as_proof_statement(id))
+        .collect()
+}
+
+/// A ZKP program input type that holds a TFHE message [linked](TfheLinkedProofBuilder::linked_input)
+/// to the program, such as `sunscreen::types::zkp::TfheMessage`.
+pub trait LinkWithTfhe: TypeName {
+    /// The number of plaintext bits in the messages this type holds. It must match the builder's
+    /// plaintext bits, since the type decodes exactly this many bits of the linked message.
+    const PLAINTEXT_BITS: u32;
+}
+
+/// A message encrypted by a [`TfheLinkedProofBuilder`] that can be
+/// [linked to a ZKP program](TfheLinkedProofBuilder::linked_input).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TfheLinkedMessage {
+    id: usize,
+}
+
+/// A builder for a [`LinkedProof`] that TFHE [`LweCiphertext`]s encrypt messages satisfying the
+/// constraints of a ZKP program.
+///
+/// `S` is the torus the ciphertexts are defined over, either `u32` or `u64`.
+pub struct TfheLinkedProofBuilder<'k, 'z, S: TorusOps> {
+    lwe: LweDef,
+    plaintext_bits: PlaintextBits,
+
+    // log proof fields
+    statements: Vec<Statement<'k, S>>,
+    messages: Vec<Torus<S>>,
+    witness: Vec<Randomness<'k, S>>,
+    context: Vec<u8>,
+
+    // linked proof fields
+    compiled_zkp_program: Option<&'z CompiledZkpProgram>,
+    linked_inputs: Vec<(TfheLinkedMessage, Type)>,
+    private_inputs: Vec<ZkpProgramInput>,
+    public_inputs: Vec<ZkpProgramInput>,
+    constant_inputs: Vec<ZkpProgramInput>,
+}
+
+impl<'k, 'z, S> TfheLinkedProofBuilder<'k, 'z, S>
+where
+    S: TorusOps + TorusZq,
+    LogProofProverKnowledge<S::Zq>: Into<SdlpProverKnowledge>,
+{
+    /// Create a new [`TfheLinkedProofBuilder`] for encryptions under the LWE parameters `lwe`,
+    /// with messages of `plaintext_bits` bits.
+    pub fn new(lwe: &LweDef, plaintext_bits: PlaintextBits) -> Self {
+        Self {
+            lwe: *lwe,
+            plaintext_bits,
+            statements: vec![],
+            messages: vec![],
+            witness: vec![],
+            context: vec![],
+            compiled_zkp_program: None,
+            linked_inputs: vec![],
+            private_inputs: vec![],
+            public_inputs: vec![],
+            constant_inputs: vec![],
+        }
+    }
+
+    /// Encrypt `message` under `public_key`, adding the encryption statement to the proof.
+    pub fn encrypt(
+        &mut self,
+        message: S,
+        public_key: &'k LwePublicKey<S>,
+    ) -> Result<LweCiphertext<S>> {
+        self.encrypt_returning_link(message, public_key)
+            .map(|(ct, _)| ct)
+    }
+
+    /// Encrypt `message` under `private_key`, adding the encryption statement to the proof.
+    pub fn encrypt_symmetric(
+        &mut self,
+        message: S,
+        private_key: &'k LweSecretKey<S>,
+    ) -> Result<LweCiphertext<S>> {
+        self.encrypt_symmetric_returning_link(message, private_key)
+            .map(|(ct, _)| ct)
+    }
+
+    /// Encrypt `message` under `public_key`, adding the encryption statement to the proof and
+    /// returning the message to be [linked to a ZKP program](Self::linked_input).
+    pub fn encrypt_returning_link(
+        &mut self,
+        message: S,
+        public_key: &'k LwePublicKey<S>,
+    ) -> Result<(LweCiphertext<S>, TfheLinkedMessage)> {
+        self.check_message(message)?;
+
+        let (ciphertext, randomness) = public_key.encrypt(message, &self.lwe, self.plaintext_bits);
+
+        self.statements.push(Statement::PublicKeyEncryption {
+            ciphertext: ciphertext.clone(),
+            public_key,
+        });
+        self.witness
+            .push(Randomness::PublicKeyEncryption { randomness });
+
+        Ok((ciphertext, self.push_message(message)))
+    }
+
+    /// Encrypt `message` under `private_key`, adding the encryption statement to the proof and
+    /// returning the message to be [linked to a ZKP program](Self::linked_input).
+    pub fn encrypt_symmetric_returning_link(
+        &mut self,
+        message: S,
+        private_key: &'k LweSecretKey<S>,
+    ) -> Result<(LweCiphertext<S>, TfheLinkedMessage)> {
+        self.check_message(message)?;
+
+        let (ciphertext, randomness) = private_key.encrypt(message, &self.lwe, self.plaintext_bits);
+
+        self.statements.push(Statement::PrivateKeyEncryption {
+            ciphertext: ciphertext.clone(),
+        });
+        self.witness.push(Randomness::PrivateKeyEncryption {
+            randomness,
+            private_key,
+        });
+
+        Ok((ciphertext, self.push_message(message)))
+    }
+
+    fn check_message(&self, message: S) -> Result<()> {
+        if self.plaintext_bits.0 == 0 || self.plaintext_bits.0 >= S::BITS {
+            return Err(BuilderError::user_error(format!(
+                "Plaintext bits must be in [1, {}) for this torus",
+                S::BITS
+            )));
+        }
+
+        if message.to_u64() >> self.plaintext_bits.0 != 0 {
+            return Err(BuilderError::user_error(format!(
+                "The message doesn't fit in {} plaintext bits",
+                self.plaintext_bits.0
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn push_message(&mut self, message: S) -> TfheLinkedMessage {
+        self.messages.push(Torus::from(message));
+
+        TfheLinkedMessage {
+            id: self.messages.len() - 1,
+        }
+    }
+
+    /// Add the ZKP program to be linked with the encryptions.
+    ///
+    /// This method is required to call [`Self::build`].
+    pub fn zkp_program(&mut self, program: &'z CompiledZkpProgram) -> &mut Self {
+        self.compiled_zkp_program = Some(program);
+        self
+    }
+
+    /// Add a linked private input to the ZKP program, passed as the ZKP type `Z`.
+    ///
+    /// Linked inputs precede the other private inputs. This method assumes that you've created
+    /// the `message` argument with _this_ builder.
+    ///
+    /// Fails if `Z` holds messages of a different number of plaintext bits than this builder
+    /// encrypts.
+    pub fn linked_input<Z: LinkWithTfhe>(
+        &mut self,
+        message: TfheLinkedMessage,
+    ) -> Result<&mut Self> {
+        if Z::PLAINTEXT_BITS != self.plaintext_bits.0 {
+            return Err(BuilderError::user_error(format!(
+                "{} holds messages of {} plaintext bits, but this builder encrypts messages of {} plaintext bits",
+                Z::type_name().name,
+                Z::PLAINTEXT_BITS,
+                self.plaintext_bits.0
+            )));
+        }
+
+        self.linked_inputs.push((message, Z::type_name()));
+        Ok(self)
+    }
+
+    /// Add a private input to the ZKP program.
+    pub fn private_input(&mut self, input: impl Into<ZkpProgramInput>) -> &mut Self {
+        self.private_inputs.push(input.into());
+        self
+    }
+
+    /// Add a public input to the ZKP program.
+    pub fn public_input(&mut self, input: impl Into<ZkpProgramInput>) -> &mut Self {
+        self.public_inputs.push(input.into());
+        self
+    }
+
+    /// Add a constant input to the ZKP program.
+    pub fn constant_input(&mut self, input: impl Into<ZkpProgramInput>) -> &mut Self {
+        self.constant_inputs.push(input.into());
+        self
+    }
+
+    /// Bind the proof to application data, such as a session identifier or the statement's
+    /// purpose. The verifier must supply the same data to
+    /// [`TfheLinkedProofVerificationBuilder::context`], so the proof can't be replayed in another
+    /// application.
+    pub fn context(&mut self, context: &[u8]) -> &mut Self {
+        self.context = context.to_owned();
+        self
+    }
+
+    /// Build the [`LinkedProof`].
+    pub fn build(&self) -> Result<LinkedProof> {
+        if self.statements.is_empty() {
+            return Err(BuilderError::user_error(
+                "Cannot build a linked proof without any encryptions.",
+            ));
+        }
+
+        let program = self.compiled_zkp_program.ok_or_else(|| {
+            BuilderError::user_error("Cannot build linked proof without a compiled ZKP program. Use the `.zkp_program()` method")
+        })?;
+
+        let statements = proof_statements(&self.statements);
+        let witness = self
+            .witness
+            .iter()
+            .map(Randomness::as_witness)
+            .collect::<Vec<_>>();
+
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &statements,
+            &self.messages,
+            &witness,
+            &self.lwe,
+            self.plaintext_bits,
+        );
+
+        let linked_indices = self
+            .linked_inputs
+            .iter()
+            .map(|(m, _)| (m.id, 0))
+            .collect::<Vec<_>>();
+        let linked_types = self
+            .linked_inputs
+            .iter()
+            .map(|(_, t)| t.clone())
+            .collect::<Vec<_>>();
+
+        LinkedProof::create(
+            &pk.into(),
+            &linked_indices,
+            &linked_types,
+            program,
+            self.private_inputs.clone(),
+            self.public_inputs.clone(),
+            self.constant_inputs.clone(),
+            &self.context,
+        )
+    }
+}
+
+/// A builder for verifying a [`LinkedProof`] created by a [`TfheLinkedProofBuilder`].
+///
+/// Add the ciphertexts in the same order, and with the same kinds of keys, as the prover.
+pub struct TfheLinkedProofVerificationBuilder<'k, 'z, S: TorusOps> {
+    lwe: LweDef,
+    plaintext_bits: PlaintextBits,
+    statements: Vec<Statement<'k, S>>,
+    context: Vec<u8>,
+    compiled_zkp_program: Option<&'z CompiledZkpProgram>,
+    public_inputs: Vec<ZkpProgramInput>,
+    constant_inputs: Vec<ZkpProgramInput>,
+    linkedproof: Option<LinkedProof>,
+}
+
+impl<'k, 'z, S> TfheLinkedProofVerificationBuilder<'k, 'z, S>
+where
+    S: TorusOps + TorusZq,
+    LogProofVerifierKnowledge<S::Zq>: Into<SdlpVerifierKnowledge>,
+{
+    /// Create a new [`TfheLinkedProofVerificationBuilder`] for encryptions under the LWE
+    /// parameters `lwe`, with messages of `plaintext_bits` bits.
+    pub fn new(lwe: &LweDef, plaintext_bits: PlaintextBits) -> Self {
+        Self {
+            lwe: *lwe,
+            plaintext_bits,
+            statements: vec![],
+            context: vec![],
+            compiled_zkp_program: None,
+            public_inputs: vec![],
+            constant_inputs: vec![],
+            linkedproof: None,
+        }
+    }
+
+    /// Set the linked proof to verify.
+    pub fn proof(&mut self, proof: LinkedProof) -> &mut Self {
+        self.linkedproof = Some(proof);
+        self
+    }
+
+    /// Add the statement for [`TfheLinkedProofBuilder::encrypt`] or
+    /// [`TfheLinkedProofBuilder::encrypt_returning_link`].
+    pub fn encrypt(
+        &mut self,
+        ciphertext: &LweCiphertext<S>,
+        public_key: &'k LwePublicKey<S>,
+    ) -> &mut Self {
+        self.statements.push(Statement::PublicKeyEncryption {
+            ciphertext: ciphertext.clone(),
+            public_key,
+        });
+        self
+    }
+
+    /// Add the statement for [`TfheLinkedProofBuilder::encrypt_symmetric`] or
+    /// [`TfheLinkedProofBuilder::encrypt_symmetric_returning_link`].
+    pub fn encrypt_symmetric(&mut self, ciphertext: &LweCiphertext<S>) -> &mut Self {
+        self.statements.push(Statement::PrivateKeyEncryption {
+            ciphertext: ciphertext.clone(),
+        });
+        self
+    }
+
+    /// Add the ZKP program to verify.
+    ///
+    /// This method is required to call [`Self::verify`].
+    pub fn zkp_program(&mut self, program: &'z CompiledZkpProgram) -> &mut Self {
+        self.compiled_zkp_program = Some(program);
+        self
+    }
+
+    /// Add a public input to the ZKP program.
+    pub fn public_input(&mut self, input: impl Into<ZkpProgramInput>) -> &mut Self {
+        self.public_inputs.push(input.into());
+        self
+    }
+
+    /// Add a constant input to the ZKP program.
+    pub fn constant_input(&mut self, input: impl Into<ZkpProgramInput>) -> &mut Self {
+        self.constant_inputs.push(input.into());
+        self
+    }
+
+    /// Set the domain separation data the prover bound the proof to with
+    /// [`TfheLinkedProofBuilder::context`].
+    pub fn context(&mut self, context: &[u8]) -> &mut Self {
+        self.context = context.to_owned();
+        self
+    }
+
+    /// Verify the linked proof.
+    pub fn verify(&mut self) -> Result<()> {
+        if self.statements.is_empty() {
+            return Err(BuilderError::user_error(
+                "Cannot verify a linked proof without any encryptions.",
+            ));
+        }
+
+        let statements = proof_statements(&self.statements);
+        let vk = generate_tfhe_sdlp_verifier_knowledge(&statements, &self.lwe, self.plaintext_bits);
+
+        let linkedproof = self.linkedproof.as_ref().ok_or_else(|| {
+            BuilderError::user_error(
+                "You must supply a proof to the verification builder before calling `verify`. Use the `.proof()` method.",
+            )
+        })?;
+        let program = self.compiled_zkp_program.ok_or_else(|| {
+            BuilderError::user_error("Cannot verify linked proof without a compiled ZKP program. Use the `.zkp_program()` method")
+        })?;
+
+        linkedproof.verify(
+            &vk.into(),
+            program,
+            self.public_inputs.drain(0..).collect(),
+            self.constant_inputs.drain(0..).collect(),
+            &self.context,
+        )
+    }
+}
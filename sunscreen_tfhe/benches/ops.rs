@@ -392,6 +392,52 @@ fn public_functional_keyswitching(c: &mut Criterion) {
     });
 }
 
+fn lwe_packing(c: &mut Criterion) {
+    let radix = RadixDecomposition {
+        count: RadixCount(8),
+        radix_log: RadixLog(4),
+    };
+
+    let glwe = high_level::keygen::generate_binary_glwe_sk(&GLWE_1_1024_80);
+
+    let pufksk = high_level::keygen::generate_lwe_packing_key(
+        glwe.to_lwe_secret_key(),
+        &glwe,
+        &GLWE_1_1024_80.as_lwe_def(),
+        &GLWE_1_1024_80,
+        &radix,
+    );
+
+    let mut group = c.benchmark_group("LWE packing");
+
+    for lwe_count in [1, 64, 1024] {
+        let values = (0..lwe_count)
+            .map(|_| {
+                high_level::encryption::encrypt_lwe_secret(
+                    0,
+                    glwe.to_lwe_secret_key(),
+                    &GLWE_1_1024_80.as_lwe_def(),
+                    PlaintextBits(1),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let lwe_refs = values.iter().map(|x| x.borrow()).collect::<Vec<_>>();
+
+        group.bench_function(format!("{lwe_count} LWEs"), |b| {
+            b.iter(|| {
+                let _ = high_level::evaluation::pack_lwes(
+                    &lwe_refs,
+                    &pufksk,
+                    &GLWE_1_1024_80.as_lwe_def(),
+                    &GLWE_1_1024_80,
+                    &radix,
+                );
+            });
+        });
+    }
+}
+
 criterion_group!(
     benches,
     cmux,
@@ -399,6 +445,7 @@ criterion_group!(
     circuit_bootstrapping,
     scheme_switch,
     keygen,
-    public_functional_keyswitching
+    public_functional_keyswitching,
+    lwe_packing
 );
 criterion_main!(benches);
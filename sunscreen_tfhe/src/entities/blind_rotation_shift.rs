@@ -5,8 +5,10 @@ use crate::{
     dst::{AsMutSlice, AsSlice, NoWrapper, OverlaySize},
     entities::{
         GgswCiphertextFftIterator, GgswCiphertextFftIteratorMut, GgswCiphertextFftRef,
-        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextRef,
+        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextNttIterator,
+        GgswCiphertextNttIteratorMut, GgswCiphertextNttRef, GgswCiphertextRef,
     },
+    goldilocks_field::Fg,
     GlweDef, GlweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
 };
 
@@ -135,3 +137,61 @@ impl BlindRotationShiftFftRef<Complex<f64>> {
         }
     }
 }
+
+dst! {
+    /// An encrypted amount to rotate the polynomials in a GLWE ciphertext by.
+    /// Used by the
+    /// [`blind_rotation_ntt`](crate::ops::bootstrapping::blind_rotation_ntt)
+    /// function, which computes the rotation exactly. The FFT version of this
+    /// type is [BlindRotationShiftFft].
+    BlindRotationShiftNtt,
+    BlindRotationShiftNttRef,
+    NoWrapper,
+    (Clone, Debug, Serialize, Deserialize),
+    ()
+}
+
+impl OverlaySize for BlindRotationShiftNttRef<Fg> {
+    type Inputs = (GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        let n_bits = (t.0.polynomial_degree.0 as u64).ilog2() as usize;
+
+        GgswCiphertextNttRef::<Fg>::size(t) * n_bits
+    }
+}
+
+impl BlindRotationShiftNtt<Fg> {
+    /// Create a new zero [BlindRotationShiftNtt] with the given parameters.
+    pub fn new(params: &GlweDef, radix: &RadixDecomposition) -> Self {
+        let len = BlindRotationShiftNttRef::size((params.dim, radix.count));
+
+        Self {
+            data: avec![<Fg as sunscreen_math::Zero>::zero(); len],
+        }
+    }
+}
+
+impl BlindRotationShiftNttRef<Fg> {
+    /// Iterate over the rows of the [BlindRotationShiftNtt].
+    pub fn rows(
+        &self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIterator<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIterator::new(self.as_slice(), stride)
+    }
+
+    /// Iterate over the rows of the [BlindRotationShiftNtt] mutably.
+    pub fn rows_mut(
+        &mut self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIteratorMut<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIteratorMut::new(self.as_mut_slice(), stride)
+    }
+}
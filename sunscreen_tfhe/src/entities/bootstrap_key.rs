@@ -5,9 +5,11 @@ use crate::{
     dst::{AsMutSlice, AsSlice, NoWrapper, OverlaySize},
     entities::{
         GgswCiphertextFftIterator, GgswCiphertextFftIteratorMut, GgswCiphertextFftRef,
-        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextRef,
+        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextNttIterator,
+        GgswCiphertextNttIteratorMut, GgswCiphertextNttRef, GgswCiphertextRef,
         ParallelGgswCiphertextIterator, ParallelGgswCiphertextIteratorMut,
     },
+    goldilocks_field::Fg,
     GlweDef, GlweDimension, LweDef, LweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
 };
 
@@ -103,6 +105,26 @@ impl<S: TorusOps> BootstrapKeyRef<S> {
             s.fft(r, glwe, radix);
         }
     }
+
+    /// Perform an NTT on the [BootstrapKey] to obtain a [BootstrapKeyNtt].
+    ///
+    /// # Panics
+    /// If the NTT isn't exact for `glwe` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn ntt(
+        &self,
+        result: &mut BootstrapKeyNttRef<Fg>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        self.assert_is_valid((lwe.dim, glwe.dim, radix.count));
+        result.assert_is_valid((lwe.dim, glwe.dim, radix.count));
+
+        for (s, r) in self.rows(glwe, radix).zip(result.rows_mut(glwe, radix)) {
+            s.ntt(r, glwe, radix);
+        }
+    }
 }
 
 dst! {
@@ -176,3 +198,63 @@ impl BootstrapKeyFftRef<Complex<f64>> {
         }
     }
 }
+
+dst! {
+    /// Keys used for bootstrapping with exact polynomial products. Used by
+    /// the bootstrapping functions such as
+    /// [`programmable_bootstrap_univariate_ntt`](crate::ops::bootstrapping::programmable_bootstrap_univariate_ntt).
+    /// The non-NTT variant of this type is [BootstrapKey].
+    ///
+    /// # Remarks
+    /// This trades speed and size for bootstrapping results that are
+    /// deterministic and bit-exact, rather than carrying the rounding error of
+    /// [BootstrapKeyFft]. See [`ntt_ops`](crate::ops::ntt_ops).
+    BootstrapKeyNtt,
+    BootstrapKeyNttRef,
+    NoWrapper,
+    (Clone, Debug, Serialize, Deserialize),
+    ()
+}
+
+impl OverlaySize for BootstrapKeyNttRef<Fg> {
+    type Inputs = (LweDimension, GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        GgswCiphertextNttRef::<Fg>::size((t.1, t.2)) * t.0 .0
+    }
+}
+
+impl BootstrapKeyNtt<Fg> {
+    /// Create a new zero [BootstrapKeyNtt] with the given parameters.
+    pub fn new(lwe_params: &LweDef, glwe_params: &GlweDef, radix: &RadixDecomposition) -> Self {
+        let len = BootstrapKeyNttRef::size((lwe_params.dim, glwe_params.dim, radix.count));
+
+        Self {
+            data: avec![<Fg as sunscreen_math::Zero>::zero(); len],
+        }
+    }
+}
+
+impl BootstrapKeyNttRef<Fg> {
+    /// Iterate over the rows of the [BootstrapKeyNtt].
+    pub fn rows(
+        &self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIterator<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIterator::new(self.as_slice(), stride)
+    }
+
+    /// Iterate over the rows of the [BootstrapKeyNtt] mutably.
+    pub fn rows_mut(
+        &mut self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIteratorMut<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIteratorMut::new(self.as_mut_slice(), stride)
+    }
+}
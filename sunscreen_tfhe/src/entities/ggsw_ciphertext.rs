@@ -2,13 +2,16 @@ use num::{Complex, Zero};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    dst::OverlaySize, ops::ciphertext::external_product_ggsw_glwe, GlweDef, GlweDimension,
-    RadixCount, RadixDecomposition, Torus, TorusOps,
+    dst::OverlaySize,
+    fft::ntt::{get_ntt, NTT_LIMBS, NTT_LIMB_BITS},
+    goldilocks_field::Fg,
+    ops::{ciphertext::external_product_ggsw_glwe, ntt_ops::ntt_is_exact},
+    GlweDef, GlweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
 };
 
 use super::{
-    GgswCiphertextFftRef, GlevCiphertextIterator, GlevCiphertextIteratorMut, GlevCiphertextRef,
-    GlweCiphertext, GlweCiphertextRef,
+    GgswCiphertextFftRef, GgswCiphertextNttRef, GlevCiphertextIterator, GlevCiphertextIteratorMut,
+    GlevCiphertextRef, GlweCiphertext, GlweCiphertextRef,
 };
 
 dst! {
@@ -108,4 +111,53 @@ where
             s.fft(r, params);
         }
     }
+
+    /// Compute the NTT of each of the GLWE ciphertexts in the GGSW ciphertext.
+    /// The result is stored in `result`.
+    ///
+    /// # Remarks
+    /// Each polynomial is split into
+    /// [`NTT_LIMBS`](crate::fft::ntt::NTT_LIMBS) limbs before taking their
+    /// NTTs. See [`GgswCiphertextNtt`](crate::entities::GgswCiphertextNtt).
+    ///
+    /// # Panics
+    /// If the NTT isn't exact for `params` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn ntt(
+        &self,
+        result: &mut GgswCiphertextNttRef<Fg>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        self.assert_is_valid((params.dim, radix.count));
+        result.assert_is_valid((params.dim, radix.count));
+        assert!(
+            ntt_is_exact(params, radix),
+            "Parameters are too large for exact NTT external products."
+        );
+
+        let n = params.dim.polynomial_degree.0;
+        let ntt = get_ntt(n.ilog2() as usize).unwrap();
+        let limb_mask = (0x1u64 << NTT_LIMB_BITS) - 1;
+
+        let glwes = self
+            .rows(params, radix)
+            .flat_map(|r| r.glwe_ciphertexts(params));
+
+        for (s, r) in glwes.zip(result.glwe_ciphertexts_mut(params)) {
+            let polys = s.a(params).chain(std::iter::once(s.b(params)));
+
+            for (p, r) in polys.zip(r.chunks_mut(NTT_LIMBS * n)) {
+                for (limb, r) in r.chunks_mut(n).enumerate() {
+                    for (c, r) in p.coeffs().iter().zip(r.iter_mut()) {
+                        let c = c.inner().to_u64() >> (limb * NTT_LIMB_BITS);
+
+                        *r = Fg::from(c & limb_mask);
+                    }
+
+                    ntt.forward_in_place(r);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use sunscreen_math::Zero;
+
+use crate::{
+    dst::{AsMutSlice, AsSlice, NoWrapper, OverlaySize},
+    fft::ntt::NTT_LIMBS,
+    goldilocks_field::Fg,
+    GlweDef, GlweDimension, RadixCount, RadixDecomposition,
+};
+
+dst! {
+    /// The NTT variant of a GGSW ciphertext. See
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext) for more details.
+    ///
+    /// # Remarks
+    /// Unlike [`GgswCiphertextFft`](crate::entities::GgswCiphertextFft),
+    /// external products with this representation are exact. See
+    /// [`ntt_ops`](crate::ops::ntt_ops).
+    ///
+    /// Each polynomial of the GGSW ciphertext is split into
+    /// [`NTT_LIMBS`](crate::fft::ntt::NTT_LIMBS) limbs whose NTTs are stored
+    /// contiguously, so this is `NTT_LIMBS` times larger than the
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext) it came from.
+    GgswCiphertextNtt,
+    GgswCiphertextNttRef,
+    NoWrapper,
+    (Clone, Debug, Serialize, Deserialize),
+    ()
+}
+dst_iter! { GgswCiphertextNttIterator, GgswCiphertextNttIteratorMut, ParallelGgswCiphertextNttIterator, ParallelGgswCiphertextNttIteratorMut, NoWrapper, GgswCiphertextNttRef, ()}
+
+impl OverlaySize for GgswCiphertextNttRef<Fg> {
+    type Inputs = (GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        let glwe_size = t.0.size.0 + 1;
+
+        glwe_size * glwe_size * t.1 .0 * NTT_LIMBS * t.0.polynomial_degree.0
+    }
+}
+
+impl GgswCiphertextNtt<Fg> {
+    /// Creates a new zero GGSW ciphertext with NTT representation.
+    pub fn new(params: &GlweDef, radix: &RadixDecomposition) -> GgswCiphertextNtt<Fg> {
+        let len = GgswCiphertextNttRef::size((params.dim, radix.count));
+
+        GgswCiphertextNtt {
+            data: avec![Fg::zero(); len],
+        }
+    }
+}
+
+impl GgswCiphertextNttRef<Fg> {
+    /// Returns an iterator over the GLWE ciphertexts in the GGSW ciphertext,
+    /// ordered by row and then by decomposition level as in
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext).
+    ///
+    /// # Remarks
+    /// Each item holds the `k + 1` polynomials of one GLWE ciphertext. Each
+    /// polynomial is [`NTT_LIMBS`] contiguous NTTs of length `N`, from least
+    /// to most significant limb.
+    pub fn glwe_ciphertexts(&self, params: &GlweDef) -> std::slice::Chunks<Fg> {
+        self.as_slice().chunks(Self::glwe_len(params))
+    }
+
+    /// Returns a mutable iterator over the GLWE ciphertexts in the GGSW
+    /// ciphertext. See [`GgswCiphertextNttRef::glwe_ciphertexts`].
+    pub fn glwe_ciphertexts_mut(&mut self, params: &GlweDef) -> std::slice::ChunksMut<Fg> {
+        self.as_mut_slice().chunks_mut(Self::glwe_len(params))
+    }
+
+    #[inline(always)]
+    fn glwe_len(params: &GlweDef) -> usize {
+        (params.dim.size.0 + 1) * NTT_LIMBS * params.dim.polynomial_degree.0
+    }
+}
@@ -0,0 +1,105 @@
+use num::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dst::{FromMutSlice, FromSlice, OverlaySize},
+    GlweDef, GlweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
+};
+
+use super::{
+    GlevCiphertextIterator, GlevCiphertextIteratorMut, GlevCiphertextRef, GlweCiphertextRef,
+};
+
+dst! {
+    /// A server key for GGSW-CMux trees, which produce
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext)s directly and thus chain
+    /// without circuit bootstrapping. See
+    /// [`evaluate_ggsw_cmux_tree`](crate::ops::leveled::evaluate_ggsw_cmux_tree).
+    ///
+    /// # Remarks
+    /// The key contains a GLWE encryption of zero followed by `k` GLev encryptions of `-s_i`
+    /// for each secret key polynomial `s_i`. These are exactly the messages in the first `k`
+    /// rows of a [`GgswCiphertext`](crate::entities::GgswCiphertext) encrypting 0 and 1
+    /// respectively. The GLev encryption of zero collapses to a single GLWE ciphertext since
+    /// every gadget multiple of zero is zero.
+    GgswCmuxKey,
+    GgswCmuxKeyRef,
+    Torus,
+    (Clone, Debug, Serialize, Deserialize),
+    (TorusOps,)
+}
+
+impl<S> OverlaySize for GgswCmuxKeyRef<S>
+where
+    S: TorusOps,
+{
+    type Inputs = (GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        GlweCiphertextRef::<S>::size(t.0) + GlevCiphertextRef::<S>::size(t) * t.0.size.0
+    }
+}
+
+impl<S> GgswCmuxKey<S>
+where
+    S: TorusOps,
+{
+    /// Create a new zero GGSW-CMux key with the given parameters.
+    pub fn new(params: &GlweDef, radix: &RadixDecomposition) -> Self {
+        let elems = GgswCmuxKeyRef::<S>::size((params.dim, radix.count));
+
+        Self {
+            data: avec![Torus::zero(); elems],
+        }
+    }
+}
+
+impl<S> GgswCmuxKeyRef<S>
+where
+    S: TorusOps,
+{
+    /// Returns the GLWE encryption of zero.
+    pub fn zero(&self, params: &GlweDef) -> &GlweCiphertextRef<S> {
+        let len = GlweCiphertextRef::<S>::size(params.dim);
+
+        GlweCiphertextRef::from_slice(&self.data[..len])
+    }
+
+    /// Returns a mutable reference to the GLWE encryption of zero.
+    pub fn zero_mut(&mut self, params: &GlweDef) -> &mut GlweCiphertextRef<S> {
+        let len = GlweCiphertextRef::<S>::size(params.dim);
+
+        GlweCiphertextRef::from_mut_slice(&mut self.data[..len])
+    }
+
+    /// Returns an iterator over the [`GlevCiphertext`](crate::entities::GlevCiphertext)
+    /// encryptions of `-s_i`, in the order of the secret key polynomials.
+    pub fn glev_ciphertexts(
+        &self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlevCiphertextIterator<S> {
+        let len = GlweCiphertextRef::<S>::size(params.dim);
+
+        GlevCiphertextIterator::new(
+            &self.data[len..],
+            GlevCiphertextRef::<S>::size((params.dim, radix.count)),
+        )
+    }
+
+    /// Returns a mutable iterator over the
+    /// [`GlevCiphertext`](crate::entities::GlevCiphertext) encryptions of `-s_i`, in the
+    /// order of the secret key polynomials.
+    pub fn glev_ciphertexts_mut(
+        &mut self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlevCiphertextIteratorMut<S> {
+        let len = GlweCiphertextRef::<S>::size(params.dim);
+
+        GlevCiphertextIteratorMut::new(
+            &mut self.data[len..],
+            GlevCiphertextRef::<S>::size((params.dim, radix.count)),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use num::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dst::OverlaySize, GlweDef, GlweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
+};
+
+use super::{GlweKeyswitchKeyIterator, GlweKeyswitchKeyIteratorMut, GlweKeyswitchKeyRef};
+
+dst! {
+    /// A set of keys for applying the automorphisms `X -> X^t` of Z\[X\]/(X^N + 1) to GLWE
+    /// ciphertexts. See
+    /// [`glwe_automorphism`](crate::ops::keyswitch::glwe_automorphism::glwe_automorphism).
+    ///
+    /// # Remarks
+    /// For each `t = 2^j + 1` with `1 <= j <= log2(N)`, the key contains a
+    /// [`GlweKeyswitchKey`](crate::entities::GlweKeyswitchKey) from the automorphism of the
+    /// secret key `s(X^t)` back to `s`. These are exactly the automorphisms a
+    /// [`homomorphic_trace`](crate::ops::keyswitch::glwe_automorphism::homomorphic_trace)
+    /// uses.
+    GlweAutomorphismKey,
+    GlweAutomorphismKeyRef,
+    Torus,
+    (Clone, Debug, Serialize, Deserialize),
+    (TorusOps,)
+}
+
+impl<S> OverlaySize for GlweAutomorphismKeyRef<S>
+where
+    S: TorusOps,
+{
+    type Inputs = (GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        GlweKeyswitchKeyRef::<S>::size(t) * t.0.polynomial_degree.0.ilog2() as usize
+    }
+}
+
+impl<S> GlweAutomorphismKey<S>
+where
+    S: TorusOps,
+{
+    /// Create a new zero GLWE automorphism key with the given parameters.
+    pub fn new(params: &GlweDef, radix: &RadixDecomposition) -> Self {
+        let elems = GlweAutomorphismKeyRef::<S>::size((params.dim, radix.count));
+
+        Self {
+            data: avec![Torus::zero(); elems],
+        }
+    }
+}
+
+impl<S> GlweAutomorphismKeyRef<S>
+where
+    S: TorusOps,
+{
+    /// Returns an iterator over the contained
+    /// [`GlweKeyswitchKey`](crate::entities::GlweKeyswitchKey)s, in order of the
+    /// automorphisms `X -> X^(2^j + 1)` for `j = 1, 2, ..., log2(N)`.
+    pub fn keyswitch_keys(
+        &self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweKeyswitchKeyIterator<S> {
+        GlweKeyswitchKeyIterator::new(
+            &self.data,
+            GlweKeyswitchKeyRef::<S>::size((params.dim, radix.count)),
+        )
+    }
+
+    /// Returns a mutable iterator over the contained
+    /// [`GlweKeyswitchKey`](crate::entities::GlweKeyswitchKey)s, in order of the
+    /// automorphisms `X -> X^(2^j + 1)` for `j = 1, 2, ..., log2(N)`.
+    pub fn keyswitch_keys_mut(
+        &mut self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweKeyswitchKeyIteratorMut<S> {
+        GlweKeyswitchKeyIteratorMut::new(
+            &mut self.data,
+            GlweKeyswitchKeyRef::<S>::size((params.dim, radix.count)),
+        )
+    }
+
+    /// Gets the keyswitch key for the automorphism `X -> X^t`.
+    ///
+    /// # Panics
+    /// If `t` isn't `2^j + 1` for some `1 <= j <= log2(N)`.
+    pub fn keyswitch_key(
+        &self,
+        t: usize,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> &GlweKeyswitchKeyRef<S> {
+        let n = params.dim.polynomial_degree.0;
+
+        assert!(
+            t > 2 && t <= n + 1 && (t - 1).is_power_of_two(),
+            "No key for automorphism X -> X^{t}"
+        );
+
+        let j = (t - 1).ilog2() as usize;
+
+        self.keyswitch_keys(params, radix).nth(j - 1).unwrap()
+    }
+}
@@ -1,3 +1,4 @@
+use rand::{thread_rng, CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -5,9 +6,10 @@ use crate::{
     entities::GgswCiphertext,
     macros::{impl_binary_op, impl_unary_op},
     ops::encryption::{
-        decrypt_glwe_ciphertext, encrypt_ggsw_ciphertext, encrypt_glwe_ciphertext_secret,
+        decrypt_glwe_ciphertext, encrypt_ggsw_ciphertext_with_rng,
+        encrypt_glwe_ciphertext_secret_with_rng,
     },
-    rand::{binary, uniform_torus},
+    rand::{binary_with_rng, uniform_torus_with_rng},
     GlweDef, GlweDimension, PlaintextBits, RadixDecomposition, Torus, TorusOps,
 };
 
@@ -49,7 +51,10 @@ impl<S> GlweSecretKey<S>
 where
     S: TorusOps,
 {
-    fn generate(params: &GlweDef, torus_element_generator: impl Fn() -> S) -> GlweSecretKey<S> {
+    fn generate(
+        params: &GlweDef,
+        mut torus_element_generator: impl FnMut() -> S,
+    ) -> GlweSecretKey<S> {
         params.assert_valid();
 
         let len = GlweSecretKeyRef::<S>::size(params.dim);
@@ -61,7 +66,15 @@ where
 
     /// Generate a random binary GLWE secret key.
     pub fn generate_binary(params: &GlweDef) -> GlweSecretKey<S> {
-        Self::generate(params, binary)
+        Self::generate_binary_with_rng(params, &mut thread_rng())
+    }
+
+    /// Generate a random binary GLWE secret key using the given generator.
+    pub fn generate_binary_with_rng(
+        params: &GlweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweSecretKey<S> {
+        Self::generate(params, || binary_with_rng(rng))
     }
 
     /// Generate a secret key with uniformly random coefficients.  This can be
@@ -70,7 +83,16 @@ where
     /// secret keys are also valid keys for encryption/decryption but are not
     /// widely used.
     pub fn generate_uniform(params: &GlweDef) -> GlweSecretKey<S> {
-        Self::generate(params, || uniform_torus::<S>().inner())
+        Self::generate_uniform_with_rng(params, &mut thread_rng())
+    }
+
+    /// Generate a secret key with uniformly random coefficients using the given
+    /// generator. See [`Self::generate_uniform`].
+    pub fn generate_uniform_with_rng(
+        params: &GlweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweSecretKey<S> {
+        Self::generate(params, || uniform_torus_with_rng::<S>(rng).inner())
     }
 }
 
@@ -116,6 +138,21 @@ where
         params: &GlweDef,
         plaintext_bits: PlaintextBits,
     ) -> GlweCiphertext<S>
+    where
+        S: TorusOps,
+    {
+        self.encode_encrypt_glwe_with_rng(plaintext, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Encodes and encrypts a message as a GLWE ciphertext using a secret key,
+    /// drawing the masks and noise from `rng`.
+    pub fn encode_encrypt_glwe_with_rng(
+        &self,
+        plaintext: &PolynomialRef<S>,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweCiphertext<S>
     where
         S: TorusOps,
     {
@@ -123,7 +160,7 @@ where
 
         let mut ct = GlweCiphertext::new(params);
 
-        encrypt_glwe_ciphertext_secret(&mut ct, &plaintext, self, params);
+        encrypt_glwe_ciphertext_secret_with_rng(&mut ct, &plaintext, self, params, rng);
 
         ct
     }
@@ -136,12 +173,28 @@ where
         radix: &RadixDecomposition,
         plaintext_bits: PlaintextBits,
     ) -> GgswCiphertext<S>
+    where
+        S: TorusOps,
+    {
+        self.encode_encrypt_ggsw_with_rng(msg, params, radix, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Encodes and encrypts a message as a GGSW ciphertext using a secret key,
+    /// drawing the masks and noise from `rng`.
+    pub fn encode_encrypt_ggsw_with_rng(
+        &self,
+        msg: &PolynomialRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GgswCiphertext<S>
     where
         S: TorusOps,
     {
         let mut ggsw = GgswCiphertext::new(params, radix);
 
-        encrypt_ggsw_ciphertext(&mut ggsw, msg, self, params, radix, plaintext_bits);
+        encrypt_ggsw_ciphertext_with_rng(&mut ggsw, msg, self, params, radix, plaintext_bits, rng);
 
         ggsw
     }
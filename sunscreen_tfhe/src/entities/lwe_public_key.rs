@@ -1,10 +1,11 @@
 use num::Zero;
+use rand::{thread_rng, CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     dst::OverlaySize,
-    ops::encryption::encode_and_encrypt_lwe_ciphertext,
-    rand::{binary, normal_torus},
+    ops::encryption::encode_and_encrypt_lwe_ciphertext_with_rng,
+    rand::{binary_with_rng, normal_torus_with_rng},
     LweDef, LweDimension, PlaintextBits, Torus, TorusOps,
 };
 
@@ -51,6 +52,16 @@ where
     /// encrypting the LWE dimension number of zeros under the secret key, and
     /// then using the resulting ciphertext as the public key.
     pub fn generate(sk: &LweSecretKeyRef<S>, params: &LweDef) -> Self {
+        Self::generate_with_rng(sk, params, &mut thread_rng())
+    }
+
+    /// Generate an LWE public key from a given secret key, drawing the
+    /// encryptions' randomness from `rng`. See [`Self::generate`].
+    pub fn generate_with_rng(
+        sk: &LweSecretKeyRef<S>,
+        params: &LweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Self {
         params.assert_valid();
         sk.assert_is_valid(params.dim);
 
@@ -60,7 +71,14 @@ where
         let enc_zeros = pk.enc_zeros_mut(params);
 
         for z in enc_zeros {
-            encode_and_encrypt_lwe_ciphertext(z, sk, <S as Zero>::zero(), params, PlaintextBits(1));
+            encode_and_encrypt_lwe_ciphertext_with_rng(
+                z,
+                sk,
+                <S as Zero>::zero(),
+                params,
+                PlaintextBits(1),
+                rng,
+            );
         }
 
         pk
@@ -88,6 +106,18 @@ where
         msg: S,
         params: &LweDef,
         plaintext_bits: PlaintextBits,
+    ) -> (LweCiphertext<S>, TlwePublicEncRandomness<S>) {
+        self.encrypt_with_rng(msg, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Encrypt a message as an LWE ciphertext using a public key, drawing the
+    /// randomness from `rng`. See [`Self::encrypt`].
+    pub fn encrypt_with_rng(
+        &self,
+        msg: S,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> (LweCiphertext<S>, TlwePublicEncRandomness<S>) {
         params.assert_valid();
         self.assert_is_valid(params.dim);
@@ -105,7 +135,7 @@ where
 
         for z in self.enc_zeros(params) {
             let (a, b) = z.a_b(params);
-            let r = binary::<S>();
+            let r = binary_with_rng::<S>(rng);
             r_noise.push(r);
 
             for i in 0..lwe_dimension {
@@ -116,13 +146,13 @@ where
         }
 
         for i in 0..lwe_dimension {
-            let a_noise = normal_torus(params.std);
+            let a_noise = normal_torus_with_rng(params.std, rng);
             e_a[i] = a_noise;
             acc_a[i] += a_noise;
         }
 
         *acc_b += msg;
-        *e_b = normal_torus(params.std);
+        *e_b = normal_torus_with_rng(params.std, rng);
         *acc_b += *e_b;
 
         let noise = TlwePublicEncRandomness { r: r_noise, e };
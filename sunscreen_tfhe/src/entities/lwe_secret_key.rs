@@ -1,11 +1,12 @@
 use num::Zero;
+use rand::{thread_rng, CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     dst::{NoWrapper, OverlaySize},
     macros::{impl_binary_op, impl_unary_op},
-    ops::encryption::encode_and_encrypt_lwe_ciphertext,
-    rand::{binary, uniform_torus},
+    ops::encryption::encode_and_encrypt_lwe_ciphertext_with_rng,
+    rand::{binary_with_rng, uniform_torus_with_rng},
     LweDef, LweDimension, PlaintextBits, Torus, TorusOps,
 };
 
@@ -39,7 +40,7 @@ impl<S> LweSecretKey<S>
 where
     S: TorusOps,
 {
-    fn generate(params: &LweDef, torus_element_generator: fn() -> S) -> Self {
+    fn generate(params: &LweDef, mut torus_element_generator: impl FnMut() -> S) -> Self {
         let len = LweSecretKeyRef::<S>::size(params.dim);
 
         LweSecretKey {
@@ -49,7 +50,12 @@ where
 
     /// Generate a random binary LWE secret key
     pub fn generate_binary(params: &LweDef) -> Self {
-        Self::generate(params, binary)
+        Self::generate_binary_with_rng(params, &mut thread_rng())
+    }
+
+    /// Generate a random binary LWE secret key using the given generator
+    pub fn generate_binary_with_rng(params: &LweDef, rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        Self::generate(params, || binary_with_rng(rng))
     }
 
     /// Generate a secret key with uniformly random coefficients.  This can be
@@ -58,7 +64,16 @@ where
     /// secret keys are also valid keys for encryption/decryption but are not
     /// widely used.
     pub fn generate_uniform(params: &LweDef) -> Self {
-        Self::generate(params, || uniform_torus::<S>().inner())
+        Self::generate_uniform_with_rng(params, &mut thread_rng())
+    }
+
+    /// Generate a secret key with uniformly random coefficients using the given
+    /// generator. See [`Self::generate_uniform`].
+    pub fn generate_uniform_with_rng(
+        params: &LweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Self {
+        Self::generate(params, || uniform_torus_with_rng::<S>(rng).inner())
     }
 }
 
@@ -74,13 +89,32 @@ where
         msg: S,
         params: &LweDef,
         plaintext_bits: PlaintextBits,
+    ) -> (LweCiphertext<S>, Torus<S>) {
+        self.encrypt_with_rng(msg, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Create an LWE ciphertext from a given message with a private key,
+    /// drawing the mask and noise from `rng`. See [`Self::encrypt`].
+    pub fn encrypt_with_rng(
+        &self,
+        msg: S,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> (LweCiphertext<S>, Torus<S>) {
         params.assert_valid();
         assert!(plaintext_bits.0 < S::BITS);
 
         let mut ct = LweCiphertext::<S>::zero(params);
 
-        let e = encode_and_encrypt_lwe_ciphertext(&mut ct, self, msg, params, plaintext_bits);
+        let e = encode_and_encrypt_lwe_ciphertext_with_rng(
+            &mut ct,
+            self,
+            msg,
+            params,
+            plaintext_bits,
+            rng,
+        );
 
         (ct, e)
     }
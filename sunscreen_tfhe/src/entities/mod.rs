@@ -16,6 +16,9 @@ pub use circuit_bootstrapping_private_keyswitch_keys::*;
 mod bootstrap_key;
 pub use bootstrap_key::*;
 
+mod seeded_keys;
+pub use seeded_keys::*;
+
 mod univariate_lookup_table;
 pub use univariate_lookup_table::*;
 
@@ -52,6 +55,9 @@ pub use ggsw_ciphertext::*;
 mod ggsw_ciphertext_fft;
 pub use ggsw_ciphertext_fft::*;
 
+mod ggsw_ciphertext_ntt;
+pub use ggsw_ciphertext_ntt::*;
+
 mod lev_ciphertext;
 pub use lev_ciphertext::*;
 
@@ -78,3 +84,9 @@ pub use scheme_switch_key::*;
 
 mod scheme_switch_key_fft;
 pub use scheme_switch_key_fft::*;
+
+mod ggsw_cmux_key;
+pub use ggsw_cmux_key::*;
+
+mod glwe_automorphism_key;
+pub use glwe_automorphism_key::*;
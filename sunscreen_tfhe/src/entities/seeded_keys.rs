@@ -0,0 +1,164 @@
+use num::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, GlweDef, LweDef, RadixDecomposition, Result, Torus, TorusOps};
+
+/// The seed from which the masks of a seeded key are expanded.
+pub type KeySeed = [u8; 32];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A compressed [`BootstrapKey`](crate::entities::BootstrapKey). Rather than
+/// the full GLWE ciphertexts, this stores only their bodies and a seed from
+/// which the masks are deterministically regenerated.
+///
+/// # Remarks
+/// Generate these with
+/// [`generate_seeded_bootstrap_key`](crate::ops::bootstrapping::generate_seeded_bootstrap_key)
+/// and decompress them with
+/// [`expand_seeded_bootstrap_key`](crate::ops::bootstrapping::expand_seeded_bootstrap_key).
+/// A seeded key is `k + 1` times smaller than the full key, where `k` is the
+/// GLWE size.
+///
+/// # Security
+/// Like the full key, the seeded key is public.
+pub struct SeededBootstrapKey<S>
+where
+    S: TorusOps,
+{
+    seed: KeySeed,
+    bodies: Vec<Torus<S>>,
+}
+
+impl<S> SeededBootstrapKey<S>
+where
+    S: TorusOps,
+{
+    /// Create a new zero [`SeededBootstrapKey`] with the given parameters and
+    /// seed.
+    pub fn new(seed: KeySeed, lwe: &LweDef, glwe: &GlweDef, radix: &RadixDecomposition) -> Self {
+        Self {
+            seed,
+            bodies: vec![Torus::zero(); Self::body_count(lwe, glwe, radix)],
+        }
+    }
+
+    /// The number of torus elements in the bodies of a [`SeededBootstrapKey`]
+    /// with the given parameters. For each of the `n` GGSW ciphertexts, we
+    /// store `(k + 1) * l` body polynomials with `N` coefficients each.
+    pub fn body_count(lwe: &LweDef, glwe: &GlweDef, radix: &RadixDecomposition) -> usize {
+        lwe.dim.0 * (glwe.dim.size.0 + 1) * radix.count.0 * glwe.dim.polynomial_degree.0
+    }
+
+    /// The seed from which the masks are generated.
+    pub fn seed(&self) -> &KeySeed {
+        &self.seed
+    }
+
+    /// The bodies of the GLWE ciphertexts in the key, in the same order as
+    /// they appear in a [`BootstrapKey`](crate::entities::BootstrapKey).
+    pub fn bodies(&self) -> &[Torus<S>] {
+        &self.bodies
+    }
+
+    /// The bodies of the GLWE ciphertexts in the key, mutably.
+    pub fn bodies_mut(&mut self) -> &mut [Torus<S>] {
+        &mut self.bodies
+    }
+
+    /// Returns [`Error::InvalidSize`] if this key isn't valid under the given
+    /// parameters.
+    pub fn check_is_valid(
+        &self,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> Result<()> {
+        if self.bodies.len() == Self::body_count(lwe, glwe, radix) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSize)
+        }
+    }
+
+    /// Panics if this key isn't valid under the given parameters.
+    pub fn assert_is_valid(&self, lwe: &LweDef, glwe: &GlweDef, radix: &RadixDecomposition) {
+        self.check_is_valid(lwe, glwe, radix)
+            .expect("Entity was not the correct length.");
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A compressed [`LweKeyswitchKey`](crate::entities::LweKeyswitchKey). Rather
+/// than the full LWE ciphertexts, this stores only their bodies and a seed from
+/// which the masks are deterministically regenerated.
+///
+/// # Remarks
+/// Generate these with
+/// [`generate_seeded_keyswitch_key_lwe`](crate::ops::keyswitch::lwe_keyswitch_key::generate_seeded_keyswitch_key_lwe)
+/// and decompress them with
+/// [`expand_seeded_keyswitch_key_lwe`](crate::ops::keyswitch::lwe_keyswitch_key::expand_seeded_keyswitch_key_lwe).
+/// A seeded key is `n + 1` times smaller than the full key, where `n` is the
+/// dimension of the output LWE.
+///
+/// # Security
+/// Like the full key, the seeded key is public.
+pub struct SeededLweKeyswitchKey<S>
+where
+    S: TorusOps,
+{
+    seed: KeySeed,
+    bodies: Vec<Torus<S>>,
+}
+
+impl<S> SeededLweKeyswitchKey<S>
+where
+    S: TorusOps,
+{
+    /// Create a new zero [`SeededLweKeyswitchKey`] with the given parameters
+    /// and seed.
+    pub fn new(seed: KeySeed, from_lwe: &LweDef, radix: &RadixDecomposition) -> Self {
+        Self {
+            seed,
+            bodies: vec![Torus::zero(); Self::body_count(from_lwe, radix)],
+        }
+    }
+
+    /// The number of torus elements in the bodies of a
+    /// [`SeededLweKeyswitchKey`] with the given parameters. The key contains
+    /// `l` LWE ciphertexts for each element of the input secret key.
+    pub fn body_count(from_lwe: &LweDef, radix: &RadixDecomposition) -> usize {
+        from_lwe.dim.0 * radix.count.0
+    }
+
+    /// The seed from which the masks are generated.
+    pub fn seed(&self) -> &KeySeed {
+        &self.seed
+    }
+
+    /// The bodies of the LWE ciphertexts in the key, in the same order as
+    /// they appear in an [`LweKeyswitchKey`](crate::entities::LweKeyswitchKey).
+    pub fn bodies(&self) -> &[Torus<S>] {
+        &self.bodies
+    }
+
+    /// The bodies of the LWE ciphertexts in the key, mutably.
+    pub fn bodies_mut(&mut self) -> &mut [Torus<S>] {
+        &mut self.bodies
+    }
+
+    /// Returns [`Error::InvalidSize`] if this key isn't valid under the given
+    /// parameters.
+    pub fn check_is_valid(&self, from_lwe: &LweDef, radix: &RadixDecomposition) -> Result<()> {
+        if self.bodies.len() == Self::body_count(from_lwe, radix) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSize)
+        }
+    }
+
+    /// Panics if this key isn't valid under the given parameters.
+    pub fn assert_is_valid(&self, from_lwe: &LweDef, radix: &RadixDecomposition) {
+        self.check_is_valid(from_lwe, radix)
+            .expect("Entity was not the correct length.");
+    }
+}
@@ -4,6 +4,29 @@ pub enum Error {
     /// The size of the given entity is invalid under the given scheme parameters.
     #[error("The given entity is the incorrect size for the requested parameters.")]
     InvalidSize,
+
+    /// Serialized data uses a format version this library doesn't support.
+    #[error("Unsupported serialization format version {0}")]
+    UnsupportedVersion(u32),
+
+    /// Serialized data contains a different kind of entity than requested.
+    #[error("Expected a serialized {expected}, found {actual}")]
+    EntityKindMismatch {
+        /// The kind of entity requested.
+        expected: String,
+
+        /// The kind of entity found in the serialized data.
+        actual: String,
+    },
+
+    /// A serialized entity's parameters don't match the expected parameters.
+    #[error("The serialized entity's parameters don't match the expected parameters.")]
+    ParameterMismatch,
+
+    /// A zero-knowledge proof statement or its witness is malformed or unsupported under the
+    /// given parameters.
+    #[error("Invalid proof statement: {0}")]
+    InvalidStatement(String),
 }
 
 /// A result that can occur in this crate.
@@ -60,19 +60,29 @@ pub const TEST_LWE_DEF_3: LweDef = LweDef {
 pub mod keygen {
     use crate::{
         entities::{
-            BootstrapKey, CircuitBootstrappingKeyswitchKeys, GlweSecretKey, GlweSecretKeyRef,
-            LweKeyswitchKey, LwePublicKey, LweSecretKey, LweSecretKeyRef, RlwePublicKey,
+            BootstrapKey, CircuitBootstrappingKeyswitchKeys, GgswCmuxKey, GlweSecretKey,
+            GlweSecretKeyRef, KeySeed, LweKeyswitchKey, LwePublicKey, LweSecretKey,
+            LweSecretKeyRef, PublicFunctionalKeyswitchKey, RlwePublicKey, SeededBootstrapKey,
+            SeededLweKeyswitchKey,
         },
         ops::{
-            bootstrapping::generate_bootstrap_key,
-            encryption::rlwe_generate_public_key,
+            bootstrapping::{
+                expand_seeded_bootstrap_key, generate_bootstrap_key_with_rng,
+                generate_seeded_bootstrap_key_with_rng,
+            },
+            encryption::rlwe_generate_public_key_with_rng,
             keyswitch::{
-                lwe_keyswitch_key::generate_keyswitch_key_lwe,
-                private_functional_keyswitch::generate_circuit_bootstrapping_pfks_keys,
+                lwe_keyswitch_key::{
+                    expand_seeded_keyswitch_key_lwe, generate_keyswitch_key_lwe_with_rng,
+                    generate_seeded_keyswitch_key_lwe_with_rng,
+                },
+                private_functional_keyswitch::generate_circuit_bootstrapping_pfks_keys_with_rng,
+                public_functional_keyswitch::generate_public_functional_keyswitch_key_with_rng,
             },
         },
         GlweDef, LweDef, RadixDecomposition,
     };
+    use rand::{thread_rng, CryptoRng, RngCore};
 
     /// Generate a new binary [`LweSecretKey`] under the given LWE parameters.
     ///
@@ -92,7 +102,15 @@ pub mod keygen {
     /// possess it. Anyone who possesses the returned [`LweSecretKey`]
     /// can decrypt any messages encrypted under it.
     pub fn generate_binary_lwe_sk(params: &LweDef) -> LweSecretKey<u64> {
-        LweSecretKey::generate_binary(params)
+        generate_binary_lwe_sk_with_rng(params, &mut thread_rng())
+    }
+
+    /// Like [`generate_binary_lwe_sk`], but draws the randomness from `rng`.
+    pub fn generate_binary_lwe_sk_with_rng(
+        params: &LweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> LweSecretKey<u64> {
+        LweSecretKey::generate_binary_with_rng(params, rng)
     }
 
     /// Generate a new binary [`LweSecretKey`] under the given LWE parameters.
@@ -112,7 +130,15 @@ pub mod keygen {
     /// possess it. Anyone who possesses the returned [`LweSecretKey`]
     /// can decrypt any messages encrypted under it.
     pub fn generate_uniform_lwe_sk(params: &LweDef) -> LweSecretKey<u64> {
-        LweSecretKey::generate_uniform(params)
+        generate_uniform_lwe_sk_with_rng(params, &mut thread_rng())
+    }
+
+    /// Like [`generate_uniform_lwe_sk`], but draws the randomness from `rng`.
+    pub fn generate_uniform_lwe_sk_with_rng(
+        params: &LweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> LweSecretKey<u64> {
+        LweSecretKey::generate_uniform_with_rng(params, rng)
     }
 
     /// Generate a new [`LwePublicKey`] under the given parameters. This
@@ -130,7 +156,16 @@ pub mod keygen {
     /// This key is public and sharing it does not compromise semantic
     /// security.
     pub fn generate_lwe_pk(sk: &LweSecretKeyRef<u64>, params: &LweDef) -> LwePublicKey<u64> {
-        LwePublicKey::generate(sk, params)
+        generate_lwe_pk_with_rng(sk, params, &mut thread_rng())
+    }
+
+    /// Like [`generate_lwe_pk`], but draws the randomness from `rng`.
+    pub fn generate_lwe_pk_with_rng(
+        sk: &LweSecretKeyRef<u64>,
+        params: &LweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> LwePublicKey<u64> {
+        LwePublicKey::generate_with_rng(sk, params, rng)
     }
 
     /// Generate a new GLWE secret key under the given GLWE parameters.
@@ -151,7 +186,15 @@ pub mod keygen {
     /// possess it. Anyone who possesses the returned [`GlweSecretKey`]
     /// can decrypt any messages encrypted under it.
     pub fn generate_binary_glwe_sk(params: &GlweDef) -> GlweSecretKey<u64> {
-        GlweSecretKey::generate_binary(params)
+        generate_binary_glwe_sk_with_rng(params, &mut thread_rng())
+    }
+
+    /// Like [`generate_binary_glwe_sk`], but draws the randomness from `rng`.
+    pub fn generate_binary_glwe_sk_with_rng(
+        params: &GlweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweSecretKey<u64> {
+        GlweSecretKey::generate_binary_with_rng(params, rng)
     }
 
     /// Generate a new GLWE secret key under the given GLWE parameters.
@@ -171,7 +214,15 @@ pub mod keygen {
     /// possess it. Anyone who possesses the returned [`GlweSecretKey`]
     /// can decrypt any messages encrypted under it.
     pub fn generate_uniform_glwe_sk(params: &GlweDef) -> GlweSecretKey<u64> {
-        GlweSecretKey::generate_uniform(params)
+        generate_uniform_glwe_sk_with_rng(params, &mut thread_rng())
+    }
+
+    /// Like [`generate_uniform_glwe_sk`], but draws the randomness from `rng`.
+    pub fn generate_uniform_glwe_sk_with_rng(
+        params: &GlweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweSecretKey<u64> {
+        GlweSecretKey::generate_uniform_with_rng(params, rng)
     }
 
     /// Generate a new bootstrapping key, which is used in bootstrapping operations.
@@ -215,10 +266,22 @@ pub mod keygen {
         lwe: &LweDef,
         glwe: &GlweDef,
         radix: &RadixDecomposition,
+    ) -> BootstrapKey<u64> {
+        generate_bootstrapping_key_with_rng(sk, glwe_key, lwe, glwe, radix, &mut thread_rng())
+    }
+
+    /// Like [`generate_bootstrapping_key`], but draws the randomness from `rng`.
+    pub fn generate_bootstrapping_key_with_rng(
+        sk: &LweSecretKeyRef<u64>,
+        glwe_key: &GlweSecretKeyRef<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> BootstrapKey<u64> {
         let mut bsk = BootstrapKey::new(lwe, glwe, radix);
 
-        generate_bootstrap_key(&mut bsk, sk, glwe_key, lwe, glwe, radix);
+        generate_bootstrap_key_with_rng(&mut bsk, sk, glwe_key, lwe, glwe, radix, rng);
 
         bsk
     }
@@ -251,10 +314,172 @@ pub mod keygen {
         from_lwe: &LweDef,
         to_lwe: &LweDef,
         radix: &RadixDecomposition,
+    ) -> LweKeyswitchKey<u64> {
+        generate_ksk_with_rng(from_sk, to_sk, from_lwe, to_lwe, radix, &mut thread_rng())
+    }
+
+    /// Like [`generate_ksk`], but draws the randomness from `rng`.
+    pub fn generate_ksk_with_rng(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &LweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> LweKeyswitchKey<u64> {
         let mut ksk = LweKeyswitchKey::new(from_lwe, to_lwe, radix);
 
-        generate_keyswitch_key_lwe(&mut ksk, from_sk, to_sk, from_lwe, to_lwe, radix);
+        generate_keyswitch_key_lwe_with_rng(&mut ksk, from_sk, to_sk, from_lwe, to_lwe, radix, rng);
+
+        ksk
+    }
+
+    /// Generate a [`SeededBootstrapKey`], the compressed form of the key
+    /// returned by [`generate_bootstrapping_key`].
+    ///
+    /// # Remarks
+    /// The key stores only the bodies of its GLWE ciphertexts and `seed`, from
+    /// which the masks are regenerated, making it `k + 1` times smaller than a
+    /// [`BootstrapKey`]. Clients should send this to the server, which calls
+    /// [`expand_bootstrapping_key`] to recover the full key.
+    ///
+    /// `seed` needn't be secret, but must be chosen uniformly at random for
+    /// every key, e.g. with [`key_seed`](crate::rand::key_seed).
+    ///
+    /// # Panics
+    /// If `lwe`, `glwe`, or `radix` are invalid.
+    /// If `glwe_key` isn't valid under `glwe`.
+    /// If `sk` isn't valid under `lwe`.
+    ///
+    /// # Security
+    /// Same as [`generate_bootstrapping_key`].
+    pub fn generate_seeded_bootstrapping_key(
+        sk: &LweSecretKeyRef<u64>,
+        glwe_key: &GlweSecretKeyRef<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+        seed: KeySeed,
+    ) -> SeededBootstrapKey<u64> {
+        generate_seeded_bootstrapping_key_with_rng(
+            sk,
+            glwe_key,
+            lwe,
+            glwe,
+            radix,
+            seed,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Like [`generate_seeded_bootstrapping_key`], but draws the randomness from `rng`.
+    pub fn generate_seeded_bootstrapping_key_with_rng(
+        sk: &LweSecretKeyRef<u64>,
+        glwe_key: &GlweSecretKeyRef<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+        seed: KeySeed,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> SeededBootstrapKey<u64> {
+        let mut bsk = SeededBootstrapKey::new(seed, lwe, glwe, radix);
+
+        generate_seeded_bootstrap_key_with_rng(&mut bsk, sk, glwe_key, lwe, glwe, radix, rng);
+
+        bsk
+    }
+
+    /// Expand a [`SeededBootstrapKey`] into the [`BootstrapKey`] it
+    /// compresses.
+    ///
+    /// # Panics
+    /// If `lwe`, `glwe`, or `radix` are invalid.
+    /// If `seeded_key` isn't valid under `lwe`, `glwe` and `radix`.
+    pub fn expand_bootstrapping_key(
+        seeded_key: &SeededBootstrapKey<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> BootstrapKey<u64> {
+        let mut bsk = BootstrapKey::new(lwe, glwe, radix);
+
+        expand_seeded_bootstrap_key(&mut bsk, seeded_key, lwe, glwe, radix);
+
+        bsk
+    }
+
+    /// Generate a [`SeededLweKeyswitchKey`], the compressed form of the key
+    /// returned by [`generate_ksk`].
+    ///
+    /// # Remarks
+    /// The key stores only the bodies of its LWE ciphertexts and `seed`, from
+    /// which the masks are regenerated, making it `to_lwe.dim + 1` times
+    /// smaller than an [`LweKeyswitchKey`]. Clients should send this to the
+    /// server, which calls [`expand_ksk`] to recover the full key.
+    ///
+    /// As with [`generate_seeded_bootstrapping_key`], `seed` must be chosen
+    /// uniformly at random for every key.
+    ///
+    /// # Panics
+    /// If the `from_lwe` parameters aren't valid for `from_sk`.
+    /// If the `to_lwe` parameters aren't valid for `to_sk`.
+    /// If `from_lwe`, `to_lwe`, or `radix` parameters are invalid.
+    ///
+    /// # Security
+    /// Same as [`generate_ksk`].
+    pub fn generate_seeded_ksk(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &LweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+        seed: KeySeed,
+    ) -> SeededLweKeyswitchKey<u64> {
+        generate_seeded_ksk_with_rng(
+            from_sk,
+            to_sk,
+            from_lwe,
+            to_lwe,
+            radix,
+            seed,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Like [`generate_seeded_ksk`], but draws the randomness from `rng`.
+    pub fn generate_seeded_ksk_with_rng(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &LweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+        seed: KeySeed,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> SeededLweKeyswitchKey<u64> {
+        let mut ksk = SeededLweKeyswitchKey::new(seed, from_lwe, radix);
+
+        generate_seeded_keyswitch_key_lwe_with_rng(
+            &mut ksk, from_sk, to_sk, from_lwe, to_lwe, radix, rng,
+        );
+
+        ksk
+    }
+
+    /// Expand a [`SeededLweKeyswitchKey`] into the [`LweKeyswitchKey`] it
+    /// compresses.
+    ///
+    /// # Panics
+    /// If `from_lwe`, `to_lwe`, or `radix` parameters are invalid.
+    /// If `seeded_key` isn't valid under `from_lwe` and `radix`.
+    pub fn expand_ksk(
+        seeded_key: &SeededLweKeyswitchKey<u64>,
+        from_lwe: &LweDef,
+        to_lwe: &LweDef,
+        radix: &RadixDecomposition,
+    ) -> LweKeyswitchKey<u64> {
+        let mut ksk = LweKeyswitchKey::new(from_lwe, to_lwe, radix);
+
+        expand_seeded_keyswitch_key_lwe(&mut ksk, seeded_key, from_lwe, to_lwe, radix);
 
         ksk
     }
@@ -295,21 +520,136 @@ pub mod keygen {
         from_lwe: &LweDef,
         to_glwe: &GlweDef,
         radix: &RadixDecomposition,
+    ) -> CircuitBootstrappingKeyswitchKeys<u64> {
+        generate_cbs_ksk_with_rng(from_sk, to_sk, from_lwe, to_glwe, radix, &mut thread_rng())
+    }
+
+    /// Like [`generate_cbs_ksk`], but draws the randomness from `rng`.
+    pub fn generate_cbs_ksk_with_rng(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &GlweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> CircuitBootstrappingKeyswitchKeys<u64> {
         let mut cbs_ksk = CircuitBootstrappingKeyswitchKeys::new(from_lwe, to_glwe, radix);
 
-        generate_circuit_bootstrapping_pfks_keys(
+        generate_circuit_bootstrapping_pfks_keys_with_rng(
             &mut cbs_ksk,
             from_sk,
             to_sk,
             from_lwe,
             to_glwe,
             radix,
+            rng,
         );
 
         cbs_ksk
     }
 
+    /// Generate a [`PublicFunctionalKeyswitchKey`] to use during
+    /// [pack_lwes](super::evaluation::pack_lwes) operations.
+    ///
+    /// # Remarks
+    /// The `from_lwe` and `to_glwe` parameters correspond to those used when you generated
+    /// `from_sk` and `to_sk`, respectively. To pack the results of
+    /// [sample_extract](super::evaluation::sample_extract), use
+    /// [`GlweSecretKeyRef::to_lwe_secret_key()`] and [`GlweDef::as_lwe_def()`] for
+    /// `from_sk` and `from_lwe`.
+    ///
+    /// `radix` must be passed as `radix` to [pack_lwes](super::evaluation::pack_lwes).
+    ///
+    /// # Panics
+    /// If `from_lwe`, `to_glwe`, or `radix` are invalid.
+    /// If `from_lwe` or `to_glwe` parameters don't correspond with `from_sk` or `to_sk`,
+    /// respectively.
+    ///
+    /// # Security
+    /// The returned [`PublicFunctionalKeyswitchKey`] is public and does not compromise
+    /// semantic security. However, anyone who possesses `to_sk` can easily recover
+    /// `from_sk` using this information.
+    pub fn generate_lwe_packing_key(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &GlweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> PublicFunctionalKeyswitchKey<u64> {
+        generate_lwe_packing_key_with_rng(
+            from_sk,
+            to_sk,
+            from_lwe,
+            to_glwe,
+            radix,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Like [`generate_lwe_packing_key`], but draws the randomness from `rng`.
+    pub fn generate_lwe_packing_key_with_rng(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &GlweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> PublicFunctionalKeyswitchKey<u64> {
+        let mut pufksk = PublicFunctionalKeyswitchKey::new(from_lwe, to_glwe, radix);
+
+        generate_public_functional_keyswitch_key_with_rng(
+            &mut pufksk,
+            from_sk,
+            to_sk,
+            from_lwe,
+            to_glwe,
+            radix,
+            rng,
+        );
+
+        pufksk
+    }
+
+    /// Generate a [`GgswCmuxKey`] to use during
+    /// [ggsw_cmux_tree](super::evaluation::ggsw_cmux_tree) operations.
+    ///
+    /// # Remarks
+    /// The key contains encryptions of zero and of the negated polynomials of `sk`. These
+    /// make up the leaves of the mux tree, so that its outputs are
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext)s rather than GLWE ciphertexts.
+    ///
+    /// `radix` is the decomposition of the resulting
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext)s and must be passed as `radix`
+    /// to [ggsw_cmux_tree](super::evaluation::ggsw_cmux_tree).
+    ///
+    /// # Panics
+    /// If `params` or `radix` are invalid.
+    /// If `sk` isn't valid under `params`.
+    ///
+    /// # Security
+    /// The returned [`GgswCmuxKey`] is public and does not compromise semantic security.
+    pub fn generate_ggsw_cmux_key(
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCmuxKey<u64> {
+        generate_ggsw_cmux_key_with_rng(sk, params, radix, &mut thread_rng())
+    }
+
+    /// Like [`generate_ggsw_cmux_key`], but draws the randomness from `rng`.
+    pub fn generate_ggsw_cmux_key_with_rng(
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GgswCmuxKey<u64> {
+        let mut key = GgswCmuxKey::new(params, radix);
+
+        crate::ops::leveled::generate_ggsw_cmux_key_with_rng(&mut key, sk, params, radix, rng);
+
+        key
+    }
+
     /// Generate an RLWE public key. This can be used to create RLWE encryptions of
     /// polynomials.
     ///
@@ -319,9 +659,18 @@ pub mod keygen {
     /// # Panics
     /// If `glwe.dim.size != 1`.
     pub fn generate_rlwe_public_key(sk: &GlweSecretKey<u64>, glwe: &GlweDef) -> RlwePublicKey<u64> {
+        generate_rlwe_public_key_with_rng(sk, glwe, &mut thread_rng())
+    }
+
+    /// Like [`generate_rlwe_public_key`], but draws the randomness from `rng`.
+    pub fn generate_rlwe_public_key_with_rng(
+        sk: &GlweSecretKey<u64>,
+        glwe: &GlweDef,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> RlwePublicKey<u64> {
         let mut pk = RlwePublicKey::new(glwe);
 
-        rlwe_generate_public_key(&mut pk, sk, glwe);
+        rlwe_generate_public_key_with_rng(&mut pk, sk, glwe, rng);
 
         pk
     }
@@ -336,12 +685,13 @@ pub mod encryption {
             Polynomial, PolynomialRef, RlwePublicKeyRef, TlwePublicEncRandomness,
         },
         ops::encryption::{
-            encrypt_ggsw_ciphertext_scalar, encrypt_rlev_ciphertext,
-            encrypt_secret_glev_ciphertext, trivially_encrypt_glev_ciphertext,
+            encrypt_ggsw_ciphertext_scalar_with_rng, encrypt_rlev_ciphertext_with_rng,
+            encrypt_secret_glev_ciphertext_with_rng, trivially_encrypt_glev_ciphertext,
             trivially_encrypt_lwe_ciphertext,
         },
         CarryBits, GlweDef, LweDef, PlaintextBits, RadixDecomposition, Torus,
     };
+    use rand::{thread_rng, CryptoRng, RngCore};
 
     /// Create an [`LweCiphertext`] encryption of `val` under
     /// [LweSecretKey](crate::entities::LweSecretKey) `sk`.
@@ -363,7 +713,18 @@ pub mod encryption {
         params: &LweDef,
         plaintext_bits: PlaintextBits,
     ) -> LweCiphertext<u64> {
-        sk.encrypt(val, params, plaintext_bits).0
+        encrypt_lwe_secret_with_rng(val, sk, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_lwe_secret`], but draws the randomness from `rng`.
+    pub fn encrypt_lwe_secret_with_rng(
+        val: u64,
+        sk: &LweSecretKeyRef<u64>,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> LweCiphertext<u64> {
+        sk.encrypt_with_rng(val, params, plaintext_bits, rng).0
     }
 
     /// Create a tuple containing an [`LweCiphertext`] encryption of `val`
@@ -393,7 +754,24 @@ pub mod encryption {
         params: &LweDef,
         plaintext_bits: PlaintextBits,
     ) -> (LweCiphertext<u64>, Torus<u64>) {
-        sk.encrypt(val, params, plaintext_bits)
+        encrypt_lwe_secret_and_return_randomness_with_rng(
+            val,
+            sk,
+            params,
+            plaintext_bits,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Like [`encrypt_lwe_secret_and_return_randomness`], but draws the randomness from `rng`.
+    pub fn encrypt_lwe_secret_and_return_randomness_with_rng(
+        val: u64,
+        sk: &LweSecretKeyRef<u64>,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> (LweCiphertext<u64>, Torus<u64>) {
+        sk.encrypt_with_rng(val, params, plaintext_bits, rng)
     }
 
     /// Create an [LweCiphertext] encryption of `val` under the secret
@@ -415,7 +793,18 @@ pub mod encryption {
         params: &LweDef,
         plaintext_bits: PlaintextBits,
     ) -> LweCiphertext<u64> {
-        pk.encrypt(val, params, plaintext_bits).0
+        encrypt_lwe_with_rng(val, pk, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_lwe`], but draws the randomness from `rng`.
+    pub fn encrypt_lwe_with_rng(
+        val: u64,
+        pk: &LwePublicKeyRef<u64>,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> LweCiphertext<u64> {
+        pk.encrypt_with_rng(val, params, plaintext_bits, rng).0
     }
 
     /// Create a tuple containing an [`LweCiphertext`] encryption of `val`
@@ -445,7 +834,24 @@ pub mod encryption {
         params: &LweDef,
         plaintext_bits: PlaintextBits,
     ) -> (LweCiphertext<u64>, TlwePublicEncRandomness<u64>) {
-        pk.encrypt(val, params, plaintext_bits)
+        encrypt_lwe_and_return_randomness_with_rng(
+            val,
+            pk,
+            params,
+            plaintext_bits,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Like [`encrypt_lwe_and_return_randomness`], but draws the randomness from `rng`.
+    pub fn encrypt_lwe_and_return_randomness_with_rng(
+        val: u64,
+        pk: &LwePublicKeyRef<u64>,
+        params: &LweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> (LweCiphertext<u64>, TlwePublicEncRandomness<u64>) {
+        pk.encrypt_with_rng(val, params, plaintext_bits, rng)
     }
 
     /// Create a [`GlweCiphertext`] encryption of `pt` under `sk`.
@@ -469,7 +875,18 @@ pub mod encryption {
         params: &GlweDef,
         plaintext_bits: PlaintextBits,
     ) -> GlweCiphertext<u64> {
-        sk.encode_encrypt_glwe(pt, params, plaintext_bits)
+        encrypt_glwe_with_rng(pt, sk, params, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_glwe`], but draws the randomness from `rng`.
+    pub fn encrypt_glwe_with_rng(
+        pt: &PolynomialRef<u64>,
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> GlweCiphertext<u64> {
+        sk.encode_encrypt_glwe_with_rng(pt, params, plaintext_bits, rng)
     }
 
     /// Create a trivial LWE encryption. Trivial encryptions have no noise and are thus
@@ -675,10 +1092,30 @@ pub mod encryption {
         params: &GlweDef,
         radix: &RadixDecomposition,
         plaintext_bits: PlaintextBits,
+    ) -> GgswCiphertext<u64> {
+        encrypt_ggsw_with_rng(msg, sk, params, radix, plaintext_bits, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_ggsw`], but draws the randomness from `rng`.
+    pub fn encrypt_ggsw_with_rng(
+        msg: u64,
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        plaintext_bits: PlaintextBits,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> GgswCiphertext<u64> {
         let mut result = GgswCiphertext::new(params, radix);
 
-        encrypt_ggsw_ciphertext_scalar(&mut result, msg, sk, params, radix, plaintext_bits);
+        encrypt_ggsw_ciphertext_scalar_with_rng(
+            &mut result,
+            msg,
+            sk,
+            params,
+            radix,
+            plaintext_bits,
+            rng,
+        );
 
         result
     }
@@ -724,10 +1161,21 @@ pub mod encryption {
         sk: &GlweSecretKeyRef<u64>,
         params: &GlweDef,
         radix: &RadixDecomposition,
+    ) -> GlevCiphertext<u64> {
+        encrypt_binary_msg_secret_glev_with_rng(msg, sk, params, radix, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_binary_msg_secret_glev`], but draws the randomness from `rng`.
+    pub fn encrypt_binary_msg_secret_glev_with_rng(
+        msg: &PolynomialRef<u64>,
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> GlevCiphertext<u64> {
         let mut ct = GlevCiphertext::new(params, radix);
 
-        encrypt_secret_glev_ciphertext(&mut ct, msg.as_torus(), sk, params, radix);
+        encrypt_secret_glev_ciphertext_with_rng(&mut ct, msg.as_torus(), sk, params, radix, rng);
 
         ct
     }
@@ -766,10 +1214,21 @@ pub mod encryption {
         pk: &RlwePublicKeyRef<u64>,
         params: &GlweDef,
         radix: &RadixDecomposition,
+    ) -> GlevCiphertext<u64> {
+        encrypt_binary_msg_rlev_with_rng(msg, pk, params, radix, &mut thread_rng())
+    }
+
+    /// Like [`encrypt_binary_msg_rlev`], but draws the randomness from `rng`.
+    pub fn encrypt_binary_msg_rlev_with_rng(
+        msg: &PolynomialRef<u64>,
+        pk: &RlwePublicKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        rng: &mut (impl CryptoRng + RngCore),
     ) -> GlevCiphertext<u64> {
         let mut ct = GlevCiphertext::new(params, radix);
 
-        encrypt_rlev_ciphertext(&mut ct, msg.as_torus(), pk, params, radix);
+        encrypt_rlev_ciphertext_with_rng(&mut ct, msg.as_torus(), pk, params, radix, rng);
 
         ct
     }
@@ -781,9 +1240,10 @@ pub mod fft {
 
     use crate::{
         entities::{
-            BootstrapKeyFft, BootstrapKeyRef, GgswCiphertextFft, GgswCiphertextRef,
-            GlweCiphertextFft, GlweCiphertextRef,
+            BootstrapKeyFft, BootstrapKeyNtt, BootstrapKeyRef, GgswCiphertextFft,
+            GgswCiphertextNtt, GgswCiphertextRef, GlweCiphertextFft, GlweCiphertextRef,
         },
+        goldilocks_field::Fg,
         GlweDef, LweDef, RadixDecomposition,
     };
 
@@ -854,6 +1314,60 @@ pub mod fft {
 
         bsk_fft
     }
+
+    /// Take the number theoretic transform of a
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext). External
+    /// products with the result are exact. See
+    /// [`ntt_ops`](crate::ops::ntt_ops). Use the result in
+    /// [`cmux_ntt`](super::evaluation::cmux_ntt).
+    ///
+    /// # Remarks
+    /// `glwe` and `radix` must be the same parameters that produced `ggsw`.
+    ///
+    /// # Panics
+    /// If `glwe` and `radix` don't correspond with `ggsw`.
+    /// If `glwe` or `radix` are invalid.
+    /// If the NTT isn't exact for `glwe` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn ntt_ggsw(
+        ggsw: &GgswCiphertextRef<u64>,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNtt<Fg> {
+        let mut ntt = GgswCiphertextNtt::new(glwe, radix);
+
+        ggsw.ntt(&mut ntt, glwe, radix);
+
+        ntt
+    }
+
+    /// Take the number theoretic transform of a
+    /// [BootstrapKey](crate::entities::BootstrapKey). The resulting
+    /// [`BootstrapKeyNtt`] may be used in
+    /// [`univariate_programmable_bootstrap_ntt`](super::evaluation::univariate_programmable_bootstrap_ntt),
+    /// which is slower than bootstrapping with a [`BootstrapKeyFft`] but
+    /// bit-exact.
+    ///
+    /// # Remarks
+    /// `glwe` and `radix` must be the same parameters that produced `bsk`.
+    ///
+    /// # Panics
+    /// If `glwe` and `radix` don't correspond with `bsk`.
+    /// If `glwe` or `radix` are invalid.
+    /// If the NTT isn't exact for `glwe` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn ntt_bootstrap_key(
+        bsk: &BootstrapKeyRef<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> BootstrapKeyNtt<Fg> {
+        let mut bsk_ntt = BootstrapKeyNtt::new(lwe, glwe, radix);
+
+        bsk.ntt(&mut bsk_ntt, lwe, glwe, radix);
+
+        bsk_ntt
+    }
 }
 
 /// TFHE operations for performing computation.
@@ -862,11 +1376,14 @@ pub mod evaluation {
 
     use crate::{
         entities::{
-            BootstrapKeyFft, BootstrapKeyFftRef, CircuitBootstrappingKeyswitchKeysRef,
-            GgswCiphertext, GgswCiphertextFftRef, GlevCiphertext, GlevCiphertextRef,
-            GlweCiphertext, GlweCiphertextRef, LweCiphertext, LweCiphertextRef, LweKeyswitchKeyRef,
-            UnivariateLookupTableRef,
+            BootstrapKeyFft, BootstrapKeyFftRef, BootstrapKeyNttRef,
+            CircuitBootstrappingKeyswitchKeysRef, GgswCiphertext, GgswCiphertextFftRef,
+            GgswCiphertextNttRef, GgswCmuxKeyRef, GlevCiphertext, GlevCiphertextRef,
+            GlweCiphertext, GlweCiphertextRef, LweCiphertext, LweCiphertextList, LweCiphertextRef,
+            LweKeyswitchKeyRef, PublicFunctionalKeyswitchKeyRef, UnivariateLookupTableRef,
         },
+        goldilocks_field::Fg,
+        ops::leveled::MuxTree,
         GlweDef, LweDef, RadixDecomposition,
     };
 
@@ -904,6 +1421,29 @@ pub mod evaluation {
         result
     }
 
+    /// Like [`cmux`], but with a [`GgswCiphertextNtt`](crate::entities::GgswCiphertextNtt)
+    /// selector from [`ntt_ggsw`](super::fft::ntt_ggsw). The result is bit-exact.
+    ///
+    /// # Panics
+    /// If `params` doesn't correspond with `b_ntt`, `d_0`, `d_1`.
+    /// If `radix` doesn't correspond with `b_ntt`.
+    /// If `radix` or `params` are invalid.
+    /// If the NTT isn't exact for `params` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn cmux_ntt(
+        b_ntt: &GgswCiphertextNttRef<Fg>,
+        d_0: &GlweCiphertextRef<u64>,
+        d_1: &GlweCiphertextRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweCiphertext<u64> {
+        let mut result = GlweCiphertext::new(params);
+
+        crate::ops::ntt_ops::cmux(&mut result, d_0, d_1, b_ntt, params, radix);
+
+        result
+    }
+
     /// Perform a multiplexing operation over [`GlevCiphertext`]s.
     /// When `b_fft` encrypts a zero polynomial, the resulting [`GlevCiphertext`] will
     /// the same message as `d_0`. When `b_fft` encrypts the 1 polynomial, the result will
@@ -939,6 +1479,49 @@ pub mod evaluation {
         result
     }
 
+    /// Evaluate the boolean functions in `tree` on the encrypted bits in `selectors`,
+    /// returning a [`GgswCiphertext`] for each output. See
+    /// [`MuxTree`] for how to build a tree from truth tables.
+    ///
+    /// # Remarks
+    /// Unlike [`cmux`] trees, which produce GLWE ciphertexts, the results can be fed
+    /// (after taking their FFT using [`fft_ggsw`](super::fft::fft_ggsw)) as selectors to
+    /// another tree without circuit bootstrapping. Each mux costs `(k + 1) * radix.count`
+    /// [`cmux`] operations.
+    ///
+    /// `key` must have been generated with
+    /// [`generate_ggsw_cmux_key`](super::keygen::generate_ggsw_cmux_key) under `params` and
+    /// `radix`, which is also the radix of the results. `ggsw_radix` must correspond to the
+    /// `selectors`. When chaining trees, both radixes are typically the same.
+    ///
+    /// # Panics
+    /// If `selectors.len() != tree.num_inputs()`.
+    /// If `params`, `radix` or `ggsw_radix` are invalid.
+    /// If `key` doesn't correspond with `params` and `radix`.
+    /// If any of the `selectors` don't correspond with `params` and `ggsw_radix`.
+    pub fn ggsw_cmux_tree(
+        tree: &MuxTree,
+        selectors: &[&GgswCiphertextFftRef<Complex<f64>>],
+        key: &GgswCmuxKeyRef<u64>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+        ggsw_radix: &RadixDecomposition,
+    ) -> Vec<GgswCiphertext<u64>> {
+        let mut result = vec![GgswCiphertext::new(params, radix); tree.outputs().len()];
+
+        crate::ops::leveled::evaluate_ggsw_cmux_tree(
+            &mut result,
+            tree,
+            selectors,
+            key,
+            params,
+            radix,
+            ggsw_radix,
+        );
+
+        result
+    }
+
     #[allow(clippy::too_many_arguments)]
     /// Perform a programmable bootstrapping operation. Bootstrapping takes
     /// `input` and produces a new ciphertext with a fixed noise level, applying
@@ -981,6 +1564,83 @@ pub mod evaluation {
         out
     }
 
+    /// Like [`univariate_programmable_bootstrap`], but with a
+    /// [`BootstrapKeyNtt`](crate::entities::BootstrapKeyNtt) from
+    /// [`ntt_bootstrap_key`](super::fft::ntt_bootstrap_key).
+    ///
+    /// # Remarks
+    /// The external products are computed exactly, so the result is bit-exact
+    /// across platforms. This is slower than bootstrapping with a
+    /// [`BootstrapKeyFft`].
+    ///
+    /// # Panics
+    /// If `lwe`, `glwe`, or `radix` parameters are invalid.
+    /// If `input` doesn't correspond to `lwe` parameters.
+    /// If `bsk` doesn't correspond to `lwe`, `glwe`, `radix` parameters.
+    /// If `lut` doesn't correspond to `glwe` parameters.
+    /// If the NTT isn't exact for `glwe` and `radix`. See
+    /// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+    pub fn univariate_programmable_bootstrap_ntt(
+        input: &LweCiphertextRef<u64>,
+        lut: &UnivariateLookupTableRef<u64>,
+        bsk: &BootstrapKeyNttRef<Fg>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> LweCiphertext<u64> {
+        let mut out = LweCiphertext::new(&glwe.as_lwe_def());
+
+        crate::ops::bootstrapping::programmable_bootstrap_univariate_ntt(
+            &mut out, input, lut, bsk, lwe, glwe, radix,
+        );
+
+        out
+    }
+
+    /// Perform a programmable bootstrapping operation that evaluates `lut_count` univariate
+    /// functions of `input` with a single blind rotation. The `i`-th ciphertext in the
+    /// returned [`LweCiphertextList`] encrypts the `i`-th function applied to `input`'s
+    /// message.
+    ///
+    /// Like [`univariate_programmable_bootstrap`], the results are encrypted under the
+    /// [`LweSecretKey`](crate::entities::LweSecretKey) defined by `glwe` interpreted as an
+    /// [`LweDef`].
+    ///
+    /// # Remarks
+    /// Create `lut` from `lut_count` functions using
+    /// [`UnivariateLookupTable::trivivial_multifunctional`](crate::entities::UnivariateLookupTable::trivivial_multifunctional).
+    /// `lwe`, `glwe`, and `radix` parameters must be the same as those used when
+    /// first creating the `bsk`.
+    ///
+    /// Evaluating `lut_count` functions costs about as much as evaluating one, but
+    /// uses `ceil(log2(lut_count))` bits of the input's noise margin. See
+    /// [`programmable_bootstrap_many_lut`](crate::ops::bootstrapping::programmable_bootstrap_many_lut)
+    /// for details.
+    ///
+    /// # Panics
+    /// If `lut_count` is 0.
+    /// If `lwe`, `glwe`, or `radix` parameters are invalid.
+    /// If `input` doesn't correspond to `lwe` parameters.
+    /// If `bsk` doesn't correspond to `lwe`, `glwe`, `radix` parameters.
+    /// If `lut` doesn't correspond to `glwe` parameters.
+    pub fn many_lut_programmable_bootstrap(
+        input: &LweCiphertextRef<u64>,
+        lut: &UnivariateLookupTableRef<u64>,
+        lut_count: usize,
+        bsk: &BootstrapKeyFftRef<Complex<f64>>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> LweCiphertextList<u64> {
+        let mut out = LweCiphertextList::new(&glwe.as_lwe_def(), lut_count);
+
+        crate::ops::bootstrapping::programmable_bootstrap_many_lut(
+            &mut out, input, lut, lut_count, bsk, lwe, glwe, radix,
+        );
+
+        out
+    }
+
     #[allow(clippy::too_many_arguments)]
     /// Perform a circuit bootstrapping operation. Circuit bootstrapping takes
     /// `input` [LweCiphertext] encrypted under a [LweSecretKey](crate::entities::LweSecretKey)
@@ -1080,6 +1740,41 @@ pub mod evaluation {
         new_ct
     }
 
+    /// Pack up to `N` [`LweCiphertext`]s into a single [`GlweCiphertext`], where `N` is
+    /// `to_glwe.dim.polynomial_degree`. The `i`-th coefficient of the result's message is the
+    /// message of `inputs[i]` and the remaining coefficients are zero.
+    ///
+    /// # Remarks
+    /// `pufksk` must come from
+    /// [generate_lwe_packing_key](super::keygen::generate_lwe_packing_key) with the same
+    /// `from_lwe`, `to_glwe` and `radix`. The result is encrypted under the key's `to_sk`.
+    ///
+    /// Use [sample_extract] to recover individual [`LweCiphertext`]s from the result.
+    ///
+    /// # Panics
+    /// If `inputs.len() > to_glwe.dim.polynomial_degree`.
+    /// If `pufksk` or `inputs` don't correspond with `from_lwe`, `to_glwe` and `radix`.
+    pub fn pack_lwes(
+        inputs: &[&LweCiphertextRef<u64>],
+        pufksk: &PublicFunctionalKeyswitchKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweCiphertext<u64> {
+        let mut output = GlweCiphertext::new(to_glwe);
+
+        crate::ops::keyswitch::lwe_packing::pack_lwes(
+            &mut output,
+            inputs,
+            pufksk,
+            from_lwe,
+            to_glwe,
+            radix,
+        );
+
+        output
+    }
+
     /// Create an [`LweCiphertext`] encrypting the `coeff_idx`-th coefficient in `ct`'s message.
     ///
     /// # Remarks
@@ -20,6 +20,10 @@ pub mod ops;
 pub mod params;
 pub use params::*;
 
+/// Analytic estimates of the noise and failure probability of TFHE
+/// operations for a given set of parameters.
+pub mod noise;
+
 /// Math operations on various math primitives such as polynomials.
 pub mod math;
 pub use math::*;
@@ -34,6 +38,9 @@ mod scratch;
 /// objects as you would expect from a Rust API.
 pub mod high_level;
 
+/// Versioned serialization of keys and ciphertexts tagged with their parameters.
+pub mod serialization;
+
 /// Zero Knowledge proofs for TFHE.
 #[cfg(feature = "logproof")]
 pub mod zkp;
@@ -3,3 +3,6 @@ pub mod cyclic;
 
 /// FFT based operations over twisted cyclotomics.
 pub mod negacyclic;
+
+/// NTT based operations over the Goldilocks field.
+pub mod ntt;
@@ -0,0 +1,295 @@
+use std::sync::OnceLock;
+
+use sunscreen_math::One;
+
+use crate::{goldilocks_field::Fg, FrequencyTransform, Inverse, RootOfUnity};
+
+/// The number of bits in each limb a torus polynomial is split into before
+/// taking its NTT. See [`NTT_LIMBS`].
+pub const NTT_LIMB_BITS: usize = 16;
+
+/// The number of limbs a torus polynomial is split into before taking its
+/// NTT.
+///
+/// # Remarks
+/// Products computed with the NTT are exact modulo the Goldilocks prime `p`,
+/// while torus products are taken modulo `2^64`. Splitting one of the operands
+/// into [`NTT_LIMB_BITS`]-bit limbs keeps each limb's product below `p / 2`
+/// so it can be lifted back to the integers and recombined modulo `2^64`
+/// without error.
+pub const NTT_LIMBS: usize = 64 / NTT_LIMB_BITS;
+
+/// The largest log N of a [NegacyclicNtt].
+///
+/// # Remarks
+/// The Goldilocks field has `2^32`-th roots of unity, so it supports
+/// negacyclic NTTs up to `N = 2^31`.
+pub const MAX_NTT_LOG_N: usize = 31;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_NTT: OnceLock<NegacyclicNtt> = OnceLock::new();
+
+static NTT_CACHE: [OnceLock<NegacyclicNtt>; MAX_NTT_LOG_N + 1] = [EMPTY_NTT; MAX_NTT_LOG_N + 1];
+
+/// Get a [NegacyclicNtt] for a given log N, or `None` if `log_n` exceeds
+/// [`MAX_NTT_LOG_N`].
+///
+/// # Remarks
+/// Each size is created the first time it's requested.
+pub fn get_ntt(log_n: usize) -> Option<&'static NegacyclicNtt> {
+    let ntt = NTT_CACHE.get(log_n)?;
+
+    Some(ntt.get_or_init(|| NegacyclicNtt::new(0x1 << log_n)))
+}
+
+/// Perform a number theoretic transform over the Goldilocks field so points
+/// can be used for negacyclic convolution.
+///
+/// # Remarks
+/// Unlike the [`TwistedFft`](super::negacyclic::TwistedFft), products in the
+/// NTT domain are exact. The twist by a 2N-th root of unity is merged into the
+/// butterflies, so the transformed values are in bit-reversed order. This
+/// doesn't matter for pointwise products.
+///
+/// See `<https://eprint.iacr.org/2016/504>` for the algorithm.
+pub struct NegacyclicNtt {
+    // Powers of a primitive 2N-th root of unity, in bit-reversed order.
+    psi_rev: Vec<Fg>,
+    psi_inv_rev: Vec<Fg>,
+    n_inv: Fg,
+}
+
+impl NegacyclicNtt {
+    /// Create a new [NegacyclicNtt] with the given size.
+    ///
+    /// # Panics
+    /// If `n` isn't a power of two or exceeds `2^`[`MAX_NTT_LOG_N`].
+    pub fn new(n: usize) -> Self {
+        assert!(n.is_power_of_two());
+
+        let log_n = n.ilog2();
+
+        assert!(log_n as usize <= MAX_NTT_LOG_N);
+
+        let psi = Fg::nth_root_of_unity(2 * n as u64);
+        let psi_inv = psi.inverse();
+
+        let bit_reversed_powers = |root: Fg| {
+            let mut powers = vec![Fg::one(); n];
+
+            for i in 1..n {
+                powers[i] = powers[i - 1] * root;
+            }
+
+            (0..n)
+                .map(|i| {
+                    let rev = i.reverse_bits().checked_shr(usize::BITS - log_n);
+
+                    powers[rev.unwrap_or(0)]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            psi_rev: bit_reversed_powers(psi),
+            psi_inv_rev: bit_reversed_powers(psi_inv),
+            n_inv: Fg::from(n as u64).inverse(),
+        }
+    }
+
+    /// The size of the transform.
+    pub fn len(&self) -> usize {
+        self.psi_rev.len()
+    }
+
+    /// Whether the transform has zero size. Always false.
+    pub fn is_empty(&self) -> bool {
+        self.psi_rev.is_empty()
+    }
+
+    /// Compute the forward transform of `data` in place.
+    pub fn forward_in_place(&self, data: &mut [Fg]) {
+        assert_eq!(data.len(), self.len());
+
+        let n = data.len();
+        let mut t = n;
+        let mut m = 1;
+
+        // Cooley-Tukey butterflies.
+        while m < n {
+            t /= 2;
+
+            for i in 0..m {
+                let s = self.psi_rev[m + i];
+                let (lo, hi) = data[2 * i * t..2 * (i + 1) * t].split_at_mut(t);
+
+                for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                    let x = *u;
+                    let y = *v * s;
+
+                    *u = x + y;
+                    *v = x - y;
+                }
+            }
+
+            m *= 2;
+        }
+    }
+
+    /// Compute the inverse transform of `data` in place.
+    pub fn reverse_in_place(&self, data: &mut [Fg]) {
+        assert_eq!(data.len(), self.len());
+
+        let n = data.len();
+        let mut t = 1;
+        let mut m = n;
+
+        // Gentleman-Sande butterflies.
+        while m > 1 {
+            let h = m / 2;
+
+            for i in 0..h {
+                let s = self.psi_inv_rev[h + i];
+                let (lo, hi) = data[2 * i * t..2 * (i + 1) * t].split_at_mut(t);
+
+                for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                    let x = *u;
+                    let y = *v;
+
+                    *u = x + y;
+                    *v = (x - y) * s;
+                }
+            }
+
+            t *= 2;
+            m = h;
+        }
+
+        for x in data.iter_mut() {
+            *x = *x * self.n_inv;
+        }
+    }
+}
+
+impl FrequencyTransform for NegacyclicNtt {
+    type BaseRepr = Fg;
+    type FrequencyRepr = Fg;
+
+    fn forward(&self, data: &[Fg], output: &mut [Fg]) {
+        output.copy_from_slice(data);
+        self.forward_in_place(output);
+    }
+
+    fn reverse(&self, data: &[Fg], output: &mut [Fg]) {
+        output.copy_from_slice(data);
+        self.reverse_in_place(output);
+    }
+}
+
+/// Map a signed integer into the Goldilocks field.
+#[inline(always)]
+pub fn fg_from_signed(x: i64) -> Fg {
+    if x < 0 {
+        -Fg::from(x.unsigned_abs())
+    } else {
+        Fg::from(x as u64)
+    }
+}
+
+/// Lift a value in the Goldilocks field to the signed integer with the
+/// smallest magnitude that maps to it.
+#[inline(always)]
+pub fn fg_to_signed(x: Fg) -> i64 {
+    const HALF_P: u64 = crate::goldilocks_field::GOLDILOCKS_PRIME / 2;
+
+    let x = u64::from(x);
+
+    if x > HALF_P {
+        -((crate::goldilocks_field::GOLDILOCKS_PRIME - x) as i64)
+    } else {
+        x as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use sunscreen_math::Zero;
+
+    use crate::goldilocks_field::GOLDILOCKS_PRIME;
+
+    use super::*;
+
+    fn random_fg(n: usize) -> Vec<Fg> {
+        (0..n)
+            .map(|_| Fg::from(thread_rng().next_u64() % GOLDILOCKS_PRIME))
+            .collect()
+    }
+
+    #[test]
+    fn can_roundtrip_ntt() {
+        for log_n in 0..15 {
+            let ntt = get_ntt(log_n).unwrap();
+            let a = random_fg(1 << log_n);
+
+            let mut a_ntt = vec![Fg::zero(); a.len()];
+            let mut actual = a_ntt.clone();
+
+            ntt.forward(&a, &mut a_ntt);
+            ntt.reverse(&a_ntt, &mut actual);
+
+            assert_eq!(actual, a);
+        }
+    }
+
+    #[test]
+    fn ntt_computes_negacyclic_product() {
+        let n = 64;
+        let ntt = get_ntt(6).unwrap();
+
+        let a = random_fg(n);
+        let b = random_fg(n);
+
+        let mut expected = vec![Fg::zero(); n];
+
+        for (i, a) in a.iter().enumerate() {
+            for (j, b) in b.iter().enumerate() {
+                if i + j < n {
+                    expected[i + j] = expected[i + j] + a * b;
+                } else {
+                    expected[i + j - n] = expected[i + j - n] - a * b;
+                }
+            }
+        }
+
+        let mut a_ntt = a.clone();
+        let mut b_ntt = b.clone();
+
+        ntt.forward_in_place(&mut a_ntt);
+        ntt.forward_in_place(&mut b_ntt);
+
+        let mut actual = a_ntt
+            .iter()
+            .zip(b_ntt.iter())
+            .map(|(a, b)| a * b)
+            .collect::<Vec<_>>();
+
+        ntt.reverse_in_place(&mut actual);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn no_ntt_beyond_goldilocks_roots_of_unity() {
+        assert!(get_ntt(MAX_NTT_LOG_N + 1).is_none());
+        assert!(get_ntt(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn can_lift_signed_values() {
+        for x in [0, 1, -1, i64::MAX / 4, -(i64::MAX / 4)] {
+            assert_eq!(fg_to_signed(fg_from_signed(x)), x);
+        }
+    }
+}
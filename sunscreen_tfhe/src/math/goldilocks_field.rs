@@ -1,21 +1,27 @@
 use std::ops::{Add, Mul, Neg, Sub};
 
 use num::traits::{WrappingAdd, WrappingMul, WrappingNeg, WrappingSub};
+use serde::{Deserialize, Serialize};
 use sunscreen_math::{refify_binary_op, One, Zero};
 
-use crate::{Inverse, Pow, RootOfUnity};
+use crate::{scratch::Pod, Inverse, Pow, RootOfUnity};
 
 /// 2^64 - 2^32 + 1
 pub const GOLDILOCKS_PRIME: u64 = 0xFFFFFFFF00000001;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[repr(transparent)]
+#[serde(transparent)]
 /// A value in the Goldilocks field (F_p where p = 2^64 - 2^32 + 1).
 /// See
 ///  https://cp4space.hatsya.com/2021/09/01/an-efficient-prime-for-number-theoretic-transforms/
 /// for why this field is so magical.
 pub struct Fg(u64);
 
+// Every bit pattern is a valid Fg in memory. Values at or above the prime
+// only arise in scratch memory that gets overwritten before being read.
+unsafe impl Pod for Fg {}
+
 impl RootOfUnity for Fg {
     fn nth_root_of_unity(n: u64) -> Self {
         assert!(
@@ -36,7 +42,7 @@ impl RootOfUnity for Fg {
 impl Fg {
     /// Returns `x % GOLDILOCKS_PRIME`
     pub fn new(x: u64) -> Self {
-        if x > GOLDILOCKS_PRIME {
+        if x >= GOLDILOCKS_PRIME {
             Self(x - GOLDILOCKS_PRIME)
         } else {
             Self(x)
@@ -44,6 +50,7 @@ impl Fg {
     }
 
     #[inline]
+    /// Compute `self + rhs` and don't reduce the result.
     pub fn unreduced_add(self, rhs: Self) -> Fg96 {
         let (c, carry) = self.0.overflowing_add(rhs.0);
 
@@ -54,11 +61,13 @@ impl Fg {
     }
 
     #[inline]
+    /// Compute `self - rhs` and don't reduce the result.
     pub fn unreduced_sub(self, rhs: Self) -> Fg96 {
         self.unreduced_add(Fg(GOLDILOCKS_PRIME - rhs.0))
     }
 
     #[inline]
+    /// Compute `self * rhs` and don't reduce the result.
     pub fn unreduced_mul(self, rhs: Self) -> Fg159 {
         let res = self.0 as u128 * rhs.0 as u128;
 
@@ -86,6 +95,12 @@ impl From<u64> for Fg {
     }
 }
 
+impl From<Fg> for u64 {
+    fn from(value: Fg) -> Self {
+        value.0
+    }
+}
+
 #[refify_binary_op]
 impl Add<&Fg> for &Fg {
     type Output = Fg;
@@ -348,6 +363,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn new_reduces() {
+        assert_eq!(Fg::new(GOLDILOCKS_PRIME), Fg::zero());
+        assert_eq!(Fg::new(GOLDILOCKS_PRIME + 1), Fg::one());
+        assert_eq!(Fg::new(u64::MAX), Fg(u64::MAX - GOLDILOCKS_PRIME));
+    }
+
     #[test]
     fn nth_root_of_unity() {
         for i in 1..16u64 {
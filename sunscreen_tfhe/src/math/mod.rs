@@ -5,7 +5,8 @@ pub use sunscreen_math::{One, Zero};
 /// FFT based operations.
 pub mod fft;
 
-mod goldilocks_field;
+/// Arithmetic in the Goldilocks field used by the number theoretic transform.
+pub mod goldilocks_field;
 
 /// Math operations on polynomials.
 pub mod polynomial;
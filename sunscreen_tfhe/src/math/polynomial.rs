@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
-use num::traits::MulAdd;
+use num::traits::{MulAdd, WrappingNeg};
 
 use crate::{
     dst::{AsMutSlice, AsSlice, FromMutSlice},
@@ -38,6 +38,42 @@ where
     }
 }
 
+/// Compute `c = a(X^t)`, the automorphism `X -> X^t` of Z\[X\]/(X^N + 1)
+/// applied to `a`.
+///
+/// # Remarks
+/// Since `X^N = -1`, coefficient `i` of `a` moves to index `i * t mod N`,
+/// negated when `i * t mod 2N >= N`.
+///
+/// # Panics
+/// If `t` is even, in which case the map isn't an automorphism.
+/// If `a.len() != c.len()` or the length isn't a power of 2.
+pub fn polynomial_automorphism<S>(c: &mut PolynomialRef<S>, a: &PolynomialRef<S>, t: usize)
+where
+    S: Clone + Copy + WrappingNeg,
+{
+    assert!(a.len().is_power_of_two());
+    assert_eq!(a.len(), c.len());
+    assert_eq!(t % 2, 1, "Automorphism index must be odd");
+
+    let len = a.len();
+
+    // Polynomial's length is a power of 2, so use a mask to reduce mod 2N.
+    let mask = 2 * len - 1;
+
+    let coeffs = c.coeffs_mut();
+
+    for (i, x) in a.coeffs().iter().enumerate() {
+        let index = (i * (t & mask)) & mask;
+
+        if index >= len {
+            coeffs[index - len] = x.wrapping_neg();
+        } else {
+            coeffs[index] = *x;
+        }
+    }
+}
+
 /// Compute `c = a * s` where `s` is scalar.
 pub fn polynomial_scalar_mul<S, T, U>(c: &mut PolynomialRef<S>, a: &PolynomialRef<T>, s: U)
 where
@@ -111,6 +147,78 @@ where
     }
 }
 
+/// Compute `c = a + b` where a, b, and c are polynomials over the torus.
+///
+/// # Remarks
+/// Unlike [`polynomial_add`], this uses vectorized kernels where available.
+pub fn torus_polynomial_add<S>(
+    c: &mut PolynomialRef<Torus<S>>,
+    a: &PolynomialRef<Torus<S>>,
+    b: &PolynomialRef<Torus<S>>,
+) where
+    S: TorusOps,
+{
+    S::slice_wrapping_add(
+        bytemuck::cast_slice_mut(c.coeffs_mut()),
+        bytemuck::cast_slice(a.coeffs()),
+        bytemuck::cast_slice(b.coeffs()),
+    );
+}
+
+/// Compute `c = a - b` where a, b, and c are polynomials over the torus.
+///
+/// # Remarks
+/// Unlike [`polynomial_sub`], this uses vectorized kernels where available.
+pub fn torus_polynomial_sub<S>(
+    c: &mut PolynomialRef<Torus<S>>,
+    a: &PolynomialRef<Torus<S>>,
+    b: &PolynomialRef<Torus<S>>,
+) where
+    S: TorusOps,
+{
+    S::slice_wrapping_sub(
+        bytemuck::cast_slice_mut(c.coeffs_mut()),
+        bytemuck::cast_slice(a.coeffs()),
+        bytemuck::cast_slice(b.coeffs()),
+    );
+}
+
+/// Polynomial addition in place over the torus. This is equivalent to
+/// `a += b` for each coefficient in the polynomial.
+///
+/// # Remarks
+/// Unlike [`polynomial_add_assign`], this uses vectorized kernels where
+/// available.
+pub fn torus_polynomial_add_assign<S>(
+    lhs: &mut PolynomialRef<Torus<S>>,
+    rhs: &PolynomialRef<Torus<S>>,
+) where
+    S: TorusOps,
+{
+    S::slice_wrapping_add_assign(
+        bytemuck::cast_slice_mut(lhs.coeffs_mut()),
+        bytemuck::cast_slice(rhs.coeffs()),
+    );
+}
+
+/// Polynomial subtraction in place over the torus. This is equivalent to
+/// `a -= b` for each coefficient in the polynomial.
+///
+/// # Remarks
+/// Unlike [`polynomial_sub_assign`], this uses vectorized kernels where
+/// available.
+pub fn torus_polynomial_sub_assign<S>(
+    lhs: &mut PolynomialRef<Torus<S>>,
+    rhs: &PolynomialRef<Torus<S>>,
+) where
+    S: TorusOps,
+{
+    S::slice_wrapping_sub_assign(
+        bytemuck::cast_slice_mut(lhs.coeffs_mut()),
+        bytemuck::cast_slice(rhs.coeffs()),
+    );
+}
+
 /// Compute `c += a \[*\] b` where `a` in Z\[X\]/f and `c, b` in T\[X\]/f, and
 /// \[*\] is the external product between these rings.
 pub fn polynomial_external_mad<S>(
@@ -282,6 +390,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn automorphism_is_ring_homomorphism() {
+        let len = 64;
+
+        for t in (1..2 * len).step_by(2) {
+            let a = (0..len)
+                .map(|_| Torus::from(thread_rng().next_u64()))
+                .collect::<Polynomial<_>>();
+            let b = (0..len)
+                .map(|_| thread_rng().next_u64() % 16)
+                .collect::<Polynomial<_>>();
+
+            let mut ab = Polynomial::<Torus<u64>>::zero(len);
+            polynomial_external_mad(&mut ab, &a, &b);
+
+            let mut a_t = Polynomial::zero(len);
+            let mut b_t = Polynomial::zero(len);
+            let mut ab_t = Polynomial::zero(len);
+            polynomial_automorphism(&mut a_t, &a, t);
+            polynomial_automorphism(&mut b_t, &b, t);
+            polynomial_automorphism(&mut ab_t, &ab, t);
+
+            let mut expected = Polynomial::<Torus<u64>>::zero(len);
+            polynomial_external_mad(&mut expected, &a_t, &b_t);
+
+            assert_eq!(ab_t, expected);
+        }
+    }
+
     #[test]
     fn can_roundtrip_polynomial() {
         let poly = (0..1024u64).collect::<Vec<_>>();
@@ -32,7 +32,7 @@ impl<S: TorusOps> ScalarRadixIterator<S> {
 }
 
 #[inline(always)]
-fn get_next_digit<S: TorusOps>(cur: &mut S, radix_log: usize) -> S {
+pub(crate) fn get_next_digit<S: TorusOps>(cur: &mut S, radix_log: usize) -> S {
     let mask = S::from_u64((0x1u64 << radix_log) - 1);
 
     // Interpreting the digits over [-B/2,B/2) reduces noise by half a bit on average.
@@ -103,14 +103,11 @@ where
 
         self.level += 1;
 
-        for (s, r) in self
-            .scratch
-            .coeffs_mut()
-            .iter_mut()
-            .zip(dst.coeffs_mut().iter_mut())
-        {
-            *r = get_next_digit(s, self.radix.radix_log.0);
-        }
+        S::slice_next_radix_digit(
+            self.scratch.coeffs_mut(),
+            dst.coeffs_mut(),
+            self.radix.radix_log.0,
+        );
 
         Some(())
     }
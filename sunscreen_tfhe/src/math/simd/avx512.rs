@@ -0,0 +1,280 @@
+use num::Complex;
+
+use std::{arch::asm, sync::OnceLock};
+
+use super::scalar;
+
+// AVX512 intrinsics aren't available on our MSRV, so these kernels are
+// written in assembly. Each kernel processes a full 512-bit register at a
+// time and hands any remainder off to the scalar implementation.
+
+/// Whether the running CPU supports AVX512F.
+#[inline(always)]
+pub fn available() -> bool {
+    static AVX512_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    // Unlike querying CPUID directly, this also checks the OS saves the AVX512
+    // registers on context switches.
+    *AVX512_AVAILABLE.get_or_init(|| std::arch::is_x86_feature_detected!("avx512f"))
+}
+
+/// Compute vector `c += a * b` over `&[Complex<f64>]`.
+///
+/// # Safety
+/// The CPU must support AVX512F.
+///
+/// The lengths of c, a, and b must be equal or UB may result.
+#[inline(always)]
+pub unsafe fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    let mut i = 0;
+
+    // Complex<T> is declared as repr(C), so the location of re and im are guaranteed
+    // at address offsets 0 and 8 for Complex<f64>. This allows us to treat
+    // &[Complex<f64>] as &[f64] for the below asm snippet.
+    let a_ptr = a.as_ptr() as *const f64;
+    let b_ptr = b.as_ptr() as *const f64;
+    let c_ptr = c.as_mut_ptr() as *mut f64;
+
+    // Each complex is 2 f64 values.
+    while i + 16 <= 2 * c.len() {
+        // This snippet reads 2 vectors of 4 complex numbers from a, b, c and computes
+        // stores the complex multiply-add result to c. Thus, it iterates over 16 f64
+        // elements from each vector at a time.
+        asm!(
+            // Load 2 __m512d of Complex<f64> from a
+            "vmovupd zmm0, [{a_ptr}+8*{i}]",
+            "vmovupd zmm1, [{a_ptr}+8*{i}+64]",
+            "vshufpd zmm2, zmm0, zmm1, $0",   // Extract the re(a) into zmm2
+            "vshufpd zmm3, zmm0, zmm1, $255", // Extract the im(a) into zmm3
+            // Load 2 __m512d of Complex<f64> from b
+            "vmovupd zmm0, [{b_ptr}+8*{i}]",
+            "vmovupd zmm1, [{b_ptr}+8*{i}+64]",
+            "vshufpd zmm4, zmm0, zmm1, $0",   // Extract the re(b) into zmm4
+            "vshufpd zmm5, zmm0, zmm1, $255", // Extract the im(b) into zmm5
+            // Load 2 __m512d of Complex<f64> from c
+            "vmovupd zmm0, [{c_ptr}+8*{i}]",
+            "vmovupd zmm1, [{c_ptr}+8*{i}+64]",
+            "vshufpd zmm6, zmm0, zmm1, $0",   // Extract the re(c) into zmm6
+            "vshufpd zmm7, zmm0, zmm1, $255", // Extract the im(c) into zmm7
+            "vfmadd231pd zmm6, zmm2, zmm4",   // re(c) += re(a) * re(b)
+            "vfmadd231pd zmm7, zmm2, zmm5",   // im(c) += re(a) * im(b)
+            "vfnmadd231pd zmm6, zmm3, zmm5",  // re(c) -= im(a) * im(b)
+            "vfmadd231pd zmm7, zmm3, zmm4",   // im(c) += im(a) * re(b)
+            "vshufpd zmm0, zmm6, zmm7, $0",   // Repack the lower 4 Complex<f64>s
+            "vshufpd zmm1, zmm6, zmm7, $255", // Repack the upper 4 Complex<f64>s
+            "vmovupd [{c_ptr}+8*{i}], zmm0",    // Write the repacked values back.
+            "vmovupd [{c_ptr}+8*{i}+64], zmm1", // Write the repacked values back.
+            a_ptr = in(reg) a_ptr,
+            b_ptr = in(reg) b_ptr,
+            c_ptr = in(reg) c_ptr,
+            i = in(reg) i,
+            out("zmm0") _, // Indicate our clobbers
+            out("zmm1") _,
+            out("zmm2") _,
+            out("zmm3") _,
+            out("zmm4") _,
+            out("zmm5") _,
+            out("zmm6") _,
+            out("zmm7") _,
+            options(nostack),
+        );
+
+        i += 16;
+    }
+
+    let i = i / 2;
+    scalar::complex_mad(&mut c[i..], &a[i..], &b[i..]);
+}
+
+macro_rules! binary_u64_kernel {
+    ($(#[$meta:meta])* $name:ident, $op:literal, $scalar:path) => {
+        $(#[$meta])*
+        ///
+        /// # Safety
+        /// The CPU must support AVX512F.
+        ///
+        /// The lengths of c, a, and b must be equal or UB may result.
+        #[inline(always)]
+        pub unsafe fn $name(c: &mut [u64], a: &[u64], b: &[u64]) {
+            let mut i = 0;
+
+            let a_ptr = a.as_ptr();
+            let b_ptr = b.as_ptr();
+            let c_ptr = c.as_mut_ptr();
+
+            // Process 8 u64 values at a time.
+            while i + 8 <= c.len() {
+                asm!(
+                    "vmovdqu64 zmm0, [{a_ptr}+8*{i}]",
+                    "vmovdqu64 zmm1, [{b_ptr}+8*{i}]",
+                    concat!($op, " zmm0, zmm0, zmm1"),
+                    "vmovdqu64 [{c_ptr}+8*{i}], zmm0",
+                    a_ptr = in(reg) a_ptr,
+                    b_ptr = in(reg) b_ptr,
+                    c_ptr = in(reg) c_ptr,
+                    i = in(reg) i,
+                    out("zmm0") _,
+                    out("zmm1") _,
+                    options(nostack),
+                );
+
+                i += 8;
+            }
+
+            $scalar(&mut c[i..], &a[i..], &b[i..]);
+        }
+    };
+}
+
+binary_u64_kernel!(
+    /// Compute vector `c = a + b` over `&[u64]` with wrapping semantics.
+    wrapping_add_u64,
+    "vpaddq",
+    scalar::wrapping_add_u64
+);
+
+binary_u64_kernel!(
+    /// Compute vector `c = a - b` over `&[u64]` with wrapping semantics.
+    wrapping_sub_u64,
+    "vpsubq",
+    scalar::wrapping_sub_u64
+);
+
+macro_rules! assign_u64_kernel {
+    ($(#[$meta:meta])* $name:ident, $op:literal, $scalar:path) => {
+        $(#[$meta])*
+        ///
+        /// # Safety
+        /// The CPU must support AVX512F.
+        ///
+        /// The lengths of c and a must be equal or UB may result.
+        #[inline(always)]
+        pub unsafe fn $name(c: &mut [u64], a: &[u64]) {
+            let mut i = 0;
+
+            let a_ptr = a.as_ptr();
+            let c_ptr = c.as_mut_ptr();
+
+            // Process 8 u64 values at a time.
+            while i + 8 <= c.len() {
+                asm!(
+                    "vmovdqu64 zmm0, [{c_ptr}+8*{i}]",
+                    "vmovdqu64 zmm1, [{a_ptr}+8*{i}]",
+                    concat!($op, " zmm0, zmm0, zmm1"),
+                    "vmovdqu64 [{c_ptr}+8*{i}], zmm0",
+                    a_ptr = in(reg) a_ptr,
+                    c_ptr = in(reg) c_ptr,
+                    i = in(reg) i,
+                    out("zmm0") _,
+                    out("zmm1") _,
+                    options(nostack),
+                );
+
+                i += 8;
+            }
+
+            $scalar(&mut c[i..], &a[i..]);
+        }
+    };
+}
+
+assign_u64_kernel!(
+    /// Compute vector `c += a` over `&[u64]` with wrapping semantics.
+    wrapping_add_assign_u64,
+    "vpaddq",
+    scalar::wrapping_add_assign_u64
+);
+
+assign_u64_kernel!(
+    /// Compute vector `c -= a` over `&[u64]` with wrapping semantics.
+    wrapping_sub_assign_u64,
+    "vpsubq",
+    scalar::wrapping_sub_assign_u64
+);
+
+/// Extract the next signed radix digit from each element of `cur`,
+/// writing it to `digits` and leaving the remaining value in `cur`. See
+/// [`get_next_digit`](crate::radix::get_next_digit).
+///
+/// # Safety
+/// The CPU must support AVX512F.
+///
+/// The lengths of cur and digits must be equal or UB may result.
+/// `radix_log` must be in `1..64`.
+#[inline(always)]
+pub unsafe fn next_radix_digit_u64(cur: &mut [u64], digits: &mut [u64], radix_log: usize) {
+    let mut i = 0;
+
+    let cur_ptr = cur.as_mut_ptr();
+    let digits_ptr = digits.as_mut_ptr();
+    let mask = (0x1u64 << radix_log) - 1;
+
+    // Process 8 u64 values at a time.
+    while i + 8 <= cur.len() {
+        asm!(
+            "vpbroadcastq zmm1, {mask}",           // Broadcast the digit mask into zmm1
+            "vmovq xmm2, {shift}",                 // shift = radix_log
+            "vmovq xmm3, {carry_shift}",           // carry_shift = radix_log - 1
+            "vmovdqu64 zmm0, [{cur_ptr}+8*{i}]",   // Load cur into zmm0
+            "vpandq zmm4, zmm0, zmm1",             // digit = cur & mask
+            "vpsrlq zmm0, zmm0, xmm2",             // cur >>= radix_log
+            "vpsrlq zmm5, zmm4, xmm3",             // carry = digit >> (radix_log - 1)
+            "vpaddq zmm0, zmm0, zmm5",             // cur += carry
+            "vpsllq zmm5, zmm5, xmm2",             // carry <<= radix_log
+            "vpsubq zmm4, zmm4, zmm5",             // digit -= carry
+            "vmovdqu64 [{cur_ptr}+8*{i}], zmm0",   // Write cur back
+            "vmovdqu64 [{digits_ptr}+8*{i}], zmm4", // Write the digits
+            mask = in(reg) mask,
+            shift = in(reg) radix_log,
+            carry_shift = in(reg) radix_log - 1,
+            cur_ptr = in(reg) cur_ptr,
+            digits_ptr = in(reg) digits_ptr,
+            i = in(reg) i,
+            out("zmm0") _,
+            out("zmm1") _,
+            out("zmm2") _,
+            out("zmm3") _,
+            out("zmm4") _,
+            out("zmm5") _,
+            options(nostack),
+        );
+
+        i += 8;
+    }
+
+    scalar::next_radix_digit_u64(&mut cur[i..], &mut digits[i..], radix_log);
+}
+
+#[cfg(test)]
+mod tests {
+    use aligned_vec::AVec;
+
+    use super::*;
+
+    #[test]
+    fn can_scalar_mad_complex_f64_slice() {
+        if !available() {
+            return;
+        }
+
+        let len = 1024;
+
+        let vals_0 = (0..len).map(|x| x as f64).collect::<Vec<_>>();
+        let vals_1 = (len..2 * len).map(|x| x as f64).collect::<Vec<_>>();
+        let vals_2 = (2 * len..3 * len).map(|x| x as f64).collect::<Vec<_>>();
+
+        let a =
+            AVec::<Complex<f64>>::from_iter(64, vals_0.chunks(2).map(|x| Complex::new(x[0], x[1])));
+        let b =
+            AVec::<Complex<f64>>::from_iter(64, vals_1.chunks(2).map(|x| Complex::new(x[0], x[1])));
+        let mut expected =
+            AVec::<Complex<f64>>::from_iter(64, vals_2.chunks(2).map(|x| Complex::new(x[0], x[1])));
+
+        let mut actual = expected.clone();
+
+        unsafe { complex_mad(&mut actual, &a, &b) };
+        scalar::complex_mad(&mut expected, &a, &b);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,17 +1,256 @@
+use num::Complex;
+
 mod scalar;
-#[cfg(not(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-)))]
-pub use scalar::*;
-
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-))]
-mod avx2;
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-))]
-pub use avx2::*;
+
+#[cfg(target_arch = "x86_64")]
+mod avx512;
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+
+// Each kernel below checks at runtime which instruction sets the CPU
+// supports and dispatches to the fastest available implementation, falling
+// back to the scalar one.
+
+macro_rules! dispatch {
+    ($name:ident, ($($arg:expr),*)) => {{
+        #[cfg(target_arch = "x86_64")]
+        if avx512::available() {
+            return unsafe { avx512::$name($($arg),*) };
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        if neon::available() {
+            return unsafe { neon::$name($($arg),*) };
+        }
+
+        scalar::$name($($arg),*)
+    }};
+}
+
+#[inline(always)]
+/// Compute vector `c += a * b` over `&[Complex<f64>]`.
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    dispatch!(complex_mad, (c, a, b))
+}
+
+#[inline(always)]
+/// Compute vector `c = a + b` over `&[u64]` with wrapping semantics.
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub fn wrapping_add_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    dispatch!(wrapping_add_u64, (c, a, b))
+}
+
+#[inline(always)]
+/// Compute vector `c = a - b` over `&[u64]` with wrapping semantics.
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub fn wrapping_sub_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    dispatch!(wrapping_sub_u64, (c, a, b))
+}
+
+#[inline(always)]
+/// Compute vector `c += a` over `&[u64]` with wrapping semantics.
+///
+/// # Panics
+/// If `c.len() != a.len()`
+pub fn wrapping_add_assign_u64(c: &mut [u64], a: &[u64]) {
+    assert_eq!(c.len(), a.len());
+
+    dispatch!(wrapping_add_assign_u64, (c, a))
+}
+
+#[inline(always)]
+/// Compute vector `c -= a` over `&[u64]` with wrapping semantics.
+///
+/// # Panics
+/// If `c.len() != a.len()`
+pub fn wrapping_sub_assign_u64(c: &mut [u64], a: &[u64]) {
+    assert_eq!(c.len(), a.len());
+
+    dispatch!(wrapping_sub_assign_u64, (c, a))
+}
+
+#[inline(always)]
+/// Extract the next signed radix digit from each element of `cur`, writing
+/// it to `digits` and leaving the remaining value in `cur`.
+///
+/// # Panics
+/// If `cur.len() != digits.len()`
+/// If `radix_log` is not in `1..64`
+pub fn next_radix_digit_u64(cur: &mut [u64], digits: &mut [u64], radix_log: usize) {
+    assert_eq!(cur.len(), digits.len());
+    assert!((1..64).contains(&radix_log));
+
+    dispatch!(next_radix_digit_u64, (cur, digits, radix_log))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use super::*;
+
+    // Compare the scalar kernels against each accelerated backend the CPU
+    // supports. Calling the backends directly, rather than through the
+    // dispatching wrappers, keeps the tests from trivially comparing the
+    // scalar kernels against themselves on CPUs without them.
+    macro_rules! for_each_backend {
+        (|$backend:ident| $body:block) => {{
+            #[cfg(target_arch = "x86_64")]
+            if avx512::available() {
+                use avx512 as $backend;
+                $body
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            if neon::available() {
+                use neon as $backend;
+                $body
+            }
+        }};
+    }
+
+    // Not a multiple of any vector width, so the remainder paths get
+    // exercised too.
+    const LEN: usize = 1027;
+
+    fn random_u64s() -> Vec<u64> {
+        (0..LEN).map(|_| thread_rng().next_u64()).collect()
+    }
+
+    fn random_complexes() -> Vec<Complex<f64>> {
+        // Keep the values small integers so the products are exact and
+        // fused and unfused multiply-adds agree.
+        let val = || (thread_rng().next_u32() % 2048) as f64 - 1024.0;
+
+        (0..LEN).map(|_| Complex::new(val(), val())).collect()
+    }
+
+    #[test]
+    fn complex_mad_matches_scalar() {
+        let a = random_complexes();
+        let b = random_complexes();
+        let mut expected = random_complexes();
+        let mut actual = expected.clone();
+
+        scalar::complex_mad(&mut expected, &a, &b);
+
+        for_each_backend!(|backend| {
+            let mut actual = actual.clone();
+            unsafe { backend::complex_mad(&mut actual, &a, &b) };
+
+            assert_eq!(actual, expected);
+        });
+
+        complex_mad(&mut actual, &a, &b);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wrapping_add_sub_match_scalar() {
+        let a = random_u64s();
+        let b = random_u64s();
+
+        // The expected output after each step of the sequence below.
+        let mut expected = vec![];
+        let mut cur = vec![0; LEN];
+
+        scalar::wrapping_add_u64(&mut cur, &a, &b);
+        expected.push(cur.clone());
+
+        scalar::wrapping_sub_u64(&mut cur, &a, &b);
+        expected.push(cur.clone());
+
+        scalar::wrapping_add_assign_u64(&mut cur, &a);
+        expected.push(cur.clone());
+
+        scalar::wrapping_sub_assign_u64(&mut cur, &b);
+        expected.push(cur);
+
+        for_each_backend!(|backend| {
+            let mut actual = vec![0; LEN];
+
+            unsafe { backend::wrapping_add_u64(&mut actual, &a, &b) };
+            assert_eq!(actual, expected[0]);
+
+            unsafe { backend::wrapping_sub_u64(&mut actual, &a, &b) };
+            assert_eq!(actual, expected[1]);
+
+            unsafe { backend::wrapping_add_assign_u64(&mut actual, &a) };
+            assert_eq!(actual, expected[2]);
+
+            unsafe { backend::wrapping_sub_assign_u64(&mut actual, &b) };
+            assert_eq!(actual, expected[3]);
+        });
+
+        let mut actual = vec![0; LEN];
+
+        wrapping_add_u64(&mut actual, &a, &b);
+        assert_eq!(actual, expected[0]);
+
+        wrapping_sub_u64(&mut actual, &a, &b);
+        assert_eq!(actual, expected[1]);
+
+        wrapping_add_assign_u64(&mut actual, &a);
+        assert_eq!(actual, expected[2]);
+
+        wrapping_sub_assign_u64(&mut actual, &b);
+        assert_eq!(actual, expected[3]);
+    }
+
+    #[test]
+    fn next_radix_digit_matches_scalar() {
+        for radix_log in [1, 4, 7, 16, 63] {
+            let cur = random_u64s();
+
+            let mut expected_cur = cur.clone();
+            let mut expected = vec![];
+
+            for _ in 0..64 / radix_log {
+                let mut digits = vec![0; LEN];
+                scalar::next_radix_digit_u64(&mut expected_cur, &mut digits, radix_log);
+                expected.push((digits, expected_cur.clone()));
+            }
+
+            for_each_backend!(|backend| {
+                let mut actual_cur = cur.clone();
+                let mut actual = vec![0; LEN];
+
+                for (digits, expected_cur) in &expected {
+                    unsafe {
+                        backend::next_radix_digit_u64(&mut actual_cur, &mut actual, radix_log)
+                    };
+
+                    assert_eq!(&actual, digits);
+                    assert_eq!(&actual_cur, expected_cur);
+                }
+            });
+
+            let mut actual_cur = cur.clone();
+            let mut actual = vec![0; LEN];
+
+            for (digits, expected_cur) in &expected {
+                next_radix_digit_u64(&mut actual_cur, &mut actual, radix_log);
+
+                assert_eq!(&actual, digits);
+                assert_eq!(&actual_cur, expected_cur);
+            }
+        }
+    }
+}
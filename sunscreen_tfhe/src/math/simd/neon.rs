@@ -0,0 +1,168 @@
+use num::Complex;
+
+use std::{arch::aarch64::*, sync::OnceLock};
+
+use super::scalar;
+
+// Each NEON register holds 2 64-bit lanes, which is exactly one
+// Complex<f64> or 2 u64 values. Any remainder is handed off to the scalar
+// implementation.
+
+/// Whether the running CPU supports NEON.
+#[inline(always)]
+pub fn available() -> bool {
+    static NEON_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *NEON_AVAILABLE.get_or_init(|| std::arch::is_aarch64_feature_detected!("neon"))
+}
+
+/// Compute vector `c += a * b` over `&[Complex<f64>]`.
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of c, a, and b must be equal or UB may result.
+#[target_feature(enable = "neon")]
+pub unsafe fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    // Complex<T> is declared as repr(C), so the location of re and im are guaranteed
+    // at address offsets 0 and 8 for Complex<f64>. This allows us to treat
+    // &[Complex<f64>] as &[f64].
+    let a_ptr = a.as_ptr() as *const f64;
+    let b_ptr = b.as_ptr() as *const f64;
+    let c_ptr = c.as_mut_ptr() as *mut f64;
+
+    let sign = vld1q_f64([-1.0, 1.0].as_ptr());
+
+    for i in 0..c.len() {
+        let a = vld1q_f64(a_ptr.add(2 * i));
+        let b = vld1q_f64(b_ptr.add(2 * i));
+        let mut acc = vld1q_f64(c_ptr.add(2 * i));
+
+        let a_re = vdupq_laneq_f64::<0>(a); // (re(a), re(a))
+        let a_im = vdupq_laneq_f64::<1>(a); // (im(a), im(a))
+        let b_rot = vmulq_f64(vextq_f64::<1>(b, b), sign); // (-im(b), re(b))
+
+        acc = vfmaq_f64(acc, a_re, b); // c += (re(a) * re(b), re(a) * im(b))
+        acc = vfmaq_f64(acc, a_im, b_rot); // c += (-im(a) * im(b), im(a) * re(b))
+
+        vst1q_f64(c_ptr.add(2 * i), acc);
+    }
+}
+
+/// Compute vector `c = a + b` over `&[u64]` with wrapping semantics.
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of c, a, and b must be equal or UB may result.
+#[target_feature(enable = "neon")]
+pub unsafe fn wrapping_add_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    let mut i = 0;
+
+    while i + 2 <= c.len() {
+        let x = vaddq_u64(vld1q_u64(a.as_ptr().add(i)), vld1q_u64(b.as_ptr().add(i)));
+        vst1q_u64(c.as_mut_ptr().add(i), x);
+
+        i += 2;
+    }
+
+    scalar::wrapping_add_u64(&mut c[i..], &a[i..], &b[i..]);
+}
+
+/// Compute vector `c = a - b` over `&[u64]` with wrapping semantics.
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of c, a, and b must be equal or UB may result.
+#[target_feature(enable = "neon")]
+pub unsafe fn wrapping_sub_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    let mut i = 0;
+
+    while i + 2 <= c.len() {
+        let x = vsubq_u64(vld1q_u64(a.as_ptr().add(i)), vld1q_u64(b.as_ptr().add(i)));
+        vst1q_u64(c.as_mut_ptr().add(i), x);
+
+        i += 2;
+    }
+
+    scalar::wrapping_sub_u64(&mut c[i..], &a[i..], &b[i..]);
+}
+
+/// Compute vector `c += a` over `&[u64]` with wrapping semantics.
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of c and a must be equal or UB may result.
+#[target_feature(enable = "neon")]
+pub unsafe fn wrapping_add_assign_u64(c: &mut [u64], a: &[u64]) {
+    let mut i = 0;
+
+    while i + 2 <= c.len() {
+        let x = vaddq_u64(vld1q_u64(c.as_ptr().add(i)), vld1q_u64(a.as_ptr().add(i)));
+        vst1q_u64(c.as_mut_ptr().add(i), x);
+
+        i += 2;
+    }
+
+    scalar::wrapping_add_assign_u64(&mut c[i..], &a[i..]);
+}
+
+/// Compute vector `c -= a` over `&[u64]` with wrapping semantics.
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of c and a must be equal or UB may result.
+#[target_feature(enable = "neon")]
+pub unsafe fn wrapping_sub_assign_u64(c: &mut [u64], a: &[u64]) {
+    let mut i = 0;
+
+    while i + 2 <= c.len() {
+        let x = vsubq_u64(vld1q_u64(c.as_ptr().add(i)), vld1q_u64(a.as_ptr().add(i)));
+        vst1q_u64(c.as_mut_ptr().add(i), x);
+
+        i += 2;
+    }
+
+    scalar::wrapping_sub_assign_u64(&mut c[i..], &a[i..]);
+}
+
+/// Extract the next signed radix digit from each element of `cur`,
+/// writing it to `digits` and leaving the remaining value in `cur`. See
+/// [`get_next_digit`](crate::radix::get_next_digit).
+///
+/// # Safety
+/// The CPU must support NEON.
+///
+/// The lengths of cur and digits must be equal or UB may result.
+/// `radix_log` must be in `1..64`.
+#[target_feature(enable = "neon")]
+pub unsafe fn next_radix_digit_u64(cur: &mut [u64], digits: &mut [u64], radix_log: usize) {
+    let mut i = 0;
+
+    let mask = vdupq_n_u64((0x1u64 << radix_log) - 1);
+
+    // vshlq_u64 shifts right when given a negative shift amount.
+    let shl = vdupq_n_s64(radix_log as i64);
+    let shr = vdupq_n_s64(-(radix_log as i64));
+    let carry_shr = vdupq_n_s64(1 - radix_log as i64);
+
+    while i + 2 <= cur.len() {
+        let mut c = vld1q_u64(cur.as_ptr().add(i));
+
+        let mut digit = vandq_u64(c, mask);
+        c = vshlq_u64(c, shr);
+        let carry = vshlq_u64(digit, carry_shr);
+        c = vaddq_u64(c, carry);
+        digit = vsubq_u64(digit, vshlq_u64(carry, shl));
+
+        vst1q_u64(cur.as_mut_ptr().add(i), c);
+        vst1q_u64(digits.as_mut_ptr().add(i), digit);
+
+        i += 2;
+    }
+
+    scalar::next_radix_digit_u64(&mut cur[i..], &mut digits[i..], radix_log);
+}
@@ -1,11 +1,43 @@
 use num::Complex;
 
+use crate::radix::get_next_digit;
+
 pub fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
     for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
         *c += a * b;
     }
 }
 
+pub fn wrapping_add_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    for (c, (a, b)) in c.iter_mut().zip(a.iter().zip(b.iter())) {
+        *c = u64::wrapping_add(*a, *b);
+    }
+}
+
+pub fn wrapping_sub_u64(c: &mut [u64], a: &[u64], b: &[u64]) {
+    for (c, (a, b)) in c.iter_mut().zip(a.iter().zip(b.iter())) {
+        *c = u64::wrapping_sub(*a, *b);
+    }
+}
+
+pub fn wrapping_add_assign_u64(c: &mut [u64], a: &[u64]) {
+    for (c, a) in c.iter_mut().zip(a.iter()) {
+        *c = u64::wrapping_add(*c, *a);
+    }
+}
+
+pub fn wrapping_sub_assign_u64(c: &mut [u64], a: &[u64]) {
+    for (c, a) in c.iter_mut().zip(a.iter()) {
+        *c = u64::wrapping_sub(*c, *a);
+    }
+}
+
+pub fn next_radix_digit_u64(cur: &mut [u64], digits: &mut [u64], radix_log: usize) {
+    for (c, d) in cur.iter_mut().zip(digits.iter_mut()) {
+        *d = get_next_digit(c, radix_log);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rand::{thread_rng, RngCore};
@@ -12,7 +12,7 @@ use std::{
 use sunscreen_math::{refify_binary_op, Zero};
 
 use crate::{
-    math::{ReinterpretAsSigned, ReinterpretAsUnsigned},
+    math::{radix::get_next_digit, simd, ReinterpretAsSigned, ReinterpretAsUnsigned},
     scratch::Pod,
     PlaintextBits,
 };
@@ -80,6 +80,88 @@ pub trait TorusOps:
     + Sync
     + Send
 {
+    /// Compute `c = a + b` element-wise with wrapping semantics.
+    ///
+    /// # Remarks
+    /// Implementations may override this with a vectorized kernel.
+    ///
+    /// # Panics
+    /// If `c.len() != a.len() != b.len()`
+    #[inline(always)]
+    fn slice_wrapping_add(c: &mut [Self], a: &[Self], b: &[Self]) {
+        assert_eq!(c.len(), a.len());
+        assert_eq!(b.len(), a.len());
+
+        for (c, (a, b)) in c.iter_mut().zip(a.iter().zip(b.iter())) {
+            *c = a.wrapping_add(b);
+        }
+    }
+
+    /// Compute `c = a - b` element-wise with wrapping semantics.
+    ///
+    /// # Remarks
+    /// Implementations may override this with a vectorized kernel.
+    ///
+    /// # Panics
+    /// If `c.len() != a.len() != b.len()`
+    #[inline(always)]
+    fn slice_wrapping_sub(c: &mut [Self], a: &[Self], b: &[Self]) {
+        assert_eq!(c.len(), a.len());
+        assert_eq!(b.len(), a.len());
+
+        for (c, (a, b)) in c.iter_mut().zip(a.iter().zip(b.iter())) {
+            *c = a.wrapping_sub(b);
+        }
+    }
+
+    /// Compute `c += a` element-wise with wrapping semantics.
+    ///
+    /// # Remarks
+    /// Implementations may override this with a vectorized kernel.
+    ///
+    /// # Panics
+    /// If `c.len() != a.len()`
+    #[inline(always)]
+    fn slice_wrapping_add_assign(c: &mut [Self], a: &[Self]) {
+        assert_eq!(c.len(), a.len());
+
+        for (c, a) in c.iter_mut().zip(a.iter()) {
+            *c = c.wrapping_add(a);
+        }
+    }
+
+    /// Compute `c -= a` element-wise with wrapping semantics.
+    ///
+    /// # Remarks
+    /// Implementations may override this with a vectorized kernel.
+    ///
+    /// # Panics
+    /// If `c.len() != a.len()`
+    #[inline(always)]
+    fn slice_wrapping_sub_assign(c: &mut [Self], a: &[Self]) {
+        assert_eq!(c.len(), a.len());
+
+        for (c, a) in c.iter_mut().zip(a.iter()) {
+            *c = c.wrapping_sub(a);
+        }
+    }
+
+    /// Extract the next signed radix digit from each element of `cur`,
+    /// writing it to `digits` and leaving the remaining value in `cur`.
+    ///
+    /// # Remarks
+    /// Implementations may override this with a vectorized kernel.
+    ///
+    /// # Panics
+    /// If `cur.len() != digits.len()`
+    #[inline(always)]
+    fn slice_next_radix_digit(cur: &mut [Self], digits: &mut [Self], radix_log: usize) {
+        assert_eq!(cur.len(), digits.len());
+
+        for (c, d) in cur.iter_mut().zip(digits.iter_mut()) {
+            *d = get_next_digit(c, radix_log);
+        }
+    }
 }
 
 // Sound since Torus is a transparent wrapper and `S` impl `Pod`
@@ -207,7 +289,33 @@ where
     }
 }
 
-impl TorusOps for u64 {}
+impl TorusOps for u64 {
+    #[inline(always)]
+    fn slice_wrapping_add(c: &mut [Self], a: &[Self], b: &[Self]) {
+        simd::wrapping_add_u64(c, a, b);
+    }
+
+    #[inline(always)]
+    fn slice_wrapping_sub(c: &mut [Self], a: &[Self], b: &[Self]) {
+        simd::wrapping_sub_u64(c, a, b);
+    }
+
+    #[inline(always)]
+    fn slice_wrapping_add_assign(c: &mut [Self], a: &[Self]) {
+        simd::wrapping_add_assign_u64(c, a);
+    }
+
+    #[inline(always)]
+    fn slice_wrapping_sub_assign(c: &mut [Self], a: &[Self]) {
+        simd::wrapping_sub_assign_u64(c, a);
+    }
+
+    #[inline(always)]
+    fn slice_next_radix_digit(cur: &mut [Self], digits: &mut [Self], radix_log: usize) {
+        simd::next_radix_digit_u64(cur, digits, radix_log);
+    }
+}
+
 impl TorusOps for u32 {}
 
 /// A wrapper around a type that supports Torus operations.
@@ -0,0 +1,571 @@
+use statrs::function::erf::erfc;
+
+use crate::{GlweDef, LweDef, PlaintextBits, RadixDecomposition};
+
+// All variances in this module are of noise on the torus `[0, 1)`, so they're
+// independent of the bit width of the torus elements. Secret keys are assumed
+// to be binary, so each key coefficient has `E[s^2] = 1/2`.
+
+/// The variance of the noise in a fresh LWE encryption under `params`.
+pub fn lwe_encryption_variance(params: &LweDef) -> f64 {
+    params.std.0 * params.std.0
+}
+
+/// The variance of the noise in each coefficient of a fresh GLWE encryption
+/// under `params`.
+pub fn glwe_encryption_variance(params: &GlweDef) -> f64 {
+    params.std.0 * params.std.0
+}
+
+/// The variance of a signed radix digit. Digits are uniform in
+/// `[-B/2, B/2)` where `B = 2^radix_log`.
+fn digit_variance(radix: &RadixDecomposition) -> f64 {
+    let b = (radix.radix_log.0 as f64).exp2();
+
+    (b * b + 2.0) / 12.0
+}
+
+/// The variance of the error from rounding a value to the `radix.count`
+/// most significant digits before decomposing it.
+fn decomposition_rounding_variance(radix: &RadixDecomposition) -> f64 {
+    (-2.0 * (radix.radix_log.0 * radix.count.0) as f64).exp2() / 12.0
+}
+
+/// The variance added to an LWE ciphertext under `lwe` when modulus
+/// switching it to `2N` at the start of programmable bootstrapping with a
+/// GLWE polynomial degree of `N`.
+///
+/// # Remarks
+/// `log_v` is the number of least significant bits dropped to pack multiple
+/// functions into the lookup table, as in
+/// [`generalized_programmable_bootstrap`](crate::ops::bootstrapping::generalized_programmable_bootstrap).
+/// Use 0 for a single function.
+pub fn modulus_switch_variance(lwe: &LweDef, glwe: &GlweDef, log_v: u32) -> f64 {
+    let step = (log_v as f64).exp2() / (2 * glwe.dim.polynomial_degree.0) as f64;
+
+    // Each of the n mask elements and the body are rounded to a multiple of
+    // step.
+    (1.0 + lwe.dim.0 as f64 / 2.0) * step * step / 12.0
+}
+
+/// The variance an external product adds to a GLWE ciphertext under `glwe`
+/// from the noise in a GGSW ciphertext decomposed with `radix` with noise
+/// variance `ggsw_variance`.
+fn ggsw_noise_variance(glwe: &GlweDef, radix: &RadixDecomposition, ggsw_variance: f64) -> f64 {
+    let k = glwe.dim.size.0 as f64;
+    let n = glwe.dim.polynomial_degree.0 as f64;
+    let l = radix.count.0 as f64;
+
+    (k + 1.0) * l * n * digit_variance(radix) * ggsw_variance
+}
+
+/// The variance an external product with a GGSW ciphertext encrypting 1
+/// adds to a GLWE ciphertext under `glwe` from rounding before decomposing
+/// with `radix`.
+fn glwe_rounding_variance(glwe: &GlweDef, radix: &RadixDecomposition) -> f64 {
+    let k = glwe.dim.size.0 as f64;
+    let n = glwe.dim.polynomial_degree.0 as f64;
+
+    (1.0 + k * n / 2.0) * decomposition_rounding_variance(radix)
+}
+
+/// The variance an external product adds to a GLWE ciphertext under `glwe`
+/// when the GGSW ciphertext decomposed with `radix` has noise variance
+/// `ggsw_variance`.
+///
+/// # Remarks
+/// This assumes the GGSW ciphertext encrypts 1, which is the worst case for
+/// a CMUX. The first term is the GGSW's noise multiplied by the decomposed
+/// digits and the second is the error from rounding before decomposing,
+/// which vanishes when the GGSW ciphertext encrypts 0.
+pub fn external_product_variance(
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    ggsw_variance: f64,
+) -> f64 {
+    ggsw_noise_variance(glwe, radix, ggsw_variance) + glwe_rounding_variance(glwe, radix)
+}
+
+/// The variance a CMUX adds to the selected GLWE ciphertext. See
+/// [`external_product_variance`].
+pub fn cmux_variance(glwe: &GlweDef, radix: &RadixDecomposition, ggsw_variance: f64) -> f64 {
+    external_product_variance(glwe, radix, ggsw_variance)
+}
+
+/// The variance of the noise in the output of programmable bootstrapping an
+/// LWE ciphertext under `lwe` with a bootstrapping key under `glwe` and
+/// `radix`.
+///
+/// # Remarks
+/// Bootstrapping performs one CMUX per input key bit starting from a
+/// noiseless lookup table, so this is independent of the input's noise.
+/// Half of the key bits are 1 on average, so only half of the CMUXes add
+/// rounding error. Sample extraction doesn't add noise.
+///
+/// This models exact external products, as computed with
+/// [`ntt_ops`](crate::ops::ntt_ops). External products computed with the
+/// FFT add a small amount of floating point error on top of this.
+pub fn programmable_bootstrap_variance(
+    lwe: &LweDef,
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) -> f64 {
+    let n = lwe.dim.0 as f64;
+
+    n * ggsw_noise_variance(glwe, radix, glwe_encryption_variance(glwe))
+        + n / 2.0 * glwe_rounding_variance(glwe, radix)
+}
+
+/// The variance an LWE keyswitch from `from` to `to` using `radix` adds to
+/// the ciphertext.
+pub fn keyswitch_variance(from: &LweDef, to: &LweDef, radix: &RadixDecomposition) -> f64 {
+    let n = from.dim.0 as f64;
+    let l = radix.count.0 as f64;
+
+    n * l * digit_variance(radix) * lwe_encryption_variance(to)
+        + n / 2.0 * decomposition_rounding_variance(radix)
+}
+
+/// The variance a private functional keyswitch of a single LWE ciphertext
+/// under `from` to a GLWE ciphertext under `to` using `radix` adds to each
+/// coefficient.
+///
+/// # Remarks
+/// This assumes the function multiplies by 1 or by a binary key polynomial,
+/// as in circuit bootstrapping, so it doesn't amplify the input's noise.
+pub fn private_functional_keyswitch_variance(
+    from: &LweDef,
+    to: &GlweDef,
+    radix: &RadixDecomposition,
+) -> f64 {
+    let n = from.dim.0 as f64;
+    let l = radix.count.0 as f64;
+
+    (n + 1.0) * l * digit_variance(radix) * glwe_encryption_variance(to)
+        + (1.0 + n / 2.0) * decomposition_rounding_variance(radix)
+}
+
+/// The variance of the noise in the GGSW ciphertext output by
+/// [`circuit_bootstrap`](crate::ops::bootstrapping::circuit_bootstrap).
+/// This is the `ggsw_variance` to use when estimating CMUXes with the result.
+pub fn circuit_bootstrap_variance(
+    lwe_0: &LweDef,
+    glwe_1: &GlweDef,
+    glwe_2: &GlweDef,
+    pbs_radix: &RadixDecomposition,
+    pfks_radix: &RadixDecomposition,
+) -> f64 {
+    programmable_bootstrap_variance(lwe_0, glwe_2, pbs_radix)
+        + private_functional_keyswitch_variance(&glwe_2.as_lwe_def(), glwe_1, pfks_radix)
+}
+
+/// The probability that normally distributed noise with the given `variance`
+/// has a magnitude of at least `bound`.
+pub fn exceed_probability(variance: f64, bound: f64) -> f64 {
+    if variance <= 0.0 {
+        return if bound <= 0.0 { 1.0 } else { 0.0 };
+    }
+
+    erfc(bound / (2.0 * variance).sqrt())
+}
+
+/// The probability that a ciphertext with noise `variance` decrypts
+/// incorrectly when encoding a message of `plaintext_bits` bits with one
+/// padding bit, as done for programmable bootstrapping.
+///
+/// # Remarks
+/// Messages are spaced `2^-(plaintext_bits + 1)` apart, so decryption fails
+/// when the noise's magnitude exceeds half of that.
+pub fn failure_probability(variance: f64, plaintext_bits: PlaintextBits) -> f64 {
+    exceed_probability(variance, (-(plaintext_bits.0 as f64) - 2.0).exp2())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// An operation applied to an LWE ciphertext in a pipeline. See
+/// [`estimate_pipeline`].
+pub enum NoiseStage {
+    /// [`programmable_bootstrap_univariate`](crate::ops::bootstrapping::programmable_bootstrap_univariate)
+    /// from `lwe` to the LWE key extracted from `glwe`.
+    ProgrammableBootstrap {
+        /// The parameters of the input ciphertext.
+        lwe: LweDef,
+
+        /// The parameters of the bootstrapping key.
+        glwe: GlweDef,
+
+        /// The decomposition of the bootstrapping key.
+        radix: RadixDecomposition,
+    },
+
+    /// [`keyswitch_lwe_to_lwe`](crate::ops::keyswitch::lwe_keyswitch::keyswitch_lwe_to_lwe)
+    /// from `from` to `to`.
+    Keyswitch {
+        /// The parameters of the input ciphertext.
+        from: LweDef,
+
+        /// The parameters of the output ciphertext.
+        to: LweDef,
+
+        /// The decomposition of the keyswitch key.
+        radix: RadixDecomposition,
+    },
+
+    /// [`circuit_bootstrap`](crate::ops::bootstrapping::circuit_bootstrap) of
+    /// a single bit. This produces a GGSW ciphertext, so it must be the last
+    /// stage.
+    CircuitBootstrap {
+        /// The parameters of the input ciphertext.
+        lwe_0: LweDef,
+
+        /// The parameters of the output GGSW ciphertext.
+        glwe_1: GlweDef,
+
+        /// The parameters of the bootstrapping key.
+        glwe_2: GlweDef,
+
+        /// The decomposition of the bootstrapping key.
+        pbs_radix: RadixDecomposition,
+
+        /// The decomposition of the output GGSW ciphertext.
+        cbs_radix: RadixDecomposition,
+
+        /// The decomposition of the private functional keyswitch keys.
+        pfks_radix: RadixDecomposition,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The estimated noise after a [`NoiseStage`].
+pub struct StageNoise {
+    /// The variance of the noise in the stage's output.
+    pub variance: f64,
+
+    /// The probability this stage produces an incorrect result.
+    ///
+    /// # Remarks
+    /// For bootstrapping stages, this is the probability the input's noise
+    /// plus the modulus switching noise selects the wrong lookup table
+    /// entry. For other stages, this is the probability the output decrypts
+    /// incorrectly.
+    pub failure_probability: f64,
+}
+
+/// Estimate the noise after each stage of applying `stages` in order to an
+/// LWE ciphertext with noise `input_variance` encrypting a message of
+/// `plaintext_bits` bits with one padding bit.
+///
+/// # Remarks
+/// Use [`pipeline_failure_probability`] to combine the failure
+/// probabilities of each stage.
+///
+/// [`NoiseStage::CircuitBootstrap`] takes a single bit message encoded
+/// without padding regardless of `plaintext_bits`.
+///
+/// # Panics
+/// If a [`NoiseStage::CircuitBootstrap`] isn't the last stage.
+pub fn estimate_pipeline(
+    input_variance: f64,
+    stages: &[NoiseStage],
+    plaintext_bits: PlaintextBits,
+) -> Vec<StageNoise> {
+    let mut variance = input_variance;
+
+    stages
+        .iter()
+        .enumerate()
+        .map(|(i, stage)| {
+            let (output_variance, failure) = match stage {
+                NoiseStage::ProgrammableBootstrap { lwe, glwe, radix } => {
+                    let ms = modulus_switch_variance(lwe, glwe, 0);
+
+                    (
+                        programmable_bootstrap_variance(lwe, glwe, radix),
+                        failure_probability(variance + ms, plaintext_bits),
+                    )
+                }
+                NoiseStage::Keyswitch { from, to, radix } => {
+                    let output_variance = variance + keyswitch_variance(from, to, radix);
+
+                    (
+                        output_variance,
+                        failure_probability(output_variance, plaintext_bits),
+                    )
+                }
+                NoiseStage::CircuitBootstrap {
+                    lwe_0,
+                    glwe_1,
+                    glwe_2,
+                    pbs_radix,
+                    cbs_radix,
+                    pfks_radix,
+                } => {
+                    assert_eq!(
+                        i,
+                        stages.len() - 1,
+                        "Circuit bootstrapping must be the last stage."
+                    );
+
+                    // Circuit bootstrapping packs one function per level
+                    // into the lookup table. The input is rotated by 1/4 so
+                    // it fails when the noise exceeds 1/4.
+                    let log_v = cbs_radix.count.0.next_power_of_two().ilog2();
+                    let ms = modulus_switch_variance(lwe_0, glwe_2, log_v);
+
+                    (
+                        circuit_bootstrap_variance(lwe_0, glwe_1, glwe_2, pbs_radix, pfks_radix),
+                        exceed_probability(variance + ms, 0.25),
+                    )
+                }
+            };
+
+            variance = output_variance;
+
+            StageNoise {
+                variance: output_variance,
+                failure_probability: failure,
+            }
+        })
+        .collect()
+}
+
+/// The probability any stage in a pipeline estimated with
+/// [`estimate_pipeline`] fails, assuming the failures are independent.
+///
+/// # Remarks
+/// A keyswitch followed by bootstrapping counts the same noise twice, so
+/// this is an upper bound.
+pub fn pipeline_failure_probability(stages: &[StageNoise]) -> f64 {
+    // Work with logarithms so tiny probabilities don't round to zero.
+    let log_success = stages
+        .iter()
+        .map(|s| (-s.failure_probability).ln_1p())
+        .sum::<f64>();
+
+    -log_success.exp_m1()
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_math::stats::RunningMeanVariance;
+
+    use crate::{
+        entities::{
+            BootstrapKey, BootstrapKeyNtt, GlweCiphertext, LweCiphertext, LweKeyswitchKey,
+            Polynomial, UnivariateLookupTable,
+        },
+        high_level::{keygen, TEST_LWE_DEF_1},
+        ops::{
+            bootstrapping::{generalized_programmable_bootstrap_ntt, generate_bootstrap_key},
+            encryption::{decrypt_glwe_ciphertext, encrypt_lwe_ciphertext},
+            keyswitch::{
+                lwe_keyswitch::keyswitch_lwe_to_lwe, lwe_keyswitch_key::generate_keyswitch_key_lwe,
+            },
+        },
+        RadixCount, RadixLog, Torus, GLWE_1_1024_80, LWE_512_80,
+    };
+
+    use super::*;
+
+    /// Whether `measured` is within a factor of `tolerance` of `predicted`.
+    fn assert_close(measured: f64, predicted: f64, tolerance: f64) {
+        let ratio = measured / predicted;
+
+        assert!(
+            (1.0 / tolerance..tolerance).contains(&ratio),
+            "measured variance {measured:e} doesn't match predicted {predicted:e}"
+        );
+    }
+
+    #[test]
+    fn keyswitch_variance_matches_measurement() {
+        let from = GLWE_1_1024_80.as_lwe_def();
+        let to = LWE_512_80;
+        let radix = RadixDecomposition {
+            count: RadixCount(4),
+            radix_log: RadixLog(3),
+        };
+
+        let from_sk = keygen::generate_binary_lwe_sk(&from);
+        let to_sk = keygen::generate_binary_lwe_sk(&to);
+
+        let mut ksk = LweKeyswitchKey::new(&from, &to, &radix);
+        generate_keyswitch_key_lwe(&mut ksk, &from_sk, &to_sk, &from, &to, &radix);
+
+        let mut stats = RunningMeanVariance::new();
+
+        for _ in 0..2000 {
+            let mut ct = LweCiphertext::new(&from);
+            encrypt_lwe_ciphertext(&mut ct, &from_sk, Torus::from(0u64), &from);
+
+            let mut output = LweCiphertext::new(&to);
+            keyswitch_lwe_to_lwe(&mut output, &ct, &ksk, &from, &to, &radix);
+
+            let phase = to_sk.decrypt_without_decode(&output, &to);
+            stats.add_sample(phase.normalized_torus_distance(&Torus::from(0u64)));
+        }
+
+        let predicted = lwe_encryption_variance(&from) + keyswitch_variance(&from, &to, &radix);
+
+        assert_close(stats.variance(), predicted, 1.25);
+    }
+
+    #[test]
+    fn programmable_bootstrap_variance_matches_measurement() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = GLWE_1_1024_80;
+        let radix = RadixDecomposition {
+            count: RadixCount(4),
+            radix_log: RadixLog(4),
+        };
+        let bits = PlaintextBits(2);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let mut bsk = BootstrapKey::new(&lwe, &glwe, &radix);
+        generate_bootstrap_key(&mut bsk, &lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+
+        let mut bsk_ntt = BootstrapKeyNtt::new(&lwe, &glwe, &radix);
+        bsk.ntt(&mut bsk_ntt, &lwe, &glwe, &radix);
+
+        let lut = UnivariateLookupTable::trivial_from_fn(|x| x, &glwe, bits);
+
+        // The blind rotated table holds messages with a padding bit.
+        let padded_bits = PlaintextBits(bits.0 + 1);
+
+        let mut stats = RunningMeanVariance::new();
+
+        for _ in 0..8 {
+            let mut ct = LweCiphertext::new(&lwe);
+            encrypt_lwe_ciphertext(&mut ct, &lwe_sk, Torus::from(0u64), &lwe);
+
+            let mut output = GlweCiphertext::new(&glwe);
+            generalized_programmable_bootstrap_ntt(
+                &mut output,
+                &ct,
+                &lut,
+                &bsk_ntt,
+                0,
+                0,
+                &lwe,
+                &glwe,
+                &radix,
+            );
+
+            let mut phase = Polynomial::zero(glwe.dim.polynomial_degree.0);
+            decrypt_glwe_ciphertext(&mut phase, &output, &glwe_sk, &glwe);
+
+            // Every coefficient of the blind rotated table is a noise sample
+            // around the nearest message.
+            for c in phase.coeffs() {
+                let m = Torus::encode(c.decode(padded_bits), padded_bits);
+                stats.add_sample(c.normalized_torus_distance(&m));
+            }
+        }
+
+        let predicted = programmable_bootstrap_variance(&lwe, &glwe, &radix);
+
+        assert_close(stats.variance(), predicted, 1.5);
+    }
+
+    #[test]
+    fn failure_probability_is_monotonic() {
+        let bits = PlaintextBits(4);
+
+        assert_eq!(failure_probability(0.0, bits), 0.0);
+
+        let small = failure_probability(1e-8, bits);
+        let large = failure_probability(1e-4, bits);
+
+        assert!(small < large);
+        assert!(small < 1e-40);
+        assert!(large > 0.01 && large <= 1.0);
+    }
+
+    #[test]
+    fn can_estimate_pipeline() {
+        let pbs_radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(16),
+        };
+        let ks_radix = RadixDecomposition {
+            count: RadixCount(5),
+            radix_log: RadixLog(3),
+        };
+        let bits = PlaintextBits(4);
+
+        let stages = [
+            NoiseStage::ProgrammableBootstrap {
+                lwe: LWE_512_80,
+                glwe: GLWE_1_1024_80,
+                radix: pbs_radix,
+            },
+            NoiseStage::Keyswitch {
+                from: GLWE_1_1024_80.as_lwe_def(),
+                to: LWE_512_80,
+                radix: ks_radix,
+            },
+            NoiseStage::ProgrammableBootstrap {
+                lwe: LWE_512_80,
+                glwe: GLWE_1_1024_80,
+                radix: pbs_radix,
+            },
+        ];
+
+        let estimate = estimate_pipeline(lwe_encryption_variance(&LWE_512_80), &stages, bits);
+
+        assert_eq!(estimate.len(), 3);
+
+        // Bootstrapping resets the noise.
+        assert_eq!(estimate[0].variance, estimate[2].variance);
+        assert!(estimate[1].variance > estimate[0].variance);
+
+        let p = pipeline_failure_probability(&estimate);
+        assert!(p >= estimate[0].failure_probability);
+        assert!(p >= estimate[2].failure_probability);
+        assert!(p > 0.0 && p < 1e-6);
+    }
+
+    #[test]
+    fn can_estimate_circuit_bootstrap() {
+        let pbs_radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(16),
+        };
+        let cbs_radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(4),
+        };
+        let pfks_radix = RadixDecomposition {
+            count: RadixCount(3),
+            radix_log: RadixLog(11),
+        };
+
+        let stages = [NoiseStage::CircuitBootstrap {
+            lwe_0: LWE_512_80,
+            glwe_1: GLWE_1_1024_80,
+            glwe_2: GLWE_1_1024_80,
+            pbs_radix,
+            cbs_radix,
+            pfks_radix,
+        }];
+
+        let estimate = estimate_pipeline(
+            lwe_encryption_variance(&LWE_512_80),
+            &stages,
+            PlaintextBits(1),
+        );
+
+        assert_eq!(
+            estimate[0].variance,
+            circuit_bootstrap_variance(
+                &LWE_512_80,
+                &GLWE_1_1024_80,
+                &GLWE_1_1024_80,
+                &pbs_radix,
+                &pfks_radix
+            )
+        );
+        assert!(estimate[0].failure_probability < 1e-20);
+    }
+}
@@ -1,9 +1,14 @@
 use num::Complex;
+use rand::{thread_rng, CryptoRng, RngCore};
 
 use crate::{
     dst::FromMutSlice,
-    entities::{BlindRotationShiftFftRef, GgswCiphertext, GlweCiphertextRef, GlweSecretKeyRef},
-    ops::{encryption::encrypt_ggsw_ciphertext_scalar, fft_ops::cmux},
+    entities::{
+        BlindRotationShiftFftRef, BlindRotationShiftNttRef, GgswCiphertext, GlweCiphertextRef,
+        GlweSecretKeyRef,
+    },
+    goldilocks_field::Fg,
+    ops::{encryption::encrypt_ggsw_ciphertext_scalar_with_rng, fft_ops::cmux, ntt_ops},
     scratch::allocate_scratch_ref,
     GlweDef, PlaintextBits, RadixDecomposition, TorusOps,
 };
@@ -222,6 +227,38 @@ pub fn blind_rotation<S>(
     }
 }
 
+/// Rotate the given ciphertext message polynomial by negative encrypted shift
+/// using an exact NTT for the external products. See [`blind_rotation`].
+///
+/// # Remarks
+/// Unlike [`blind_rotation`], the result is bit-exact across platforms.
+///
+/// # Panics
+/// If the NTT isn't exact for `params` and `radix`. See
+/// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+pub fn blind_rotation_ntt<S>(
+    output: &mut GlweCiphertextRef<S>,
+    blind_rotation_index: &BlindRotationShiftNttRef<Fg>,
+    ct: &GlweCiphertextRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    // Initialize with the unrotated message m
+    output.clone_from_ref(ct);
+    allocate_scratch_ref!(rotated_ct, GlweCiphertextRef<S>, (params.dim));
+
+    for (i, index_select) in blind_rotation_index.rows(params, radix).enumerate() {
+        let rotation = 1 << i;
+
+        rotate_glwe_negative_monomial_negacyclic(rotated_ct, output, rotation, params);
+
+        let tmp = output.to_owned();
+        ntt_ops::cmux(output, &tmp, rotated_ct, index_select, params, radix);
+    }
+}
+
 /// Encrypt an amount to rotate the message polynomial by.
 ///
 /// This function is mostly provided as a convenience. Bootstrapping will rotate
@@ -236,38 +273,132 @@ pub fn generate_blind_rotation_shift<S>(
     plaintext_bits: PlaintextBits,
 ) where
     S: TorusOps,
+{
+    generate_blind_rotation_shift_with_rng(
+        bootstrap_key,
+        rotation,
+        sk,
+        params,
+        radix,
+        plaintext_bits,
+        &mut thread_rng(),
+    )
+}
+
+/// Encrypt an amount to rotate the message polynomial by, drawing the
+/// encryptions' randomness from `rng`. See [`generate_blind_rotation_shift`].
+pub fn generate_blind_rotation_shift_with_rng<S>(
+    bootstrap_key: &mut BlindRotationShiftFftRef<Complex<f64>>,
+    rotation: usize,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     let degree = params.dim.polynomial_degree.0;
     assert!(rotation < degree);
 
     for (i, ggsw_fft) in bootstrap_key.rows_mut(params, radix).enumerate() {
-        let bit = ((rotation >> i) & 1) as u64;
-        let mut ct = GgswCiphertext::new(params, radix);
-
-        encrypt_ggsw_ciphertext_scalar(
-            &mut ct,
-            S::from_u64(bit),
-            sk,
-            params,
-            radix,
-            plaintext_bits,
-        );
+        let ct = encrypt_rotation_bit(rotation, i, sk, params, radix, plaintext_bits, rng);
 
         ct.fft(ggsw_fft, params, radix);
     }
 }
 
+/// Encrypt an amount to rotate the message polynomial by for use with
+/// [`blind_rotation_ntt`]. See [`generate_blind_rotation_shift`].
+pub fn generate_blind_rotation_shift_ntt<S>(
+    bootstrap_key: &mut BlindRotationShiftNttRef<Fg>,
+    rotation: usize,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+) where
+    S: TorusOps,
+{
+    generate_blind_rotation_shift_ntt_with_rng(
+        bootstrap_key,
+        rotation,
+        sk,
+        params,
+        radix,
+        plaintext_bits,
+        &mut thread_rng(),
+    )
+}
+
+/// Encrypt an amount to rotate the message polynomial by for use with
+/// [`blind_rotation_ntt`], drawing the encryptions' randomness from `rng`.
+/// See [`generate_blind_rotation_shift`].
+pub fn generate_blind_rotation_shift_ntt_with_rng<S>(
+    bootstrap_key: &mut BlindRotationShiftNttRef<Fg>,
+    rotation: usize,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    let degree = params.dim.polynomial_degree.0;
+    assert!(rotation < degree);
+
+    for (i, ggsw_ntt) in bootstrap_key.rows_mut(params, radix).enumerate() {
+        let ct = encrypt_rotation_bit(rotation, i, sk, params, radix, plaintext_bits, rng);
+
+        ct.ntt(ggsw_ntt, params, radix);
+    }
+}
+
+/// Encrypt bit `i` of `rotation` as a GGSW ciphertext.
+fn encrypt_rotation_bit<S>(
+    rotation: usize,
+    i: usize,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> GgswCiphertext<S>
+where
+    S: TorusOps,
+{
+    let bit = ((rotation >> i) & 1) as u64;
+    let mut ct = GgswCiphertext::new(params, radix);
+
+    encrypt_ggsw_ciphertext_scalar_with_rng(
+        &mut ct,
+        S::from_u64(bit),
+        sk,
+        params,
+        radix,
+        plaintext_bits,
+        rng,
+    );
+
+    ct
+}
+
 #[cfg(test)]
 mod tests {
     use blind_rotation::generate_blind_rotation_shift;
 
     use crate::{
         entities::{
-            BlindRotationShiftFft, GgswCiphertext, GlweCiphertext, GlweSecretKey, Polynomial,
+            BlindRotationShiftFft, BlindRotationShiftNtt, GgswCiphertext, GlweCiphertext,
+            GlweSecretKey, Polynomial,
         },
         high_level::{TEST_GLWE_DEF_1, TEST_RADIX},
         ops::{
-            bootstrapping::{blind_rotation, rotate_glwe_monomial_negacyclic},
+            bootstrapping::{
+                blind_rotation, blind_rotation_ntt, generate_blind_rotation_shift_ntt,
+                rotate_glwe_monomial_negacyclic, rotate_glwe_negative_monomial_negacyclic,
+            },
             encryption::decrypt_ggsw_ciphertext,
         },
         polynomial::polynomial_external_mad,
@@ -473,4 +604,50 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn can_blind_rotate_ntt() {
+        let params = GlweDef {
+            dim: GlweDimension {
+                polynomial_degree: PolynomialDegree(32),
+                size: GlweSize(2),
+            },
+            ..TEST_GLWE_DEF_1
+        };
+        let radix = TEST_RADIX;
+        let plaintext_bits = PlaintextBits(4);
+
+        let modulus = 1 << plaintext_bits.0;
+        let degree = params.dim.polynomial_degree.0;
+
+        let sk = GlweSecretKey::<u64>::generate_binary(&params);
+
+        let msg_coeffs = (0..degree)
+            .map(|i| (i % modulus) as u64)
+            .collect::<Vec<_>>();
+        let msg = Polynomial::new(&msg_coeffs);
+
+        let ct = sk.encode_encrypt_glwe(&msg, &params, plaintext_bits);
+
+        for rotation in 0..degree {
+            let mut expected_ct = GlweCiphertext::new(&params);
+            rotate_glwe_negative_monomial_negacyclic(&mut expected_ct, &ct, rotation, &params);
+            let expected = sk.decrypt_decode_glwe(&expected_ct, &params, plaintext_bits);
+
+            let mut ggsw_index = BlindRotationShiftNtt::new(&params, &radix);
+            generate_blind_rotation_shift_ntt(
+                &mut ggsw_index,
+                rotation,
+                &sk,
+                &params,
+                &radix,
+                plaintext_bits,
+            );
+            let mut output_ct = GlweCiphertext::new(&params);
+            blind_rotation_ntt(&mut output_ct, &ggsw_index, &ct, &params, &radix);
+            let output_msg = sk.decrypt_decode_glwe(&output_ct, &params, plaintext_bits);
+
+            assert_eq!(output_msg, expected);
+        }
+    }
 }
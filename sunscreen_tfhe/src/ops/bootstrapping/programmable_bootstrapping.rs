@@ -1,22 +1,33 @@
 use num::Complex;
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rand::{thread_rng, CryptoRng, RngCore};
+use rayon::{
+    iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator},
+    slice::{ParallelSlice, ParallelSliceMut},
+};
 
 use crate::{
     dst::FromMutSlice,
     entities::{
-        BivariateLookupTableRef, BootstrapKeyFftRef, BootstrapKeyRef, GlweCiphertextRef,
-        GlweSecretKeyRef, LweCiphertextRef, LweSecretKeyRef, Polynomial, PolynomialRef,
-        UnivariateLookupTableRef,
+        BivariateLookupTableRef, BootstrapKeyFftRef, BootstrapKeyNttRef, BootstrapKeyRef,
+        GgswCiphertext, GgswCiphertextRef, GlweCiphertextRef, GlweSecretKeyRef,
+        LweCiphertextListRef, LweCiphertextRef, LweSecretKeyRef, Polynomial, PolynomialRef,
+        SeededBootstrapKey, UnivariateLookupTableRef,
     },
+    goldilocks_field::Fg,
     ops::{
         bootstrapping::rotate_glwe_positive_monomial_negacyclic,
         ciphertext::{
             add_lwe_inplace, lwe_ciphertext_modulus_switch, sample_extract,
             scalar_mul_ciphertext_mad,
         },
-        encryption::encrypt_ggsw_ciphertext_scalar,
+        encryption::{
+            encrypt_ggsw_ciphertext_generic, encrypt_ggsw_ciphertext_scalar_with_rng,
+            encrypt_glwe_ciphertext_secret_with_mask,
+        },
         fft_ops::cmux,
+        ntt_ops,
     },
+    rand::{key_seed_with_rng, seeded_stream_rng, uniform_torus_with_rng},
     scratch::allocate_scratch_ref,
     CarryBits, GlweDef, LweDef, OverlaySize, PlaintextBits, RadixDecomposition, Torus, TorusOps,
 };
@@ -40,6 +51,35 @@ pub fn generate_bootstrap_key<S>(
     radix: &RadixDecomposition,
 ) where
     S: TorusOps,
+{
+    generate_bootstrap_key_with_rng(
+        bootstrap_key,
+        sk_to_encrypt,
+        sk,
+        lwe,
+        glwe,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generate a bootstrap key from a LWE secret key to a GLWE secret key,
+/// drawing the encryptions' randomness from `rng`. See
+/// [`generate_bootstrap_key`].
+///
+/// # Remarks
+/// The GGSW ciphertexts are generated in parallel, each from its own ChaCha20
+/// stream seeded from `rng`, so the result only depends on `rng`'s output.
+pub fn generate_bootstrap_key_with_rng<S>(
+    bootstrap_key: &mut BootstrapKeyRef<S>,
+    sk_to_encrypt: &LweSecretKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    lwe: &LweDef,
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     lwe.assert_valid();
     glwe.assert_valid();
@@ -48,15 +88,208 @@ pub fn generate_bootstrap_key<S>(
     sk.assert_is_valid(glwe.dim);
     sk_to_encrypt.assert_is_valid(lwe.dim);
 
+    let seed = key_seed_with_rng(rng);
+
     sk_to_encrypt
         .s()
         .par_iter()
+        .enumerate()
         .zip(bootstrap_key.rows_par_mut(glwe, radix))
-        .for_each(|(s_i, ggsw)| {
-            encrypt_ggsw_ciphertext_scalar(ggsw, *s_i, sk, glwe, radix, PlaintextBits(1));
+        .for_each(|((i, s_i), ggsw)| {
+            encrypt_ggsw_ciphertext_scalar_with_rng(
+                ggsw,
+                *s_i,
+                sk,
+                glwe,
+                radix,
+                PlaintextBits(1),
+                &mut seeded_stream_rng(&seed, i as u64),
+            );
+        });
+}
+
+/// Generate a [`SeededBootstrapKey`] from a LWE secret key to a GLWE secret
+/// key. This is the compressed form of the key produced by
+/// [`generate_bootstrap_key`].
+///
+/// # Remarks
+/// The masks of the GLWE ciphertexts in the key are generated from the seed in
+/// `seeded_key` with a ChaCha20 generator, using one stream per GGSW
+/// ciphertext. Only the bodies are stored, which reduces the size of the key by
+/// a factor of `k + 1`. Use [`expand_seeded_bootstrap_key`] to recover the full
+/// [`BootstrapKey`](crate::entities::BootstrapKey).
+///
+/// The seed needn't be secret, but must be chosen uniformly at random for
+/// every key.
+///
+/// # Panics
+/// If `seeded_key`, `sk_to_encrypt` or `sk` aren't valid under `lwe`, `glwe`
+/// and `radix`.
+pub fn generate_seeded_bootstrap_key<S>(
+    seeded_key: &mut SeededBootstrapKey<S>,
+    sk_to_encrypt: &LweSecretKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    lwe: &LweDef,
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    generate_seeded_bootstrap_key_with_rng(
+        seeded_key,
+        sk_to_encrypt,
+        sk,
+        lwe,
+        glwe,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generate a [`SeededBootstrapKey`] from a LWE secret key to a GLWE secret
+/// key, drawing the encryptions' noise from `rng`. See
+/// [`generate_seeded_bootstrap_key`].
+///
+/// # Remarks
+/// The masks still come from the seed in `seeded_key`.
+pub fn generate_seeded_bootstrap_key_with_rng<S>(
+    seeded_key: &mut SeededBootstrapKey<S>,
+    sk_to_encrypt: &LweSecretKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    lwe: &LweDef,
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    lwe.assert_valid();
+    glwe.assert_valid();
+    radix.assert_valid::<S>();
+    seeded_key.assert_is_valid(lwe, glwe, radix);
+    sk.assert_is_valid(glwe.dim);
+    sk_to_encrypt.assert_is_valid(lwe.dim);
+
+    let seed = *seeded_key.seed();
+    let noise_seed = key_seed_with_rng(rng);
+    let stride = seeded_bootstrap_key_row_len(glwe, radix);
+
+    sk_to_encrypt
+        .s()
+        .par_iter()
+        .zip(seeded_key.bodies_mut().par_chunks_mut(stride))
+        .enumerate()
+        .for_each(|(i, (s_i, bodies))| {
+            let mut ggsw = GgswCiphertext::new(glwe, radix);
+            fill_ggsw_masks(
+                &mut ggsw,
+                &mut seeded_stream_rng(&seed, i as u64),
+                glwe,
+                radix,
+            );
+
+            let mut msg = Polynomial::<S>::zero(glwe.dim.polynomial_degree.0);
+            msg.coeffs_mut()[0] = *s_i;
+
+            let mut noise_rng = seeded_stream_rng(&noise_seed, i as u64);
+
+            encrypt_ggsw_ciphertext_generic(
+                &mut ggsw,
+                &msg,
+                sk,
+                glwe,
+                radix,
+                PlaintextBits(1),
+                |ct, msg, sk, glwe| {
+                    encrypt_glwe_ciphertext_secret_with_mask(ct, msg, sk, glwe, &mut noise_rng)
+                },
+            );
+
+            let n = glwe.dim.polynomial_degree.0;
+
+            for (ct, b) in ggsw
+                .rows(glwe, radix)
+                .flat_map(|row| row.glwe_ciphertexts(glwe))
+                .zip(bodies.chunks_mut(n))
+            {
+                b.copy_from_slice(ct.b(glwe).coeffs());
+            }
         });
 }
 
+/// Expand a [`SeededBootstrapKey`] into the full
+/// [`BootstrapKey`](crate::entities::BootstrapKey) it compresses. The result
+/// is identical to the key produced by [`generate_seeded_bootstrap_key`] before
+/// its masks were discarded.
+///
+/// # Panics
+/// If `bootstrap_key` or `seeded_key` aren't valid under `lwe`, `glwe` and
+/// `radix`.
+pub fn expand_seeded_bootstrap_key<S>(
+    bootstrap_key: &mut BootstrapKeyRef<S>,
+    seeded_key: &SeededBootstrapKey<S>,
+    lwe: &LweDef,
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    lwe.assert_valid();
+    glwe.assert_valid();
+    radix.assert_valid::<S>();
+    bootstrap_key.assert_is_valid((lwe.dim, glwe.dim, radix.count));
+    seeded_key.assert_is_valid(lwe, glwe, radix);
+
+    let seed = *seeded_key.seed();
+    let stride = seeded_bootstrap_key_row_len(glwe, radix);
+
+    seeded_key
+        .bodies()
+        .par_chunks(stride)
+        .enumerate()
+        .zip(bootstrap_key.rows_par_mut(glwe, radix))
+        .for_each(|((i, bodies), ggsw)| {
+            fill_ggsw_masks(ggsw, &mut seeded_stream_rng(&seed, i as u64), glwe, radix);
+
+            let n = glwe.dim.polynomial_degree.0;
+
+            for (ct, b) in ggsw
+                .rows_mut(glwe, radix)
+                .flat_map(|row| row.glwe_ciphertexts_mut(glwe))
+                .zip(bodies.chunks(n))
+            {
+                ct.b_mut(glwe).coeffs_mut().copy_from_slice(b);
+            }
+        });
+}
+
+/// The number of body coefficients for each GGSW ciphertext in a
+/// [`SeededBootstrapKey`].
+fn seeded_bootstrap_key_row_len(glwe: &GlweDef, radix: &RadixDecomposition) -> usize {
+    (glwe.dim.size.0 + 1) * radix.count.0 * glwe.dim.polynomial_degree.0
+}
+
+/// Fill the masks of every GLWE ciphertext in `ggsw` with uniform values from
+/// `rng`, in storage order.
+fn fill_ggsw_masks<S>(
+    ggsw: &mut GgswCiphertextRef<S>,
+    rng: &mut (impl CryptoRng + RngCore),
+    glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    for row in ggsw.rows_mut(glwe, radix) {
+        for ct in row.glwe_ciphertexts_mut(glwe) {
+            for a in ct.a_mut(glwe) {
+                for c in a.coeffs_mut() {
+                    *c = uniform_torus_with_rng(rng);
+                }
+            }
+        }
+    }
+}
+
 /// Generate a negacyclic LUT for bootstrapping. Another name for this structure
 /// is a test polynomial.
 ///
@@ -314,6 +547,115 @@ pub fn programmable_bootstrap_univariate<S>(
     sample_extract(output, glwe, 0, glwe_params);
 }
 
+/// [`programmable_bootstrap_univariate`] using a bootstrapping key in NTT
+/// form. Create one with
+/// [`BootstrapKeyRef::ntt`](crate::entities::BootstrapKeyRef::ntt).
+///
+/// # Remarks
+/// The external products are computed exactly, so unlike
+/// [`programmable_bootstrap_univariate`] the result is bit-exact across
+/// platforms. This is slower than bootstrapping with an FFT key.
+///
+/// # Panics
+/// If the NTT isn't exact for `glwe_params` and `radix`. See
+/// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+pub fn programmable_bootstrap_univariate_ntt<S>(
+    output: &mut LweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key: &BootstrapKeyNttRef<Fg>,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    allocate_scratch_ref!(glwe, GlweCiphertextRef<S>, (glwe_params.dim));
+
+    generalized_programmable_bootstrap_ntt(
+        glwe,
+        input,
+        lut,
+        bootstrap_key,
+        0,
+        0,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+
+    sample_extract(output, glwe, 0, glwe_params);
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Programmable bootstrapping with multiple univariate functions of the same
+/// input, sometimes called many-LUT or multi-value bootstrapping. Writes
+/// `f_i(input)` to the `i`-th ciphertext in `output` for each of the
+/// `lut_count` functions `f_i` encoded in `lut`.
+///
+/// # Remarks
+/// Create `lut` with
+/// [`UnivariateLookupTable::trivivial_multifunctional`](crate::entities::UnivariateLookupTable::trivivial_multifunctional),
+/// passing the `lut_count` functions in order. The functions occupy adjacent
+/// coefficients of the test polynomial, so all of them are evaluated with a
+/// single blind rotation and then sample extracted. This costs about the
+/// same as one [`programmable_bootstrap_univariate`].
+///
+/// Packing the functions reduces the precision of the blind rotation by
+/// `ceil(log2(lut_count))` bits. `input` must have at least that many more
+/// bits of noise margin than with [`programmable_bootstrap_univariate`], and
+/// `2^(plaintext_bits + 1) * 2^ceil(log2(lut_count))` must not exceed the
+/// polynomial degree of `glwe_params`.
+///
+/// Like [`programmable_bootstrap_univariate`], this doesn't keyswitch. The
+/// results are encrypted under the LWE key extracted from the GLWE secret
+/// key used for the bootstrapping key.
+///
+/// # Panics
+/// If `lut_count` is 0.
+/// If `output` isn't a list of `lut_count` ciphertexts valid under
+/// `glwe_params.as_lwe_def()`.
+/// If `input`, `lut` or `bootstrap_key` aren't valid under `lwe_params`,
+/// `glwe_params` and `radix`.
+pub fn programmable_bootstrap_many_lut<S>(
+    output: &mut LweCiphertextListRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    lut_count: usize,
+    bootstrap_key: &BootstrapKeyFftRef<Complex<f64>>,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    assert!(lut_count > 0);
+    output.assert_is_valid((glwe_params.dim.as_lwe_dimension(), lut_count));
+
+    let log_v = lut_count.next_power_of_two().ilog2();
+
+    allocate_scratch_ref!(glwe, GlweCiphertextRef<S>, (glwe_params.dim));
+
+    generalized_programmable_bootstrap(
+        glwe,
+        input,
+        lut,
+        bootstrap_key,
+        0,
+        log_v,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+
+    for (i, lwe) in output
+        .ciphertexts_mut(&glwe_params.as_lwe_def())
+        .enumerate()
+    {
+        sample_extract(lwe, glwe, i, glwe_params);
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 /// A generalized version of programmable bootstrapping.
 /// Computes a function `lut` of the encrypted `input`.
@@ -353,6 +695,81 @@ pub fn generalized_programmable_bootstrap<S>(
     glwe_params.assert_valid();
     radix.assert_valid::<S>();
     bootstrap_key.assert_is_valid((lwe_params.dim, glwe_params.dim, radix.count));
+
+    blind_rotate_lut(
+        output,
+        input,
+        lut,
+        bootstrap_key.rows(glwe_params, radix),
+        log_chi,
+        log_v,
+        lwe_params,
+        glwe_params,
+        |c, d_0, d_1, b| cmux(c, d_0, d_1, b, glwe_params, radix),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+/// [`generalized_programmable_bootstrap`] using a bootstrapping key in NTT
+/// form.
+///
+/// # Remarks
+/// The external products are computed exactly, so unlike
+/// [`generalized_programmable_bootstrap`] the result is bit-exact across
+/// platforms and matches bootstrapping with the non-transformed key.
+///
+/// # Panics
+/// If the NTT isn't exact for `glwe_params` and `radix`. See
+/// [`ntt_is_exact`](crate::ops::ntt_ops::ntt_is_exact).
+pub fn generalized_programmable_bootstrap_ntt<S>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key: &BootstrapKeyNttRef<Fg>,
+    log_chi: u32,
+    log_v: u32,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    lwe_params.assert_valid();
+    glwe_params.assert_valid();
+    radix.assert_valid::<S>();
+    bootstrap_key.assert_is_valid((lwe_params.dim, glwe_params.dim, radix.count));
+
+    blind_rotate_lut(
+        output,
+        input,
+        lut,
+        bootstrap_key.rows(glwe_params, radix),
+        log_chi,
+        log_v,
+        lwe_params,
+        glwe_params,
+        |c, d_0, d_1, b| ntt_ops::cmux(c, d_0, d_1, b, glwe_params, radix),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Blind rotate `lut` by the modulus switched `input`, selecting each
+/// rotation with `cmux` and the matching row of the bootstrapping key.
+fn blind_rotate_lut<'a, S, K, F>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key_rows: impl Iterator<Item = &'a K>,
+    log_chi: u32,
+    log_v: u32,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    cmux: F,
+) where
+    S: TorusOps,
+    K: ?Sized + 'a,
+    F: Fn(&mut GlweCiphertextRef<S>, &GlweCiphertextRef<S>, &GlweCiphertextRef<S>, &K),
+{
     lut.assert_is_valid(glwe_params.dim);
     input.assert_is_valid(lwe_params.dim);
     output.assert_is_valid(glwe_params.dim);
@@ -390,7 +807,7 @@ pub fn generalized_programmable_bootstrap<S>(
 
     // Perform the cmux tree from the bootstrap key with the relation
     // V_n = V_{n-1} ^ X^{a_{n-1} s_{n-1}}
-    for (a_i, index_select) in ct_a.iter().zip(bootstrap_key.rows(glwe_params, radix)) {
+    for (a_i, index_select) in ct_a.iter().zip(bootstrap_key_rows) {
         let tmp = output.to_owned();
 
         // This operation performs a copy so the rotated_ct doesn't need to be
@@ -402,7 +819,7 @@ pub fn generalized_programmable_bootstrap<S>(
             glwe_params,
         );
 
-        cmux(output, &tmp, rotated_ct, index_select, glwe_params, radix);
+        cmux(output, &tmp, rotated_ct, index_select);
     }
 }
 
@@ -620,10 +1037,15 @@ pub fn programmable_bootstrap_bivariate<S>(
 #[cfg(test)]
 mod tests {
 
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
     use crate::{
+        dst::AsSlice,
         entities::{
-            BivariateLookupTable, BootstrapKey, BootstrapKeyFft, GlweCiphertext, LweCiphertext,
-            LweKeyswitchKey, UnivariateLookupTable,
+            BivariateLookupTable, BootstrapKey, BootstrapKeyFft, BootstrapKeyNtt, GlweCiphertext,
+            GlweSecretKey, LweCiphertext, LweCiphertextList, LweKeyswitchKey, LweSecretKey,
+            SeededBootstrapKey, UnivariateLookupTable,
         },
         high_level::{encryption, fft, keygen, TEST_GLWE_DEF_1, TEST_LWE_DEF_1, TEST_RADIX},
         ops::{
@@ -703,6 +1125,90 @@ mod tests {
         assert_eq!(count, sk.s().len());
     }
 
+    #[test]
+    fn seeded_rng_gives_reproducible_bootstrap_key() {
+        let lwe_params = TEST_LWE_DEF_1;
+        let glwe_params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+
+        let generate = |seed| {
+            let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+            let sk = LweSecretKey::<u64>::generate_binary_with_rng(&lwe_params, &mut rng);
+            let glwe_sk = GlweSecretKey::generate_binary_with_rng(&glwe_params, &mut rng);
+
+            let mut bootstrap_key = BootstrapKey::new(&lwe_params, &glwe_params, &radix);
+            generate_bootstrap_key_with_rng(
+                &mut bootstrap_key,
+                &sk,
+                &glwe_sk,
+                &lwe_params,
+                &glwe_params,
+                &radix,
+                &mut rng,
+            );
+
+            bootstrap_key
+        };
+
+        assert_eq!(generate(42), generate(42));
+        assert_ne!(generate(42), generate(43));
+    }
+
+    #[test]
+    fn can_expand_seeded_bootstrap_key() {
+        let lwe_params = TEST_LWE_DEF_1;
+        let glwe_params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+
+        let sk = keygen::generate_binary_lwe_sk(&lwe_params);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe_params);
+
+        let mut seeded_key = SeededBootstrapKey::new([7; 32], &lwe_params, &glwe_params, &radix);
+        generate_seeded_bootstrap_key(
+            &mut seeded_key,
+            &sk,
+            &glwe_sk,
+            &lwe_params,
+            &glwe_params,
+            &radix,
+        );
+
+        let mut bootstrap_key = BootstrapKey::new(&lwe_params, &glwe_params, &radix);
+        expand_seeded_bootstrap_key(
+            &mut bootstrap_key,
+            &seeded_key,
+            &lwe_params,
+            &glwe_params,
+            &radix,
+        );
+
+        for (s_i, ct) in sk.s().iter().zip(bootstrap_key.rows(&glwe_params, &radix)) {
+            let mut msg = Polynomial::<Torus<u64>>::zero(glwe_params.dim.polynomial_degree.0);
+            decrypt_ggsw_ciphertext(&mut msg, ct, &glwe_sk, &glwe_params, &radix);
+
+            assert_eq!(msg.coeffs()[0].inner(), *s_i);
+        }
+
+        // Expansion is deterministic.
+        let mut expanded = BootstrapKey::new(&lwe_params, &glwe_params, &radix);
+        expand_seeded_bootstrap_key(
+            &mut expanded,
+            &seeded_key,
+            &lwe_params,
+            &glwe_params,
+            &radix,
+        );
+
+        assert_eq!(expanded, bootstrap_key);
+
+        let k_plus_1 = glwe_params.dim.size.0 + 1;
+        assert_eq!(
+            seeded_key.bodies().len() * k_plus_1,
+            bootstrap_key.as_slice().len()
+        );
+    }
+
     fn bootstrap_helper(map: impl Fn(u64) -> u64) {
         let bits = PlaintextBits(3);
         let lwe = TEST_LWE_DEF_1;
@@ -785,6 +1291,57 @@ mod tests {
         bootstrap_helper(|x| (x + 3) % 8);
     }
 
+    #[test]
+    fn can_bootstrap_ntt() {
+        let bits = PlaintextBits(3);
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = GLWE_1_1024_80;
+        let radix = TEST_RADIX;
+
+        let original_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let mut bsk_nonntt = BootstrapKey::new(&lwe, &glwe, &radix);
+        generate_bootstrap_key(&mut bsk_nonntt, &original_sk, &glwe_sk, &lwe, &glwe, &radix);
+
+        let mut bsk = BootstrapKeyNtt::new(&lwe, &glwe, &radix);
+        bsk_nonntt.ntt(&mut bsk, &lwe, &glwe, &radix);
+
+        let map = |x| (x + 3) % 8;
+        let lut = UnivariateLookupTable::trivial_from_fn(map, &glwe, bits);
+
+        for msg in 0..(1 << bits.0) {
+            let mut original_ct = LweCiphertext::new(&lwe);
+
+            // Adding a padding bit
+            let encoded_msg = msg << (64 - bits.0 - 1);
+            encrypt_lwe_ciphertext(
+                &mut original_ct,
+                &original_sk,
+                Torus::from(encoded_msg),
+                &lwe,
+            );
+
+            let mut new_ct = LweCiphertext::new(&glwe.as_lwe_def());
+
+            programmable_bootstrap_univariate_ntt(
+                &mut new_ct,
+                &original_ct,
+                &lut,
+                &bsk,
+                &lwe,
+                &glwe,
+                &radix,
+            );
+
+            let decoded = glwe_sk
+                .to_lwe_secret_key()
+                .decrypt(&new_ct, &glwe.as_lwe_def(), bits);
+
+            assert_eq!(decoded, map(msg));
+        }
+    }
+
     fn bivariate_bootstrap_helper(map: impl Fn(u64, u64) -> u64) {
         let lwe = TEST_LWE_DEF_1;
         let glwe = TEST_GLWE_DEF_1;
@@ -981,4 +1538,49 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn can_bootstrap_many_lut() {
+        let bits = PlaintextBits(2);
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = GLWE_1_1024_80;
+        let radix = TEST_RADIX;
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+        let bsk = fft::fft_bootstrap_key(&bsk, &lwe, &glwe, &radix);
+
+        let maps: [&dyn Fn(u64) -> u64; 3] = [&|x| x, &|x| (x + 1) % 4, &|x| (3 * x) % 4];
+        let lut = UnivariateLookupTable::trivivial_multifunctional(&maps, &glwe, bits);
+
+        for msg in 0..(1 << bits.0) {
+            // Adding a padding bit
+            let input = encryption::encrypt_lwe_secret(msg, &lwe_sk, &lwe, PlaintextBits(3));
+
+            let mut output = LweCiphertextList::new(&glwe.as_lwe_def(), maps.len());
+
+            programmable_bootstrap_many_lut(
+                &mut output,
+                &input,
+                &lut,
+                maps.len(),
+                &bsk,
+                &lwe,
+                &glwe,
+                &radix,
+            );
+
+            for (map, ct) in maps.iter().zip(output.ciphertexts(&glwe.as_lwe_def())) {
+                let actual = encryption::decrypt_lwe(
+                    ct,
+                    glwe_sk.to_lwe_secret_key(),
+                    &glwe.as_lwe_def(),
+                    bits,
+                );
+
+                assert_eq!(actual, map(msg));
+            }
+        }
+    }
 }
@@ -10,12 +10,16 @@ use crate::{
         PolynomialFft, PolynomialRef, SchemeSwitchKeyRef,
     },
     iteration::TriangularPairsExt,
-    ops::{ciphertext::decomposed_polynomial_glev_mad, encryption::encrypt_secret_glev_ciphertext},
+    ops::{
+        ciphertext::decomposed_polynomial_glev_mad,
+        encryption::encrypt_secret_glev_ciphertext_with_rng,
+    },
     radix::PolynomialRadixIterator,
     scratch::allocate_scratch_ref,
     GlweDef, OverlaySize, RadixDecomposition, Torus, TorusOps,
 };
 use num::{Complex, Zero};
+use rand::{thread_rng, CryptoRng, RngCore};
 
 /// Generate a scheme switch key. This encrypts the secret key `sk` in a series
 /// of GLev encryptions under that same key.
@@ -29,6 +33,23 @@ pub fn generate_scheme_switch_key<S>(
     Wrapping<S>: Sub<Wrapping<S>, Output = Wrapping<S>>
         + Add<Wrapping<S>, Output = Wrapping<S>>
         + Mul<Wrapping<S>, Output = Wrapping<S>>,
+{
+    generate_scheme_switch_key_with_rng(scheme_switch_key, sk, params, radix, &mut thread_rng())
+}
+
+/// Generate a scheme switch key, drawing the encryptions' randomness from
+/// `rng`. See [`generate_scheme_switch_key`].
+pub fn generate_scheme_switch_key_with_rng<S>(
+    scheme_switch_key: &mut SchemeSwitchKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+    Wrapping<S>: Sub<Wrapping<S>, Output = Wrapping<S>>
+        + Add<Wrapping<S>, Output = Wrapping<S>>
+        + Mul<Wrapping<S>, Output = Wrapping<S>>,
 {
     params.assert_valid();
     radix.assert_valid::<S>();
@@ -59,7 +80,14 @@ pub fn generate_scheme_switch_key<S>(
         s_i_j_fft.ifft(&mut s_i_j);
 
         // Encrypt the secret key under itself
-        encrypt_secret_glev_ciphertext(glev_ciphertext, s_i_j.as_torus(), sk, params, radix)
+        encrypt_secret_glev_ciphertext_with_rng(
+            glev_ciphertext,
+            s_i_j.as_torus(),
+            sk,
+            params,
+            radix,
+            rng,
+        )
     });
 }
 
@@ -336,6 +364,7 @@ mod tests {
         ops::{
             encryption::{
                 decrypt_ggsw_ciphertext, decrypt_glev_ciphertext, decrypt_glwe_ciphertext,
+                encrypt_secret_glev_ciphertext,
             },
             fft_ops::cmux,
         },
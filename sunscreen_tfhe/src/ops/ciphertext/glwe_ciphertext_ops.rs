@@ -5,8 +5,8 @@ use crate::{
     },
     ops::ciphertext::decomposed_polynomial_glev_mad,
     polynomial::{
-        polynomial_add, polynomial_external_mad, polynomial_negate, polynomial_scalar_mad,
-        polynomial_sub,
+        polynomial_external_mad, polynomial_negate, polynomial_scalar_mad, torus_polynomial_add,
+        torus_polynomial_sub,
     },
     radix::PolynomialRadixIterator,
     scratch::allocate_scratch_ref,
@@ -92,10 +92,10 @@ pub fn add_glwe_ciphertexts<S>(
     assert_eq!(c_a.len(), b_a.len());
 
     for (c, (a, b)) in c_a.zip(a_a.zip(b_a)) {
-        polynomial_add(c, a, b);
+        torus_polynomial_add(c, a, b);
     }
 
-    polynomial_add(c_b, a_b, b_b);
+    torus_polynomial_add(c_b, a_b, b_b);
 }
 
 /// Subtract two GLWE ciphertexts together, storing the result in `c`.
@@ -115,10 +115,10 @@ pub fn sub_glwe_ciphertexts<S>(
     assert_eq!(c_a.len(), b_a.len());
 
     for (c, (a, b)) in c_a.zip(a_a.zip(b_a)) {
-        polynomial_sub(c, a, b);
+        torus_polynomial_sub(c, a, b);
     }
 
-    polynomial_sub(c_b, a_b, b_b);
+    torus_polynomial_sub(c_b, a_b, b_b);
 }
 
 /// Homomorphically compute -ct.
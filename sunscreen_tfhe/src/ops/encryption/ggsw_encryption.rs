@@ -1,3 +1,5 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
 use crate::{
     dst::FromMutSlice,
     entities::{GgswCiphertextRef, GlweCiphertextRef, GlweSecretKeyRef, Polynomial, PolynomialRef},
@@ -8,7 +10,8 @@ use crate::{
 };
 
 use super::{
-    decrypt_glwe_in_glev, encrypt_glwe_ciphertext_secret, trivially_encrypt_glwe_with_sk_argument,
+    decrypt_glwe_in_glev, encrypt_glwe_ciphertext_secret_with_rng,
+    trivially_encrypt_glwe_with_sk_argument,
 };
 
 /// Perform a GGSW encryption. This is generic in case a trivial GGSW encryption
@@ -20,7 +23,7 @@ pub(crate) fn encrypt_ggsw_ciphertext_generic<S>(
     params: &GlweDef,
     radix: &RadixDecomposition,
     plaintext_bits: PlaintextBits,
-    encrypt: impl Fn(
+    mut encrypt: impl FnMut(
         &mut GlweCiphertextRef<S>,
         &PolynomialRef<Torus<S>>,
         &GlweSecretKeyRef<S>,
@@ -65,7 +68,7 @@ pub(crate) fn encrypt_ggsw_ciphertext_generic<S>(
             glwe_secret_key,
             params,
             radix,
-            &encrypt,
+            &mut encrypt,
         );
     }
 }
@@ -80,6 +83,30 @@ pub fn encrypt_ggsw_ciphertext<S>(
     plaintext_bits: PlaintextBits,
 ) where
     S: TorusOps,
+{
+    encrypt_ggsw_ciphertext_with_rng(
+        ggsw_ciphertext,
+        msg,
+        glwe_secret_key,
+        params,
+        radix,
+        plaintext_bits,
+        &mut thread_rng(),
+    );
+}
+
+/// Encrypt a GGSW ciphertext with a given message polynomial and secret key,
+/// drawing the masks and noise from `rng`.
+pub fn encrypt_ggsw_ciphertext_with_rng<S>(
+    ggsw_ciphertext: &mut GgswCiphertextRef<S>,
+    msg: &PolynomialRef<S>,
+    glwe_secret_key: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     encrypt_ggsw_ciphertext_generic(
         ggsw_ciphertext,
@@ -88,7 +115,7 @@ pub fn encrypt_ggsw_ciphertext<S>(
         params,
         radix,
         plaintext_bits,
-        encrypt_glwe_ciphertext_secret,
+        |ct, msg, sk, params| encrypt_glwe_ciphertext_secret_with_rng(ct, msg, sk, params, rng),
     );
 }
 
@@ -128,19 +155,44 @@ pub fn encrypt_ggsw_ciphertext_scalar<S>(
     plaintext_bits: PlaintextBits,
 ) where
     S: TorusOps,
+{
+    encrypt_ggsw_ciphertext_scalar_with_rng(
+        ggsw_ciphertext,
+        msg,
+        glwe_secret_key,
+        params,
+        radix,
+        plaintext_bits,
+        &mut thread_rng(),
+    )
+}
+
+/// Encrypt scalar (i.e. degree 0 polynomial) msg as a GGSW ciphertext, drawing
+/// the masks and noise from `rng`.
+pub fn encrypt_ggsw_ciphertext_scalar_with_rng<S>(
+    ggsw_ciphertext: &mut GgswCiphertextRef<S>,
+    msg: S,
+    glwe_secret_key: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     let polynomial_degree = params.dim.polynomial_degree.0;
 
     let mut poly_msg = Polynomial::<S>::zero(polynomial_degree);
     poly_msg.coeffs_mut()[0] = msg;
 
-    encrypt_ggsw_ciphertext(
+    encrypt_ggsw_ciphertext_with_rng(
         ggsw_ciphertext,
         &poly_msg,
         glwe_secret_key,
         params,
         radix,
         plaintext_bits,
+        rng,
     )
 }
 
@@ -1,16 +1,18 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
 use crate::{
     dst::FromMutSlice,
     entities::{
         GlevCiphertextRef, GlweCiphertextRef, GlweSecretKeyRef, PolynomialRef, RlwePublicKeyRef,
     },
-    ops::encryption::rlwe_encrypt_public,
+    ops::encryption::rlwe_encrypt_public_with_rng,
     polynomial::polynomial_scalar_mul,
     scratch::allocate_scratch_ref,
     GlweDef, OverlaySize, RadixDecomposition, Torus, TorusOps,
 };
 
 use super::{
-    decrypt_glwe_ciphertext, encrypt_glwe_ciphertext_secret,
+    decrypt_glwe_ciphertext, encrypt_glwe_ciphertext_secret_with_rng,
     trivially_encrypt_glwe_with_sk_argument,
 };
 
@@ -26,7 +28,7 @@ pub(crate) fn encrypt_secret_glev_ciphertext_generic<S>(
     glwe_secret_key: &GlweSecretKeyRef<S>,
     params: &GlweDef,
     radix: &RadixDecomposition,
-    encrypt: impl Fn(
+    mut encrypt: impl FnMut(
         &mut GlweCiphertextRef<S>,
         &PolynomialRef<Torus<S>>,
         &GlweSecretKeyRef<S>,
@@ -86,6 +88,28 @@ pub fn encrypt_secret_glev_ciphertext<S>(
     radix: &RadixDecomposition,
 ) where
     S: TorusOps,
+{
+    encrypt_secret_glev_ciphertext_with_rng(
+        glev_ciphertext,
+        msg,
+        glwe_secret_key,
+        params,
+        radix,
+        &mut thread_rng(),
+    );
+}
+
+/// Encrypt a GLev ciphertext with a given message polynomial and secret key,
+/// drawing the masks and noise from `rng`.
+pub fn encrypt_secret_glev_ciphertext_with_rng<S>(
+    glev_ciphertext: &mut GlevCiphertextRef<S>,
+    msg: &PolynomialRef<Torus<S>>,
+    glwe_secret_key: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     encrypt_secret_glev_ciphertext_generic(
         glev_ciphertext,
@@ -93,7 +117,7 @@ pub fn encrypt_secret_glev_ciphertext<S>(
         glwe_secret_key,
         params,
         radix,
-        encrypt_glwe_ciphertext_secret,
+        |ct, msg, sk, params| encrypt_glwe_ciphertext_secret_with_rng(ct, msg, sk, params, rng),
     );
 }
 
@@ -140,6 +164,28 @@ pub fn encrypt_rlev_ciphertext<S>(
     radix: &RadixDecomposition,
 ) where
     S: TorusOps,
+{
+    encrypt_rlev_ciphertext_with_rng(
+        rlev_ciphertext,
+        msg,
+        rlwe_public_key,
+        params,
+        radix,
+        &mut thread_rng(),
+    );
+}
+
+/// Encrypts `msg` as an RLEV ciphertext using `rlwe_public_key`, drawing the
+/// randomness from `rng`. See [`encrypt_rlev_ciphertext`].
+pub fn encrypt_rlev_ciphertext_with_rng<S>(
+    rlev_ciphertext: &mut GlevCiphertextRef<S>,
+    msg: &PolynomialRef<Torus<S>>,
+    rlwe_public_key: &RlwePublicKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     radix.assert_valid::<S>();
     msg.assert_is_valid(params.dim.polynomial_degree);
@@ -157,7 +203,7 @@ pub fn encrypt_rlev_ciphertext<S>(
 
         dbg!(&scaled_msg.coeffs()[0..16]);
 
-        rlwe_encrypt_public(glwe, scaled_msg, rlwe_public_key, params);
+        rlwe_encrypt_public_with_rng(glwe, scaled_msg, rlwe_public_key, params, rng);
     }
 }
 
@@ -1,9 +1,12 @@
 use num::Zero;
+use rand::{thread_rng, CryptoRng, RngCore};
 
 use crate::{
     entities::{GlweCiphertextRef, GlweSecretKeyRef, Polynomial, PolynomialRef},
-    polynomial::{polynomial_add_assign, polynomial_external_mad, polynomial_sub_assign},
-    rand::{normal_torus, uniform_torus},
+    polynomial::{
+        polynomial_external_mad, torus_polynomial_add_assign, torus_polynomial_sub_assign,
+    },
+    rand::{normal_torus_with_rng, uniform_torus_with_rng},
     GlweDef, Torus, TorusOps,
 };
 
@@ -26,6 +29,42 @@ pub fn encrypt_glwe_ciphertext_secret_generic<S>(
     params: &GlweDef,
 ) where
     S: TorusOps,
+{
+    encrypt_glwe_ciphertext_secret_with_rng(c, msg, sk, params, &mut thread_rng());
+}
+
+/// Encrypt `msg` into the given GLWE ciphertext `c` using the secret key `sk`,
+/// drawing the mask and noise from `rng`.
+pub fn encrypt_glwe_ciphertext_secret_with_rng<S>(
+    c: &mut GlweCiphertextRef<S>,
+    msg: &PolynomialRef<Torus<S>>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    // Fill the a_i with uniform data
+    for a_i in c.a_mut(params) {
+        for c in a_i.coeffs_mut() {
+            *c = uniform_torus_with_rng(rng);
+        }
+    }
+
+    encrypt_glwe_ciphertext_secret_with_mask(c, msg, sk, params, rng);
+}
+
+/// Encrypt `msg` into the given GLWE ciphertext `c` using the secret key `sk`
+/// and the mask polynomials already in `c`. Used when the mask comes from a
+/// seeded generator rather than fresh randomness.
+pub(crate) fn encrypt_glwe_ciphertext_secret_with_mask<S>(
+    c: &mut GlweCiphertextRef<S>,
+    msg: &PolynomialRef<Torus<S>>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     let mut tmp = Polynomial::zero(params.dim.polynomial_degree.0);
 
@@ -33,19 +72,14 @@ pub fn encrypt_glwe_ciphertext_secret_generic<S>(
 
     // tmp = A_i * S_i
     for (a_i, s_i) in a.zip(sk.s(params)) {
-        // Fill a_i with uniform data
-        for c in a_i.coeffs_mut() {
-            *c = uniform_torus();
-        }
-
         polynomial_external_mad(&mut tmp, a_i, s_i);
     }
 
     // b = A * S
-    polynomial_add_assign(b, &tmp);
+    torus_polynomial_add_assign(b, &tmp);
 
     // b = A * S + m
-    polynomial_add_assign(b, msg);
+    torus_polynomial_add_assign(b, msg);
 
     // Do not add e if the standard deviation is zero
     if params.std.0 == 0.0 {
@@ -54,12 +88,12 @@ pub fn encrypt_glwe_ciphertext_secret_generic<S>(
 
     let e = Polynomial::new(
         &(0..msg.len())
-            .map(|_| normal_torus::<S>(params.std))
+            .map(|_| normal_torus_with_rng::<S>(params.std, rng))
             .collect::<Vec<_>>(),
     );
 
     // b = A * S + m + e
-    polynomial_add_assign(b, &e);
+    torus_polynomial_add_assign(b, &e);
 }
 
 /// Encrypt `msg` into a into the given GLWE ciphertext `c` using the secret key `sk.`
@@ -122,7 +156,7 @@ pub fn decrypt_glwe_ciphertext<S>(
     }
 
     // msg = b - A * S = m + e
-    polynomial_sub_assign(msg, &tmp);
+    torus_polynomial_sub_assign(msg, &tmp);
 }
 
 #[cfg(test)]
@@ -1,10 +1,11 @@
+use rand::{thread_rng, CryptoRng, RngCore};
 use sunscreen_math::Zero;
 
 use crate::{
     dst::AsSlice,
     entities::{LweCiphertextRef, LweSecretKeyRef},
     math::{Torus, TorusOps},
-    rand::{normal_torus, uniform_torus},
+    rand::{normal_torus_with_rng, uniform_torus_with_rng},
     LweDef, OverlaySize, PlaintextBits,
 };
 
@@ -39,20 +40,55 @@ pub fn encrypt_lwe_ciphertext<S>(
     msg: Torus<S>,
     params: &LweDef,
 ) -> Torus<S>
+where
+    S: TorusOps,
+{
+    encrypt_lwe_ciphertext_with_rng(ct, sk, msg, params, &mut thread_rng())
+}
+
+/// Encrypts the given message under sk, writing the ciphertext to ct and
+/// drawing the mask and noise from `rng`. Returns the randomness used to
+/// generate the ciphertext.
+pub fn encrypt_lwe_ciphertext_with_rng<S>(
+    ct: &mut LweCiphertextRef<S>,
+    sk: &LweSecretKeyRef<S>,
+    msg: Torus<S>,
+    params: &LweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Torus<S>
 where
     S: TorusOps,
 {
     params.assert_valid();
 
+    for a_i in ct.a_mut(params) {
+        *a_i = uniform_torus_with_rng::<S>(rng);
+    }
+
+    encrypt_lwe_ciphertext_with_mask(ct, sk, msg, params, rng)
+}
+
+/// Encrypts the given message under sk using the mask already in ct, writing
+/// the body to ct. Returns the noise used to generate the ciphertext. Used
+/// when the mask comes from a seeded generator rather than fresh randomness.
+pub(crate) fn encrypt_lwe_ciphertext_with_mask<S>(
+    ct: &mut LweCiphertextRef<S>,
+    sk: &LweSecretKeyRef<S>,
+    msg: Torus<S>,
+    params: &LweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Torus<S>
+where
+    S: TorusOps,
+{
     let (a, b) = ct.a_b_mut(params);
     *b = Torus::zero();
 
-    for (a_i, d_i) in a.iter_mut().zip(sk.as_slice().iter()) {
-        *a_i = uniform_torus::<S>();
+    for (a_i, d_i) in a.iter().zip(sk.as_slice().iter()) {
         *b += *a_i * d_i;
     }
 
-    let e = normal_torus(params.std);
+    let e = normal_torus_with_rng(params.std, rng);
     *b += msg + e;
 
     e
@@ -67,18 +103,52 @@ pub fn encode_and_encrypt_lwe_ciphertext<S>(
     params: &LweDef,
     plaintext_bits: PlaintextBits,
 ) -> Torus<S>
+where
+    S: TorusOps,
+{
+    encode_and_encrypt_lwe_ciphertext_with_rng(
+        ct,
+        sk,
+        msg,
+        params,
+        plaintext_bits,
+        &mut thread_rng(),
+    )
+}
+
+/// Encodes and encrypts the given message under sk, writing the ciphertext to
+/// ct and drawing the mask and noise from `rng`. Returns the randomness used
+/// to generate the ciphertext.
+pub fn encode_and_encrypt_lwe_ciphertext_with_rng<S>(
+    ct: &mut LweCiphertextRef<S>,
+    sk: &LweSecretKeyRef<S>,
+    msg: S,
+    params: &LweDef,
+    plaintext_bits: PlaintextBits,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Torus<S>
 where
     S: TorusOps,
 {
     let msg = Torus::<S>::encode(msg, plaintext_bits);
 
-    encrypt_lwe_ciphertext(ct, sk, msg, params)
+    encrypt_lwe_ciphertext_with_rng(ct, sk, msg, params, rng)
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::{high_level::*, ops::encryption::encode_and_encrypt_lwe_ciphertext, PlaintextBits};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::{
+        entities::{LweCiphertext, LweSecretKey},
+        high_level::*,
+        ops::encryption::encode_and_encrypt_lwe_ciphertext,
+        LweDimension, PlaintextBits, LWE_512_80,
+    };
+
+    use super::*;
 
     #[test]
     fn can_encrypt_decrypt() {
@@ -137,4 +207,62 @@ mod tests {
 
         assert_eq!(pt, 4);
     }
+
+    #[test]
+    fn seeded_rng_gives_reproducible_encryptions() {
+        let params = TEST_LWE_DEF_1;
+        let bits = PlaintextBits(4);
+
+        let encrypt = |seed| {
+            let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+            let sk = LweSecretKey::<u64>::generate_binary_with_rng(&params, &mut rng);
+            let mut ct = LweCiphertext::new(&params);
+            let e = encode_and_encrypt_lwe_ciphertext_with_rng(
+                &mut ct, &sk, 4, &params, bits, &mut rng,
+            );
+
+            (sk, ct, e)
+        };
+
+        let (sk, ct, e) = encrypt(1234);
+
+        assert_eq!(encryption::decrypt_lwe(&ct, &sk, &params, bits), 4);
+        assert_eq!((sk.clone(), ct.clone(), e), encrypt(1234));
+        assert_ne!(ct, encrypt(1235).1);
+    }
+
+    // Fixes the output of the default ChaCha20 generator, so changes to how keygen and
+    // encryption draw randomness are caught.
+    #[test]
+    fn known_answer_lwe_encryption() {
+        let params = LweDef {
+            dim: LweDimension(8),
+            ..LWE_512_80
+        };
+        let bits = PlaintextBits(4);
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+
+        let sk = keygen::generate_binary_lwe_sk_with_rng(&params, &mut rng);
+        let (ct, e) = encryption::encrypt_lwe_secret_and_return_randomness_with_rng(
+            5, &sk, &params, bits, &mut rng,
+        );
+
+        assert_eq!(sk.s(), &[0, 1, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(
+            ct.a(&params).iter().map(|x| x.inner()).collect::<Vec<_>>(),
+            [
+                0x101c_eec9_79c7_2ca2,
+                0x5fbd_69c8_72e1_666f,
+                0x1b55_0032_c4b4_0d8f,
+                0xb860_9a87_f978_94e4,
+                0xf05d_a78f_f74f_f232,
+                0x9ef6_2b92_6720_4e04,
+                0xb82f_b520_c4a6_5e7f,
+                0x12a7_05b1_5771_77c4,
+            ]
+        );
+        assert_eq!(ct.b(&params).inner(), 0x739f_aa4a_a552_bcb8);
+        assert_eq!(e.inner(), 0xffff_e39e_b1c6_f809);
+    }
 }
@@ -1,12 +1,14 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
 use crate::{
     dst::FromMutSlice,
     entities::{
         GlweCiphertext, GlweCiphertextRef, GlweSecretKeyRef, Polynomial, PolynomialRef,
         RlwePublicKeyRef,
     },
-    ops::encryption::encrypt_glwe_ciphertext_secret,
-    polynomial::{polynomial_add_assign, polynomial_external_mad},
-    rand::{binary_torus_polynomial, normal_torus_polynomial},
+    ops::encryption::encrypt_glwe_ciphertext_secret_with_rng,
+    polynomial::{polynomial_external_mad, torus_polynomial_add_assign},
+    rand::{binary_torus_polynomial_with_rng, normal_torus_polynomial_with_rng},
     scratch::allocate_scratch_ref,
     GlweDef, OverlaySize, PlaintextBits, Torus, TorusOps,
 };
@@ -21,6 +23,7 @@ use crate::{
 /// # Remarks
 /// See [`rlwe_encrypt_public`] for an explanation of the RLWE public-key encryption
 /// algorithm and the significance of `e_1`, `e_2`, and `u`.
+#[derive(Debug)]
 pub struct RlwePublicEncryptionRandomness<S>
 where
     S: TorusOps,
@@ -50,13 +53,35 @@ pub fn rlwe_generate_public_key<S>(
     glwe: &GlweDef,
 ) where
     S: TorusOps,
+{
+    rlwe_generate_public_key_with_rng(public_key, secret_key, glwe, &mut thread_rng());
+}
+
+/// Generate an RLWE public key into `public_key` for the given `secret_key`,
+/// drawing the randomness from `rng`. See [`rlwe_generate_public_key`].
+///
+/// # Panics
+/// If `glwe.dim.size != 1`.
+pub fn rlwe_generate_public_key_with_rng<S>(
+    public_key: &mut RlwePublicKeyRef<S>,
+    secret_key: &GlweSecretKeyRef<S>,
+    glwe: &GlweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     assert_eq!(glwe.dim.size.0, 1);
 
     allocate_scratch_ref!(pt, PolynomialRef<Torus<S>>, (glwe.dim.polynomial_degree));
     pt.clear();
 
-    encrypt_glwe_ciphertext_secret(public_key.zero_encryption_mut(), pt, secret_key, glwe);
+    encrypt_glwe_ciphertext_secret_with_rng(
+        public_key.zero_encryption_mut(),
+        pt,
+        secret_key,
+        glwe,
+        rng,
+    );
 }
 
 /// Encode and encrypt `msg` into a GLWE ciphertext using the given RLWE public key.
@@ -73,6 +98,32 @@ pub fn rlwe_encode_encrypt_public<S>(
     plaintext_bits: &PlaintextBits,
     glwe: &GlweDef,
 ) -> RlwePublicEncryptionRandomness<S>
+where
+    S: TorusOps,
+{
+    rlwe_encode_encrypt_public_with_rng(
+        ct,
+        msg,
+        public_key,
+        plaintext_bits,
+        glwe,
+        &mut thread_rng(),
+    )
+}
+
+/// Encode and encrypt `msg` into a GLWE ciphertext using the given RLWE public
+/// key, drawing the randomness from `rng`. See [`rlwe_encode_encrypt_public`].
+///
+/// # Panics
+/// If `glwe.dim.size != 1` or if the parameters don't match `ct`, `msg`, or `public_key`.
+pub fn rlwe_encode_encrypt_public_with_rng<S>(
+    ct: &mut GlweCiphertext<S>,
+    msg: &PolynomialRef<S>,
+    public_key: &RlwePublicKeyRef<S>,
+    plaintext_bits: &PlaintextBits,
+    glwe: &GlweDef,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> RlwePublicEncryptionRandomness<S>
 where
     S: TorusOps,
 {
@@ -82,7 +133,7 @@ where
         *enc = Torus::encode(*msg, *plaintext_bits);
     }
 
-    rlwe_encrypt_public(ct, encoded, public_key, glwe)
+    rlwe_encrypt_public_with_rng(ct, encoded, public_key, glwe, rng)
 }
 
 /// Encrypts an encoded polynomial message using the given [`RlwePublicKey`](RlwePublicKeyRef).
@@ -119,14 +170,21 @@ pub fn rlwe_encrypt_public<S>(
 where
     S: TorusOps,
 {
-    rlwe_encrypt_public_impl(ct, encoded_msg, public_key, glwe)
+    rlwe_encrypt_public_with_rng(ct, encoded_msg, public_key, glwe, &mut thread_rng())
 }
 
-fn rlwe_encrypt_public_impl<S>(
+/// Encrypts an encoded polynomial message using the given
+/// [`RlwePublicKey`](RlwePublicKeyRef), drawing `u`, `e0` and `e1` from `rng`.
+/// See [`rlwe_encrypt_public`].
+///
+/// # Panics
+/// If `glwe.dim.size != 1` or if the parameters don't match `ct`, `msg`, or `public_key`.
+pub fn rlwe_encrypt_public_with_rng<S>(
     ct: &mut GlweCiphertextRef<S>,
     encoded_msg: &PolynomialRef<Torus<S>>,
     public_key: &RlwePublicKeyRef<S>,
     glwe: &GlweDef,
+    rng: &mut (impl CryptoRng + RngCore),
 ) -> RlwePublicEncryptionRandomness<S>
 where
     S: TorusOps,
@@ -139,23 +197,23 @@ where
     ct.clear();
 
     let mut u = Polynomial::<S>::zero(glwe.dim.polynomial_degree.0);
-    binary_torus_polynomial(&mut u);
+    binary_torus_polynomial_with_rng(&mut u, rng);
 
     let mut e0 = Polynomial::<Torus<S>>::zero(glwe.dim.polynomial_degree.0);
     let mut e1 = e0.clone();
-    normal_torus_polynomial(&mut e0, glwe.std);
-    normal_torus_polynomial(&mut e1, glwe.std);
+    normal_torus_polynomial_with_rng(&mut e0, glwe.std, rng);
+    normal_torus_polynomial_with_rng(&mut e1, glwe.std, rng);
 
     let (p0, p1) = public_key.p0_p1(glwe);
     let (mut a, b) = ct.a_b_mut(glwe);
     let a = a.next().unwrap();
 
     polynomial_external_mad(a, p0, &u);
-    polynomial_add_assign(a, &e0);
+    torus_polynomial_add_assign(a, &e0);
 
     polynomial_external_mad(b, p1, &u);
-    polynomial_add_assign(b, &e1);
-    polynomial_add_assign(b, encoded_msg);
+    torus_polynomial_add_assign(b, &e1);
+    torus_polynomial_add_assign(b, encoded_msg);
 
     RlwePublicEncryptionRandomness { e0, e1, u }
 }
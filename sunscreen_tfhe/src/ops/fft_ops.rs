@@ -3,8 +3,9 @@ use num::Complex;
 use crate::{
     dst::{FromMutSlice, OverlaySize},
     entities::{
-        GgswCiphertextFftRef, GlevCiphertextFftRef, GlevCiphertextRef, GlweCiphertextFftRef,
-        GlweCiphertextRef, PolynomialFftRef, PolynomialRef, SchemeSwitchKeyFftRef,
+        GgswCiphertextFftRef, GgswCiphertextRef, GlevCiphertextFftRef, GlevCiphertextRef,
+        GlweCiphertextFftRef, GlweCiphertextRef, PolynomialFftRef, PolynomialRef,
+        SchemeSwitchKeyFftRef,
     },
     ops::ciphertext::{add_glwe_ciphertexts, sub_glwe_ciphertexts},
     radix::PolynomialRadixIterator,
@@ -215,6 +216,51 @@ pub fn glev_cmux<S>(
     }
 }
 
+/// Compute a cmux between [`GgswCiphertext`](crate::entities::GgswCiphertext)s `d_0`,
+/// `d_1`, and select bit `b_fft`.
+///
+/// # Remarks
+/// A ggsw_cmux computes a [`glev_cmux`] over each of the `k + 1` GLev rows of the inputs.
+/// `d_0` and `d_1` need not be valid GGSW ciphertexts; the result is a GGSW encryption
+/// of `m` if, row by row, the selected input's first `k` rows encrypt `-s_i * m` and its
+/// last row encrypts `m`. See
+/// [`evaluate_ggsw_cmux_tree`](crate::ops::leveled::evaluate_ggsw_cmux_tree).
+///
+/// `radix` describes the decomposition of `c`, `d_0` and `d_1`, while `ggsw_radix`
+/// describes that of `b_fft`.
+///
+/// To make some internal computations, this function actually homomorphically computes
+///
+/// ```text
+/// c += cmux(d_0, d_1, b_fft);
+/// ```
+///
+/// Unless you want this behavior, you should first call `c.clear()` or use a freshly
+/// allocated `c`.
+pub fn ggsw_cmux<S>(
+    c: &mut GgswCiphertextRef<S>,
+    d_0: &GgswCiphertextRef<S>,
+    d_1: &GgswCiphertextRef<S>,
+    b_fft: &GgswCiphertextFftRef<Complex<f64>>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    ggsw_radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    c.assert_is_valid((params.dim, radix.count));
+    d_0.assert_is_valid((params.dim, radix.count));
+    d_1.assert_is_valid((params.dim, radix.count));
+
+    for ((c, d_0), d_1) in c
+        .rows_mut(params, radix)
+        .zip(d_0.rows(params, radix))
+        .zip(d_1.rows(params, radix))
+    {
+        glev_cmux(c, d_0, d_1, b_fft, params, ggsw_radix);
+    }
+}
+
 /// This is the same as `generate_encrypted_secret_key_component` but it assumes
 /// that all the positions where the index is not being written are already
 /// zeroed out.
@@ -0,0 +1,174 @@
+use crate::{
+    dst::FromMutSlice,
+    entities::{GlweAutomorphismKeyRef, GlweCiphertextRef},
+    ops::{ciphertext::add_glwe_ciphertexts, keyswitch::glwe_keyswitch::keyswitch_glwe_to_glwe},
+    polynomial::polynomial_automorphism,
+    scratch::allocate_scratch_ref,
+    GlweDef, OverlaySize, RadixDecomposition, TorusOps,
+};
+
+/// Apply the automorphism `X -> X^t` to the message of a GLWE ciphertext. If
+/// `input` encrypts `m(X)`, `output` encrypts `m(X^t)` under the same key.
+///
+/// # Remarks
+/// Applying the automorphism to each polynomial of `input` gives an encryption
+/// of `m(X^t)` under `s(X^t)`, which we then keyswitch back to `s`. This adds
+/// keyswitching noise.
+///
+/// `radix` must be the radix used to generate `automorphism_key`.
+///
+/// # Panics
+/// If `t` isn't `2^j + 1` for some `1 <= j <= log2(N)`.
+/// If `output`, `input` or `automorphism_key` aren't valid under `params` and
+/// `radix`.
+pub fn glwe_automorphism<S>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &GlweCiphertextRef<S>,
+    t: usize,
+    automorphism_key: &GlweAutomorphismKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    output.assert_is_valid(params.dim);
+    input.assert_is_valid(params.dim);
+    automorphism_key.assert_is_valid((params.dim, radix.count));
+
+    let keyswitch_key = automorphism_key.keyswitch_key(t, params, radix);
+
+    allocate_scratch_ref!(permuted, GlweCiphertextRef<S>, (params.dim));
+
+    for (p, a) in permuted.a_mut(params).zip(input.a(params)) {
+        polynomial_automorphism(p, a, t);
+    }
+
+    polynomial_automorphism(permuted.b_mut(params), input.b(params), t);
+
+    keyswitch_glwe_to_glwe(output, permuted, keyswitch_key, params, radix);
+}
+
+/// Compute the trace of the message of a GLWE ciphertext. If `input` encrypts
+/// `m(X)`, `output` encrypts `N * m_0`, where `m_0` is the constant coefficient
+/// of `m`. All other coefficients are zeroed.
+///
+/// # Remarks
+/// The trace sums `m(X^t)` over all odd `t < 2N`. We compute it with
+/// `log2(N)` automorphisms, applying `X -> X^(2^j + 1)` for
+/// `j = log2(N), ..., 1` and adding the result at each step. The step for `j`
+/// maps `X^i` to `(-1)^(i / 2^(log2(N) - j)) X^i` for the remaining `i`,
+/// zeroing half of the remaining coefficients and doubling the others.
+///
+/// Since the result is scaled by `N`, callers typically scale the input
+/// message by `1/N` beforehand. The noise is amplified by up to `N`, plus
+/// `log2(N)` keyswitches.
+///
+/// # Panics
+/// If `output`, `input` or `automorphism_key` aren't valid under `params` and
+/// `radix`.
+pub fn homomorphic_trace<S>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &GlweCiphertextRef<S>,
+    automorphism_key: &GlweAutomorphismKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    let log_n = params.dim.polynomial_degree.0.ilog2();
+
+    allocate_scratch_ref!(acc, GlweCiphertextRef<S>, (params.dim));
+    allocate_scratch_ref!(permuted, GlweCiphertextRef<S>, (params.dim));
+
+    output.clone_from_ref(input);
+
+    for j in (1..=log_n).rev() {
+        glwe_automorphism(
+            permuted,
+            output,
+            (0x1 << j) + 1,
+            automorphism_key,
+            params,
+            radix,
+        );
+
+        acc.clone_from_ref(output);
+        add_glwe_ciphertexts(output, acc, permuted, params);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        entities::{GlweAutomorphismKey, GlweCiphertext, Polynomial},
+        high_level::*,
+        ops::keyswitch::glwe_automorphism_key::generate_glwe_automorphism_key,
+        PlaintextBits, RadixCount, RadixLog,
+    };
+
+    use super::*;
+
+    const RADIX: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(6),
+        radix_log: RadixLog(7),
+    };
+
+    #[test]
+    fn can_apply_glwe_automorphism() {
+        let params = TEST_GLWE_DEF_1;
+        let bits = PlaintextBits(4);
+        let n = params.dim.polynomial_degree.0;
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+        let mut key = GlweAutomorphismKey::new(&params, &RADIX);
+        generate_glwe_automorphism_key(&mut key, &sk, &params, &RADIX);
+
+        let msg = (0..n)
+            .map(|_| thread_rng().next_u64() % 16)
+            .collect::<Polynomial<_>>();
+        let ct = encryption::encrypt_glwe(&msg, &sk, &params, bits);
+
+        for j in 1..=n.ilog2() {
+            let t = (0x1 << j) + 1;
+
+            let mut expected = Polynomial::zero(n);
+            polynomial_automorphism(&mut expected, &msg, t);
+            let expected = expected.map(|x| x % 16);
+
+            let mut actual = GlweCiphertext::new(&params);
+            glwe_automorphism(&mut actual, &ct, t, &key, &params, &RADIX);
+
+            let actual = encryption::decrypt_glwe(&actual, &sk, &params, bits);
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn trace_extracts_constant_coefficient() {
+        let params = TEST_GLWE_DEF_1;
+        let n = params.dim.polynomial_degree.0;
+        let log_n = n.ilog2() as usize;
+        let bits = PlaintextBits(4);
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+        let mut key = GlweAutomorphismKey::new(&params, &RADIX);
+        generate_glwe_automorphism_key(&mut key, &sk, &params, &RADIX);
+
+        // Encode the messages with an extra factor of 1 / N.
+        let msg = (0..n)
+            .map(|_| thread_rng().next_u64() % 16)
+            .collect::<Polynomial<_>>();
+        let ct = encryption::encrypt_glwe(&msg, &sk, &params, PlaintextBits(4 + log_n as u32));
+
+        let mut actual = GlweCiphertext::new(&params);
+        homomorphic_trace(&mut actual, &ct, &key, &params, &RADIX);
+
+        let actual = encryption::decrypt_glwe(&actual, &sk, &params, bits);
+
+        assert_eq!(actual.coeffs()[0], msg.coeffs()[0]);
+        assert!(actual.coeffs().iter().skip(1).all(|x| *x == 0));
+    }
+}
@@ -0,0 +1,63 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
+use crate::{
+    dst::FromMutSlice,
+    entities::{GlweAutomorphismKeyRef, GlweSecretKeyRef},
+    ops::keyswitch::glwe_keyswitch_key::generate_keyswitch_key_glwe_with_rng,
+    polynomial::polynomial_automorphism,
+    scratch::allocate_scratch_ref,
+    GlweDef, OverlaySize, RadixDecomposition, TorusOps,
+};
+
+/// Generate a GLWE automorphism key for `sk`. For each `t = 2^j + 1` with
+/// `1 <= j <= log2(N)`, this generates a keyswitch key from `s(X^t)` to `s`.
+/// For use with
+/// [`glwe_automorphism`](crate::ops::keyswitch::glwe_automorphism::glwe_automorphism)
+/// and
+/// [`homomorphic_trace`](crate::ops::keyswitch::glwe_automorphism::homomorphic_trace).
+///
+/// # Panics
+/// If `params` or `radix` are invalid.
+/// If `automorphism_key` or `sk` aren't valid under `params` and `radix`.
+pub fn generate_glwe_automorphism_key<S>(
+    automorphism_key: &mut GlweAutomorphismKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    generate_glwe_automorphism_key_with_rng(automorphism_key, sk, params, radix, &mut thread_rng())
+}
+
+/// Generate a GLWE automorphism key for `sk`, drawing the encryptions'
+/// randomness from `rng`. See [`generate_glwe_automorphism_key`].
+pub fn generate_glwe_automorphism_key_with_rng<S>(
+    automorphism_key: &mut GlweAutomorphismKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    automorphism_key.assert_is_valid((params.dim, radix.count));
+    sk.assert_is_valid(params.dim);
+
+    allocate_scratch_ref!(sk_t, GlweSecretKeyRef<S>, (params.dim));
+
+    for (j, ksk) in automorphism_key
+        .keyswitch_keys_mut(params, radix)
+        .enumerate()
+    {
+        let t = (0x1 << (j + 1)) + 1;
+
+        for (s_t, s) in sk_t.s_mut(params).zip(sk.s(params)) {
+            polynomial_automorphism(s_t, s, t);
+        }
+
+        generate_keyswitch_key_glwe_with_rng(ksk, sk_t, sk, params, radix, rng);
+    }
+}
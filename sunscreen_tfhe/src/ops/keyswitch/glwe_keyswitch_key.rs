@@ -1,23 +1,14 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
 use crate::{
     entities::{
         GlweCiphertextRef, GlweKeyswitchKeyRef, GlweSecretKeyRef, Polynomial, PolynomialRef,
     },
-    ops::encryption::encrypt_glwe_ciphertext_secret_generic,
+    ops::encryption::encrypt_glwe_ciphertext_secret_with_rng,
     polynomial::polynomial_scalar_mul,
     GlweDef, RadixDecomposition, Torus, TorusOps,
 };
 
-fn encrypt_glwe_ciphertext_secret_with_keyswitch_noise<S>(
-    c: &mut GlweCiphertextRef<S>,
-    msg: &PolynomialRef<Torus<S>>,
-    sk: &GlweSecretKeyRef<S>,
-    params: &GlweDef,
-) where
-    S: TorusOps,
-{
-    encrypt_glwe_ciphertext_secret_generic(c, msg, sk, params);
-}
-
 /**
  * Generates a keyswitch key from the original key to the new key. The resulting
  * keyswitch key is encrypted under the new key. This function is generic over
@@ -35,7 +26,7 @@ fn encrypt_keyswitch_key_generic<S>(
     new_glwe_secret_key: &GlweSecretKeyRef<S>,
     params: &GlweDef,
     radix: &RadixDecomposition,
-    encrypt: impl Fn(
+    mut encrypt: impl FnMut(
         &mut GlweCiphertextRef<S>,
         &PolynomialRef<Torus<S>>,
         &GlweSecretKeyRef<S>,
@@ -79,6 +70,28 @@ pub fn generate_keyswitch_key_glwe<S>(
     radix: &RadixDecomposition,
 ) where
     S: TorusOps,
+{
+    generate_keyswitch_key_glwe_with_rng(
+        keyswitch_key,
+        original_glwe_secret_key,
+        new_glwe_secret_key,
+        params,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generate a keyswitch key from the original key to the new key, drawing
+/// the encryptions' randomness from `rng`. See [`generate_keyswitch_key_glwe`].
+pub fn generate_keyswitch_key_glwe_with_rng<S>(
+    keyswitch_key: &mut GlweKeyswitchKeyRef<S>,
+    original_glwe_secret_key: &GlweSecretKeyRef<S>,
+    new_glwe_secret_key: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     encrypt_keyswitch_key_generic(
         keyswitch_key,
@@ -86,7 +99,7 @@ pub fn generate_keyswitch_key_glwe<S>(
         new_glwe_secret_key,
         params,
         radix,
-        encrypt_glwe_ciphertext_secret_with_keyswitch_noise,
+        |c, msg, sk, params| encrypt_glwe_ciphertext_secret_with_rng(c, msg, sk, params, rng),
     )
 }
 
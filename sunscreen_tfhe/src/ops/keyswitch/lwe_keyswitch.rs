@@ -66,7 +66,7 @@ mod tests {
 
     use rand::{thread_rng, RngCore};
 
-    use crate::{high_level::*, PlaintextBits};
+    use crate::{dst::AsSlice, high_level::*, rand::key_seed, PlaintextBits};
 
     #[test]
     fn keyswitch_lwe() {
@@ -93,4 +93,45 @@ mod tests {
             assert_eq!(new_decrypted, msg);
         }
     }
+
+    #[test]
+    fn keyswitch_lwe_with_seeded_key() {
+        let bits = PlaintextBits(4);
+        let from_lwe = TEST_LWE_DEF_1;
+        let to_lwe = TEST_LWE_DEF_2;
+        let radix = TEST_RADIX;
+
+        let original_sk = keygen::generate_binary_lwe_sk(&from_lwe);
+        let new_sk = keygen::generate_binary_lwe_sk(&to_lwe);
+
+        let seeded_ksk = keygen::generate_seeded_ksk(
+            &original_sk,
+            &new_sk,
+            &from_lwe,
+            &to_lwe,
+            &radix,
+            key_seed(),
+        );
+        let ksk = keygen::expand_ksk(&seeded_ksk, &from_lwe, &to_lwe, &radix);
+
+        assert_eq!(
+            ksk,
+            keygen::expand_ksk(&seeded_ksk, &from_lwe, &to_lwe, &radix)
+        );
+        assert_eq!(
+            seeded_ksk.bodies().len() * (to_lwe.dim.0 + 1),
+            ksk.as_slice().len()
+        );
+
+        for _ in 0..50 {
+            let msg = thread_rng().next_u64() % (1 << bits.0);
+
+            let original_ct = original_sk.encrypt(msg, &from_lwe, bits).0;
+
+            let new_ct =
+                evaluation::keyswitch_lwe_to_lwe(&original_ct, &ksk, &from_lwe, &to_lwe, &radix);
+
+            assert_eq!(new_sk.decrypt(&new_ct, &to_lwe, bits), msg);
+        }
+    }
 }
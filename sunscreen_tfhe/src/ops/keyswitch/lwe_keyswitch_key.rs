@@ -1,6 +1,13 @@
+use rand::{thread_rng, CryptoRng, RngCore};
+
 use crate::{
-    entities::{LweKeyswitchKeyRef, LweSecretKeyRef},
-    ops::encryption::encrypt_lwe_ciphertext,
+    dst::FromMutSlice,
+    entities::{
+        KeySeed, LevCiphertextRef, LweKeyswitchKeyRef, LweSecretKeyRef, SeededLweKeyswitchKey,
+    },
+    ops::encryption::{encrypt_lwe_ciphertext_with_mask, encrypt_lwe_ciphertext_with_rng},
+    rand::{seeded_stream_rng, uniform_torus_with_rng},
+    scratch::allocate_scratch_ref,
     LweDef, OverlaySize, RadixDecomposition, Torus, TorusOps,
 };
 
@@ -22,6 +29,31 @@ pub fn generate_keyswitch_key_lwe<S>(
     radix: &RadixDecomposition,
 ) where
     S: TorusOps,
+{
+    generate_keyswitch_key_lwe_with_rng(
+        keyswitch_key,
+        original_lwe_secret_key,
+        new_lwe_secret_key,
+        old_params,
+        new_params,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generates a keyswitch key from an original LWE key to a new LWE key,
+/// drawing the encryptions' randomness from `rng`. See
+/// [`generate_keyswitch_key_lwe`].
+pub fn generate_keyswitch_key_lwe_with_rng<S>(
+    keyswitch_key: &mut LweKeyswitchKeyRef<S>,
+    original_lwe_secret_key: &LweSecretKeyRef<S>,
+    new_lwe_secret_key: &LweSecretKeyRef<S>,
+    old_params: &LweDef,
+    new_params: &LweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
 {
     old_params.assert_valid();
     new_params.assert_valid();
@@ -44,7 +76,157 @@ pub fn generate_keyswitch_key_lwe<S>(
 
             let msg = decomp_factor * s_i;
 
-            encrypt_lwe_ciphertext(col, new_lwe_secret_key, Torus::from(msg), new_params);
+            encrypt_lwe_ciphertext_with_rng(
+                col,
+                new_lwe_secret_key,
+                Torus::from(msg),
+                new_params,
+                rng,
+            );
+        }
+    }
+}
+
+/// Generates a [`SeededLweKeyswitchKey`] from an original LWE key to a new LWE
+/// key. This is the compressed form of the key produced by
+/// [`generate_keyswitch_key_lwe`].
+///
+/// # Remarks
+/// The masks of the LWE ciphertexts in the key are generated from the seed in
+/// `keyswitch_key` with a ChaCha20 generator, using one stream per element of
+/// the original secret key. Only the bodies are stored, which reduces the size
+/// of the key by a factor of `new_params.dim + 1`. Use
+/// [`expand_seeded_keyswitch_key_lwe`] to recover the full
+/// [`LweKeyswitchKey`](crate::entities::LweKeyswitchKey).
+///
+/// The seed needn't be secret, but must be chosen uniformly at random for
+/// every key.
+pub fn generate_seeded_keyswitch_key_lwe<S>(
+    keyswitch_key: &mut SeededLweKeyswitchKey<S>,
+    original_lwe_secret_key: &LweSecretKeyRef<S>,
+    new_lwe_secret_key: &LweSecretKeyRef<S>,
+    old_params: &LweDef,
+    new_params: &LweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    generate_seeded_keyswitch_key_lwe_with_rng(
+        keyswitch_key,
+        original_lwe_secret_key,
+        new_lwe_secret_key,
+        old_params,
+        new_params,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generates a [`SeededLweKeyswitchKey`], drawing the encryptions' noise
+/// from `rng`. See [`generate_seeded_keyswitch_key_lwe`].
+///
+/// # Remarks
+/// The masks still come from the seed in `keyswitch_key`.
+pub fn generate_seeded_keyswitch_key_lwe_with_rng<S>(
+    keyswitch_key: &mut SeededLweKeyswitchKey<S>,
+    original_lwe_secret_key: &LweSecretKeyRef<S>,
+    new_lwe_secret_key: &LweSecretKeyRef<S>,
+    old_params: &LweDef,
+    new_params: &LweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    old_params.assert_valid();
+    new_params.assert_valid();
+    radix.assert_valid::<S>();
+    original_lwe_secret_key.assert_is_valid(old_params.dim);
+    new_lwe_secret_key.assert_is_valid(new_params.dim);
+    keyswitch_key.assert_is_valid(old_params, radix);
+
+    let decomposition_radix_log = radix.radix_log.0;
+    let seed = *keyswitch_key.seed();
+
+    allocate_scratch_ref!(row, LevCiphertextRef<S>, (new_params.dim, radix.count));
+
+    for (i, bodies) in keyswitch_key
+        .bodies_mut()
+        .chunks_mut(radix.count.0)
+        .enumerate()
+    {
+        let s_i = original_lwe_secret_key.s()[i];
+
+        fill_lev_masks(row, &seed, i, new_params);
+
+        for (j, (col, b)) in row
+            .lwe_ciphertexts_mut(new_params)
+            .zip(bodies.iter_mut())
+            .enumerate()
+        {
+            let decomp_factor =
+                S::from_u64(0x1 << (S::BITS as usize - decomposition_radix_log * (j + 1)));
+
+            let msg = decomp_factor * s_i;
+
+            encrypt_lwe_ciphertext_with_mask(
+                col,
+                new_lwe_secret_key,
+                Torus::from(msg),
+                new_params,
+                rng,
+            );
+
+            *b = *col.b(new_params);
+        }
+    }
+}
+
+/// Expands a [`SeededLweKeyswitchKey`] into the full
+/// [`LweKeyswitchKey`](crate::entities::LweKeyswitchKey) it compresses. The
+/// result is identical to the key produced by
+/// [`generate_seeded_keyswitch_key_lwe`] before its masks were discarded.
+pub fn expand_seeded_keyswitch_key_lwe<S>(
+    keyswitch_key: &mut LweKeyswitchKeyRef<S>,
+    seeded_key: &SeededLweKeyswitchKey<S>,
+    old_params: &LweDef,
+    new_params: &LweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    old_params.assert_valid();
+    new_params.assert_valid();
+    radix.assert_valid::<S>();
+    keyswitch_key.assert_is_valid((old_params.dim, new_params.dim, radix.count));
+    seeded_key.assert_is_valid(old_params, radix);
+
+    let seed = *seeded_key.seed();
+
+    for (i, (row, bodies)) in keyswitch_key
+        .rows_mut(new_params, radix)
+        .zip(seeded_key.bodies().chunks(radix.count.0))
+        .enumerate()
+    {
+        fill_lev_masks(row, &seed, i, new_params);
+
+        for (col, b) in row.lwe_ciphertexts_mut(new_params).zip(bodies.iter()) {
+            *col.b_mut(new_params) = *b;
+        }
+    }
+}
+
+/// Fill the masks of the LWE ciphertexts in the `i`-th row of a seeded
+/// keyswitch key.
+fn fill_lev_masks<S>(row: &mut LevCiphertextRef<S>, seed: &KeySeed, i: usize, params: &LweDef)
+where
+    S: TorusOps,
+{
+    let mut rng = seeded_stream_rng(seed, i as u64);
+
+    for col in row.lwe_ciphertexts_mut(params) {
+        for a in col.a_mut(params) {
+            *a = uniform_torus_with_rng(&mut rng);
         }
     }
 }
@@ -0,0 +1,226 @@
+use crate::{
+    entities::{
+        GlweCiphertextRef, LweCiphertextRef, PolynomialRef, PublicFunctionalKeyswitchKeyRef,
+    },
+    ops::keyswitch::public_functional_keyswitch::public_functional_keyswitch,
+    rand::Stddev,
+    GlweDef, LweDef, RadixDecomposition, Torus, TorusOps,
+};
+
+/// Pack a list of LWE ciphertexts into a single GLWE ciphertext. If `inputs[i]`
+/// encrypts `m_i`, `output` encrypts the polynomial `sum_i m_i X^i`.
+///
+/// # Remarks
+/// This is a [`public_functional_keyswitch`] that places each LWE message in
+/// its own coefficient, so `pufksk` must be a key generated with
+/// [`generate_public_functional_keyswitch_key`](crate::ops::keyswitch::public_functional_keyswitch::generate_public_functional_keyswitch_key)
+/// from the LWE secret key of `inputs` to the GLWE secret key of `output`.
+///
+/// Packing lets a server return a single GLWE ciphertext rather than up to
+/// `N` LWE ciphertexts. Unlike blind rotation outputs, the result's key need
+/// not match the inputs' key, so packing also switches keys. Use
+/// [`pack_lwes_noise`] to estimate the noise in `output`.
+///
+/// The cost is dominated by `from_lwe.dim * radix.count` polynomial
+/// multiplications, regardless of the number of inputs.
+///
+/// # Panics
+/// If `inputs.len() > to_glwe.dim.polynomial_degree`.
+/// If `output`, `inputs` or `pufksk` aren't valid under `from_lwe`,
+/// `to_glwe` and `radix`.
+pub fn pack_lwes<S>(
+    output: &mut GlweCiphertextRef<S>,
+    inputs: &[&LweCiphertextRef<S>],
+    pufksk: &PublicFunctionalKeyswitchKeyRef<S>,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    let place_in_coefficients = |poly: &mut PolynomialRef<Torus<S>>, tori: &[Torus<S>]| {
+        poly.clear();
+
+        for (c, t) in poly.coeffs_mut().iter_mut().zip(tori.iter()) {
+            *c = *t;
+        }
+    };
+
+    public_functional_keyswitch(
+        output,
+        inputs,
+        pufksk,
+        place_in_coefficients,
+        from_lwe,
+        to_glwe,
+        radix,
+    );
+}
+
+/// Estimate the standard deviation of the noise in each coefficient of the
+/// result of [`pack_lwes`], given `input_std`, the noise in the packed LWE
+/// ciphertexts, and `lwe_count`, the number of LWE ciphertexts packed.
+///
+/// # Remarks
+/// The variance is the sum of
+/// * the input noise, `input_std^2`,
+/// * the keyswitch key noise, `n * l * lwe_count * (beta^2 / 12) * to_glwe.std^2`,
+///   where each of the `n * l` decomposed polynomials has `lwe_count` digits
+///   roughly uniform in `[-beta/2, beta/2)`,
+/// * the decomposition rounding error, `n / (24 * beta^(2l))`, which assumes
+///   binary LWE secret keys.
+///
+/// Here `n = from_lwe.dim`, `beta = 2^radix.radix_log` and `l = radix.count`.
+/// The estimate assumes independent, zero-mean noise terms.
+pub fn pack_lwes_noise(
+    input_std: Stddev,
+    lwe_count: usize,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) -> Stddev {
+    let n = from_lwe.dim.0 as f64;
+    let l = radix.count.0 as f64;
+    let beta = (radix.radix_log.0 as f64).exp2();
+
+    let keyswitch_variance =
+        n * l * lwe_count as f64 * (beta * beta / 12.0) * to_glwe.std.0.powi(2);
+    let rounding_variance = n / (24.0 * beta.powf(2.0 * l));
+
+    Stddev((input_std.0.powi(2) + keyswitch_variance + rounding_variance).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        entities::{GlweCiphertext, Polynomial, PublicFunctionalKeyswitchKey},
+        high_level::{encryption, evaluation, keygen, TEST_GLWE_DEF_1, TEST_LWE_DEF_1, TEST_RADIX},
+        ops::{
+            encryption::decrypt_glwe_ciphertext,
+            keyswitch::public_functional_keyswitch::generate_public_functional_keyswitch_key,
+        },
+        PlaintextBits,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_pack_lwes() {
+        let lwe_params = TEST_LWE_DEF_1;
+        let glwe_params = TEST_GLWE_DEF_1;
+        let n = glwe_params.dim.polynomial_degree.0;
+        let bits = PlaintextBits(4);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe_params);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe_params);
+
+        let mut pufksk = PublicFunctionalKeyswitchKey::new(&lwe_params, &glwe_params, &TEST_RADIX);
+        generate_public_functional_keyswitch_key(
+            &mut pufksk,
+            &lwe_sk,
+            &glwe_sk,
+            &lwe_params,
+            &glwe_params,
+            &TEST_RADIX,
+        );
+
+        for lwe_count in [1, n / 2, n] {
+            let pts = (0..lwe_count)
+                .map(|_| thread_rng().next_u64() % 16)
+                .collect::<Vec<_>>();
+
+            let lwes = pts
+                .iter()
+                .map(|x| encryption::encrypt_lwe_secret(*x, &lwe_sk, &lwe_params, bits))
+                .collect::<Vec<_>>();
+            let lwe_refs = lwes.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+            let mut output = GlweCiphertext::new(&glwe_params);
+            pack_lwes(
+                &mut output,
+                &lwe_refs,
+                &pufksk,
+                &lwe_params,
+                &glwe_params,
+                &TEST_RADIX,
+            );
+
+            let actual = encryption::decrypt_glwe(&output, &glwe_sk, &glwe_params, bits);
+
+            assert_eq!(&actual.coeffs()[..lwe_count], &pts);
+            assert!(actual.coeffs()[lwe_count..].iter().all(|x| *x == 0));
+
+            // Unpacking with sample extraction recovers the inputs.
+            for (i, pt) in pts.iter().enumerate() {
+                let lwe = evaluation::sample_extract(&output, &glwe_params, i);
+                let lwe_sk = glwe_sk.to_lwe_secret_key();
+
+                let actual = encryption::decrypt_lwe(&lwe, lwe_sk, &glwe_params.as_lwe_def(), bits);
+
+                assert_eq!(actual, *pt);
+            }
+        }
+    }
+
+    #[test]
+    fn pack_lwes_noise_matches_estimate() {
+        let lwe_params = TEST_LWE_DEF_1;
+        let glwe_params = TEST_GLWE_DEF_1;
+        let n = glwe_params.dim.polynomial_degree.0;
+        let bits = PlaintextBits(4);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe_params);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe_params);
+
+        let mut pufksk = PublicFunctionalKeyswitchKey::new(&lwe_params, &glwe_params, &TEST_RADIX);
+        generate_public_functional_keyswitch_key(
+            &mut pufksk,
+            &lwe_sk,
+            &glwe_sk,
+            &lwe_params,
+            &glwe_params,
+            &TEST_RADIX,
+        );
+
+        let pts = (0..n)
+            .map(|_| thread_rng().next_u64() % 16)
+            .collect::<Vec<_>>();
+
+        let lwes = pts
+            .iter()
+            .map(|x| encryption::encrypt_lwe_secret(*x, &lwe_sk, &lwe_params, bits))
+            .collect::<Vec<_>>();
+        let lwe_refs = lwes.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+        let mut output = GlweCiphertext::new(&glwe_params);
+        pack_lwes(
+            &mut output,
+            &lwe_refs,
+            &pufksk,
+            &lwe_params,
+            &glwe_params,
+            &TEST_RADIX,
+        );
+
+        let mut phase = Polynomial::zero(n);
+        decrypt_glwe_ciphertext(&mut phase, &output, &glwe_sk, &glwe_params);
+
+        let variance = phase
+            .coeffs()
+            .iter()
+            .zip(pts.iter())
+            .map(|(p, m)| {
+                p.normalized_torus_distance(&Torus::encode(*m, bits))
+                    .powi(2)
+            })
+            .sum::<f64>()
+            / n as f64;
+
+        let expected = pack_lwes_noise(lwe_params.std, n, &lwe_params, &glwe_params, &TEST_RADIX);
+        let expected = expected.0.powi(2);
+
+        assert!(variance > expected / 2.0 && variance < expected * 2.0);
+    }
+}
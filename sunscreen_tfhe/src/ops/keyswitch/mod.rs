@@ -15,3 +15,13 @@ pub mod lwe_keyswitch;
 
 /// Methods for performing a GLWE keyswitch.
 pub mod glwe_keyswitch;
+
+/// Generate GLWE automorphism keys.
+pub mod glwe_automorphism_key;
+
+/// Methods for applying automorphisms to GLWE ciphertexts and computing their
+/// trace.
+pub mod glwe_automorphism;
+
+/// Methods for packing many LWE ciphertexts into a single GLWE ciphertext.
+pub mod lwe_packing;
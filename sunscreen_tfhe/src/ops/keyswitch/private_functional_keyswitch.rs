@@ -1,3 +1,4 @@
+use rand::{thread_rng, CryptoRng, RngCore};
 use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use sunscreen_math::Zero;
 
@@ -9,9 +10,10 @@ use crate::{
     },
     ops::{
         ciphertext::{decomposed_scalar_glev_mad, glwe_negate_inplace},
-        encryption::encrypt_glwe_ciphertext_secret,
+        encryption::encrypt_glwe_ciphertext_secret_with_rng,
     },
     radix::{scale_by_decomposition_factor, ScalarRadixIterator},
+    rand::{key_seed_with_rng, seeded_stream_rng},
     scratch::allocate_scratch_ref,
     GlweDef, LweDef, OverlaySize, PrivateFunctionalKeyswitchLweCount, RadixDecomposition, Torus,
     TorusOps,
@@ -51,6 +53,38 @@ pub fn generate_private_functional_keyswitch_key<S, F>(
 ) where
     S: TorusOps,
     F: Fn(&mut PolynomialRef<Torus<S>>, &[Torus<S>]),
+{
+    generate_private_functional_keyswitch_key_with_rng(
+        output,
+        from_key,
+        to_key,
+        map,
+        from_lwe,
+        to_glwe,
+        radix,
+        lwe_count,
+        &mut thread_rng(),
+    )
+}
+
+/// Initialize `output`, a
+/// [`PrivateFunctionalKeyswitchKey`](crate::entities::PrivateFunctionalKeyswitchKey),
+/// drawing the encryptions' randomness from `rng`. See
+/// [`generate_private_functional_keyswitch_key`].
+#[allow(clippy::too_many_arguments)]
+pub fn generate_private_functional_keyswitch_key_with_rng<S, F>(
+    output: &mut PrivateFunctionalKeyswitchKeyRef<S>,
+    from_key: &LweSecretKeyRef<S>,
+    to_key: &GlweSecretKeyRef<S>,
+    map: F,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    lwe_count: &PrivateFunctionalKeyswitchLweCount,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+    F: Fn(&mut PolynomialRef<Torus<S>>, &[Torus<S>]),
 {
     output.assert_is_valid((from_lwe.dim, to_glwe.dim, radix.count, *lwe_count));
     radix.assert_valid::<S>();
@@ -84,7 +118,7 @@ pub fn generate_private_functional_keyswitch_key<S, F>(
 
                 map(pt_poly, pt_touri);
 
-                encrypt_glwe_ciphertext_secret(glwe, pt_poly, to_key, to_glwe);
+                encrypt_glwe_ciphertext_secret_with_rng(glwe, pt_poly, to_key, to_glwe, rng);
             }
         }
     }
@@ -138,6 +172,33 @@ pub fn generate_circuit_bootstrapping_pfks_keys<S: TorusOps>(
     from_lwe: &LweDef,
     to_glwe: &GlweDef,
     radix: &RadixDecomposition,
+) {
+    generate_circuit_bootstrapping_pfks_keys_with_rng(
+        output,
+        from_key,
+        to_key,
+        from_lwe,
+        to_glwe,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generate the keys for a private functional keyswitch, drawing the
+/// encryptions' randomness from `rng`. See
+/// [`generate_circuit_bootstrapping_pfks_keys`].
+///
+/// # Remarks
+/// The keys are generated in parallel, each from its own ChaCha20 stream
+/// seeded from `rng`, so the result only depends on `rng`'s output.
+pub fn generate_circuit_bootstrapping_pfks_keys_with_rng<S: TorusOps>(
+    output: &mut CircuitBootstrappingKeyswitchKeysRef<S>,
+    from_key: &LweSecretKeyRef<S>,
+    to_key: &GlweSecretKeyRef<S>,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
 ) {
     output.assert_is_valid((from_lwe.dim, to_glwe.dim, radix.count));
     from_key.assert_is_valid(from_lwe.dim);
@@ -146,20 +207,22 @@ pub fn generate_circuit_bootstrapping_pfks_keys<S: TorusOps>(
     radix.assert_valid::<S>();
     from_lwe.assert_valid();
 
+    let seed = key_seed_with_rng(rng);
+
     // Fill in k pfks keys that multiply each of the "a" GLEVs by the corresponding
     // polynomial in the GLWE secret key.
     output
         .keys_par_mut(from_lwe, to_glwe, radix)
         .zip(to_key.s_par(to_glwe))
-        .take(to_glwe.dim.size.0)
-        .for_each(|(pfksk, s)| {
+        .zip(0..to_glwe.dim.size.0)
+        .for_each(|((pfksk, s), i)| {
             let map = |poly: &mut PolynomialRef<Torus<S>>, x: &[Torus<S>]| {
                 for (c, a) in poly.coeffs_mut().iter_mut().zip(s.coeffs().iter()) {
                     *c = -x[0] * a;
                 }
             };
 
-            generate_private_functional_keyswitch_key(
+            generate_private_functional_keyswitch_key_with_rng(
                 pfksk,
                 from_key,
                 to_key,
@@ -168,6 +231,7 @@ pub fn generate_circuit_bootstrapping_pfks_keys<S: TorusOps>(
                 to_glwe,
                 radix,
                 &PrivateFunctionalKeyswitchLweCount(1),
+                &mut seeded_stream_rng(&seed, i as u64),
             );
         });
 
@@ -183,7 +247,7 @@ pub fn generate_circuit_bootstrapping_pfks_keys<S: TorusOps>(
         poly.coeffs_mut()[0] = x[0];
     };
 
-    generate_private_functional_keyswitch_key(
+    generate_private_functional_keyswitch_key_with_rng(
         b,
         from_key,
         to_key,
@@ -192,6 +256,7 @@ pub fn generate_circuit_bootstrapping_pfks_keys<S: TorusOps>(
         to_glwe,
         radix,
         &PrivateFunctionalKeyswitchLweCount(1),
+        rng,
     )
 }
 
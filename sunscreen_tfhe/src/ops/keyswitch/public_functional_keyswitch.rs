@@ -1,13 +1,14 @@
 use num::Complex;
+use rand::{thread_rng, CryptoRng, RngCore};
 
 use crate::dst::FromMutSlice;
 use crate::entities::{
     GlevCiphertextFftRef, GlweCiphertextRef, LweCiphertextRef, LweSecretKeyRef, PolynomialRef,
 };
 use crate::ops::ciphertext::glwe_negate_inplace;
-use crate::ops::encryption::encrypt_glwe_ciphertext_secret;
+use crate::ops::encryption::encrypt_glwe_ciphertext_secret_with_rng;
 use crate::ops::fft_ops::decomposed_polynomial_glev_mad;
-use crate::polynomial::polynomial_add_assign;
+use crate::polynomial::torus_polynomial_add_assign;
 use crate::radix::PolynomialRadixIterator;
 use crate::scratch::allocate_scratch;
 use crate::{
@@ -33,6 +34,28 @@ pub fn generate_public_functional_keyswitch_key<S: TorusOps>(
     from_lwe: &LweDef,
     to_glwe: &GlweDef,
     radix: &RadixDecomposition,
+) {
+    generate_public_functional_keyswitch_key_with_rng(
+        output,
+        from_sk,
+        to_sk,
+        from_lwe,
+        to_glwe,
+        radix,
+        &mut thread_rng(),
+    )
+}
+
+/// Generate a public functional keyswitch key, drawing the encryptions'
+/// randomness from `rng`. See [`generate_public_functional_keyswitch_key`].
+pub fn generate_public_functional_keyswitch_key_with_rng<S: TorusOps>(
+    output: &mut PublicFunctionalKeyswitchKeyRef<S>,
+    from_sk: &LweSecretKeyRef<S>,
+    to_sk: &GlweSecretKeyRef<S>,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
 ) {
     from_sk.assert_is_valid(from_lwe.dim);
     to_sk.assert_is_valid(to_glwe.dim);
@@ -47,7 +70,7 @@ pub fn generate_public_functional_keyswitch_key<S: TorusOps>(
 
             pt.coeffs_mut()[0] = Torus::from(x);
 
-            encrypt_glwe_ciphertext_secret(glwe_ct, pt, to_sk, to_glwe);
+            encrypt_glwe_ciphertext_secret_with_rng(glwe_ct, pt, to_sk, to_glwe, rng);
         }
     }
 }
@@ -144,7 +167,7 @@ pub fn public_functional_keyswitch<S, F>(
 
     // Compute (0, b) - output
     glwe_negate_inplace(output, to_glwe);
-    polynomial_add_assign(output.b_mut(to_glwe), poly);
+    torus_polynomial_add_assign(output.b_mut(to_glwe), poly);
 }
 
 #[cfg(test)]
@@ -0,0 +1,349 @@
+use num::Complex;
+use rand::{thread_rng, CryptoRng, RngCore};
+
+use crate::{
+    entities::{
+        GgswCiphertext, GgswCiphertextFftRef, GgswCmuxKeyRef, GlweSecretKeyRef, Polynomial,
+    },
+    ops::{
+        encryption::{
+            encrypt_glwe_ciphertext_secret_with_rng, encrypt_secret_glev_ciphertext_with_rng,
+            trivially_encrypt_glev_ciphertext,
+        },
+        fft_ops::ggsw_cmux,
+    },
+    GlweDef, OverlaySize, RadixDecomposition, Torus, TorusOps,
+};
+
+use super::{MuxTree, MuxWire};
+
+/// Generate a [`GgswCmuxKey`](crate::entities::GgswCmuxKey) for evaluating GGSW-CMux trees
+/// over ciphertexts encrypted under `sk`.
+///
+/// # Remarks
+/// `radix` is the decomposition of the key and of the
+/// [`GgswCiphertext`](crate::entities::GgswCiphertext)s
+/// [`evaluate_ggsw_cmux_tree`] produces with it.
+///
+/// # Panics
+/// If `params` or `radix` are invalid.
+/// If `key` or `sk` aren't valid under `params` and `radix`.
+pub fn generate_ggsw_cmux_key<S>(
+    key: &mut GgswCmuxKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    generate_ggsw_cmux_key_with_rng(key, sk, params, radix, &mut thread_rng())
+}
+
+/// Generate a [`GgswCmuxKey`](crate::entities::GgswCmuxKey), drawing the
+/// encryptions' randomness from `rng`. See [`generate_ggsw_cmux_key`].
+pub fn generate_ggsw_cmux_key_with_rng<S>(
+    key: &mut GgswCmuxKeyRef<S>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    rng: &mut (impl CryptoRng + RngCore),
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    key.assert_is_valid((params.dim, radix.count));
+    sk.assert_is_valid(params.dim);
+
+    let zero = Polynomial::<Torus<S>>::zero(params.dim.polynomial_degree.0);
+    encrypt_glwe_ciphertext_secret_with_rng(key.zero_mut(params), &zero, sk, params, rng);
+
+    for (s_i, glev) in sk.s(params).zip(key.glev_ciphertexts_mut(params, radix)) {
+        let neg_s_i = s_i.map(|c| Torus::from(c.wrapping_neg()));
+
+        encrypt_secret_glev_ciphertext_with_rng(glev, &neg_s_i, sk, params, radix, rng);
+    }
+}
+
+/// The GGSW-shaped leaf of a GGSW-CMux tree for the constant `value`. The first `k` rows
+/// come from `key` and encrypt `-s_i * value`, while the last row trivially encrypts
+/// `value`.
+fn constant_leaf<S>(
+    value: bool,
+    key: &GgswCmuxKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) -> GgswCiphertext<S>
+where
+    S: TorusOps,
+{
+    let k = params.dim.size.0;
+    let mut leaf = GgswCiphertext::new(params, radix);
+    let mut rows = leaf.rows_mut(params, radix);
+
+    for (row, neg_s_i) in rows
+        .by_ref()
+        .take(k)
+        .zip(key.glev_ciphertexts(params, radix))
+    {
+        if value {
+            row.clone_from_ref(neg_s_i);
+        } else {
+            for glwe in row.glwe_ciphertexts_mut(params) {
+                glwe.clone_from_ref(key.zero(params));
+            }
+        }
+    }
+
+    if value {
+        let mut one = Polynomial::<Torus<S>>::zero(params.dim.polynomial_degree.0);
+        one.coeffs_mut()[0] = Torus::from(S::one());
+
+        trivially_encrypt_glev_ciphertext(rows.next().unwrap(), &one, params, radix);
+    }
+
+    leaf
+}
+
+/// Evaluate a [`MuxTree`] with GGSW-CMux operations, producing a
+/// [`GgswCiphertext`](crate::entities::GgswCiphertext) for each of the tree's outputs.
+///
+/// # Remarks
+/// Input `i` of the tree is the encrypted bit in `selectors[i]`. Since the results are
+/// GGSW ciphertexts, you can take their FFT and use them directly as the selectors of
+/// another tree, with neither circuit bootstrapping nor scheme switching in between.
+///
+/// Every mux performs a [`ggsw_cmux`], i.e. `(k + 1) * radix.count` GLWE cmuxes. The
+/// leaves of the tree are built from `key`: a 0 leaf uses `key`'s encryption of zero in
+/// its first `k` rows, a 1 leaf uses its encryptions of `-s_i`, and the last row is a
+/// trivial GLev encryption of the constant. Selecting between these yields the messages
+/// `-s_i * m` and `m` of a GGSW encryption of `m`. A constant output is simply the
+/// corresponding leaf.
+///
+/// `radix` describes the decomposition of `key` and of the outputs. `ggsw_radix`
+/// describes the decomposition of the `selectors`. To chain trees, the former's
+/// decomposition must be fine enough for the outputs to serve as selectors.
+///
+/// # Panics
+/// If `selectors.len() != tree.num_inputs()`.
+/// If `outputs.len() != tree.outputs().len()`.
+/// If `params`, `radix` or `ggsw_radix` are invalid.
+/// If any of the arguments aren't valid under `params` and their radix.
+pub fn evaluate_ggsw_cmux_tree<S>(
+    outputs: &mut [GgswCiphertext<S>],
+    tree: &MuxTree,
+    selectors: &[&GgswCiphertextFftRef<Complex<f64>>],
+    key: &GgswCmuxKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    ggsw_radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    ggsw_radix.assert_valid::<S>();
+    key.assert_is_valid((params.dim, radix.count));
+    assert_eq!(selectors.len(), tree.num_inputs());
+    assert_eq!(outputs.len(), tree.outputs().len());
+
+    for s in selectors {
+        s.assert_is_valid((params.dim, ggsw_radix.count));
+    }
+
+    for o in outputs.iter() {
+        o.assert_is_valid((params.dim, radix.count));
+    }
+
+    let leaves = [
+        constant_leaf(false, key, params, radix),
+        constant_leaf(true, key, params, radix),
+    ];
+
+    // Free each mux's result after its last use; outputs are kept until the end.
+    let mut last_use = vec![0; tree.muxes().len()];
+
+    for (i, mux) in tree.muxes().iter().enumerate() {
+        for wire in [mux.lo, mux.hi] {
+            if let MuxWire::Mux(j) = wire {
+                last_use[j] = i;
+            }
+        }
+    }
+
+    for wire in tree.outputs() {
+        if let MuxWire::Mux(j) = wire {
+            last_use[*j] = usize::MAX;
+        }
+    }
+
+    let mut values: Vec<Option<GgswCiphertext<S>>> = vec![None; tree.muxes().len()];
+
+    for (i, mux) in tree.muxes().iter().enumerate() {
+        let mut result = GgswCiphertext::new(params, radix);
+
+        {
+            let value = |wire: MuxWire| match wire {
+                MuxWire::Constant(x) => &leaves[x as usize],
+                MuxWire::Mux(j) => values[j].as_ref().unwrap(),
+            };
+
+            ggsw_cmux(
+                &mut result,
+                value(mux.lo),
+                value(mux.hi),
+                selectors[mux.select],
+                params,
+                radix,
+                ggsw_radix,
+            );
+        }
+
+        for wire in [mux.lo, mux.hi] {
+            if let MuxWire::Mux(j) = wire {
+                if last_use[j] == i {
+                    values[j] = None;
+                }
+            }
+        }
+
+        values[i] = Some(result);
+    }
+
+    for (output, wire) in outputs.iter_mut().zip(tree.outputs()) {
+        let value = match wire {
+            MuxWire::Constant(x) => &leaves[*x as usize],
+            MuxWire::Mux(j) => values[*j].as_ref().unwrap(),
+        };
+
+        output.clone_from_ref(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        entities::{GgswCiphertextFft, GgswCmuxKey},
+        high_level::*,
+        PlaintextBits, RadixCount, RadixLog,
+    };
+
+    use super::*;
+
+    // The outputs are used as selectors, so they need a fine decomposition.
+    const RADIX: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(4),
+        radix_log: RadixLog(8),
+    };
+
+    fn random_table(num_inputs: usize) -> Vec<bool> {
+        (0..1 << num_inputs)
+            .map(|_| thread_rng().next_u64() % 2 == 1)
+            .collect()
+    }
+
+    fn encrypt_inputs(
+        inputs: &[bool],
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+    ) -> Vec<GgswCiphertextFft<Complex<f64>>> {
+        inputs
+            .iter()
+            .map(|x| {
+                let ct = encryption::encrypt_ggsw(*x as u64, sk, params, &RADIX, PlaintextBits(1));
+                fft::fft_ggsw(&ct, params, &RADIX)
+            })
+            .collect()
+    }
+
+    fn evaluate(
+        tree: &MuxTree,
+        selectors: &[GgswCiphertextFft<Complex<f64>>],
+        key: &GgswCmuxKeyRef<u64>,
+        params: &GlweDef,
+    ) -> Vec<GgswCiphertext<u64>> {
+        let selectors = selectors.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+        let mut outputs = vec![GgswCiphertext::new(params, &RADIX); tree.outputs().len()];
+
+        evaluate_ggsw_cmux_tree(&mut outputs, tree, &selectors, key, params, &RADIX, &RADIX);
+
+        outputs
+    }
+
+    fn decrypt(ct: &GgswCiphertext<u64>, sk: &GlweSecretKeyRef<u64>, params: &GlweDef) -> bool {
+        let pt = encryption::decrypt_ggsw(ct, sk, params, &RADIX, PlaintextBits(1));
+
+        assert!(pt.coeffs().iter().skip(1).all(|c| *c == 0));
+
+        pt.coeffs()[0] == 1
+    }
+
+    #[test]
+    fn can_evaluate_ggsw_cmux_tree() {
+        let params = TEST_GLWE_DEF_1;
+        let num_inputs = 3;
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+        let mut key = GgswCmuxKey::new(&params, &RADIX);
+        generate_ggsw_cmux_key(&mut key, &sk, &params, &RADIX);
+
+        let tables = [
+            random_table(num_inputs),
+            random_table(num_inputs),
+            vec![true; 1 << num_inputs],
+            vec![false; 1 << num_inputs],
+        ];
+        let tree = MuxTree::new(num_inputs, &tables);
+
+        for r in 0..1 << num_inputs {
+            let inputs = (0..num_inputs)
+                .map(|i| (r >> i) & 0x1 == 1)
+                .collect::<Vec<_>>();
+            let selectors = encrypt_inputs(&inputs, &sk, &params);
+
+            let outputs = evaluate(&tree, &selectors, &key, &params);
+
+            for (table, output) in tables.iter().zip(outputs.iter()) {
+                assert_eq!(decrypt(output, &sk, &params), table[r]);
+            }
+        }
+    }
+
+    #[test]
+    fn can_chain_ggsw_cmux_trees() {
+        let params = TEST_GLWE_DEF_1;
+        let num_inputs = 2;
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+        let mut key = GgswCmuxKey::new(&params, &RADIX);
+        generate_ggsw_cmux_key(&mut key, &sk, &params, &RADIX);
+
+        // A 2-bit incrementer, followed by a check the result is 0b10.
+        let increment = MuxTree::new(
+            num_inputs,
+            &[
+                vec![true, false, true, false],
+                vec![false, true, true, false],
+            ],
+        );
+        let is_two = MuxTree::new(num_inputs, &[vec![false, false, true, false]]);
+
+        for r in 0..1 << num_inputs {
+            let inputs = (0..num_inputs)
+                .map(|i| (r >> i) & 0x1 == 1)
+                .collect::<Vec<_>>();
+            let selectors = encrypt_inputs(&inputs, &sk, &params);
+
+            let incremented = evaluate(&increment, &selectors, &key, &params)
+                .iter()
+                .map(|ct| fft::fft_ggsw(ct, &params, &RADIX))
+                .collect::<Vec<_>>();
+
+            let output = evaluate(&is_two, &incremented, &key, &params);
+
+            assert_eq!(decrypt(&output[0], &sk, &params), (r + 1) % 4 == 2);
+        }
+    }
+}
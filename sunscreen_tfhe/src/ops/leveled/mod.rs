@@ -0,0 +1,8 @@
+mod mux_tree;
+pub use mux_tree::*;
+
+mod ggsw_cmux;
+pub use ggsw_cmux::*;
+
+mod packed_cmux;
+pub use packed_cmux::*;
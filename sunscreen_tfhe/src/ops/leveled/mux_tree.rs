@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+/// An input to a [`Mux`] or an output of a [`MuxTree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MuxWire {
+    /// A constant 0 (`false`) or 1 (`true`).
+    Constant(bool),
+
+    /// The output of the mux at the given index in [`MuxTree::muxes`].
+    Mux(usize),
+}
+
+/// A multiplexer that outputs `hi` when input `select` is 1 and `lo` when it is 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Mux {
+    /// The index of the circuit input driving the select line.
+    pub select: usize,
+
+    /// The value selected when input `select` is 0.
+    pub lo: MuxWire,
+
+    /// The value selected when input `select` is 1.
+    pub hi: MuxWire,
+}
+
+/// A simplified circuit of multiplexers computing one or more boolean functions given as
+/// truth tables.
+///
+/// # Remarks
+/// Each truth table is first laid out as the canonical mux tree over its `2^n` rows, where
+/// the first layer selects on input 0, the next on input 1 and so on. The tree is then
+/// simplified layer by layer:
+/// * A mux whose inputs are the same wire is replaced by that wire. In the first layer, this
+///   means replacing the mux with a constant.
+/// * Muxes with the same select line and inputs are deduplicated, both within a table and
+///   across tables, which eliminates common subexpressions between the functions.
+///
+/// The muxes are stored in topological order: every [`MuxWire::Mux`] input refers to an
+/// earlier mux.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MuxTree {
+    num_inputs: usize,
+    muxes: Vec<Mux>,
+    outputs: Vec<MuxWire>,
+}
+
+impl MuxTree {
+    /// Build a [`MuxTree`] computing each of the given truth tables over `num_inputs` inputs.
+    ///
+    /// # Remarks
+    /// Row `r` of a truth table is the function's output when input `i` equals bit `i` of
+    /// `r`, so the least significant bit of the row index corresponds to input 0.
+    ///
+    /// # Panics
+    /// If any truth table doesn't have `2^num_inputs` rows.
+    pub fn new<T>(num_inputs: usize, tables: &[T]) -> Self
+    where
+        T: AsRef<[bool]>,
+    {
+        let mut muxes = vec![];
+        let mut existing = HashMap::new();
+
+        let mut outputs = vec![];
+
+        for table in tables {
+            let table = table.as_ref();
+
+            assert_eq!(
+                table.len(),
+                1 << num_inputs,
+                "Truth table must have 2^num_inputs rows"
+            );
+
+            let mut layer = table
+                .iter()
+                .map(|x| MuxWire::Constant(*x))
+                .collect::<Vec<_>>();
+
+            for select in 0..num_inputs {
+                let mut next_layer = Vec::with_capacity(layer.len() / 2);
+
+                for pair in layer.chunks(2) {
+                    let (lo, hi) = (pair[0], pair[1]);
+
+                    // Selecting between identical wires is just a wire.
+                    if lo == hi {
+                        next_layer.push(lo);
+                        continue;
+                    }
+
+                    let mux = Mux { select, lo, hi };
+
+                    let wire = *existing.entry(mux).or_insert_with(|| {
+                        muxes.push(mux);
+                        MuxWire::Mux(muxes.len() - 1)
+                    });
+
+                    next_layer.push(wire);
+                }
+
+                layer = next_layer;
+            }
+
+            outputs.push(layer[0]);
+        }
+
+        Self {
+            num_inputs,
+            muxes,
+            outputs,
+        }
+    }
+
+    /// The number of inputs to the circuit.
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// The muxes in the circuit, in topological order.
+    pub fn muxes(&self) -> &[Mux] {
+        &self.muxes
+    }
+
+    /// The output of each truth table, in the order they were given.
+    pub fn outputs(&self) -> &[MuxWire] {
+        &self.outputs
+    }
+
+    /// Evaluate the circuit on plaintext `inputs`, returning the value of each output.
+    ///
+    /// # Panics
+    /// If `inputs.len() != self.num_inputs()`.
+    pub fn evaluate(&self, inputs: &[bool]) -> Vec<bool> {
+        assert_eq!(inputs.len(), self.num_inputs);
+
+        let mut values = Vec::with_capacity(self.muxes.len());
+
+        let value = |values: &[bool], wire: MuxWire| match wire {
+            MuxWire::Constant(x) => x,
+            MuxWire::Mux(i) => values[i],
+        };
+
+        for mux in &self.muxes {
+            let x = if inputs[mux.select] {
+                value(&values, mux.hi)
+            } else {
+                value(&values, mux.lo)
+            };
+
+            values.push(x);
+        }
+
+        self.outputs.iter().map(|o| value(&values, *o)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(num_inputs: usize, f: impl Fn(usize) -> bool) -> Vec<bool> {
+        (0..1 << num_inputs).map(f).collect()
+    }
+
+    #[test]
+    fn constant_tables_have_no_muxes() {
+        let tree = MuxTree::new(3, &[table(3, |_| false), table(3, |_| true)]);
+
+        assert!(tree.muxes().is_empty());
+        assert_eq!(
+            tree.outputs(),
+            &[MuxWire::Constant(false), MuxWire::Constant(true)]
+        );
+    }
+
+    #[test]
+    fn single_input_table_is_one_mux() {
+        // Only depends on input 1.
+        let tree = MuxTree::new(3, &[table(3, |r| r & 0x2 != 0)]);
+
+        assert_eq!(
+            tree.muxes(),
+            &[Mux {
+                select: 1,
+                lo: MuxWire::Constant(false),
+                hi: MuxWire::Constant(true)
+            }]
+        );
+        assert_eq!(tree.outputs(), &[MuxWire::Mux(0)]);
+    }
+
+    #[test]
+    fn deduplicates_muxes() {
+        // XOR: the first layer has two distinct muxes, which then repeat.
+        let xor = table(3, |r| (r.count_ones() % 2) == 1);
+        let tree = MuxTree::new(3, &[xor]);
+
+        assert_eq!(tree.muxes().len(), 5);
+    }
+
+    #[test]
+    fn eliminates_common_subexpressions_across_tables() {
+        let and = table(2, |r| r == 0x3);
+        let or = table(2, |r| r != 0);
+
+        let both = MuxTree::new(2, &[and.clone(), or.clone(), and.clone()]);
+
+        // and(a, b) = b ? a : 0 and or(a, b) = b ? 1 : a share the mux a = a ? 1 : 0.
+        assert_eq!(both.muxes().len(), 3);
+        assert_eq!(both.outputs()[0], both.outputs()[2]);
+    }
+
+    #[test]
+    fn evaluates_truth_tables() {
+        let num_inputs = 4;
+
+        let tables = [
+            table(num_inputs, |r| r % 3 == 0),
+            table(num_inputs, |r| r < 5),
+            table(num_inputs, |r| r.count_ones() > 1),
+            table(num_inputs, |_| true),
+        ];
+
+        let tree = MuxTree::new(num_inputs, &tables);
+
+        for r in 0..1 << num_inputs {
+            let inputs = (0..num_inputs)
+                .map(|i| (r >> i) & 0x1 == 1)
+                .collect::<Vec<_>>();
+
+            let expected = tables.iter().map(|t| t[r]).collect::<Vec<_>>();
+
+            assert_eq!(tree.evaluate(&inputs), expected);
+        }
+
+        for (i, mux) in tree.muxes().iter().enumerate() {
+            for wire in [mux.lo, mux.hi] {
+                if let MuxWire::Mux(j) = wire {
+                    assert!(j < i);
+                }
+            }
+        }
+    }
+}
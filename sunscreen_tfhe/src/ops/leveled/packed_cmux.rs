@@ -0,0 +1,228 @@
+use crate::{
+    dst::FromMutSlice,
+    entities::{GlevCiphertextRef, GlweAutomorphismKeyRef, GlweCiphertextRef, PolynomialRef},
+    ops::keyswitch::glwe_automorphism::homomorphic_trace,
+    scratch::allocate_scratch_ref,
+    GlweDef, OverlaySize, RadixDecomposition, Torus, TorusOps,
+};
+
+fn assert_packable<S>(params: &GlweDef, radix: &RadixDecomposition)
+where
+    S: TorusOps,
+{
+    let log_n = params.dim.polynomial_degree.0.ilog2() as usize;
+
+    assert!(
+        radix.count.0 <= params.dim.polynomial_degree.0,
+        "Can't pack more GLev levels than polynomial coefficients"
+    );
+    assert!(
+        log_n + radix.count.0 * radix.radix_log.0 <= S::BITS as usize,
+        "N * beta^l must not exceed the ciphertext modulus"
+    );
+}
+
+/// Encode `msg` as a packed GLev message for PackedCMux, writing the result
+/// to `result`. Coefficient `j < radix.count` is `msg * q / (N * beta^(j + 1))`,
+/// and all others are zero.
+///
+/// # Remarks
+/// Use trivial GLWE encryptions of the packed encodings of 0 and 1 as the
+/// inputs to a CMux tree. The tree's GLWE result then contains the gadget
+/// decomposition of its message in its first `radix.count` coefficients, which
+/// [`glwe_to_glev`] turns into a GLev ciphertext. The extra `1 / N` factor
+/// cancels the `N` introduced by [`homomorphic_trace`].
+///
+/// # Panics
+/// If `log2(N) + radix.count * radix.radix_log > S::BITS` or
+/// `radix.count > N`.
+/// If `result` isn't valid under `params`.
+pub fn encode_packed_glev_message<S>(
+    result: &mut PolynomialRef<Torus<S>>,
+    msg: S,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    assert_packable::<S>(params, radix);
+    result.assert_is_valid(params.dim.polynomial_degree);
+
+    let log_n = params.dim.polynomial_degree.0.ilog2() as usize;
+
+    result.clear();
+
+    for (j, c) in result
+        .coeffs_mut()
+        .iter_mut()
+        .take(radix.count.0)
+        .enumerate()
+    {
+        let shift = S::BITS as usize - log_n - radix.radix_log.0 * (j + 1);
+
+        *c = Torus::from(msg.wrapping_shl(shift as u32));
+    }
+}
+
+/// Convert a GLWE ciphertext whose message packs a GLev message in its first
+/// `radix.count` coefficients (see [`encode_packed_glev_message`]) into a
+/// GLev ciphertext under `radix`, suitable for
+/// [`scheme_switch`](crate::ops::bootstrapping::scheme_switch).
+///
+/// # Remarks
+/// For each level `j`, we rotate `input` by `X^-j` to move coefficient `j` to
+/// the constant term and extract it with a [`homomorphic_trace`]. This costs
+/// `radix.count * log2(N)` GLWE keyswitches.
+///
+/// `automorphism_radix` must be the radix used to generate
+/// `automorphism_key`.
+///
+/// # Panics
+/// If `log2(N) + radix.count * radix.radix_log > S::BITS` or
+/// `radix.count > N`.
+/// If `output`, `input` or `automorphism_key` aren't valid under `params` and
+/// their radix.
+pub fn glwe_to_glev<S>(
+    output: &mut GlevCiphertextRef<S>,
+    input: &GlweCiphertextRef<S>,
+    automorphism_key: &GlweAutomorphismKeyRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+    automorphism_radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    assert_packable::<S>(params, radix);
+    output.assert_is_valid((params.dim, radix.count));
+    input.assert_is_valid(params.dim);
+
+    allocate_scratch_ref!(rotated, GlweCiphertextRef<S>, (params.dim));
+
+    for (j, glwe) in output.glwe_ciphertexts_mut(params).enumerate() {
+        rotated.clone_from_ref(input);
+
+        for a in rotated.a_mut(params) {
+            a.mul_by_negative_monomial_negacyclic(j);
+        }
+
+        rotated.b_mut(params).mul_by_negative_monomial_negacyclic(j);
+
+        homomorphic_trace(glwe, rotated, automorphism_key, params, automorphism_radix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        entities::{
+            GgswCiphertext, GlevCiphertext, GlweAutomorphismKey, GlweCiphertext, Polynomial,
+            SchemeSwitchKey,
+        },
+        high_level::*,
+        ops::{
+            bootstrapping::{generate_scheme_switch_key, scheme_switch},
+            encryption::{decrypt_glwe_in_glev, trivially_encrypt_glwe_ciphertext},
+            fft_ops::cmux,
+            keyswitch::glwe_automorphism_key::generate_glwe_automorphism_key,
+        },
+        PlaintextBits, RadixCount, RadixLog,
+    };
+
+    use super::*;
+
+    const RADIX_SELECT: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(4),
+        radix_log: RadixLog(8),
+    };
+
+    const RADIX_GLEV: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(2),
+        radix_log: RadixLog(8),
+    };
+
+    const RADIX_AUTOMORPHISM: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(6),
+        radix_log: RadixLog(7),
+    };
+
+    const RADIX_SS: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(2),
+        radix_log: RadixLog(19),
+    };
+
+    fn trivial_packed_glwe(msg: u64, params: &GlweDef) -> GlweCiphertext<u64> {
+        let mut poly = Polynomial::zero(params.dim.polynomial_degree.0);
+        encode_packed_glev_message(&mut poly, msg, params, &RADIX_GLEV);
+
+        let mut ct = GlweCiphertext::new(params);
+        trivially_encrypt_glwe_ciphertext(&mut ct, &poly, params);
+
+        ct
+    }
+
+    #[test]
+    fn packed_cmux_feeds_scheme_switch() {
+        let params = TEST_GLWE_DEF_1;
+        let n = params.dim.polynomial_degree.0;
+        let bits = PlaintextBits(1);
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+
+        let mut ak = GlweAutomorphismKey::new(&params, &RADIX_AUTOMORPHISM);
+        generate_glwe_automorphism_key(&mut ak, &sk, &params, &RADIX_AUTOMORPHISM);
+
+        let mut ssk = SchemeSwitchKey::new(&params, &RADIX_SS);
+        generate_scheme_switch_key(&mut ssk, &sk, &params, &RADIX_SS);
+
+        let zero = trivial_packed_glwe(0, &params);
+        let one = trivial_packed_glwe(1, &params);
+
+        for sel in [0, 1] {
+            let sel_ct = encryption::encrypt_ggsw(sel, &sk, &params, &RADIX_SELECT, bits);
+            let sel_fft = fft::fft_ggsw(&sel_ct, &params, &RADIX_SELECT);
+
+            // PackedCMux: an ordinary cmux over the packed encodings.
+            let mut packed = GlweCiphertext::new(&params);
+            cmux(&mut packed, &zero, &one, &sel_fft, &params, &RADIX_SELECT);
+
+            let mut glev = GlevCiphertext::new(&params, &RADIX_GLEV);
+            glwe_to_glev(
+                &mut glev,
+                &packed,
+                &ak,
+                &params,
+                &RADIX_GLEV,
+                &RADIX_AUTOMORPHISM,
+            );
+
+            for j in 0..RADIX_GLEV.count.0 {
+                let mut msg = Polynomial::zero(n);
+                decrypt_glwe_in_glev(&mut msg, &glev, &sk, &params, &RADIX_GLEV, j);
+
+                assert_eq!(msg.coeffs()[0].inner(), sel);
+                assert!(msg.coeffs().iter().skip(1).all(|c| c.inner() == 0));
+            }
+
+            // The result scheme switches to a GGSW ciphertext usable in another cmux.
+            let mut ggsw = GgswCiphertext::new(&params, &RADIX_GLEV);
+            scheme_switch(&mut ggsw, &glev, &ssk, &params, &RADIX_GLEV, &RADIX_SS);
+            let ggsw_fft = fft::fft_ggsw(&ggsw, &params, &RADIX_GLEV);
+
+            let a = (0..n)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let b = (0..n)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let a_ct = encryption::encrypt_glwe(&a, &sk, &params, bits);
+            let b_ct = encryption::encrypt_glwe(&b, &sk, &params, bits);
+
+            let res = evaluation::cmux(&ggsw_fft, &a_ct, &b_ct, &params, &RADIX_GLEV);
+            let res = encryption::decrypt_glwe(&res, &sk, &params, bits);
+
+            assert_eq!(res, if sel == 1 { b } else { a });
+        }
+    }
+}
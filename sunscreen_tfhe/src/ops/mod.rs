@@ -1,6 +1,19 @@
 /// Ciphertext operations where one of the operands is in FFT form.
 pub mod fft_ops;
 
+/// Ciphertext operations where one of the operands is in NTT form. These
+/// compute polynomial products exactly.
+///
+/// # Remarks
+/// The NTT is selected by the form keys are transformed into rather than by
+/// the [`GlweDef`](crate::GlweDef): a
+/// [`BootstrapKeyNtt`](crate::entities::BootstrapKeyNtt) or
+/// [`GgswCiphertextNtt`](crate::entities::GgswCiphertextNtt) goes to the
+/// `_ntt` variant of an operation, while the FFT forms go to the default
+/// operations. The parameters are the same for both, so keys generated under
+/// any [`GlweDef`](crate::GlweDef) can be transformed either way.
+pub mod ntt_ops;
+
 /// Methods for key switching a ciphertext from one key to another, potentially
 /// switching the parameters at the same time.
 pub mod keyswitch;
@@ -9,6 +22,10 @@ pub mod keyswitch;
 /// refreshing the noise in the ciphertext.
 pub mod bootstrapping;
 
+/// Methods for chaining CMux trees without bootstrapping between them. See
+/// `notes/leveled_computation.md`.
+pub mod leveled;
+
 /// Methods for homomorphic operations on ciphertexts.
 pub mod homomorphisms;
 
@@ -0,0 +1,317 @@
+use sunscreen_math::Zero;
+
+use crate::{
+    dst::{FromMutSlice, OverlaySize},
+    entities::{GgswCiphertextNttRef, GlweCiphertext, GlweCiphertextRef, PolynomialRef},
+    fft::ntt::{fg_from_signed, fg_to_signed, get_ntt, MAX_NTT_LOG_N, NTT_LIMBS, NTT_LIMB_BITS},
+    goldilocks_field::{Fg, GOLDILOCKS_PRIME},
+    ops::ciphertext::{add_glwe_ciphertexts, sub_glwe_ciphertexts},
+    radix::PolynomialRadixIterator,
+    scratch::{allocate_scratch, allocate_scratch_ref},
+    GlweDef, RadixDecomposition, Torus, TorusOps,
+};
+
+/// Returns whether external products under `params` and `radix` computed
+/// with the NTT are exact.
+///
+/// # Remarks
+/// Each limb of an external product sums `(k + 1) * l * N` products of a
+/// decomposed digit of magnitude at most `2^(radix_log - 1)` with a
+/// [`NTT_LIMB_BITS`]-bit limb of the GGSW ciphertext. The result is exact if
+/// this sum can't exceed half the Goldilocks prime, which holds for all
+/// practical TFHE parameters.
+///
+/// The polynomial degree must also be at most `2^`[`MAX_NTT_LOG_N`].
+pub fn ntt_is_exact(params: &GlweDef, radix: &RadixDecomposition) -> bool {
+    let n = params.dim.polynomial_degree.0;
+
+    if !n.is_power_of_two() || n.ilog2() as usize > MAX_NTT_LOG_N || radix.radix_log.0 == 0 {
+        return false;
+    }
+
+    let terms = (params.dim.size.0 as u128 + 1) * radix.count.0 as u128 * n as u128;
+
+    let max_limb = (0x1u128 << NTT_LIMB_BITS) - 1;
+
+    0x1u128
+        .checked_shl(radix.radix_log.0 as u32 - 1)
+        .and_then(|max_digit| terms.checked_mul(max_digit * max_limb))
+        .is_some_and(|x| x <= GOLDILOCKS_PRIME as u128 / 2)
+}
+
+/// Compute `c += a \[*\] b` where
+/// * `a` is a GLWE ciphertext
+/// * `b` is a GGSW ciphertext in NTT form
+/// * `\[*\]` is the external product operator GGSW \[*\] GLWE -> GLWE
+///
+/// # Remarks
+/// Unlike [`fft_ops::glwe_ggsw_mad`](crate::ops::fft_ops::glwe_ggsw_mad), the
+/// result is exactly that of
+/// [`ciphertext::glwe_ggsw_mad`](crate::ops::ciphertext::glwe_ggsw_mad).
+///
+/// # Panics
+/// If the NTT isn't exact for `params` and `radix`. See [`ntt_is_exact`].
+pub fn glwe_ggsw_mad<S>(
+    c: &mut GlweCiphertextRef<S>,
+    a: &GlweCiphertextRef<S>,
+    b_ntt: &GgswCiphertextNttRef<Fg>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    assert!(
+        ntt_is_exact(params, radix),
+        "Parameters are too large for exact NTT external products."
+    );
+
+    let n = params.dim.polynomial_degree.0;
+    let glwe_size = params.dim.size.0 + 1;
+    let levels = radix.count.0;
+    let ntt = get_ntt(n.ilog2() as usize).unwrap();
+
+    // Sign extending the decomposed digits requires knowing their width.
+    let sign_shift = 64 - S::BITS;
+
+    // Compute the NTTs of the decomposition of each of the GLWE polynomials.
+    // The decomposition iterator returns the digits from least to most
+    // significant, which is the reverse of the GLEV ciphertexts in each row.
+    let mut digits = allocate_scratch::<Fg>(glwe_size * levels * n);
+    let digits = digits.as_mut_slice();
+
+    allocate_scratch_ref!(scratch, PolynomialRef<S>, (params.dim.polynomial_degree));
+    allocate_scratch_ref!(cur_radix, PolynomialRef<S>, (params.dim.polynomial_degree));
+
+    let (a_a, a_b) = a.a_b(params);
+
+    for (a_i, row) in a_a
+        .chain(std::iter::once(a_b))
+        .zip(digits.chunks_mut(levels * n))
+    {
+        let mut decomp = PolynomialRadixIterator::new(a_i, scratch, radix);
+
+        for d in row.chunks_mut(n).rev() {
+            decomp.write_next(cur_radix);
+
+            for (d, x) in d.iter_mut().zip(cur_radix.coeffs().iter()) {
+                *d = fg_from_signed(((x.to_u64() << sign_shift) as i64) >> sign_shift);
+            }
+
+            ntt.forward_in_place(d);
+        }
+    }
+
+    let mut acc = allocate_scratch::<Fg>(n);
+    let acc = acc.as_mut_slice();
+
+    let mut out = allocate_scratch::<u64>(n);
+    let out = out.as_mut_slice();
+
+    // Each output polynomial is the sum over the GGSW's GLWE ciphertexts of
+    // the digit times the matching polynomial. Accumulate each limb in the
+    // NTT domain, then lift it to the integers and recombine mod 2^64.
+    let (c_a, c_b) = c.a_b_mut(params);
+
+    for (col, c_poly) in c_a.chain(std::iter::once(c_b)).enumerate() {
+        out.fill(0);
+
+        for limb in 0..NTT_LIMBS {
+            acc.fill(Fg::zero());
+
+            for (d, glwe) in digits.chunks(n).zip(b_ntt.glwe_ciphertexts(params)) {
+                let offset = (col * NTT_LIMBS + limb) * n;
+
+                for ((acc, d), b) in acc
+                    .iter_mut()
+                    .zip(d.iter())
+                    .zip(glwe[offset..offset + n].iter())
+                {
+                    *acc = d.mad(*b, *acc);
+                }
+            }
+
+            ntt.reverse_in_place(acc);
+
+            for (o, x) in out.iter_mut().zip(acc.iter()) {
+                let x = fg_to_signed(*x) as u64;
+
+                *o = o.wrapping_add(x << (limb * NTT_LIMB_BITS));
+            }
+        }
+
+        for (c, o) in c_poly.coeffs_mut().iter_mut().zip(out.iter()) {
+            *c += Torus::from(S::from_u64(*o));
+        }
+    }
+}
+
+/// Compute the external product of a GLWE ciphertext and a GGSW ciphertext
+/// in NTT form. GGSW ⊡ GLWE -> GLWE
+///
+/// # Remarks
+/// The result is exactly that of
+/// [`external_product_ggsw_glwe`](crate::ops::ciphertext::external_product_ggsw_glwe)
+/// on the GGSW ciphertext `ggsw_ntt` came from.
+///
+/// # Panics
+/// If the NTT isn't exact for `params` and `radix`. See [`ntt_is_exact`].
+pub fn external_product_ggsw_glwe<S>(
+    ggsw_ntt: &GgswCiphertextNttRef<Fg>,
+    glwe: &GlweCiphertextRef<S>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) -> GlweCiphertext<S>
+where
+    S: TorusOps,
+{
+    ggsw_ntt.assert_is_valid((params.dim, radix.count));
+    glwe.assert_is_valid(params.dim);
+
+    let mut result = GlweCiphertext::new(params);
+    glwe_ggsw_mad(&mut result, glwe, ggsw_ntt, params, radix);
+
+    result
+}
+
+/// Performs a CMUX operation exactly using a GGSW ciphertext in NTT form.
+/// See [`fft_ops::cmux`](crate::ops::fft_ops::cmux).
+///
+/// # Panics
+/// If the NTT isn't exact for `params` and `radix`. See [`ntt_is_exact`].
+pub fn cmux<S>(
+    c: &mut GlweCiphertextRef<S>,
+    d_0: &GlweCiphertextRef<S>,
+    d_1: &GlweCiphertextRef<S>,
+    b_ntt: &GgswCiphertextNttRef<Fg>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    c.assert_is_valid(params.dim);
+    d_0.assert_is_valid(params.dim);
+    d_1.assert_is_valid(params.dim);
+    b_ntt.assert_is_valid((params.dim, radix.count));
+
+    allocate_scratch_ref!(diff, GlweCiphertextRef<S>, (params.dim));
+
+    sub_glwe_ciphertexts(diff, d_1, d_0, params);
+
+    allocate_scratch_ref!(prod, GlweCiphertextRef<S>, (params.dim));
+
+    prod.clear();
+
+    glwe_ggsw_mad(prod, diff, b_ntt, params, radix);
+
+    add_glwe_ciphertexts(c, prod, d_0, params);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::{GgswCiphertext, GgswCiphertextNtt, Polynomial},
+        high_level::*,
+        ops::{ciphertext, encryption::encrypt_ggsw_ciphertext},
+        GlweDimension, GlweSize, PlaintextBits, PolynomialDegree, RadixCount, RadixLog,
+        GLWE_1_1024_128, GLWE_1_1024_80, GLWE_1_2048_128, GLWE_1_512_128, GLWE_5_256_128,
+        GLWE_5_256_80,
+    };
+
+    use super::*;
+
+    #[test]
+    fn ntt_external_product_is_exact() {
+        let params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+        let bits = PlaintextBits(4);
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+
+        let msg = Polynomial::new(
+            &(0..params.dim.polynomial_degree.0 as u64)
+                .map(|x| x % 16)
+                .collect::<Vec<_>>(),
+        );
+        let glwe = encryption::encrypt_glwe(&msg, &sk, &params, bits);
+
+        // Constant polynomial: [1, 0, 0, ...]
+        let one = Polynomial::new(
+            &(0..params.dim.polynomial_degree.0 as u64)
+                .map(|x| if x < 1 { 1 } else { 0 })
+                .collect::<Vec<_>>(),
+        );
+
+        let mut ggsw = GgswCiphertext::new(&params, &radix);
+        encrypt_ggsw_ciphertext(&mut ggsw, &one, &sk, &params, &radix, bits);
+
+        let mut ggsw_ntt = GgswCiphertextNtt::new(&params, &radix);
+        ggsw.ntt(&mut ggsw_ntt, &params, &radix);
+
+        let expected = ciphertext::external_product_ggsw_glwe(&ggsw, &glwe, &params, &radix);
+        let actual = external_product_ggsw_glwe(&ggsw_ntt, &glwe, &params, &radix);
+
+        assert_eq!(actual, expected);
+        assert_eq!(encryption::decrypt_glwe(&actual, &sk, &params, bits), msg);
+    }
+
+    #[test]
+    fn ntt_is_exact_for_presets() {
+        let radix = |count, radix_log| RadixDecomposition {
+            count: RadixCount(count),
+            radix_log: RadixLog(radix_log),
+        };
+
+        // The radixes documented and benchmarked alongside the presets.
+        let radixes = [
+            TEST_RADIX,
+            radix(1, 11),
+            radix(1, 23),
+            radix(2, 5),
+            radix(2, 10),
+            radix(2, 16),
+            radix(2, 19),
+            radix(3, 6),
+            radix(3, 11),
+            radix(6, 4),
+            radix(8, 4),
+            radix(8, 7),
+        ];
+
+        let presets = [
+            GLWE_1_512_128,
+            GLWE_5_256_128,
+            GLWE_1_1024_128,
+            GLWE_1_2048_128,
+            GLWE_5_256_80,
+            GLWE_1_1024_80,
+        ];
+
+        for params in presets {
+            for radix in radixes {
+                assert!(ntt_is_exact(&params, &radix), "{params:?} {radix:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn ntt_is_not_exact_for_oversized_parameters() {
+        let big_radix = RadixDecomposition {
+            count: RadixCount(1),
+            radix_log: RadixLog(48),
+        };
+
+        assert!(!ntt_is_exact(&GLWE_1_1024_80, &big_radix));
+
+        let huge_degree = GlweDef {
+            dim: GlweDimension {
+                polynomial_degree: PolynomialDegree(0x1 << (MAX_NTT_LOG_N + 1)),
+                size: GlweSize(1),
+            },
+            ..GLWE_1_1024_80
+        };
+
+        assert!(!ntt_is_exact(&huge_degree, &TEST_RADIX));
+    }
+}
@@ -28,7 +28,7 @@ pub trait SecurityLevel {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of torus elements in the LWE lattice.
 pub struct LweDimension(pub usize);
@@ -50,12 +50,12 @@ impl LweDimension {
 /// are integers mod `q`.
 pub struct PolynomialDegree(pub usize);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of polynomials in a GLWE instance.
 pub struct GlweSize(pub usize);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of plaintext bits to encode into a message.
 ///
@@ -66,22 +66,22 @@ pub struct GlweSize(pub usize);
 /// For binary, set this to one.
 pub struct PlaintextBits(pub u32);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of padding bits to include in an LWE ciphertext.
 pub struct CarryBits(pub u32);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of digits to decompose a value into.
 pub struct RadixCount(pub usize);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of bits in a digit output during base decomposition.
 pub struct RadixLog(pub usize);
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The number of [`LweCiphertext`](crate::entities::LweCiphertext)s that get
 /// mapped into a [`GlweCiphertext`](crate::entities::GlweCiphertext) during
@@ -96,7 +96,7 @@ impl PrivateFunctionalKeyswitchLweCount {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 /// The parameters defining how to do approximately perform base decomposition. I.e.
 /// decompose values into digits.
 ///
@@ -127,7 +127,7 @@ impl RadixDecomposition {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 /// A [`PolynomialDegree`] and [`GlweSize`] in a GLWE instance.
 pub struct GlweDimension {
     /// The degree of the polynomial in a GLWE instance.
@@ -153,7 +153,7 @@ impl GlweDimension {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 /// Parameters that define an LWE problem instance.
 ///
 /// # Security
@@ -181,7 +181,7 @@ impl SecurityLevel for LweDef {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 /// Parameters that define a GLWE problem instance.
 ///
 /// # Security
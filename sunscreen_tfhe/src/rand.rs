@@ -1,15 +1,16 @@
 use std::fmt::Debug;
 
-use rand::{thread_rng, Rng, RngCore};
+use rand::{thread_rng, CryptoRng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use rand_distr::Normal;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entities::PolynomialRef,
+    entities::{KeySeed, PolynomialRef},
     math::{Torus, TorusOps},
 };
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 /// The standard deviation of a Gaussian distribution normalized over the torus
 /// `T_q`.
@@ -18,9 +19,18 @@ pub struct Stddev(pub f64);
 /// Sample a random torus element from the a normal distribution
 /// with a mean of 0 and the given stddev
 pub fn normal_torus<S: TorusOps>(std: Stddev) -> Torus<S> {
+    normal_torus_with_rng(std, &mut thread_rng())
+}
+
+/// Sample a random torus element from the a normal distribution
+/// with a mean of 0 and the given stddev using the given generator
+pub fn normal_torus_with_rng<S: TorusOps>(
+    std: Stddev,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Torus<S> {
     let dist = Normal::new(0., std.0).unwrap();
 
-    let e_0 = thread_rng().sample(dist);
+    let e_0 = rng.sample(dist);
     let q = (S::BITS as f64).exp2();
 
     let e = f64::round(e_0 * q) as i64;
@@ -31,26 +41,75 @@ pub fn normal_torus<S: TorusOps>(std: Stddev) -> Torus<S> {
 
 /// Generate a random torus element uniformly
 pub fn uniform_torus<S: TorusOps>() -> Torus<S> {
-    Torus::from(S::from_u64(thread_rng().next_u64()))
+    uniform_torus_with_rng(&mut thread_rng())
+}
+
+/// Generate a random torus element uniformly using the given generator
+pub fn uniform_torus_with_rng<S: TorusOps>(rng: &mut (impl CryptoRng + RngCore)) -> Torus<S> {
+    Torus::from(S::from_u64(rng.next_u64()))
+}
+
+/// Create the deterministic generator for the `stream`-th row of a key
+/// generated from `seed`, e.g. the masks of a seeded key. Each row uses its own
+/// ChaCha20 stream so rows can be generated and expanded in parallel.
+pub(crate) fn seeded_stream_rng(seed: &KeySeed, stream: u64) -> ChaCha20Rng {
+    let mut rng = ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(stream);
+
+    rng
+}
+
+/// Generate a random seed for a seeded key
+pub fn key_seed() -> KeySeed {
+    key_seed_with_rng(&mut thread_rng())
+}
+
+/// Generate a random seed for a seeded key using the given generator
+pub fn key_seed_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> KeySeed {
+    rng.gen()
 }
 
 /// Generate a random binary torus element
 pub fn binary<S: TorusOps>() -> S {
-    S::from_u64(thread_rng().next_u64() % 2)
+    binary_with_rng(&mut thread_rng())
+}
+
+/// Generate a random binary torus element using the given generator
+pub fn binary_with_rng<S: TorusOps>(rng: &mut (impl CryptoRng + RngCore)) -> S {
+    S::from_u64(rng.next_u64() % 2)
 }
 
 /// Fill in a polynomial with random binary coefficients
 pub fn binary_torus_polynomial<S: TorusOps>(out: &mut PolynomialRef<S>) {
+    binary_torus_polynomial_with_rng(out, &mut thread_rng())
+}
+
+/// Fill in a polynomial with random binary coefficients using the given
+/// generator
+pub fn binary_torus_polynomial_with_rng<S: TorusOps>(
+    out: &mut PolynomialRef<S>,
+    rng: &mut (impl CryptoRng + RngCore),
+) {
     for c in out.coeffs_mut().iter_mut() {
-        *c = binary();
+        *c = binary_with_rng(rng);
     }
 }
 
 /// Sample a random polynomial with coefficients chosen from a normal distribution
 /// with a mean of 0 and the given stddev
 pub fn normal_torus_polynomial<S: TorusOps>(out: &mut PolynomialRef<Torus<S>>, std: Stddev) {
+    normal_torus_polynomial_with_rng(out, std, &mut thread_rng())
+}
+
+/// Sample a random polynomial with coefficients chosen from a normal distribution
+/// with a mean of 0 and the given stddev using the given generator
+pub fn normal_torus_polynomial_with_rng<S: TorusOps>(
+    out: &mut PolynomialRef<Torus<S>>,
+    std: Stddev,
+    rng: &mut (impl CryptoRng + RngCore),
+) {
     for c in out.coeffs_mut().iter_mut() {
-        *c = normal_torus(std);
+        *c = normal_torus_with_rng(std, rng);
     }
 }
 
@@ -0,0 +1,497 @@
+use std::fmt::Debug;
+
+use num::Complex;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    entities::*, Error, GlweDef, LweDef, OverlaySize, PrivateFunctionalKeyswitchLweCount,
+    RadixDecomposition, Result, TorusOps,
+};
+
+/// The version of the format [`WithParams`] serializes to. Deserialization
+/// rejects any other version.
+pub const SERIALIZATION_VERSION: u32 = 1;
+
+/// An entity that can be serialized along with the parameters that define it.
+/// See [`WithParams`].
+pub trait TfheEntity: Serialize + DeserializeOwned {
+    /// The parameters that define this entity's layout.
+    type Params: Clone + Debug + PartialEq + Serialize + DeserializeOwned;
+
+    /// A name identifying this entity's type and scalar type in serialized
+    /// data, e.g. `LweCiphertext<u64>`.
+    fn kind() -> String;
+
+    /// Returns an error if this entity isn't valid under `params`.
+    fn check_params(&self, params: &Self::Params) -> Result<()>;
+}
+
+/// A [`TfheEntity`] tagged with the parameters it was created under.
+///
+/// # Remarks
+/// `WithParams` serializes a format version, the kind of entity, the
+/// parameters and the entity itself. Deserializing fails if the version or
+/// kind differ from what this library expects or if the entity isn't valid
+/// under the embedded parameters. Use [`WithParams::into_entity`] to also
+/// reject entities created under parameters other than the ones you expect.
+///
+/// This works with any serde format.
+///
+/// # Example
+/// ```
+/// use sunscreen_tfhe::{
+///     entities::LweCiphertext,
+///     high_level::{encryption, keygen},
+///     serialization::WithParams,
+///     PlaintextBits, LWE_512_80,
+/// };
+///
+/// let sk = keygen::generate_binary_lwe_sk(&LWE_512_80);
+/// let ct = encryption::encrypt_lwe_secret(1, &sk, &LWE_512_80, PlaintextBits(1));
+///
+/// let json = serde_json::to_string(&WithParams::new(ct, LWE_512_80).unwrap()).unwrap();
+///
+/// let ct: LweCiphertext<u64> = serde_json::from_str::<WithParams<_>>(&json)
+///     .unwrap()
+///     .into_entity(&LWE_512_80)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithParams<T>
+where
+    T: TfheEntity,
+{
+    params: T::Params,
+    entity: T,
+}
+
+impl<T> WithParams<T>
+where
+    T: TfheEntity,
+{
+    /// Tag `entity` with `params`.
+    ///
+    /// # Remarks
+    /// Returns [`Error::InvalidSize`] if `entity` isn't valid under `params`.
+    pub fn new(entity: T, params: T::Params) -> Result<Self> {
+        entity.check_params(&params)?;
+
+        Ok(Self { params, entity })
+    }
+
+    /// The parameters the entity was created under.
+    pub fn params(&self) -> &T::Params {
+        &self.params
+    }
+
+    /// The tagged entity.
+    pub fn entity(&self) -> &T {
+        &self.entity
+    }
+
+    /// Returns the tagged entity if it was created under `expected`, or
+    /// [`Error::ParameterMismatch`] otherwise.
+    pub fn into_entity(self, expected: &T::Params) -> Result<T> {
+        if &self.params != expected {
+            return Err(Error::ParameterMismatch);
+        }
+
+        Ok(self.entity)
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T, P> {
+    version: u32,
+    kind: String,
+    params: &'a P,
+    entity: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Envelope<T, P> {
+    version: u32,
+    kind: String,
+    params: P,
+    entity: T,
+}
+
+impl<T> Serialize for WithParams<T>
+where
+    T: TfheEntity,
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        EnvelopeRef {
+            version: SERIALIZATION_VERSION,
+            kind: T::kind(),
+            params: &self.params,
+            entity: &self.entity,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for WithParams<T>
+where
+    T: TfheEntity,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let envelope = Envelope::<T, T::Params>::deserialize(deserializer)?;
+
+        let check = || {
+            if envelope.version != SERIALIZATION_VERSION {
+                return Err(Error::UnsupportedVersion(envelope.version));
+            }
+
+            if envelope.kind != T::kind() {
+                return Err(Error::EntityKindMismatch {
+                    expected: T::kind(),
+                    actual: envelope.kind.clone(),
+                });
+            }
+
+            envelope.entity.check_params(&envelope.params)
+        };
+
+        check().map_err(serde::de::Error::custom)?;
+
+        Ok(Self {
+            params: envelope.params,
+            entity: envelope.entity,
+        })
+    }
+}
+
+macro_rules! torus_entity {
+    ($t:ident, $params:ty, |$p:ident| $inputs:expr) => {
+        impl<S> TfheEntity for $t<S>
+        where
+            S: TorusOps + Serialize + DeserializeOwned,
+        {
+            type Params = $params;
+
+            fn kind() -> String {
+                format!("{}<u{}>", stringify!($t), S::BITS)
+            }
+
+            fn check_params(&self, $p: &Self::Params) -> Result<()> {
+                self.check_is_valid($inputs)
+            }
+        }
+    };
+}
+
+macro_rules! fft_entity {
+    ($t:ident, $params:ty, |$p:ident| $inputs:expr) => {
+        impl TfheEntity for $t<Complex<f64>> {
+            type Params = $params;
+
+            fn kind() -> String {
+                format!("{}<Complex<f64>>", stringify!($t))
+            }
+
+            fn check_params(&self, $p: &Self::Params) -> Result<()> {
+                self.check_is_valid($inputs)
+            }
+        }
+    };
+}
+
+// Secret and public keys
+torus_entity!(LweSecretKey, LweDef, |p| p.dim);
+torus_entity!(GlweSecretKey, GlweDef, |p| p.dim);
+torus_entity!(LwePublicKey, LweDef, |p| p.dim);
+torus_entity!(RlwePublicKey, GlweDef, |p| p.dim);
+
+// Ciphertexts
+torus_entity!(LweCiphertext, LweDef, |p| p.dim);
+torus_entity!(LweCiphertextList, (LweDef, usize), |p| (p.0.dim, p.1));
+torus_entity!(GlweCiphertext, GlweDef, |p| p.dim);
+torus_entity!(GlevCiphertext, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+torus_entity!(GgswCiphertext, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+fft_entity!(GlweCiphertextFft, GlweDef, |p| p.dim);
+fft_entity!(GlevCiphertextFft, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+fft_entity!(GgswCiphertextFft, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+
+// Evaluation keys
+torus_entity!(BootstrapKey, (LweDef, GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.dim, p.2.count
+));
+fft_entity!(
+    BootstrapKeyFft,
+    (LweDef, GlweDef, RadixDecomposition),
+    |p| (p.0.dim, p.1.dim, p.2.count)
+);
+torus_entity!(LweKeyswitchKey, (LweDef, LweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.dim, p.2.count
+));
+torus_entity!(GlweKeyswitchKey, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+torus_entity!(SchemeSwitchKey, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+fft_entity!(SchemeSwitchKeyFft, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+torus_entity!(
+    CircuitBootstrappingKeyswitchKeys,
+    (LweDef, GlweDef, RadixDecomposition),
+    |p| (p.0.dim, p.1.dim, p.2.count)
+);
+torus_entity!(
+    PublicFunctionalKeyswitchKey,
+    (LweDef, GlweDef, RadixDecomposition),
+    |p| (p.0.dim, p.1.dim, p.2.count)
+);
+torus_entity!(
+    PrivateFunctionalKeyswitchKey,
+    (
+        LweDef,
+        GlweDef,
+        RadixDecomposition,
+        PrivateFunctionalKeyswitchLweCount
+    ),
+    |p| (p.0.dim, p.1.dim, p.2.count, p.3)
+);
+torus_entity!(GlweAutomorphismKey, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+torus_entity!(GgswCmuxKey, (GlweDef, RadixDecomposition), |p| (
+    p.0.dim, p.1.count
+));
+
+// Seeded keys share their parameters with the keys they compress.
+impl<S> TfheEntity for SeededBootstrapKey<S>
+where
+    S: TorusOps + Serialize + DeserializeOwned,
+{
+    type Params = (LweDef, GlweDef, RadixDecomposition);
+
+    fn kind() -> String {
+        format!("SeededBootstrapKey<u{}>", S::BITS)
+    }
+
+    fn check_params(&self, p: &Self::Params) -> Result<()> {
+        self.check_is_valid(&p.0, &p.1, &p.2)
+    }
+}
+
+impl<S> TfheEntity for SeededLweKeyswitchKey<S>
+where
+    S: TorusOps + Serialize + DeserializeOwned,
+{
+    type Params = (LweDef, LweDef, RadixDecomposition);
+
+    fn kind() -> String {
+        format!("SeededLweKeyswitchKey<u{}>", S::BITS)
+    }
+
+    fn check_params(&self, p: &Self::Params) -> Result<()> {
+        self.check_is_valid(&p.0, &p.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        high_level::{encryption, fft, keygen, TEST_GLWE_DEF_1, TEST_LWE_DEF_1, TEST_RADIX},
+        rand::key_seed,
+        PlaintextBits,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_roundtrip_ciphertexts() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+
+        let sk = keygen::generate_binary_lwe_sk(&lwe);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &lwe, PlaintextBits(1));
+
+        let tagged = WithParams::new(ct.clone(), lwe).unwrap();
+
+        let json = serde_json::to_string(&tagged).unwrap();
+        let actual = serde_json::from_str::<WithParams<LweCiphertext<u64>>>(&json)
+            .unwrap()
+            .into_entity(&lwe)
+            .unwrap();
+
+        assert_eq!(actual, ct);
+
+        let bin = bincode::serialize(&tagged).unwrap();
+        let actual = bincode::deserialize::<WithParams<LweCiphertext<u64>>>(&bin).unwrap();
+
+        assert_eq!(actual, tagged);
+
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let ggsw = encryption::encrypt_ggsw(1, &glwe_sk, &glwe, &TEST_RADIX, PlaintextBits(1));
+        let ggsw = fft::fft_ggsw(&ggsw, &glwe, &TEST_RADIX);
+
+        let tagged = WithParams::new(ggsw, (glwe, TEST_RADIX)).unwrap();
+        let bin = bincode::serialize(&tagged).unwrap();
+        let actual = bincode::deserialize::<WithParams<GgswCiphertextFft<_>>>(&bin).unwrap();
+
+        assert_eq!(actual, tagged);
+    }
+
+    #[test]
+    fn can_roundtrip_keys() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+        let params = (lwe, glwe, TEST_RADIX);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &TEST_RADIX);
+        let bsk_fft = fft::fft_bootstrap_key(&bsk, &lwe, &glwe, &TEST_RADIX);
+
+        let tagged = WithParams::new(bsk.clone(), params).unwrap();
+        let bin = bincode::serialize(&tagged).unwrap();
+        let actual = bincode::deserialize::<WithParams<BootstrapKey<u64>>>(&bin)
+            .unwrap()
+            .into_entity(&params)
+            .unwrap();
+
+        assert_eq!(actual, bsk);
+
+        let tagged = WithParams::new(bsk_fft.clone(), params).unwrap();
+        let bin = bincode::serialize(&tagged).unwrap();
+        let actual = bincode::deserialize::<WithParams<BootstrapKeyFft<_>>>(&bin)
+            .unwrap()
+            .into_entity(&params)
+            .unwrap();
+
+        assert_eq!(actual, bsk_fft);
+
+        let tagged = WithParams::new(lwe_sk.clone(), lwe).unwrap();
+        let json = serde_json::to_string(&tagged).unwrap();
+        let actual = serde_json::from_str::<WithParams<LweSecretKey<u64>>>(&json)
+            .unwrap()
+            .into_entity(&lwe)
+            .unwrap();
+
+        assert_eq!(actual, lwe_sk);
+    }
+
+    #[test]
+    fn can_roundtrip_seeded_keys() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+        let params = (lwe, glwe, TEST_RADIX);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let bsk = keygen::generate_seeded_bootstrapping_key(
+            &lwe_sk,
+            &glwe_sk,
+            &lwe,
+            &glwe,
+            &TEST_RADIX,
+            key_seed(),
+        );
+
+        let tagged = WithParams::new(bsk.clone(), params).unwrap();
+        let bin = bincode::serialize(&tagged).unwrap();
+        let actual = bincode::deserialize::<WithParams<SeededBootstrapKey<u64>>>(&bin)
+            .unwrap()
+            .into_entity(&params)
+            .unwrap();
+
+        assert_eq!(actual, bsk);
+
+        // The seeded key is much smaller than the full key.
+        let full = keygen::expand_bootstrapping_key(&bsk, &lwe, &glwe, &TEST_RADIX);
+        let full_bin = bincode::serialize(&WithParams::new(full, params).unwrap()).unwrap();
+
+        assert!(bin.len() * (glwe.dim.size.0 + 1) < full_bin.len() + 1024);
+
+        let params = (lwe, lwe, TEST_RADIX);
+        let ksk =
+            keygen::generate_seeded_ksk(&lwe_sk, &lwe_sk, &lwe, &lwe, &TEST_RADIX, key_seed());
+
+        let tagged = WithParams::new(ksk.clone(), params).unwrap();
+        let json = serde_json::to_string(&tagged).unwrap();
+        let actual = serde_json::from_str::<WithParams<SeededLweKeyswitchKey<u64>>>(&json)
+            .unwrap()
+            .into_entity(&params)
+            .unwrap();
+
+        assert_eq!(actual, ksk);
+    }
+
+    #[test]
+    fn rejects_mismatched_params() {
+        let lwe = TEST_LWE_DEF_1;
+        let mut other = lwe;
+        other.dim.0 += 1;
+
+        let sk = keygen::generate_binary_lwe_sk(&lwe);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &lwe, PlaintextBits(1));
+
+        // Tagging with the wrong parameters fails.
+        assert!(matches!(
+            WithParams::new(ct.clone(), other),
+            Err(Error::InvalidSize)
+        ));
+
+        // Loading under different parameters fails.
+        let tagged = WithParams::new(ct, lwe).unwrap();
+        let json = serde_json::to_string(&tagged).unwrap();
+
+        let res = serde_json::from_str::<WithParams<LweCiphertext<u64>>>(&json)
+            .unwrap()
+            .into_entity(&other);
+
+        assert!(matches!(res, Err(Error::ParameterMismatch)));
+
+        // Editing the embedded parameters so they don't match the entity fails.
+        let mut value = serde_json::to_value(&tagged).unwrap();
+        value["params"]["dim"] = serde_json::json!(other.dim.0);
+
+        assert!(serde_json::from_value::<WithParams<LweCiphertext<u64>>>(value).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_version_and_kind() {
+        let lwe = TEST_LWE_DEF_1;
+
+        let sk = keygen::generate_binary_lwe_sk(&lwe);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &lwe, PlaintextBits(1));
+
+        let tagged = WithParams::new(ct, lwe).unwrap();
+        let value = serde_json::to_value(&tagged).unwrap();
+
+        let mut wrong_version = value.clone();
+        wrong_version["version"] = serde_json::json!(SERIALIZATION_VERSION + 1);
+
+        let err = serde_json::from_value::<WithParams<LweCiphertext<u64>>>(wrong_version)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Unsupported serialization format version"));
+
+        // An LWE ciphertext isn't an LWE secret key, even though their layouts
+        // match.
+        let err = serde_json::from_value::<WithParams<LweSecretKey<u64>>>(value)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Expected a serialized LweSecretKey<u64>"));
+    }
+}
@@ -0,0 +1,1518 @@
+use logproof::{
+    linear_algebra::{Matrix, PolynomialMatrix},
+    Bounds, LogProofProverKnowledge, LogProofVerifierKnowledge as VerifierKnowledge,
+};
+use sunscreen_math::{poly::Polynomial, One, Zero};
+
+use crate::{
+    entities::{
+        self, BootstrapKey, GgswCiphertext, GgswCiphertextRef, GlweCiphertext, GlweCiphertextRef,
+        GlweSecretKey, LweCiphertextRef, LweKeyswitchKey, LweSecretKey, PolynomialRef,
+        RlwePublicKey,
+    },
+    ops::encryption::RlwePublicEncryptionRandomness,
+    polynomial::{polynomial_external_mad, polynomial_sub_assign},
+    rand::Stddev,
+    Error, GlweDef, LweDef, OverlaySize, PlaintextBits, RadixDecomposition, Result, Torus,
+    TorusOps,
+};
+
+use super::TorusZq;
+
+/// Proof statements about GLWE and GGSW ciphertexts and the evaluation keys built from them.
+///
+/// # Remarks
+/// Secret keys and messages are identified by index. Statements sharing a
+/// `glwe_secret_key_id`, `lwe_secret_key_id` or `message_id` prove they use the same
+/// key or message.
+///
+/// Every referenced secret key is additionally proven binary, as are the messages of
+/// [`GgswEncryption`](GlweProofStatement::GgswEncryption) statements.
+#[derive(Debug)]
+pub enum GlweProofStatement<'a, S: TorusOps + TorusZq> {
+    /// A GLWE secret key encryption of a message encoded with `plaintext_bits`.
+    SecretKeyEncryption {
+        /// The message ID being encrypted.
+        message_id: usize,
+
+        /// The GLWE secret key ID used to encrypt the message.
+        glwe_secret_key_id: usize,
+
+        /// The encrypted data.
+        ciphertext: &'a GlweCiphertext<S>,
+    },
+
+    /// An RLWE public key encryption of a message encoded with `plaintext_bits`.
+    PublicKeyEncryption {
+        /// The message ID being encrypted.
+        message_id: usize,
+
+        /// The encrypted data under the provided public key.
+        ciphertext: &'a GlweCiphertext<S>,
+
+        /// The public key being used to encrypt the message.
+        public_key: &'a RlwePublicKey<S>,
+    },
+
+    /// A GGSW encryption of a binary scalar message.
+    GgswEncryption {
+        /// The message ID being encrypted. The message must be a constant polynomial.
+        message_id: usize,
+
+        /// The GLWE secret key ID used to encrypt the message.
+        glwe_secret_key_id: usize,
+
+        /// The encrypted data.
+        ciphertext: &'a GgswCiphertext<S>,
+
+        /// The radix decomposition of the ciphertext.
+        radix: &'a RadixDecomposition,
+    },
+
+    /// A bootstrap key encrypting each bit of an LWE secret key as a GGSW ciphertext.
+    BootstrapKey {
+        /// The ID of the LWE secret key being encrypted.
+        lwe_secret_key_id: usize,
+
+        /// The GLWE secret key ID used to encrypt the LWE secret key.
+        glwe_secret_key_id: usize,
+
+        /// The bootstrap key.
+        bootstrap_key: &'a BootstrapKey<S>,
+
+        /// The parameters of the encrypted LWE secret key.
+        lwe: &'a LweDef,
+
+        /// The radix decomposition of the bootstrap key.
+        radix: &'a RadixDecomposition,
+    },
+
+    /// A keyswitch key from one LWE secret key to another.
+    ///
+    /// # Remarks
+    /// When the original key is a GLWE secret key reinterpreted as an LWE key (see
+    /// [`to_lwe_secret_key`](crate::entities::GlweSecretKeyRef::to_lwe_secret_key)), the
+    /// prover supplies it as a separate LWE secret key and sets `from_glwe_secret_key_id`. The
+    /// proof then shows the LWE key's coefficients are those of the GLWE key.
+    LweKeyswitchKey {
+        /// The ID of the LWE secret key the keyswitch key switches from.
+        from_lwe_secret_key_id: usize,
+
+        /// The ID of the GLWE secret key whose coefficients the original LWE secret key holds,
+        /// if any.
+        from_glwe_secret_key_id: Option<usize>,
+
+        /// The ID of the LWE secret key the keyswitch key switches to.
+        to_lwe_secret_key_id: usize,
+
+        /// The keyswitch key.
+        keyswitch_key: &'a LweKeyswitchKey<S>,
+
+        /// The parameters of the original LWE secret key.
+        from_lwe: &'a LweDef,
+
+        /// The parameters of the new LWE secret key.
+        to_lwe: &'a LweDef,
+
+        /// The radix decomposition of the keyswitch key.
+        radix: &'a RadixDecomposition,
+    },
+}
+
+/// Witness information for a list of [`GlweProofStatement`]s.
+///
+/// # Remarks
+/// The noise in secret key encryptions is recomputed from the secret keys and messages, so
+/// only public key encryptions need their randomness.
+#[derive(Debug)]
+pub struct GlweWitness<'a, S: TorusOps + TorusZq> {
+    /// The GLWE secret keys, indexed by `glwe_secret_key_id`.
+    pub glwe_secret_keys: &'a [&'a GlweSecretKey<S>],
+
+    /// The LWE secret keys, indexed by `lwe_secret_key_id`.
+    pub lwe_secret_keys: &'a [&'a LweSecretKey<S>],
+
+    /// The unencoded messages, indexed by `message_id`.
+    pub messages: &'a [entities::Polynomial<S>],
+
+    /// The randomness of each [`PublicKeyEncryption`](GlweProofStatement::PublicKeyEncryption)
+    /// statement, in the order the statements appear.
+    pub public_randomness: &'a [&'a RlwePublicEncryptionRandomness<S>],
+}
+
+/// Generate LogProofProverKnowledge for the SDLP proof system over GLWE statements.
+///
+/// # Errors
+/// Returns [`Error::InvalidStatement`] if the statements are invalid (see
+/// [`generate_glwe_sdlp_verifier_knowledge`]) or `witness` doesn't contain every key, message
+/// and public key randomness the statements refer to.
+pub fn generate_glwe_sdlp_prover_knowledge<S: TorusOps + TorusZq>(
+    statements: &[GlweProofStatement<S>],
+    witness: &GlweWitness<S>,
+    glwe: &GlweDef,
+    plaintext_bits: PlaintextBits,
+) -> Result<LogProofProverKnowledge<S::Zq>> {
+    let relation = Relation::build(statements, Some(witness), glwe, plaintext_bits)?;
+
+    let vk = relation.verifier_knowledge();
+
+    let mut s = PolynomialMatrix::new(relation.s.len(), 1);
+
+    for (i, x) in relation.s.into_iter().enumerate() {
+        s[(i, 0)] = x;
+    }
+
+    Ok(LogProofProverKnowledge { vk, s })
+}
+
+/// Computes the public information needed to prove and verify GLWE statements.
+///
+/// # Details
+/// Each statement contributes one or more rows to SDLP's `A S = T` relation, where
+/// `S` is a single column laid out as:
+/// * the GLWE secret keys' polynomials,
+/// * the LWE secret keys' coefficients as constant polynomials,
+/// * the messages,
+/// * the per-statement noise, randomness and products, then
+/// * the slack terms proving keys and GGSW messages are binary.
+///
+/// A GLWE encryption `(a_1, .. a_k, b)` of `m` under `s` contributes the row
+/// `a_1 s_1 + .. + a_k s_k + Δ m + e = b`. An RLWE public key encryption `(c_0, c_1)`
+/// under the public key `(p_0, p_1)` contributes the rows `p_0 u + e_0 = c_0` and
+/// `p_1 u + Δ m + e_1 = c_1`.
+///
+/// The GGSW rows encrypting `-m s_i` aren't linear in the secrets, so we introduce
+/// `w_i = m s_i` and prove it's the product of the binary values `m` and `s_i` by
+/// showing `w_i`, `s_i - w_i`, `m - w_i` and `w_i - s_i - m + 1` are all binary.
+/// We prove a combination `x` of bounded secrets is binary with the row `2 x - z = 1`,
+/// where `|z| <= 1`. Since `2 x` is even, `z` must be `±1`, so `x` is `0` or `1`
+/// modulo `q / 2`.
+///
+/// An LWE secret key extracted from a GLWE secret key `(s_1, .. s_k)` holds the coefficients of
+/// each `s_i` in turn, so for each `i` we add the row `sum_j x^j s'_{(i - 1) N + j} - s_i = 0`
+/// over the extracted key's coefficients `s'`.
+///
+/// ## Quotient ring modulus `f`
+/// When `statements` contains only [`LweKeyswitchKey`](GlweProofStatement::LweKeyswitchKey)
+/// statements that don't switch from a GLWE secret key, `f = X + 1`. Otherwise, `f = X^N + 1`
+/// where `N` is the GLWE polynomial degree.
+///
+/// # Errors
+/// Returns [`Error::InvalidStatement`] if
+/// * `glwe` or a statement's radix decomposition isn't valid,
+/// * `plaintext_bits` isn't in `[1, S::BITS)`,
+/// * an LWE secret key is used with different dimensions,
+/// * a public key encryption is under a GLWE size other than 1, or
+/// * a keyswitch key switches from a GLWE secret key with a dimension other than `k N`.
+///
+/// Returns [`Error::InvalidSize`] if a ciphertext or key is the wrong size for its parameters.
+pub fn generate_glwe_sdlp_verifier_knowledge<S: TorusOps + TorusZq>(
+    statements: &[GlweProofStatement<S>],
+    glwe: &GlweDef,
+    plaintext_bits: PlaintextBits,
+) -> Result<VerifierKnowledge<S::Zq>> {
+    Ok(Relation::build(statements, None, glwe, plaintext_bits)?.verifier_knowledge())
+}
+
+/// The rows of the SDLP witness `S` holding each secret key referenced by a list of
+/// [`GlweProofStatement`]s.
+///
+/// # Remarks
+/// `S` has a single column, so the secret key in row `i` is at index `(i, 0)`. Sharing these
+/// indices with another proof, e.g. with
+/// [`LogProof::create_with_shared`](logproof::LogProof::create_with_shared), links the keys
+/// committed to by both proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlweKeyIndices {
+    /// The rows of each GLWE secret key's polynomials, indexed by `glwe_secret_key_id`.
+    pub glwe_secret_keys: Vec<Vec<usize>>,
+
+    /// The rows of each LWE secret key's coefficients, indexed by `lwe_secret_key_id`. Keys no
+    /// statement refers to have no rows.
+    pub lwe_secret_keys: Vec<Vec<usize>>,
+}
+
+/// Computes where the secret keys referenced by `statements` lie in the SDLP witness of
+/// [`generate_glwe_sdlp_prover_knowledge`].
+///
+/// # Errors
+/// As [`generate_glwe_sdlp_verifier_knowledge`].
+pub fn glwe_sdlp_key_indices<S: TorusOps + TorusZq>(
+    statements: &[GlweProofStatement<S>],
+    glwe: &GlweDef,
+) -> Result<GlweKeyIndices> {
+    Ok(KeyLayout::new(statements, glwe)?.indices(glwe))
+}
+
+fn invalid_statement(msg: impl Into<String>) -> Error {
+    Error::InvalidStatement(msg.into())
+}
+
+fn check_radix<S: TorusOps>(radix: &RadixDecomposition) -> Result<()> {
+    if radix.count.0 == 0
+        || radix.radix_log.0 == 0
+        || radix.count.0 * radix.radix_log.0 > S::BITS as usize
+    {
+        return Err(invalid_statement(format!(
+            "Invalid radix decomposition {radix:?}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The number of bits bounding noise sampled with the given standard deviation. Noise exceeds
+/// 16 standard deviations with negligible probability.
+fn noise_bits<S: TorusOps>(std: Stddev) -> u32 {
+    let bits = (16.0 * std.0 * (S::BITS as f64).exp2()).log2().ceil();
+
+    (bits.max(1.0) as u32).min(S::BITS - 1)
+}
+
+/// The gadget factor `q / B^{j+1}` of the `j`th level in a radix decomposition.
+fn gadget_factor<S: TorusOps>(radix: &RadixDecomposition, j: usize) -> S {
+    S::from_u64(0x1 << (S::BITS as usize - radix.radix_log.0 * (j + 1)))
+}
+
+fn zq<S: TorusOps + TorusZq>(x: S) -> S::Zq {
+    S::Zq::from(x)
+}
+
+fn zq_signed<S: TorusOps + TorusZq>(x: i64) -> S::Zq {
+    S::Zq::from(S::from_u64(x as u64))
+}
+
+fn constant<S: TorusOps + TorusZq>(x: S::Zq) -> Polynomial<S::Zq> {
+    Polynomial::new(&[x])
+}
+
+fn zq_poly<S: TorusOps + TorusZq>(p: &PolynomialRef<S>) -> Polynomial<S::Zq> {
+    Polynomial::new(&p.coeffs().iter().map(|x| zq(*x)).collect::<Vec<_>>())
+}
+
+fn zq_torus_poly<S: TorusOps + TorusZq>(p: &PolynomialRef<Torus<S>>) -> Polynomial<S::Zq> {
+    Polynomial::new(&p.coeffs().iter().map(|x| zq(x.inner())).collect::<Vec<_>>())
+}
+
+fn signed<S: TorusOps>(x: S) -> i64 {
+    x.to_u64() as i64
+}
+
+/// Computes `b - a_1 s_1 - .. - a_k s_k - m`, the noise in a GLWE ciphertext.
+fn glwe_noise<S: TorusOps>(
+    ct: &GlweCiphertextRef<S>,
+    sk: &GlweSecretKey<S>,
+    encoded_msg: &PolynomialRef<Torus<S>>,
+    glwe: &GlweDef,
+) -> entities::Polynomial<Torus<S>> {
+    let (a, b) = ct.a_b(glwe);
+
+    let mut a_s = entities::Polynomial::zero(glwe.dim.polynomial_degree.0);
+
+    for (a_i, s_i) in a.zip(sk.s(glwe)) {
+        polynomial_external_mad(&mut a_s, a_i, s_i);
+    }
+
+    let mut e = b.to_owned();
+    polynomial_sub_assign(&mut e, &a_s);
+    polynomial_sub_assign(&mut e, encoded_msg);
+
+    e
+}
+
+/// A linear relation `A S = T` where `S` has a single column, built one row at a time.
+struct Relation<'w, S: TorusOps + TorusZq> {
+    witness: Option<&'w GlweWitness<'w, S>>,
+    degree: usize,
+    a: Vec<Vec<(usize, Polynomial<S::Zq>)>>,
+    t: Vec<Polynomial<S::Zq>>,
+    s: Vec<Polynomial<S::Zq>>,
+    bounds: Vec<Bounds>,
+    public_randomness: usize,
+}
+
+impl<'w, S: TorusOps + TorusZq> Relation<'w, S> {
+    fn build(
+        statements: &[GlweProofStatement<S>],
+        witness: Option<&'w GlweWitness<'w, S>>,
+        glwe: &GlweDef,
+        plaintext_bits: PlaintextBits,
+    ) -> Result<Self> {
+        if plaintext_bits.0 == 0 || plaintext_bits.0 >= S::BITS {
+            return Err(invalid_statement(format!(
+                "Plaintext bits must be in [1, {})",
+                S::BITS
+            )));
+        }
+
+        let keys = KeyLayout::new(statements, glwe)?;
+
+        if let Some(w) = witness {
+            keys.check_witness(w, glwe)?;
+        }
+
+        let only_lwe = statements.iter().all(|x| {
+            matches!(
+                x,
+                GlweProofStatement::LweKeyswitchKey {
+                    from_glwe_secret_key_id: None,
+                    ..
+                }
+            )
+        });
+
+        let mut relation = Self {
+            witness,
+            degree: if only_lwe {
+                1
+            } else {
+                glwe.dim.polynomial_degree.0
+            },
+            a: vec![],
+            t: vec![],
+            s: vec![],
+            bounds: vec![],
+            public_randomness: 0,
+        };
+
+        let glwe_keys = (0..keys.num_glwe_keys)
+            .map(|id| {
+                (0..glwe.dim.size.0)
+                    .map(|i| {
+                        relation.secret(relation.poly_bounds(1), |w| {
+                            zq_poly(w.glwe_secret_keys[id].s(glwe).nth(i).unwrap())
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let lwe_keys = keys
+            .lwe_dims
+            .iter()
+            .enumerate()
+            .map(|(id, dim)| {
+                (0..*dim)
+                    .map(|i| {
+                        relation.secret(relation.scalar_bounds(1), |w| {
+                            constant::<S>(zq(w.lwe_secret_keys[id].s()[i]))
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        debug_assert_eq!(
+            keys.indices(glwe),
+            GlweKeyIndices {
+                glwe_secret_keys: glwe_keys.clone(),
+                lwe_secret_keys: lwe_keys.clone(),
+            }
+        );
+
+        let messages = keys
+            .binary_messages
+            .iter()
+            .enumerate()
+            .map(|(id, is_binary)| {
+                let bounds = if *is_binary {
+                    relation.scalar_bounds(1)
+                } else {
+                    relation.poly_bounds(plaintext_bits.0)
+                };
+
+                relation.secret(bounds, |w| zq_poly(&w.messages[id]))
+            })
+            .collect::<Vec<_>>();
+
+        let delta = constant::<S>(zq(Torus::<S>::encode(S::one(), plaintext_bits).inner()));
+
+        for s in statements {
+            match s {
+                GlweProofStatement::SecretKeyEncryption {
+                    message_id,
+                    glwe_secret_key_id,
+                    ciphertext,
+                } => {
+                    ciphertext.check_is_valid(glwe.dim)?;
+
+                    relation.glwe_encryption(
+                        ciphertext,
+                        *glwe_secret_key_id,
+                        &glwe_keys[*glwe_secret_key_id],
+                        vec![(messages[*message_id], delta.clone())],
+                        |w| w.messages[*message_id].map(|x| Torus::encode(*x, plaintext_bits)),
+                        glwe,
+                    );
+                }
+                GlweProofStatement::PublicKeyEncryption {
+                    message_id,
+                    ciphertext,
+                    public_key,
+                } => {
+                    relation.public_key_encryption(
+                        ciphertext,
+                        public_key,
+                        messages[*message_id],
+                        delta.clone(),
+                        glwe,
+                    )?;
+                }
+                GlweProofStatement::GgswEncryption {
+                    message_id,
+                    glwe_secret_key_id,
+                    ciphertext,
+                    radix,
+                } => {
+                    check_radix::<S>(radix)?;
+                    ciphertext.check_is_valid((glwe.dim, radix.count))?;
+
+                    relation.ggsw_encryption(
+                        ciphertext,
+                        *glwe_secret_key_id,
+                        &glwe_keys[*glwe_secret_key_id],
+                        messages[*message_id],
+                        |w| w.messages[*message_id].coeffs()[0],
+                        glwe,
+                        radix,
+                    );
+                }
+                GlweProofStatement::BootstrapKey {
+                    lwe_secret_key_id,
+                    glwe_secret_key_id,
+                    bootstrap_key,
+                    lwe,
+                    radix,
+                } => {
+                    check_radix::<S>(radix)?;
+                    bootstrap_key.check_is_valid((lwe.dim, glwe.dim, radix.count))?;
+
+                    for (i, ggsw) in bootstrap_key.rows(glwe, radix).enumerate() {
+                        relation.ggsw_encryption(
+                            ggsw,
+                            *glwe_secret_key_id,
+                            &glwe_keys[*glwe_secret_key_id],
+                            lwe_keys[*lwe_secret_key_id][i],
+                            |w| w.lwe_secret_keys[*lwe_secret_key_id].s()[i],
+                            glwe,
+                            radix,
+                        );
+                    }
+                }
+                GlweProofStatement::LweKeyswitchKey {
+                    from_lwe_secret_key_id,
+                    to_lwe_secret_key_id,
+                    keyswitch_key,
+                    from_lwe,
+                    to_lwe,
+                    radix,
+                    ..
+                } => {
+                    let from_key = &lwe_keys[*from_lwe_secret_key_id];
+                    let to_key = &lwe_keys[*to_lwe_secret_key_id];
+
+                    check_radix::<S>(radix)?;
+                    keyswitch_key.check_is_valid((from_lwe.dim, to_lwe.dim, radix.count))?;
+
+                    for (i, lev) in keyswitch_key.rows(to_lwe, radix).enumerate() {
+                        for (j, ct) in lev.lwe_ciphertexts(to_lwe).enumerate() {
+                            let g = gadget_factor::<S>(radix, j);
+
+                            relation.lwe_encryption(
+                                ct,
+                                *to_lwe_secret_key_id,
+                                to_key,
+                                vec![(from_key[i], constant::<S>(zq(g)))],
+                                |w| {
+                                    Torus::from(g.wrapping_mul(
+                                        &w.lwe_secret_keys[*from_lwe_secret_key_id].s()[i],
+                                    ))
+                                },
+                                to_lwe,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        // Prove each extracted LWE key holds its GLWE key's coefficients.
+        for (glwe_id, lwe_id) in &keys.extracted_keys {
+            for (i, col) in glwe_keys[*glwe_id].iter().enumerate() {
+                let mut terms = (0..relation.degree)
+                    .map(|j| {
+                        let mut x_j = vec![S::Zq::zero(); j + 1];
+                        x_j[j] = S::Zq::one();
+
+                        (
+                            lwe_keys[*lwe_id][i * relation.degree + j],
+                            Polynomial::new(&x_j),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                terms.push((*col, -Polynomial::one()));
+
+                relation.row(terms, Polynomial::zero());
+            }
+        }
+
+        // Prove the keys and the GGSW messages are binary.
+        for (id, key) in glwe_keys.iter().enumerate() {
+            for (i, col) in key.iter().enumerate() {
+                relation.binary(
+                    vec![(*col, Polynomial::one())],
+                    Polynomial::zero(),
+                    false,
+                    |w| {
+                        let s = w.glwe_secret_keys[id].s(glwe).nth(i).unwrap();
+                        s.coeffs().iter().map(|x| signed(*x)).collect()
+                    },
+                );
+            }
+        }
+
+        for (id, key) in lwe_keys.iter().enumerate() {
+            for (i, col) in key.iter().enumerate() {
+                relation.binary(
+                    vec![(*col, Polynomial::one())],
+                    Polynomial::zero(),
+                    true,
+                    |w| vec![signed(w.lwe_secret_keys[id].s()[i])],
+                );
+            }
+        }
+
+        for (id, _) in keys.binary_messages.iter().enumerate().filter(|x| *x.1) {
+            relation.binary(
+                vec![(messages[id], Polynomial::one())],
+                Polynomial::zero(),
+                true,
+                |w| vec![signed(w.messages[id].coeffs()[0])],
+            );
+        }
+
+        Ok(relation)
+    }
+
+    fn poly_bounds(&self, bits: u32) -> Bounds {
+        Bounds(vec![bits; self.degree])
+    }
+
+    fn scalar_bounds(&self, bits: u32) -> Bounds {
+        let mut bounds = vec![0; self.degree];
+        bounds[0] = bits;
+
+        Bounds(bounds)
+    }
+
+    /// The polynomial whose coefficients are all one.
+    fn ones(&self) -> Polynomial<S::Zq> {
+        Polynomial::new(&vec![S::Zq::one(); self.degree])
+    }
+
+    /// Appends a secret to `S`, returning its index. We only compute its value when proving.
+    fn secret(
+        &mut self,
+        bounds: Bounds,
+        value: impl FnOnce(&GlweWitness<S>) -> Polynomial<S::Zq>,
+    ) -> usize {
+        if let Some(w) = self.witness {
+            self.s.push(value(w));
+        }
+
+        self.bounds.push(bounds);
+
+        self.bounds.len() - 1
+    }
+
+    fn row(&mut self, terms: Vec<(usize, Polynomial<S::Zq>)>, t: Polynomial<S::Zq>) {
+        self.a.push(terms);
+        self.t.push(t);
+    }
+
+    /// Proves the linear combination `terms + constant` of secrets is binary in every
+    /// coefficient or, when `scalar` is set, is a binary constant polynomial. `value` computes
+    /// the combination's coefficients when proving.
+    fn binary(
+        &mut self,
+        terms: Vec<(usize, Polynomial<S::Zq>)>,
+        constant: Polynomial<S::Zq>,
+        scalar: bool,
+        value: impl FnOnce(&GlweWitness<S>) -> Vec<i64>,
+    ) {
+        let (bounds, one) = if scalar {
+            (self.scalar_bounds(1), Polynomial::one())
+        } else {
+            (self.poly_bounds(1), self.ones())
+        };
+
+        let z = self.secret(bounds, |w| {
+            Polynomial::new(
+                &value(w)
+                    .into_iter()
+                    .map(|x| zq_signed::<S>(2 * x - 1))
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        let mut terms = terms
+            .into_iter()
+            .map(|(col, c)| (col, c.clone() + c))
+            .collect::<Vec<_>>();
+        terms.push((z, -Polynomial::one()));
+
+        let t = one - constant.clone() - constant;
+
+        self.row(terms, t);
+    }
+
+    /// Adds the row `a_1 s_1 + .. + a_k s_k + terms + e = b` for a GLWE ciphertext encrypting
+    /// `encoded_msg` under the given GLWE secret key.
+    fn glwe_encryption(
+        &mut self,
+        ct: &GlweCiphertextRef<S>,
+        key_id: usize,
+        key: &[usize],
+        mut terms: Vec<(usize, Polynomial<S::Zq>)>,
+        encoded_msg: impl FnOnce(&GlweWitness<S>) -> entities::Polynomial<Torus<S>>,
+        glwe: &GlweDef,
+    ) {
+        let bits = noise_bits::<S>(glwe.std);
+
+        let e = self.secret(self.poly_bounds(bits), |w| {
+            zq_torus_poly(&glwe_noise(
+                ct,
+                w.glwe_secret_keys[key_id],
+                &encoded_msg(w),
+                glwe,
+            ))
+        });
+
+        let (a, b) = ct.a_b(glwe);
+
+        terms.extend(
+            key.iter()
+                .zip(a)
+                .map(|(col, a_i)| (*col, zq_torus_poly(a_i))),
+        );
+        terms.push((e, Polynomial::one()));
+
+        self.row(terms, zq_torus_poly(b));
+    }
+
+    /// Adds the row `a_1 s_1 + .. + a_n s_n + terms + e = b` for an LWE ciphertext encrypting
+    /// `encoded_msg` under the given LWE secret key.
+    fn lwe_encryption(
+        &mut self,
+        ct: &LweCiphertextRef<S>,
+        key_id: usize,
+        key: &[usize],
+        mut terms: Vec<(usize, Polynomial<S::Zq>)>,
+        encoded_msg: impl FnOnce(&GlweWitness<S>) -> Torus<S>,
+        lwe: &LweDef,
+    ) {
+        let bits = noise_bits::<S>(lwe.std);
+
+        let (a, b) = ct.a_b(lwe);
+
+        let e = self.secret(self.scalar_bounds(bits), |w| {
+            let sk = w.lwe_secret_keys[key_id];
+
+            let a_s = a.iter().zip(sk.s()).fold(Torus::zero(), |acc, (a_i, s_i)| {
+                acc + Torus::from(a_i.inner().wrapping_mul(s_i))
+            });
+
+            constant::<S>(zq((*b - a_s - encoded_msg(w)).inner()))
+        });
+
+        terms.extend(
+            key.iter()
+                .zip(a)
+                .map(|(col, a_i)| (*col, constant::<S>(zq(a_i.inner())))),
+        );
+        terms.push((e, Polynomial::one()));
+
+        self.row(terms, constant::<S>(zq(b.inner())));
+    }
+
+    /// Adds the rows `p_0 u + e_0 = c_0` and `p_1 u + Δ m + e_1 = c_1`.
+    fn public_key_encryption(
+        &mut self,
+        ct: &GlweCiphertextRef<S>,
+        public_key: &RlwePublicKey<S>,
+        message: usize,
+        delta: Polynomial<S::Zq>,
+        glwe: &GlweDef,
+    ) -> Result<()> {
+        if glwe.dim.size.0 != 1 {
+            return Err(invalid_statement(
+                "Public key encryptions require a GLWE size of 1",
+            ));
+        }
+
+        ct.check_is_valid(glwe.dim)?;
+        public_key.check_is_valid(glwe.dim)?;
+
+        let idx = self.public_randomness;
+        self.public_randomness += 1;
+
+        let bits = noise_bits::<S>(glwe.std);
+
+        let u = self.secret(self.poly_bounds(1), |w| {
+            zq_poly(&w.public_randomness[idx].u)
+        });
+        let e0 = self.secret(self.poly_bounds(bits), |w| {
+            zq_torus_poly(&w.public_randomness[idx].e0)
+        });
+        let e1 = self.secret(self.poly_bounds(bits), |w| {
+            zq_torus_poly(&w.public_randomness[idx].e1)
+        });
+
+        let (p0, p1) = public_key.p0_p1(glwe);
+        let (mut c0, c1) = ct.a_b(glwe);
+        let c0 = c0.next().unwrap();
+
+        self.row(
+            vec![(u, zq_torus_poly(p0)), (e0, Polynomial::one())],
+            zq_torus_poly(c0),
+        );
+        self.row(
+            vec![
+                (u, zq_torus_poly(p1)),
+                (message, delta),
+                (e1, Polynomial::one()),
+            ],
+            zq_torus_poly(c1),
+        );
+
+        Ok(())
+    }
+
+    /// Adds the rows of a GGSW ciphertext encrypting the binary scalar at `message`.
+    #[allow(clippy::too_many_arguments)]
+    fn ggsw_encryption(
+        &mut self,
+        ct: &GgswCiphertextRef<S>,
+        key_id: usize,
+        key: &[usize],
+        message: usize,
+        message_value: impl Fn(&GlweWitness<S>) -> S,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        let degree = glwe.dim.polynomial_degree.0;
+
+        // w_i = m s_i
+        let products = (0..key.len())
+            .map(|i| {
+                self.secret(self.poly_bounds(1), |w| {
+                    let m = message_value(w);
+                    let s = w.glwe_secret_keys[key_id].s(glwe).nth(i).unwrap();
+
+                    zq_poly(&s.map(|x| x.wrapping_mul(&m)))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (i, glev) in ct.rows(glwe, radix).enumerate() {
+            for (j, glwe_ct) in glev.glwe_ciphertexts(glwe).enumerate() {
+                let g = gadget_factor::<S>(radix, j);
+
+                if i < key.len() {
+                    // Encrypts -g_j m s_i = -g_j w_i.
+                    self.glwe_encryption(
+                        glwe_ct,
+                        key_id,
+                        key,
+                        vec![(products[i], constant::<S>(-zq(g)))],
+                        |w| {
+                            let m = message_value(w);
+                            let s = w.glwe_secret_keys[key_id].s(glwe).nth(i).unwrap();
+
+                            s.map(|x| {
+                                Torus::from(x.wrapping_mul(&m).wrapping_mul(&g).wrapping_neg())
+                            })
+                        },
+                        glwe,
+                    );
+                } else {
+                    // Encrypts g_j m.
+                    self.glwe_encryption(
+                        glwe_ct,
+                        key_id,
+                        key,
+                        vec![(message, constant::<S>(zq(g)))],
+                        |w| {
+                            let mut msg = entities::Polynomial::zero(degree);
+                            msg.coeffs_mut()[0] = Torus::from(message_value(w).wrapping_mul(&g));
+                            msg
+                        },
+                        glwe,
+                    );
+                }
+            }
+        }
+
+        // Prove w_i = m s_i for binary m and s_i by showing
+        // w_i, s_i - w_i, m - w_i and w_i - s_i - m + 1 are binary.
+        let ones = self.ones();
+
+        for (i, (w_i, s_i)) in products.iter().zip(key).enumerate() {
+            let values = |w: &GlweWitness<S>| {
+                let m = signed(message_value(w));
+                let s = w.glwe_secret_keys[key_id].s(glwe).nth(i).unwrap();
+
+                s.coeffs()
+                    .iter()
+                    .map(|x| (m, signed(*x), m * signed(*x)))
+                    .collect::<Vec<_>>()
+            };
+
+            let one = Polynomial::one();
+            let minus_one = -Polynomial::one();
+
+            self.binary(vec![(*w_i, one.clone())], Polynomial::zero(), false, |w| {
+                values(w).into_iter().map(|(_, _, w)| w).collect()
+            });
+            self.binary(
+                vec![(*s_i, one.clone()), (*w_i, minus_one.clone())],
+                Polynomial::zero(),
+                false,
+                |w| values(w).into_iter().map(|(_, s, w)| s - w).collect(),
+            );
+            self.binary(
+                vec![(message, ones.clone()), (*w_i, minus_one.clone())],
+                Polynomial::zero(),
+                false,
+                |w| values(w).into_iter().map(|(m, _, w)| m - w).collect(),
+            );
+            self.binary(
+                vec![(*w_i, one), (*s_i, minus_one), (message, -ones.clone())],
+                ones.clone(),
+                false,
+                |w| {
+                    values(w)
+                        .into_iter()
+                        .map(|(m, s, w)| w - s - m + 1)
+                        .collect()
+                },
+            );
+        }
+    }
+
+    fn verifier_knowledge(&self) -> VerifierKnowledge<S::Zq> {
+        let rows = self.a.len();
+        let cols = self.bounds.len();
+
+        let mut a = Matrix::<Polynomial<S::Zq>>::new(rows, cols);
+        let mut t = PolynomialMatrix::new(rows, 1);
+        let mut bounds = Matrix::<Bounds>::new(cols, 1);
+
+        for (i, row) in self.a.iter().enumerate() {
+            for (j, x) in row {
+                a[(i, *j)] = a[(i, *j)].clone() + x;
+            }
+
+            t[(i, 0)] = self.t[i].clone();
+        }
+
+        for (i, b) in self.bounds.iter().enumerate() {
+            bounds[(i, 0)] = b.clone();
+        }
+
+        let mut f = vec![S::Zq::zero(); self.degree + 1];
+        f[0] = S::Zq::one();
+        f[self.degree] = S::Zq::one();
+
+        VerifierKnowledge::new(a, t, Polynomial::new(&f), bounds)
+    }
+}
+
+/// The secret keys and messages referenced by a list of statements.
+struct KeyLayout {
+    num_glwe_keys: usize,
+    lwe_dims: Vec<usize>,
+    binary_messages: Vec<bool>,
+    num_public_key_encryptions: usize,
+
+    /// The `(glwe_secret_key_id, lwe_secret_key_id)` pairs where the LWE key is extracted from
+    /// the GLWE key.
+    extracted_keys: Vec<(usize, usize)>,
+}
+
+impl KeyLayout {
+    fn new<S: TorusOps + TorusZq>(
+        statements: &[GlweProofStatement<S>],
+        glwe: &GlweDef,
+    ) -> Result<Self> {
+        let dim = glwe.dim;
+
+        if !dim.polynomial_degree.0.is_power_of_two() || dim.size.0 == 0 {
+            return Err(invalid_statement(format!(
+                "Invalid GLWE parameters {dim:?}"
+            )));
+        }
+
+        let mut num_glwe_keys = 0;
+        let mut lwe_dims: Vec<Option<usize>> = vec![];
+        let mut binary_messages: Vec<Option<bool>> = vec![];
+        let mut num_public_key_encryptions = 0;
+        let mut extracted_keys = vec![];
+
+        let mut use_message = |id: usize, binary: bool| {
+            if binary_messages.len() <= id {
+                binary_messages.resize(id + 1, None);
+            }
+
+            binary_messages[id] = Some(binary_messages[id].unwrap_or(false) || binary);
+        };
+
+        let mut use_lwe_key = |id: usize, lwe: &LweDef| {
+            if lwe_dims.len() <= id {
+                lwe_dims.resize(id + 1, None);
+            }
+
+            let dim = lwe_dims[id].get_or_insert(lwe.dim.0);
+
+            if *dim != lwe.dim.0 {
+                return Err(invalid_statement(format!(
+                    "LWE secret key {id} used with different dimensions"
+                )));
+            }
+
+            Ok(())
+        };
+
+        for s in statements {
+            match s {
+                GlweProofStatement::SecretKeyEncryption {
+                    message_id,
+                    glwe_secret_key_id,
+                    ..
+                } => {
+                    use_message(*message_id, false);
+                    num_glwe_keys = usize::max(num_glwe_keys, glwe_secret_key_id + 1);
+                }
+                GlweProofStatement::PublicKeyEncryption { message_id, .. } => {
+                    use_message(*message_id, false);
+                    num_public_key_encryptions += 1;
+                }
+                GlweProofStatement::GgswEncryption {
+                    message_id,
+                    glwe_secret_key_id,
+                    ..
+                } => {
+                    use_message(*message_id, true);
+                    num_glwe_keys = usize::max(num_glwe_keys, glwe_secret_key_id + 1);
+                }
+                GlweProofStatement::BootstrapKey {
+                    lwe_secret_key_id,
+                    glwe_secret_key_id,
+                    lwe,
+                    ..
+                } => {
+                    use_lwe_key(*lwe_secret_key_id, lwe)?;
+                    num_glwe_keys = usize::max(num_glwe_keys, glwe_secret_key_id + 1);
+                }
+                GlweProofStatement::LweKeyswitchKey {
+                    from_lwe_secret_key_id,
+                    from_glwe_secret_key_id,
+                    to_lwe_secret_key_id,
+                    from_lwe,
+                    to_lwe,
+                    ..
+                } => {
+                    use_lwe_key(*from_lwe_secret_key_id, from_lwe)?;
+                    use_lwe_key(*to_lwe_secret_key_id, to_lwe)?;
+
+                    if let Some(glwe_id) = from_glwe_secret_key_id {
+                        if from_lwe.dim != dim.as_lwe_dimension() {
+                            return Err(invalid_statement(format!(
+                                "LWE secret key {from_lwe_secret_key_id} can't be extracted from \
+                                 GLWE secret key {glwe_id}; expected dimension {}",
+                                dim.as_lwe_dimension().0
+                            )));
+                        }
+
+                        num_glwe_keys = usize::max(num_glwe_keys, glwe_id + 1);
+
+                        let pair = (*glwe_id, *from_lwe_secret_key_id);
+
+                        if !extracted_keys.contains(&pair) {
+                            extracted_keys.push(pair);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            num_glwe_keys,
+            // Unreferenced IDs still get (empty) slots so the layout is the same for every
+            // set of statements with the same IDs.
+            lwe_dims: lwe_dims.into_iter().map(|x| x.unwrap_or(0)).collect(),
+            binary_messages: binary_messages
+                .into_iter()
+                .map(|x| x.unwrap_or(false))
+                .collect(),
+            num_public_key_encryptions,
+            extracted_keys,
+        })
+    }
+
+    /// The rows of `S` holding each key. Keys come first in [`Relation::build`], so these don't
+    /// depend on the messages or randomness.
+    fn indices(&self, glwe: &GlweDef) -> GlweKeyIndices {
+        let k = glwe.dim.size.0;
+
+        let glwe_secret_keys = (0..self.num_glwe_keys)
+            .map(|id| (id * k..(id + 1) * k).collect())
+            .collect();
+
+        let mut next = self.num_glwe_keys * k;
+
+        let lwe_secret_keys = self
+            .lwe_dims
+            .iter()
+            .map(|dim| {
+                let rows = (next..next + dim).collect();
+                next += dim;
+                rows
+            })
+            .collect();
+
+        GlweKeyIndices {
+            glwe_secret_keys,
+            lwe_secret_keys,
+        }
+    }
+
+    fn check_witness<S: TorusOps + TorusZq>(
+        &self,
+        witness: &GlweWitness<S>,
+        glwe: &GlweDef,
+    ) -> Result<()> {
+        if witness.glwe_secret_keys.len() < self.num_glwe_keys {
+            return Err(invalid_statement(format!(
+                "Expected {} GLWE secret keys",
+                self.num_glwe_keys
+            )));
+        }
+
+        for (id, key) in witness.glwe_secret_keys[..self.num_glwe_keys]
+            .iter()
+            .enumerate()
+        {
+            if key.check_is_valid(glwe.dim).is_err() {
+                return Err(invalid_statement(format!(
+                    "GLWE secret key {id} has the wrong size"
+                )));
+            }
+        }
+
+        if witness.lwe_secret_keys.len() < self.lwe_dims.len() {
+            return Err(invalid_statement(format!(
+                "Expected {} LWE secret keys",
+                self.lwe_dims.len()
+            )));
+        }
+
+        for (id, (key, dim)) in witness
+            .lwe_secret_keys
+            .iter()
+            .zip(&self.lwe_dims)
+            .enumerate()
+        {
+            if *dim > 0 && key.s().len() != *dim {
+                return Err(invalid_statement(format!(
+                    "LWE secret key {id} has the wrong dimension"
+                )));
+            }
+        }
+
+        if witness.messages.len() < self.binary_messages.len() {
+            return Err(invalid_statement(format!(
+                "Expected {} messages",
+                self.binary_messages.len()
+            )));
+        }
+
+        for (id, m) in witness.messages[..self.binary_messages.len()]
+            .iter()
+            .enumerate()
+        {
+            if m.coeffs().len() != glwe.dim.polynomial_degree.0 {
+                return Err(invalid_statement(format!(
+                    "Message {id} has the wrong degree"
+                )));
+            }
+        }
+
+        if witness.public_randomness.len() != self.num_public_key_encryptions {
+            return Err(invalid_statement(
+                "Expected randomness for each public key encryption",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use logproof::{InnerProductVerifierKnowledge, LogProof, LogProofGenerators};
+    use merlin::Transcript;
+
+    use crate::{
+        entities::GlweCiphertext, high_level::*, ops::encryption::rlwe_encode_encrypt_public,
+        GlweDimension, GlweSize, LweDimension, PolynomialDegree, RadixCount, RadixLog,
+        GLWE_1_1024_128, LWE_512_80,
+    };
+
+    use super::*;
+
+    const GLWE: GlweDef = GlweDef {
+        dim: GlweDimension {
+            size: GlweSize(1),
+            polynomial_degree: PolynomialDegree(8),
+        },
+        ..GLWE_1_1024_128
+    };
+
+    const LWE: LweDef = LweDef {
+        dim: LweDimension(4),
+        ..LWE_512_80
+    };
+
+    const RADIX: RadixDecomposition = RadixDecomposition {
+        count: RadixCount(2),
+        radix_log: RadixLog(8),
+    };
+
+    fn prove<S: TorusOps + TorusZq>(pk: &LogProofProverKnowledge<S::Zq>) -> LogProof {
+        let gen = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+        let mut p_t = Transcript::new(b"test");
+
+        LogProof::create(&mut p_t, pk, &gen.g, &gen.h, &u)
+    }
+
+    fn verify<S: TorusOps + TorusZq>(
+        proof: &LogProof,
+        vk: &VerifierKnowledge<S::Zq>,
+    ) -> std::result::Result<(), logproof::ProofError> {
+        let gen = LogProofGenerators::new(vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+        let mut v_t = Transcript::new(b"test");
+
+        proof.verify(&mut v_t, vk, &gen.g, &gen.h, &u)
+    }
+
+    #[test]
+    fn secret_and_public_key_encryptions() {
+        let bits = PlaintextBits(2);
+
+        let sk = keygen::generate_binary_glwe_sk(&GLWE);
+        let public_key = keygen::generate_rlwe_public_key(&sk, &GLWE);
+
+        let msg = entities::Polynomial::new(&[0, 1, 2, 3, 3, 2, 1, 0]);
+
+        let ct_secret = sk.encode_encrypt_glwe(&msg, &GLWE, bits);
+
+        let mut ct_public = GlweCiphertext::new(&GLWE);
+        let randomness =
+            rlwe_encode_encrypt_public(&mut ct_public, &msg, &public_key, &bits, &GLWE);
+
+        let statements = [
+            GlweProofStatement::SecretKeyEncryption {
+                message_id: 0,
+                glwe_secret_key_id: 0,
+                ciphertext: &ct_secret,
+            },
+            GlweProofStatement::PublicKeyEncryption {
+                message_id: 0,
+                ciphertext: &ct_public,
+                public_key: &public_key,
+            },
+        ];
+
+        let pk = generate_glwe_sdlp_prover_knowledge(
+            &statements,
+            &GlweWitness {
+                glwe_secret_keys: &[&sk],
+                lwe_secret_keys: &[],
+                messages: &[msg],
+                public_randomness: &[&randomness],
+            },
+            &GLWE,
+            bits,
+        )
+        .unwrap();
+
+        let proof = prove::<u64>(&pk);
+        verify::<u64>(&proof, &pk.vk).unwrap();
+
+        // The verifier derives the same knowledge without the witness.
+        let vk = generate_glwe_sdlp_verifier_knowledge(&statements, &GLWE, bits).unwrap();
+        verify::<u64>(&proof, &vk).unwrap();
+    }
+
+    #[test]
+    fn ggsw_encryption() {
+        let sk = keygen::generate_binary_glwe_sk(&GLWE);
+
+        for m in [0, 1] {
+            let ct = encryption::encrypt_ggsw(m, &sk, &GLWE, &RADIX, PlaintextBits(1));
+
+            let mut msg = entities::Polynomial::zero(GLWE.dim.polynomial_degree.0);
+            msg.coeffs_mut()[0] = m;
+
+            let pk = generate_glwe_sdlp_prover_knowledge(
+                &[GlweProofStatement::GgswEncryption {
+                    message_id: 0,
+                    glwe_secret_key_id: 0,
+                    ciphertext: &ct,
+                    radix: &RADIX,
+                }],
+                &GlweWitness {
+                    glwe_secret_keys: &[&sk],
+                    lwe_secret_keys: &[],
+                    messages: &[msg],
+                    public_randomness: &[],
+                },
+                &GLWE,
+                PlaintextBits(1),
+            )
+            .unwrap();
+
+            let proof = prove::<u64>(&pk);
+            verify::<u64>(&proof, &pk.vk).unwrap();
+        }
+    }
+
+    #[test]
+    fn keyswitch_key() {
+        let from_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let to_sk = keygen::generate_binary_lwe_sk(&LWE);
+
+        let ksk = keygen::generate_ksk(&from_sk, &to_sk, &LWE, &LWE, &RADIX);
+
+        let pk = generate_glwe_sdlp_prover_knowledge(
+            &[GlweProofStatement::LweKeyswitchKey {
+                from_lwe_secret_key_id: 0,
+                from_glwe_secret_key_id: None,
+                to_lwe_secret_key_id: 1,
+                keyswitch_key: &ksk,
+                from_lwe: &LWE,
+                to_lwe: &LWE,
+                radix: &RADIX,
+            }],
+            &GlweWitness {
+                glwe_secret_keys: &[],
+                lwe_secret_keys: &[&from_sk, &to_sk],
+                messages: &[],
+                public_randomness: &[],
+            },
+            &GLWE,
+            PlaintextBits(1),
+        )
+        .unwrap();
+
+        // Keyswitch keys alone only need the scalar ring.
+        assert_eq!(pk.vk.f.coeffs.len(), 2);
+
+        let proof = prove::<u64>(&pk);
+        verify::<u64>(&proof, &pk.vk).unwrap();
+    }
+
+    #[test]
+    fn bootstrap_and_keyswitch_keys() {
+        let lwe_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&GLWE);
+        let extracted_sk = glwe_sk.to_lwe_secret_key().to_owned();
+        let extracted_lwe = GLWE.as_lwe_def();
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &LWE, &GLWE, &RADIX);
+        let ksk = keygen::generate_ksk(&extracted_sk, &lwe_sk, &extracted_lwe, &LWE, &RADIX);
+
+        let bootstrap_key_statement = |bootstrap_key| GlweProofStatement::BootstrapKey {
+            lwe_secret_key_id: 0,
+            glwe_secret_key_id: 0,
+            bootstrap_key,
+            lwe: &LWE,
+            radix: &RADIX,
+        };
+        let keyswitch_key_statement = || GlweProofStatement::LweKeyswitchKey {
+            from_lwe_secret_key_id: 1,
+            from_glwe_secret_key_id: Some(0),
+            to_lwe_secret_key_id: 0,
+            keyswitch_key: &ksk,
+            from_lwe: &extracted_lwe,
+            to_lwe: &LWE,
+            radix: &RADIX,
+        };
+
+        let pk = generate_glwe_sdlp_prover_knowledge(
+            &[bootstrap_key_statement(&bsk), keyswitch_key_statement()],
+            &GlweWitness {
+                glwe_secret_keys: &[&glwe_sk],
+                lwe_secret_keys: &[&lwe_sk, &extracted_sk],
+                messages: &[],
+                public_randomness: &[],
+            },
+            &GLWE,
+            PlaintextBits(1),
+        )
+        .unwrap();
+
+        let proof = prove::<u64>(&pk);
+        verify::<u64>(&proof, &pk.vk).unwrap();
+
+        // The proof doesn't verify for a bootstrap key encrypting a different LWE key.
+        let other_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let other_bsk =
+            keygen::generate_bootstrapping_key(&other_sk, &glwe_sk, &LWE, &GLWE, &RADIX);
+
+        let vk = generate_glwe_sdlp_verifier_knowledge(
+            &[
+                bootstrap_key_statement(&other_bsk),
+                keyswitch_key_statement(),
+            ],
+            &GLWE,
+            PlaintextBits(1),
+        )
+        .unwrap();
+        assert!(verify::<u64>(&proof, &vk).is_err());
+    }
+
+    #[test]
+    fn keyswitch_key_from_glwe_key_must_be_extracted() {
+        let lwe_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&GLWE);
+        let extracted_lwe = GLWE.as_lwe_def();
+
+        let is_satisfied = |from_sk: &LweSecretKey<u64>| {
+            let ksk = keygen::generate_ksk(from_sk, &lwe_sk, &extracted_lwe, &LWE, &RADIX);
+
+            let pk = generate_glwe_sdlp_prover_knowledge(
+                &[GlweProofStatement::LweKeyswitchKey {
+                    from_lwe_secret_key_id: 1,
+                    from_glwe_secret_key_id: Some(0),
+                    to_lwe_secret_key_id: 0,
+                    keyswitch_key: &ksk,
+                    from_lwe: &extracted_lwe,
+                    to_lwe: &LWE,
+                    radix: &RADIX,
+                }],
+                &GlweWitness {
+                    glwe_secret_keys: &[&glwe_sk],
+                    lwe_secret_keys: &[&lwe_sk, from_sk],
+                    messages: &[],
+                    public_randomness: &[],
+                },
+                &GLWE,
+                PlaintextBits(1),
+            )
+            .unwrap();
+
+            let t = (&pk.vk.a * &pk.s).map(|x| x.vartime_div_rem_restricted_rhs(&pk.vk.f).1);
+
+            t == pk.vk.t
+        };
+
+        assert!(is_satisfied(&glwe_sk.to_lwe_secret_key().to_owned()));
+        assert!(!is_satisfied(&keygen::generate_binary_lwe_sk(
+            &extracted_lwe
+        )));
+    }
+
+    #[test]
+    fn key_indices_match_witness() {
+        let lwe_sk = keygen::generate_binary_lwe_sk(&LWE);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&GLWE);
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &LWE, &GLWE, &RADIX);
+
+        let statements = [GlweProofStatement::BootstrapKey {
+            lwe_secret_key_id: 0,
+            glwe_secret_key_id: 0,
+            bootstrap_key: &bsk,
+            lwe: &LWE,
+            radix: &RADIX,
+        }];
+
+        let indices = glwe_sdlp_key_indices(&statements, &GLWE).unwrap();
+
+        assert_eq!(indices.glwe_secret_keys, vec![vec![0]]);
+        assert_eq!(indices.lwe_secret_keys, vec![vec![1, 2, 3, 4]]);
+
+        let pk = generate_glwe_sdlp_prover_knowledge(
+            &statements,
+            &GlweWitness {
+                glwe_secret_keys: &[&glwe_sk],
+                lwe_secret_keys: &[&lwe_sk],
+                messages: &[],
+                public_randomness: &[],
+            },
+            &GLWE,
+            PlaintextBits(1),
+        )
+        .unwrap();
+
+        for (row, s) in indices.lwe_secret_keys[0].iter().zip(lwe_sk.s()) {
+            assert_eq!(pk.s[(*row, 0)], constant::<u64>(zq(*s)));
+        }
+    }
+
+    #[test]
+    fn invalid_statements_return_errors() {
+        let glwe = GlweDef {
+            dim: GlweDimension {
+                size: GlweSize(2),
+                ..GLWE.dim
+            },
+            ..GLWE
+        };
+
+        let sk = keygen::generate_binary_glwe_sk(&glwe);
+        let public_key = keygen::generate_rlwe_public_key(&sk, &GLWE);
+        let ct = GlweCiphertext::new(&glwe);
+
+        // Public key encryptions are only supported for a GLWE size of 1.
+        let result = generate_glwe_sdlp_verifier_knowledge(
+            &[GlweProofStatement::PublicKeyEncryption {
+                message_id: 0,
+                ciphertext: &ct,
+                public_key: &public_key,
+            }],
+            &glwe,
+            PlaintextBits(2),
+        );
+        assert!(matches!(result, Err(Error::InvalidStatement(_))));
+
+        let statements = [GlweProofStatement::SecretKeyEncryption {
+            message_id: 0,
+            glwe_secret_key_id: 0,
+            ciphertext: &ct,
+        }];
+
+        for bits in [0, 64] {
+            let result =
+                generate_glwe_sdlp_verifier_knowledge(&statements, &glwe, PlaintextBits(bits));
+            assert!(matches!(result, Err(Error::InvalidStatement(_))));
+        }
+
+        // The witness is missing the message.
+        let result = generate_glwe_sdlp_prover_knowledge(
+            &statements,
+            &GlweWitness {
+                glwe_secret_keys: &[&sk],
+                lwe_secret_keys: &[],
+                messages: &[],
+                public_randomness: &[],
+            },
+            &glwe,
+            PlaintextBits(2),
+        );
+        assert!(matches!(result, Err(Error::InvalidStatement(_))));
+    }
+}
@@ -17,6 +17,9 @@ use crate::{
     LweDef, PlaintextBits,
 };
 
+mod glwe;
+pub use glwe::*;
+
 /// Proof statements for the SDLP proof system when applied to TFHE.
 #[derive(Debug)]
 pub enum ProofStatement<'a, 'b, S: TorusOps + TorusZq> {
@@ -222,7 +225,7 @@ fn compute_bounds<S: TorusOps + TorusZq>(
     for i in 0..num_messages {
         let mut b = Bounds(vec![0; num_coeffs]);
         b.0[0] = plaintext_bits.0;
-        debug_assert_eq!(bounds[(i, 0)].0, &[]);
+        debug_assert_eq!(bounds[(i, 0)].0, &[] as &[u32]);
         bounds[(i, 0)] = b;
     }
 
@@ -235,7 +238,7 @@ fn compute_bounds<S: TorusOps + TorusZq>(
             b.0[0] = plaintext_bits.0;
             debug_assert_eq!(
                 bounds[(offsets.public_keys + i * lwe_dimension + j, 0)].0,
-                &[]
+                &[] as &[u32]
             );
             bounds[(offsets.public_keys + i * lwe_dimension + j, 0)] = b;
         }
@@ -244,7 +247,7 @@ fn compute_bounds<S: TorusOps + TorusZq>(
         // TODO: This bound is too high. Get a tighter bound.
         let b = Bounds(vec![60 - plaintext_bits.0; num_coeffs]);
 
-        debug_assert_eq!(bounds[(offsets.public_e + i, 0)].0, &[]);
+        debug_assert_eq!(bounds[(offsets.public_e + i, 0)].0, &[] as &[u32]);
         bounds[(offsets.public_e + i, 0)] = b;
     }
 
@@ -257,7 +260,7 @@ fn compute_bounds<S: TorusOps + TorusZq>(
             b.0[0] = plaintext_bits.0;
             debug_assert_eq!(
                 bounds[(offsets.private_a + j + i * lwe_dimension, 0)].0,
-                &[]
+                &[] as &[u32]
             );
             bounds[(offsets.private_a + j + i * lwe_dimension, 0)] = b;
         }
@@ -267,7 +270,7 @@ fn compute_bounds<S: TorusOps + TorusZq>(
         // e is normal distributed over the torus.
         // TODO: This bound is too high. Get a tighter bound.
         b.0[0] = 62 - plaintext_bits.0;
-        debug_assert_eq!(bounds[(offsets.private_e + i, 0)].0, &[]);
+        debug_assert_eq!(bounds[(offsets.private_e + i, 0)].0, &[] as &[u32]);
         bounds[(offsets.private_e + i, 0)] = b;
     }
 
@@ -393,9 +396,9 @@ fn compute_a<S: TorusZq + TorusOps>(
                 a[(i, private_key_idx)] = Polynomial::new(&[S::Zq::from(a_j.inner())]);
             }
 
-            debug_assert_eq!(a[(i, offsets.private_e)], Polynomial::zero());
+            debug_assert_eq!(a[(i, offsets.private_e + cur_private)], Polynomial::zero());
 
-            a[(i, offsets.private_e + i)] = Polynomial::one();
+            a[(i, offsets.private_e + cur_private)] = Polynomial::one();
             cur_private += 1;
         }
     }
@@ -891,6 +894,52 @@ mod tests {
         prove_and_verify::<u64>(&pk);
     }
 
+    #[test]
+    fn one_public_then_one_private() {
+        let params = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let bits = PlaintextBits(1);
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let pk = keygen::generate_lwe_pk(&sk, &params);
+
+        let (ct_pub, rng_pub) =
+            encryption::encrypt_lwe_and_return_randomness(1, &pk, &params, bits);
+        let (ct_priv, rng_priv) =
+            encryption::encrypt_lwe_secret_and_return_randomness(1, &sk, &params, bits);
+
+        // Private statements following public ones must still use their own noise columns.
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &[
+                ProofStatement::PublicKeyEncryption {
+                    message_id: 0,
+                    public_key: &pk,
+                    ciphertext: &ct_pub,
+                },
+                ProofStatement::PrivateKeyEncryption {
+                    message_id: 0,
+                    ciphertext: &ct_priv,
+                },
+            ],
+            &[Torus::from(1)],
+            &[
+                Witness::PublicKeyEncryption {
+                    randomness: &rng_pub,
+                },
+                Witness::PrivateKeyEncryption {
+                    randomness: rng_priv,
+                    private_key: &sk,
+                },
+            ],
+            &params,
+            bits,
+        );
+
+        prove_and_verify::<u64>(&pk);
+    }
+
     #[ignore]
     #[test]
     fn complex_examples() {
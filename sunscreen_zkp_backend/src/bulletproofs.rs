@@ -4,11 +4,14 @@ use std::{
 };
 
 use bulletproofs::{
-    r1cs::{ConstraintSystem, LinearCombination, Metrics, Prover, R1CSError, R1CSProof, Verifier},
+    r1cs::{
+        ConstraintSystem, LinearCombination, Metrics, Prover, R1CSError, R1CSProof, Variable,
+        Verifier,
+    },
     BulletproofGens, PedersenGens,
 };
 use crypto_bigint::{Limb, Uint};
-use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
 use log::trace;
 use merlin::Transcript;
 use petgraph::stable_graph::NodeIndex;
@@ -17,8 +20,8 @@ use serde::{Deserialize, Serialize};
 use sunscreen_compiler_common::{forward_traverse, GraphQuery};
 
 use crate::{
-    exec::Operation, jit::jit_verifier, jit_prover, BigInt, CompiledZkpProgram, Error,
-    ExecutableZkpProgram, FieldSpec, Proof, Result, ZkpBackend,
+    exec::Operation, jit::jit_verifier, jit_prover, BigInt, Commitment, CommitmentOpening,
+    CompiledZkpProgram, Error, ExecutableZkpProgram, FieldSpec, Proof, Result, ZkpBackend,
 };
 
 #[derive(Clone)]
@@ -110,6 +113,13 @@ struct BulletproofsCircuit {
  */
 pub struct BulletproofsR1CSProof(pub R1CSProof);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A Pedersen commitment `value * B + blinding * B_blinding`, where `B` and
+ * `B_blinding` are the default [`PedersenGens`].
+ */
+pub struct BulletproofsCommitment(pub CompressedRistretto);
+
 impl BulletproofsCircuit {
     /**
      * Create a [`BulletproofsCircuit`].
@@ -127,6 +137,21 @@ impl BulletproofsCircuit {
         transcript
     }
 
+    /**
+     * Makes the base transcript, binding the user's domain separation
+     * data if there is any. Omitting empty contexts keeps proofs
+     * without one unchanged.
+     */
+    fn make_context_transcript(context: &[u8]) -> Transcript {
+        let mut transcript = Self::make_base_transcript();
+
+        if !context.is_empty() {
+            transcript.append_message(b"context", context);
+        }
+
+        transcript
+    }
+
     fn make_gens(len: usize) -> (PedersenGens, BulletproofGens) {
         let len = len.next_power_of_two();
 
@@ -137,6 +162,9 @@ impl BulletproofsCircuit {
     }
 
     /**
+     * `commitments` are the variables bound to each of the program's input
+     * commitments, in order.
+     *
      * # Notes
      * `graph` is declared as mutable, but the value won't actually be
      * mutated. This is due to [`forward_traverse`] requiring such.
@@ -145,6 +173,7 @@ impl BulletproofsCircuit {
         &mut self,
         graph: &ExecutableZkpProgram,
         cs: &mut CS,
+        commitments: &[Variable],
         get_input: I,
     ) -> Result<()>
     where
@@ -305,6 +334,23 @@ impl BulletproofsCircuit {
 
                     self.nodes[idx.index()] = Some(x.into());
                 }
+                Operation::Commit(x) => {
+                    let o_idx = query.get_unary_operand(idx)?;
+
+                    let o = self.nodes[o_idx.index()]
+                        .as_ref()
+                        .unwrap_or_else(|| panic!("{}", dependency_not_found_msg(o_idx)))
+                        .clone();
+
+                    let o: LinearCombination = match o {
+                        Node::LinearCombination(o) => o,
+                        Node::Scalar(o) => o.into(),
+                    };
+
+                    cs.constrain(o - commitments[x]);
+
+                    ref_count(&mut self.nodes, o_idx, &mut unprocessed_child_count);
+                }
             }
 
             Ok::<(), Error>(())
@@ -353,7 +399,13 @@ impl BulletproofsBackend {
 
         let mut prover = Prover::new(pc_gens, transcript);
 
-        let _ = circuit.gen_circuit(&prog, &mut prover, |x| Some(inputs[x]));
+        // The commitments' values don't affect the circuit's shape, so
+        // commit to zero.
+        let commitments = (0..commitment_count(&prog))
+            .map(|_| prover.commit(Scalar::zero(), Scalar::zero()).1)
+            .collect::<Vec<_>>();
+
+        let _ = circuit.gen_circuit(&prog, &mut prover, &commitments, |x| Some(inputs[x]));
 
         Ok(prover)
     }
@@ -421,7 +473,7 @@ fn constraint_count(graph: &ExecutableZkpProgram) -> Result<usize> {
 
                 input_count += 1;
             }
-            Operation::Constraint(_) => count += 1,
+            Operation::Constraint(_) | Operation::Commit(_) => count += 1,
             Operation::Mul => {
                 let (left, right) = query.get_binary_operands(i)?;
 
@@ -439,6 +491,14 @@ fn constraint_count(graph: &ExecutableZkpProgram) -> Result<usize> {
     Ok(count)
 }
 
+/// Get the number of input commitments in the given program.
+fn commitment_count(graph: &ExecutableZkpProgram) -> usize {
+    graph
+        .node_weights()
+        .filter(|x| matches!(x.operation, Operation::Commit(_)))
+        .count()
+}
+
 /// Parameters for verifying Bulletproof circuit.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct BulletproofVerifierParameters {
@@ -492,53 +552,17 @@ impl BulletproofProverParameters {
     }
 }
 
-impl ZkpBackend for BulletproofsBackend {
-    type Field = BulletproofsFieldSpec;
-
-    type ProverParameters = BulletproofProverParameters;
-    type VerifierParameters = BulletproofVerifierParameters;
-
+impl BulletproofsBackend {
     /**
-     * Create a proof for the given executable Sunscreen
-     * program with the given inputs.
+     * [`ZkpBackend::prove_with_parameters`], opening the given input
+     * commitments in the proof.
      */
-    fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
-        let mut transcript = BulletproofsCircuit::make_base_transcript();
-
-        let constraint_count = constraint_count(graph)?;
-
-        let mut rng = {
-            let mut builder = transcript.build_rng();
-
-            // commit to all the inputs, including the private ones.
-            for input in inputs {
-                let words = input.0.as_words();
-                let bytes: Vec<u8> = words.iter().flat_map(|x| x.to_le_bytes()).collect();
-
-                builder = builder.rekey_with_witness_bytes(b"input", &bytes);
-            }
-
-            // And throw in some thread RNG for good measure. The bulletproofs
-            // library does this as well.
-            builder.finalize(&mut thread_rng())
-        };
-        let blinding_factor = Scalar::random(&mut rng);
-
-        let verifier_parameters = Self::VerifierParameters::new(
-            PedersenGens::default(),
-            BulletproofGens::new(2 * constraint_count, 1),
-            0,
-        );
-
-        let parameters = Self::ProverParameters::new(verifier_parameters, blinding_factor);
-        self.prove_with_parameters(graph, inputs, &parameters, &mut transcript)
-    }
-
-    fn prove_with_parameters(
+    fn prove_committed_with_parameters(
         &self,
         graph: &ExecutableZkpProgram,
         inputs: &[BigInt],
-        parameters: &Self::ProverParameters,
+        commitment_openings: &[CommitmentOpening],
+        parameters: &BulletproofProverParameters,
         transcript: &mut Transcript,
     ) -> Result<Proof> {
         let expected_input_count = graph
@@ -554,6 +578,16 @@ impl ZkpBackend for BulletproofsBackend {
             )));
         }
 
+        let expected_commitment_count = commitment_count(graph);
+
+        if expected_commitment_count != commitment_openings.len() {
+            return Err(Error::inputs_mismatch(&format!(
+                "Expected {} commitment openings, got {}.",
+                expected_commitment_count,
+                commitment_openings.len()
+            )));
+        }
+
         let constraint_count = constraint_count(graph)?;
 
         // Convert the inputs to Scalars
@@ -562,6 +596,11 @@ impl ZkpBackend for BulletproofsBackend {
             .map(|x| x.try_into())
             .collect::<Result<Vec<Scalar>>>()?;
 
+        let commitment_openings = commitment_openings
+            .iter()
+            .map(|x| Ok((Scalar::try_from(&x.value)?, Scalar::try_from(&x.blinding)?)))
+            .collect::<Result<Vec<(Scalar, Scalar)>>>()?;
+
         transcript.append_message(b"dom-sep", b"R1CS proof");
         transcript.append_u64(b"gen-len", constraint_count as u64);
 
@@ -572,9 +611,14 @@ impl ZkpBackend for BulletproofsBackend {
             transcript,
         );
 
+        let commitments = commitment_openings
+            .iter()
+            .map(|(value, blinding)| prover.commit(*value, *blinding).1)
+            .collect::<Vec<_>>();
+
         let now = Instant::now();
 
-        circuit.gen_circuit(graph, &mut prover, |x| Some(inputs[x]))?;
+        circuit.gen_circuit(graph, &mut prover, &commitments, |x| Some(inputs[x]))?;
 
         trace!("Bulletproofs encode time {}s", now.elapsed().as_secs_f64());
         trace!("{:#?}", prover.metrics());
@@ -594,23 +638,16 @@ impl ZkpBackend for BulletproofsBackend {
         Ok(Proof::Bulletproofs(Box::new(BulletproofsR1CSProof(proof))))
     }
 
-    fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()> {
-        let constraint_count = constraint_count(graph)?;
-        let mut transcript = BulletproofsCircuit::make_base_transcript();
-
-        let (pedersen_gens, bulletproof_gens) =
-            BulletproofsCircuit::make_gens(2 * constraint_count);
-
-        let parameters = Self::VerifierParameters::new(pedersen_gens, bulletproof_gens, 0);
-
-        self.verify_with_parameters(graph, proof, &parameters, &mut transcript)
-    }
-
-    fn verify_with_parameters(
+    /**
+     * [`ZkpBackend::verify_with_parameters`] against the given input
+     * commitments.
+     */
+    fn verify_committed_with_parameters(
         &self,
         graph: &ExecutableZkpProgram,
         proof: &Proof,
-        parameters: &Self::VerifierParameters,
+        commitments: &[Commitment],
+        parameters: &BulletproofVerifierParameters,
         transcript: &mut Transcript,
     ) -> Result<()> {
         let proof = match proof {
@@ -622,6 +659,24 @@ impl ZkpBackend for BulletproofsBackend {
 
         trace!("Starting backend verify...");
 
+        let expected_commitment_count = commitment_count(graph);
+
+        if expected_commitment_count != commitments.len() {
+            return Err(Error::inputs_mismatch(&format!(
+                "Expected {} commitments, got {}.",
+                expected_commitment_count,
+                commitments.len()
+            )));
+        }
+
+        let commitments = commitments
+            .iter()
+            .map(|x| match x {
+                Commitment::Bulletproofs(x) => Ok(x.0),
+                _ => Err(Error::IncorrectCommitmentType),
+            })
+            .collect::<Result<Vec<CompressedRistretto>>>()?;
+
         let constraint_count = constraint_count(graph)?;
 
         transcript.append_message(b"dom-sep", b"R1CS proof");
@@ -631,9 +686,14 @@ impl ZkpBackend for BulletproofsBackend {
 
         let mut verifier = Verifier::new(transcript);
 
+        let commitments = commitments
+            .into_iter()
+            .map(|x| verifier.commit(x))
+            .collect::<Vec<_>>();
+
         let now = Instant::now();
 
-        circuit.gen_circuit(graph, &mut verifier, |_| None)?;
+        circuit.gen_circuit(graph, &mut verifier, &commitments, |_| None)?;
 
         trace!("Bulletproofs encode time {}s", now.elapsed().as_secs_f64());
 
@@ -649,6 +709,127 @@ impl ZkpBackend for BulletproofsBackend {
 
         Ok(())
     }
+}
+
+impl ZkpBackend for BulletproofsBackend {
+    type Field = BulletproofsFieldSpec;
+
+    type ProverParameters = BulletproofProverParameters;
+    type VerifierParameters = BulletproofVerifierParameters;
+
+    fn prove_with_context(
+        &self,
+        graph: &ExecutableZkpProgram,
+        inputs: &[BigInt],
+        commitment_openings: &[CommitmentOpening],
+        context: &[u8],
+    ) -> Result<Proof> {
+        let mut transcript = BulletproofsCircuit::make_context_transcript(context);
+
+        let constraint_count = constraint_count(graph)?;
+
+        let mut rng = {
+            let mut builder = transcript.build_rng();
+
+            // commit to all the inputs, including the private ones.
+            for input in inputs {
+                let words = input.0.as_words();
+                let bytes: Vec<u8> = words.iter().flat_map(|x| x.to_le_bytes()).collect();
+
+                builder = builder.rekey_with_witness_bytes(b"input", &bytes);
+            }
+
+            for opening in commitment_openings {
+                for x in [opening.value, opening.blinding] {
+                    let words = x.0.as_words();
+                    let bytes: Vec<u8> = words.iter().flat_map(|x| x.to_le_bytes()).collect();
+
+                    builder = builder.rekey_with_witness_bytes(b"opening", &bytes);
+                }
+            }
+
+            // And throw in some thread RNG for good measure. The bulletproofs
+            // library does this as well.
+            builder.finalize(&mut thread_rng())
+        };
+        let blinding_factor = Scalar::random(&mut rng);
+
+        let verifier_parameters = Self::VerifierParameters::new(
+            PedersenGens::default(),
+            BulletproofGens::new(2 * constraint_count, 1),
+            0,
+        );
+
+        let parameters = Self::ProverParameters::new(verifier_parameters, blinding_factor);
+        self.prove_committed_with_parameters(
+            graph,
+            inputs,
+            commitment_openings,
+            &parameters,
+            &mut transcript,
+        )
+    }
+
+    fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
+        self.prove_with_context(graph, inputs, &[], &[])
+    }
+
+    fn prove_with_parameters(
+        &self,
+        graph: &ExecutableZkpProgram,
+        inputs: &[BigInt],
+        parameters: &Self::ProverParameters,
+        transcript: &mut Transcript,
+    ) -> Result<Proof> {
+        self.prove_committed_with_parameters(graph, inputs, &[], parameters, transcript)
+    }
+
+    fn verify_with_context(
+        &self,
+        graph: &ExecutableZkpProgram,
+        proof: &Proof,
+        commitments: &[Commitment],
+        context: &[u8],
+    ) -> Result<()> {
+        let constraint_count = constraint_count(graph)?;
+        let mut transcript = BulletproofsCircuit::make_context_transcript(context);
+
+        let (pedersen_gens, bulletproof_gens) =
+            BulletproofsCircuit::make_gens(2 * constraint_count);
+
+        let parameters = Self::VerifierParameters::new(pedersen_gens, bulletproof_gens, 0);
+
+        self.verify_committed_with_parameters(
+            graph,
+            proof,
+            commitments,
+            &parameters,
+            &mut transcript,
+        )
+    }
+
+    fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()> {
+        self.verify_with_context(graph, proof, &[], &[])
+    }
+
+    fn verify_with_parameters(
+        &self,
+        graph: &ExecutableZkpProgram,
+        proof: &Proof,
+        parameters: &Self::VerifierParameters,
+        transcript: &mut Transcript,
+    ) -> Result<()> {
+        self.verify_committed_with_parameters(graph, proof, &[], parameters, transcript)
+    }
+
+    fn commit(&self, opening: &CommitmentOpening) -> Result<Commitment> {
+        let value = Scalar::try_from(&opening.value)?;
+        let blinding = Scalar::try_from(&opening.blinding)?;
+
+        let commitment = PedersenGens::default().commit(value, blinding).compress();
+
+        Ok(Commitment::Bulletproofs(BulletproofsCommitment(commitment)))
+    }
 
     fn jit_prover(
         &self,
@@ -787,7 +968,7 @@ mod tests {
     use sunscreen_compiler_common::{EdgeInfo, NodeInfo};
 
     use super::*;
-    use crate::exec::Operation as BackendOperation;
+    use crate::{exec::Operation as BackendOperation, find_unsatisfied_constraint, ZkpInto};
 
     #[test]
     fn can_convert_small_u512_to_scalar() {
@@ -922,4 +1103,201 @@ mod tests {
 
         assert!(backend.verify(&graph, &proof).is_err());
     }
+
+    #[test]
+    fn proofs_are_bound_to_context() {
+        let mut graph = ExecutableZkpProgram::new();
+
+        let in_0 = graph.add_node(NodeInfo {
+            operation: BackendOperation::Input(0),
+        });
+        let mul = graph.add_node(NodeInfo {
+            operation: BackendOperation::Mul,
+        });
+        let constraint = graph.add_node(NodeInfo {
+            operation: BackendOperation::Constraint(BigInt::from_u32(49)),
+        });
+
+        graph.add_edge(in_0, mul, EdgeInfo::Left);
+        graph.add_edge(in_0, mul, EdgeInfo::Right);
+        graph.add_edge(mul, constraint, EdgeInfo::Unordered);
+
+        let backend = BulletproofsBackend::new();
+        let inputs = [BigInt::from_u32(7)];
+
+        let proof = backend
+            .prove_with_context(&graph, &inputs, &[], b"app-a")
+            .unwrap();
+
+        backend
+            .verify_with_context(&graph, &proof, &[], b"app-a")
+            .unwrap();
+        assert!(backend
+            .verify_with_context(&graph, &proof, &[], b"app-b")
+            .is_err());
+        assert!(backend.verify(&graph, &proof).is_err());
+
+        // An empty context is the same as no context.
+        let proof = backend.prove(&graph, &inputs).unwrap();
+
+        backend
+            .verify_with_context(&graph, &proof, &[], b"")
+            .unwrap();
+        assert!(backend
+            .verify_with_context(&graph, &proof, &[], b"app-a")
+            .is_err());
+    }
+
+    #[test]
+    fn can_find_unsatisfied_constraint() {
+        let mut graph = ExecutableZkpProgram::new();
+
+        let mut add_node = |op: BackendOperation, edges: &[(NodeIndex, EdgeInfo)]| {
+            let n = graph.add_node(NodeInfo { operation: op });
+
+            for (source, edge) in edges {
+                graph.add_edge(*source, n, *edge);
+            }
+
+            n
+        };
+
+        let in_0 = add_node(BackendOperation::Input(0), &[]);
+        let in_1 = add_node(BackendOperation::Input(1), &[]);
+        let in_2 = add_node(BackendOperation::Input(2), &[]);
+
+        let mul_1 = add_node(
+            BackendOperation::Mul,
+            &[(in_0, EdgeInfo::Left), (in_1, EdgeInfo::Right)],
+        );
+        let sub_1 = add_node(
+            BackendOperation::Sub,
+            &[(in_2, EdgeInfo::Left), (mul_1, EdgeInfo::Right)],
+        );
+
+        let constraint = add_node(
+            BackendOperation::Constraint(BigInt::from_u32(2)),
+            &[(sub_1, EdgeInfo::Unordered)],
+        );
+
+        let inputs = |x: &[u32]| x.iter().map(|x| BigInt::from_u32(*x)).collect::<Vec<_>>();
+
+        // 42 - 10 * 4 == 2
+        let result =
+            find_unsatisfied_constraint::<BulletproofsFieldSpec>(&graph, &inputs(&[10, 4, 42]))
+                .unwrap();
+
+        assert_eq!(result, None);
+
+        // 42 - 8 * 5 == 2
+        let result =
+            find_unsatisfied_constraint::<BulletproofsFieldSpec>(&graph, &inputs(&[8, 5, 42]))
+                .unwrap();
+
+        assert_eq!(result, None);
+
+        // 30 - 8 * 5 == 2 fails, as 30 - 40 == -10.
+        let result =
+            find_unsatisfied_constraint::<BulletproofsFieldSpec>(&graph, &inputs(&[8, 5, 30]))
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(result.constraint, constraint);
+        assert_eq!(result.operand, sub_1);
+        assert_eq!(result.expected, BigInt::from_u32(2));
+
+        let minus_10: BigInt = (-Scalar::from(10u32)).zkp_into();
+        assert_eq!(result.actual, minus_10);
+
+        let trace = result
+            .trace
+            .iter()
+            .map(|x| (x.node, x.operands.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            trace,
+            vec![
+                (in_0, vec![]),
+                (in_1, vec![]),
+                (in_2, vec![]),
+                (mul_1, vec![in_0, in_1]),
+                (sub_1, vec![in_2, mul_1]),
+            ]
+        );
+        assert_eq!(result.trace[3].value, BigInt::from_u32(40));
+
+        let report = result.to_string();
+
+        assert!(report.contains("to equal 2, but it equals -10"));
+        assert!(report.contains("n4 = Sub(n2, n3) = -10"));
+    }
+
+    #[test]
+    fn can_prove_committed_input() {
+        let mut graph = ExecutableZkpProgram::new();
+
+        let mut add_node = |op: BackendOperation, edges: &[(NodeIndex, EdgeInfo)]| {
+            let n = graph.add_node(NodeInfo { operation: op });
+
+            for (source, edge) in edges {
+                graph.add_edge(*source, n, *edge);
+            }
+
+            n
+        };
+
+        let in_0 = add_node(BackendOperation::Input(0), &[]);
+        let _ = add_node(BackendOperation::Commit(0), &[(in_0, EdgeInfo::Unary)]);
+
+        let mul_1 = add_node(
+            BackendOperation::Mul,
+            &[(in_0, EdgeInfo::Left), (in_0, EdgeInfo::Right)],
+        );
+
+        let _ = add_node(
+            BackendOperation::Constraint(BigInt::from_u32(49)),
+            &[(mul_1, EdgeInfo::Unordered)],
+        );
+
+        let backend = BulletproofsBackend::new();
+
+        let opening = CommitmentOpening::new(BigInt::from_u32(7), BigInt::from_u32(1234));
+        let commitment = backend.commit(&opening).unwrap();
+
+        // 7 * 7 == 49
+        let proof = backend
+            .prove_with_context(&graph, &[BigInt::from_u32(7)], &[opening], &[])
+            .unwrap();
+
+        backend
+            .verify_with_context(&graph, &proof, std::slice::from_ref(&commitment), &[])
+            .unwrap();
+
+        // The same value under a different blinding factor is a different
+        // commitment.
+        let other_blinding = CommitmentOpening::new(BigInt::from_u32(7), BigInt::from_u32(5678));
+        let other_commitment = backend.commit(&other_blinding).unwrap();
+
+        assert_ne!(commitment, other_commitment);
+        assert!(backend
+            .verify_with_context(&graph, &proof, &[other_commitment], &[])
+            .is_err());
+
+        // Committing to a value other than the input should fail.
+        let wrong_opening = CommitmentOpening::new(BigInt::from_u32(6), BigInt::from_u32(1234));
+        let wrong_commitment = backend.commit(&wrong_opening).unwrap();
+
+        let proof = backend
+            .prove_with_context(&graph, &[BigInt::from_u32(7)], &[wrong_opening], &[])
+            .unwrap();
+
+        assert!(backend
+            .verify_with_context(&graph, &proof, &[wrong_commitment], &[])
+            .is_err());
+
+        // Omitting the commitment is an error.
+        assert!(backend.verify(&graph, &proof).is_err());
+        assert!(backend.prove(&graph, &[BigInt::from_u32(7)]).is_err());
+    }
 }
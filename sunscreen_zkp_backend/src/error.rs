@@ -27,6 +27,13 @@ pub enum Error {
      */
     InputsMismatch(Box<String>),
 
+    #[error("The given commitment isn't valid for the backend proof system.")]
+    /**
+     * Attempted to verify against a commitment incompatible with the given
+     * backend proof system.
+     */
+    IncorrectCommitmentType,
+
     #[error("The given proof isn't valid for the backend proof system.")]
     /**
      * Attempted to verify a proof incompatible with the given backend proof system.
@@ -56,6 +63,20 @@ pub enum Error {
      * A constraint could not be satisfied.
      */
     UnsatisfiableConstraint(NodeIndex),
+
+    #[error("{0}")]
+    /**
+     * A constraint could not be satisfied. Unlike
+     * [`Error::UnsatisfiableConstraint`], this describes the values
+     * involved and how they were computed.
+     */
+    UnsatisfiedConstraint(Box<crate::UnsatisfiedConstraint>),
+
+    #[error("Unsupported: {0}")]
+    /**
+     * The backend doesn't support the requested feature.
+     */
+    Unsupported(Box<String>),
 }
 
 impl Error {
@@ -86,6 +107,13 @@ impl Error {
     pub fn inputs_mismatch(msg: &str) -> Self {
         Self::InputsMismatch(Box::new(msg.to_owned()))
     }
+
+    /**
+     * Create an [`Error::Unsupported`].
+     */
+    pub fn unsupported(msg: &str) -> Self {
+        Self::Unsupported(Box::new(msg.to_owned()))
+    }
 }
 
 impl From<bulletproofs::r1cs::R1CSError> for Error {
@@ -1,8 +1,25 @@
-use crate::BigInt;
-use sunscreen_compiler_common::{CompilationResult, Operation as OperationTrait};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crypto_bigint::U512;
+use petgraph::{stable_graph::NodeIndex, Direction};
+use sunscreen_compiler_common::{
+    forward_traverse, CompilationResult, GraphQuery, Operation as OperationTrait,
+};
+
+use crate::{BigInt, Error, FieldSpec, Result, ZkpInto};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+/**
+ * An operation in a JIT'd ZKP program.
+ */
 pub enum Operation {
+    /**
+     * Loads the input at the given index. Public inputs come first,
+     * followed by private inputs.
+     */
     Input(usize),
 
     /**
@@ -11,17 +28,41 @@ pub enum Operation {
      */
     HiddenInput(Option<BigInt>),
 
+    /**
+     * Adds the node's 2 parents.
+     */
     Add,
 
+    /**
+     * Multiplies the node's 2 parents.
+     */
     Mul,
 
+    /**
+     * Subtracts the node's right parent from its left parent.
+     */
     Sub,
 
+    /**
+     * Negates the node's parent.
+     */
     Neg,
 
+    /**
+     * Constrain each of the node's parents to equal the given value.
+     */
     Constraint(BigInt),
 
+    /**
+     * A constant value.
+     */
     Constant(BigInt),
+
+    /**
+     * Constrain the node's parent to equal the value in the input
+     * commitment at the given index.
+     */
+    Commit(usize),
 }
 
 impl OperationTrait for Operation {
@@ -34,7 +75,7 @@ impl OperationTrait for Operation {
     }
 
     fn is_unary(&self) -> bool {
-        matches!(self, Operation::Neg)
+        matches!(self, Operation::Neg | Operation::Commit(_))
     }
 
     fn is_unordered(&self) -> bool {
@@ -50,3 +91,282 @@ impl OperationTrait for Operation {
  * A ZKP program that has been JIT'd and is ready for use in a ZKP backend.
  */
 pub type ExecutableZkpProgram = CompilationResult<Operation>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * A node that contributed to an [`UnsatisfiedConstraint`] and the value
+ * it took when the program ran.
+ */
+pub struct TracedNode {
+    /**
+     * The node's index in the [`ExecutableZkpProgram`].
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The node's operation.
+     */
+    pub operation: Operation,
+
+    /**
+     * The node's operands, in order.
+     */
+    pub operands: Vec<NodeIndex>,
+
+    /**
+     * The value the node computed.
+     */
+    pub value: BigInt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * A [`Operation::Constraint`] the prover's inputs fail to satisfy.
+ */
+pub struct UnsatisfiedConstraint {
+    /**
+     * The constraint's node index in the [`ExecutableZkpProgram`].
+     */
+    pub constraint: NodeIndex,
+
+    /**
+     * The value the constraint requires.
+     */
+    pub expected: BigInt,
+
+    /**
+     * The node whose value doesn't equal [`expected`](Self::expected).
+     */
+    pub operand: NodeIndex,
+
+    /**
+     * The value `operand` actually took.
+     */
+    pub actual: BigInt,
+
+    /**
+     * The chain of operations that computed `operand`, in the order
+     * they ran. The last entry is `operand` itself.
+     */
+    pub trace: Vec<TracedNode>,
+
+    modulus: BigInt,
+}
+
+impl UnsatisfiedConstraint {
+    /**
+     * Formats a field element as a signed decimal when it's small, else
+     * as hex.
+     */
+    fn fmt_value(&self, x: &BigInt) -> String {
+        let small = |x: &U512| x.as_words()[1..].iter().all(|w| *w == 0);
+
+        let negated = self.modulus.wrapping_sub(x);
+
+        if small(x) {
+            x.as_words()[0].to_string()
+        } else if small(&negated) {
+            format!("-{}", negated.as_words()[0])
+        } else {
+            format!("0x{}", x.0)
+        }
+    }
+}
+
+impl Display for UnsatisfiedConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Constraint {} requires node {} to equal {}, but it equals {}",
+            self.constraint.index(),
+            self.operand.index(),
+            self.fmt_value(&self.expected),
+            self.fmt_value(&self.actual)
+        )?;
+
+        for n in &self.trace {
+            // Constants' values already appear on the right.
+            let operation = match n.operation {
+                Operation::HiddenInput(_) => "HiddenInput".to_owned(),
+                Operation::Constant(_) => "Constant".to_owned(),
+                x => format!("{x:?}"),
+            };
+
+            let operands = n
+                .operands
+                .iter()
+                .map(|x| format!("n{}", x.index()))
+                .collect::<Vec<_>>();
+
+            write!(f, "  n{} = {operation}", n.node.index())?;
+
+            if !operands.is_empty() {
+                write!(f, "({})", operands.join(", "))?;
+            }
+
+            writeln!(f, " = {}", self.fmt_value(&n.value))?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Runs the given program directly (not as a ZKP) and returns the first
+ * constraint the inputs fail to satisfy, if any.
+ *
+ * `inputs` are the public inputs followed by the private inputs. `prog`
+ * must come from a prover JIT, as the verifier doesn't know the values
+ * of hidden inputs.
+ *
+ * # Remarks
+ * This is a debugging aid and doesn't run in constant time. Commitments
+ * aren't checked, as doing so requires the backend's proof system.
+ */
+pub fn find_unsatisfied_constraint<U>(
+    prog: &ExecutableZkpProgram,
+    inputs: &[BigInt],
+) -> Result<Option<UnsatisfiedConstraint>>
+where
+    U: FieldSpec,
+{
+    let mut node_outputs: HashMap<NodeIndex, U::BackendField> = HashMap::new();
+
+    forward_traverse(prog, |query, id| {
+        let node = query.get_node(id).unwrap();
+
+        let output = match node.operation {
+            Operation::Input(x) => {
+                let input = inputs.get(x).ok_or_else(|| {
+                    Error::inputs_mismatch(&format!(
+                        "Node {} loads input {x}, but there are only {} inputs",
+                        id.index(),
+                        inputs.len()
+                    ))
+                })?;
+
+                U::BackendField::try_from(*input)?
+            }
+            Operation::HiddenInput(Some(x)) => U::BackendField::try_from(x)?,
+            Operation::HiddenInput(None) => {
+                return Err(Error::malformed_zkp_program(
+                    "Hidden inputs have no values. Did you use a verifier's program?",
+                ));
+            }
+            Operation::Constant(x) => U::BackendField::try_from(x)?,
+            Operation::Add => {
+                let (left, right) = query.get_binary_operands(id)?;
+
+                node_outputs[&left].clone() + node_outputs[&right].clone()
+            }
+            Operation::Mul => {
+                let (left, right) = query.get_binary_operands(id)?;
+
+                node_outputs[&left].clone() * node_outputs[&right].clone()
+            }
+            Operation::Sub => {
+                let (left, right) = query.get_binary_operands(id)?;
+
+                node_outputs[&left].clone() - node_outputs[&right].clone()
+            }
+            Operation::Neg => {
+                let left = query.get_unary_operand(id)?;
+
+                -node_outputs[&left].clone()
+            }
+            // These produce no outputs.
+            Operation::Constraint(_) | Operation::Commit(_) => return Ok(()),
+        };
+
+        node_outputs.insert(id, output);
+
+        Ok::<_, Error>(())
+    })?;
+
+    let query = GraphQuery::new(prog);
+
+    let mut constraints = prog
+        .node_indices()
+        .filter(|x| matches!(prog[*x].operation, Operation::Constraint(_)))
+        .collect::<Vec<_>>();
+
+    constraints.sort();
+
+    for constraint in constraints {
+        let Operation::Constraint(expected) = prog[constraint].operation else {
+            unreachable!();
+        };
+
+        for operand in query.get_unordered_operands(constraint)? {
+            let actual: BigInt = node_outputs[&operand].clone().zkp_into();
+
+            if actual != expected {
+                let trace = trace_operand(prog, operand, &node_outputs)?;
+
+                return Ok(Some(UnsatisfiedConstraint {
+                    constraint,
+                    expected,
+                    operand,
+                    actual,
+                    trace,
+                    modulus: U::FIELD_MODULUS,
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/**
+ * Collects `operand` and every node it transitively depends on.
+ */
+fn trace_operand<F>(
+    prog: &ExecutableZkpProgram,
+    operand: NodeIndex,
+    node_outputs: &HashMap<NodeIndex, F>,
+) -> Result<Vec<TracedNode>>
+where
+    F: Clone + ZkpInto<BigInt>,
+{
+    let query = GraphQuery::new(prog);
+
+    let mut visited = HashSet::from([operand]);
+    let mut stack = vec![operand];
+
+    while let Some(n) = stack.pop() {
+        for parent in prog.neighbors_directed(n, Direction::Incoming) {
+            if visited.insert(parent) {
+                stack.push(parent);
+            }
+        }
+    }
+
+    // Node indices follow program order, so this lists operands before
+    // the operations that use them.
+    let mut visited = visited.into_iter().collect::<Vec<_>>();
+    visited.sort();
+
+    visited
+        .into_iter()
+        .map(|node| {
+            let operation = prog[node].operation;
+
+            let operands = if operation.is_binary() {
+                let (left, right) = query.get_binary_operands(node)?;
+                vec![left, right]
+            } else if operation.is_unary() {
+                vec![query.get_unary_operand(node)?]
+            } else {
+                vec![]
+            };
+
+            Ok(TracedNode {
+                node,
+                operation,
+                operands,
+                value: node_outputs[&node].clone().zkp_into(),
+            })
+        })
+        .collect()
+}
@@ -73,6 +73,13 @@ pub enum Operation {
      * A constant field element.
      */
     Constant(BigInt),
+
+    /**
+     * Constrain the node's parent to equal the value in the input commitment
+     * at the given positional index. The prover supplies the commitment's
+     * opening and the verifier supplies only the commitment.
+     */
+    Commit(usize),
 }
 
 impl Hash for Operation {
@@ -110,6 +117,10 @@ impl Hash for Operation {
                 state.write_u8(10);
                 x.hash(state);
             }
+            Self::Commit(x) => {
+                state.write_u8(11);
+                state.write_usize(*x);
+            }
         }
     }
 }
@@ -122,6 +133,7 @@ impl PartialEq for Operation {
             (Self::HiddenInput(x), Self::HiddenInput(y)) => x == y,
             (Self::Constraint(x), Self::Constraint(y)) => x == y,
             (Self::Constant(x), Self::Constant(y)) => x == y,
+            (Self::Commit(x), Self::Commit(y)) => x == y,
             (Self::InvokeGadget(x), Self::InvokeGadget(y)) => x.type_id() == y.type_id(),
             (Self::Add, Self::Add) => true,
             (Self::Sub, Self::Sub) => true,
@@ -143,6 +155,7 @@ impl Debug for Operation {
             Self::HiddenInput(x) => write!(f, "HiddenInput({x})"),
             Self::Constraint(x) => write!(f, "Constraint({x:#?})"),
             Self::Constant(x) => write!(f, "Constant({x:#?})"),
+            Self::Commit(x) => write!(f, "Commit({x})"),
             Self::InvokeGadget(g) => write!(f, "InvokeGadget({})", g.debug_name()),
             Self::Add => write!(f, "Add"),
             Self::Sub => write!(f, "Sub"),
@@ -162,7 +175,7 @@ impl OperationTrait for Operation {
     }
 
     fn is_unary(&self) -> bool {
-        matches!(self, Operation::Neg)
+        matches!(self, Operation::Neg | Operation::Commit(_))
     }
 
     fn is_unordered(&self) -> bool {
@@ -227,6 +240,17 @@ fn validate_zkp_program(prog: &CompiledZkpProgram) -> Result<()> {
     public_inputs.sort();
     assert_range(&private_inputs, "private input")?;
 
+    let mut commitments = prog
+        .node_weights()
+        .filter_map(|x| match x.operation {
+            Operation::Commit(x) => Some(x),
+            _ => None,
+        })
+        .collect::<Vec<usize>>();
+
+    commitments.sort();
+    assert_range(&commitments, "commitment")?;
+
     // TODO: check for cycles, assert each node has correct inputs.
 
     Ok(())
@@ -245,6 +269,40 @@ pub fn jit_prover<U>(
     public_inputs: &[U::BackendField],
     constant_inputs: &[U::BackendField],
 ) -> Result<ExecutableZkpProgram>
+where
+    U: FieldSpec,
+{
+    jit_prover_internal::<U>(prog, private_inputs, public_inputs, constant_inputs, true)
+}
+
+/**
+ * Like [`jit_prover`], but doesn't fail when the inputs don't satisfy the
+ * program's constraints.
+ *
+ * # Remarks
+ * Proofs created from the returned program will fail to verify. This is
+ * useful for finding which constraint failed with
+ * [`find_unsatisfied_constraint`](crate::find_unsatisfied_constraint).
+ */
+pub fn jit_prover_unchecked<U>(
+    prog: &CompiledZkpProgram,
+    private_inputs: &[U::BackendField],
+    public_inputs: &[U::BackendField],
+    constant_inputs: &[U::BackendField],
+) -> Result<ExecutableZkpProgram>
+where
+    U: FieldSpec,
+{
+    jit_prover_internal::<U>(prog, private_inputs, public_inputs, constant_inputs, false)
+}
+
+fn jit_prover_internal<U>(
+    prog: &CompiledZkpProgram,
+    private_inputs: &[U::BackendField],
+    public_inputs: &[U::BackendField],
+    constant_inputs: &[U::BackendField],
+    check_constraints: bool,
+) -> Result<ExecutableZkpProgram>
 where
     U: FieldSpec,
 {
@@ -296,6 +354,9 @@ where
                 node_outputs.insert(id, constant_inputs[x].clone());
             }
             Operation::HiddenInput(_) => {} // Gadgets populate these outputs.
+            // Commitments produce no outputs and only the backend can
+            // check them.
+            Operation::Commit(_) => {}
             Operation::Add => {
                 let (left, right) = query.get_binary_operands(id)?;
 
@@ -324,6 +385,7 @@ where
 
                 node_outputs.insert(id, output);
             }
+            Operation::Constraint(_) if !check_constraints => {}
             Operation::Constraint(x) => {
                 // Constraints produce no outputs, but verify it's met.
                 let parents = query.get_unordered_operands(id)?;
@@ -480,6 +542,7 @@ where
             Operation::Neg => NodeInfo::new(ExecOperation::Neg),
             Operation::Constant(x) => NodeInfo::new(ExecOperation::Constant(x)),
             Operation::Constraint(x) => NodeInfo::new(ExecOperation::Constraint(x)),
+            Operation::Commit(x) => NodeInfo::new(ExecOperation::Commit(x)),
             Operation::PublicInput(id) => NodeInfo::new(ExecOperation::Input(id)),
             Operation::PrivateInput(id) => {
                 NodeInfo::new(ExecOperation::Input(public_inputs.len() + id))
@@ -25,8 +25,11 @@ use crypto_bigint::{
     Limb, NonZero, U512,
 };
 pub use error::*;
-pub use exec::ExecutableZkpProgram;
-pub use jit::{jit_prover, jit_verifier, CompiledZkpProgram, Operation};
+pub use exec::{
+    find_unsatisfied_constraint, ExecutableZkpProgram, Operation as ExecOperation, TracedNode,
+    UnsatisfiedConstraint,
+};
+pub use jit::{jit_prover, jit_prover_unchecked, jit_verifier, CompiledZkpProgram, Operation};
 use merlin::Transcript;
 use petgraph::stable_graph::NodeIndex;
 use serde::{Deserialize, Serialize};
@@ -153,6 +156,64 @@ pub enum Proof {
     },
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A commitment to a single native field element of a `#[committed]` ZKP
+ * program input. The verifier learns only the commitment, so the same
+ * hidden value can be bound across multiple proofs or to commitments
+ * published elsewhere.
+ */
+pub enum Commitment {
+    #[cfg(feature = "bulletproofs")]
+    /**
+     * A Pedersen commitment over the Ristretto group.
+     */
+    Bulletproofs(bulletproofs::BulletproofsCommitment),
+
+    /**
+     * A custom commitment type provided by an external crate.
+     */
+    Custom {
+        /**
+         * The name of the proof system.
+         */
+        name: String,
+        /**
+         * The commitment data.
+         */
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * The secret opening of a [`Commitment`], known only to the prover.
+ */
+pub struct CommitmentOpening {
+    /**
+     * The committed field element.
+     */
+    pub value: BigInt,
+
+    /**
+     * The blinding factor that hides `value`.
+     *
+     * # Remarks
+     * This must be a uniformly random field element. Reusing a blinding
+     * factor for different values leaks their difference.
+     */
+    pub blinding: BigInt,
+}
+
+impl CommitmentOpening {
+    /**
+     * Create a [`CommitmentOpening`].
+     */
+    pub fn new(value: BigInt, blinding: BigInt) -> Self {
+        Self { value, blinding }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 /**
  * A large integer representing a backend-agnostic
@@ -395,6 +456,48 @@ pub trait ZkpBackend {
      */
     fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof>;
 
+    /**
+     * Create a proof for the given executable Sunscreen
+     * program with the given inputs, bound to the given
+     * application-defined `context` (e.g. a session or chain ID).
+     *
+     * `commitment_openings` holds the opening for each of the
+     * program's input commitments, in order.
+     *
+     * # Remarks
+     * The proof only verifies when the verifier passes the same
+     * `context` to [`verify_with_context`](ZkpBackend::verify_with_context),
+     * which prevents replaying it in another application. Empty
+     * `commitment_openings` and `context` are the same as calling
+     * [`prove`](ZkpBackend::prove).
+     *
+     * The default implementation delegates to [`prove`](ZkpBackend::prove)
+     * and returns [`Error::Unsupported`] for a non-empty `context` or
+     * `commitment_openings`, as silently dropping either would weaken
+     * the proof.
+     */
+    fn prove_with_context(
+        &self,
+        graph: &ExecutableZkpProgram,
+        inputs: &[BigInt],
+        commitment_openings: &[CommitmentOpening],
+        context: &[u8],
+    ) -> Result<Proof> {
+        if !commitment_openings.is_empty() {
+            return Err(Error::unsupported(
+                "This backend doesn't support committed inputs.",
+            ));
+        }
+
+        if !context.is_empty() {
+            return Err(Error::unsupported(
+                "This backend doesn't support proof contexts.",
+            ));
+        }
+
+        self.prove(graph, inputs)
+    }
+
     /**
      * Create a proof for the given executable Sunscreen
      * program with the given inputs.
@@ -413,6 +516,42 @@ pub trait ZkpBackend {
      */
     fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()>;
 
+    /**
+     * Verify the given proof for the given executable
+     * Sunscreen program was created with
+     * [`prove_with_context`](ZkpBackend::prove_with_context) using the
+     * same `context`.
+     *
+     * `commitments` holds each of the program's input
+     * commitments, in order.
+     *
+     * # Remarks
+     * The default implementation delegates to
+     * [`verify`](ZkpBackend::verify) and returns [`Error::Unsupported`]
+     * for a non-empty `context` or `commitments`.
+     */
+    fn verify_with_context(
+        &self,
+        graph: &ExecutableZkpProgram,
+        proof: &Proof,
+        commitments: &[Commitment],
+        context: &[u8],
+    ) -> Result<()> {
+        if !commitments.is_empty() {
+            return Err(Error::unsupported(
+                "This backend doesn't support committed inputs.",
+            ));
+        }
+
+        if !context.is_empty() {
+            return Err(Error::unsupported(
+                "This backend doesn't support proof contexts.",
+            ));
+        }
+
+        self.verify(graph, proof)
+    }
+
     /**
      * Verify the given proof for the given executable
      * Sunscreen program.
@@ -425,6 +564,24 @@ pub trait ZkpBackend {
         transcript: &mut Transcript,
     ) -> Result<()>;
 
+    /**
+     * Commit to the given opening's value. Pass the opening to
+     * [`prove_with_context`](ZkpBackend::prove_with_context) and the
+     * returned commitment to
+     * [`verify_with_context`](ZkpBackend::verify_with_context) to bind a
+     * `#[committed]` program input.
+     *
+     * # Remarks
+     * The default implementation returns [`Error::Unsupported`].
+     */
+    fn commit(&self, opening: &CommitmentOpening) -> Result<Commitment> {
+        let _ = opening;
+
+        Err(Error::unsupported(
+            "This backend doesn't support committed inputs.",
+        ))
+    }
+
     /**
      * JIT the given frontend-compiled ZKP program
      * to an executable Sunscreen program for use by
@@ -541,4 +698,104 @@ mod tests {
             <BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS,
         );
     }
+
+    #[test]
+    fn default_context_methods_delegate() {
+        // A backend implementing only the required methods, as an external
+        // crate written before contexts and commitments existed would.
+        struct CustomBackend;
+
+        impl ZkpBackend for CustomBackend {
+            type Field = <BulletproofsBackend as ZkpBackend>::Field;
+            type ProverParameters = ();
+            type VerifierParameters = ();
+
+            fn prove(&self, _graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
+                Ok(Proof::Custom {
+                    name: "custom".to_owned(),
+                    data: vec![inputs.len() as u8],
+                })
+            }
+
+            fn prove_with_parameters(
+                &self,
+                graph: &ExecutableZkpProgram,
+                inputs: &[BigInt],
+                _parameters: &(),
+                _transcript: &mut Transcript,
+            ) -> Result<Proof> {
+                self.prove(graph, inputs)
+            }
+
+            fn verify(&self, _graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()> {
+                match proof {
+                    Proof::Custom { name, .. } if name == "custom" => Ok(()),
+                    _ => Err(Error::IncorrectProofType),
+                }
+            }
+
+            fn verify_with_parameters(
+                &self,
+                graph: &ExecutableZkpProgram,
+                proof: &Proof,
+                _parameters: &(),
+                _transcript: &mut Transcript,
+            ) -> Result<()> {
+                self.verify(graph, proof)
+            }
+
+            fn jit_prover(
+                &self,
+                _prog: &CompiledZkpProgram,
+                _private_inputs: &[BigInt],
+                _public_inputs: &[BigInt],
+                _constant_inputs: &[BigInt],
+            ) -> Result<ExecutableZkpProgram> {
+                Ok(ExecutableZkpProgram::new())
+            }
+
+            fn jit_verifier(
+                &self,
+                _prog: &CompiledZkpProgram,
+                _public_inputs: &[BigInt],
+                _constant_inputs: &[BigInt],
+            ) -> Result<ExecutableZkpProgram> {
+                Ok(ExecutableZkpProgram::new())
+            }
+        }
+
+        let backend = CustomBackend;
+        let graph = ExecutableZkpProgram::new();
+        let inputs = [BigInt::from(1u16), BigInt::from(2u16)];
+
+        let proof = backend
+            .prove_with_context(&graph, &inputs, &[], &[])
+            .unwrap();
+        assert!(matches!(&proof, Proof::Custom { data, .. } if data == &[2]));
+
+        backend
+            .verify_with_context(&graph, &proof, &[], &[])
+            .unwrap();
+
+        // Silently dropping a context or commitment would weaken the proof,
+        // so the defaults refuse them.
+        let opening = CommitmentOpening::new(BigInt::from(1u16), BigInt::from(2u16));
+
+        assert!(matches!(
+            backend.prove_with_context(&graph, &inputs, &[], b"app-a"),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            backend.prove_with_context(&graph, &inputs, std::slice::from_ref(&opening), &[]),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            backend.verify_with_context(&graph, &proof, &[], b"app-a"),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            backend.commit(&opening),
+            Err(Error::Unsupported(_))
+        ));
+    }
 }